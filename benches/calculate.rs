@@ -0,0 +1,58 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use titration_curve::worker::{
+    analysis::Stoichiometry, model::StrongModel, uncertainty::Uncertainty, Input,
+};
+
+/// Creates the titration of 20 mL of 0.1 mol/L acid with 0.1 mol/L base up to 40 mL in the given
+/// number of steps.
+fn input(points: usize, uncertainty: Uncertainty) -> Input {
+    Input {
+        t_v: 0.02,
+        t_c: 0.1,
+        m_c: 0.1,
+        m_v: (0..points)
+            .map(|it| it as f32 * 40.0 / points as f32)
+            .collect(),
+        temperature: 25.0,
+        temperatures: Vec::new(),
+        uncertainty,
+        stoichiometry: Stoichiometry::default(),
+        second_acid: None,
+        titrant: None,
+        analyte: None,
+        model: &StrongModel,
+    }
+}
+
+fn calculate_output(c: &mut Criterion) {
+    let mut group = c.benchmark_group("calculate_output");
+    group.sample_size(10);
+    for points in [100, 10_000, 100_000] {
+        let input = input(points, Uncertainty::default());
+        group.bench_with_input(BenchmarkId::from_parameter(points), &input, |b, input| {
+            b.iter(|| input.calculate_output())
+        });
+    }
+    group.finish();
+}
+
+fn propagate_uncertainty(c: &mut Criterion) {
+    let uncertainty = Uncertainty {
+        t_v: 0.0001,
+        t_c: 0.001,
+        m_c: 0.001,
+        m_v: 0.05,
+    };
+    let mut group = c.benchmark_group("propagate_uncertainty");
+    group.sample_size(10);
+    for points in [100, 10_000] {
+        let input = input(points, uncertainty);
+        group.bench_with_input(BenchmarkId::from_parameter(points), &input, |b, input| {
+            b.iter(|| input.calculate_output())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, calculate_output, propagate_uncertainty);
+criterion_main!(benches);
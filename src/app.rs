@@ -1,128 +1,3486 @@
+pub mod annotation;
+pub mod burette;
+pub mod calculator;
+#[cfg(feature = "live")]
+pub mod calibration;
+pub mod data;
+pub mod decimation;
 pub mod diagram;
+pub mod events;
+pub mod examples;
+pub mod form;
+pub mod generator;
+pub mod html;
+pub mod keys;
+pub mod layout;
+#[cfg(feature = "live")]
+pub mod live;
 pub mod options;
+pub mod playback;
+pub mod profile;
+pub mod render_cache;
+pub mod report;
+pub mod session;
+pub mod simulation;
+pub mod solution;
 pub mod strings;
+pub mod style;
+pub mod toast;
+pub mod tutorial;
 
 use std::{
-    sync::{mpsc::Receiver, Arc},
-    time::{Duration, Instant},
+    collections::BTreeMap,
+    fmt::Write,
+    iter,
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread,
+    time::{Duration, Instant, SystemTime},
 };
 
+use chrono::{DateTime, Local};
 use iced::{
-    executor,
+    clipboard, executor,
+    futures::{
+        channel::mpsc::{unbounded, UnboundedReceiver},
+        future,
+        lock::Mutex,
+        SinkExt, StreamExt,
+    },
+    multi_window::Application,
+    subscription,
     time::every,
-    widget::{button, checkbox, column, container, row, svg, svg::Handle, text},
-    Application, Command, ContentFit, Element, Length, Subscription, Theme,
+    widget::{
+        button, canvas, checkbox, column, container, mouse_area, pick_list, progress_bar, radio,
+        responsive, row, scrollable, slider, svg, text, text_input, vertical_rule, Column,
+        Container, Row, Text,
+    },
+    window, Alignment, Color, Command, ContentFit, Element, Length, Size, Subscription, Theme,
 };
+use tracing::{info, warn};
 
+#[cfg(feature = "live")]
+use crate::worker::calibration::Calibration;
+#[cfg(feature = "scripting")]
+use crate::worker::script;
 use crate::{
+    log,
     util::*,
-    worker::{Output, Response, Signal, Worker},
+    worker::{
+        activity,
+        analysis::{self, Stoichiometry},
+        complexometry::BufferPh,
+        explain::{number, Quantity, Rounding},
+        fit,
+        format::Notation,
+        gran, indicator,
+        locale::{self, localized, NumberLocale},
+        model::{TitrationModel, MODELS},
+        parser::Field,
+        substances::{
+            Indicator, Substance, EDTA_METALS, INDICATORS, SILVER_SALTS, STRONG_ACIDS, STRONG_BASES,
+        },
+        table::{TableExport, TableFormat},
+        temperature::{self, STANDARD_TEMPERATURE, WATER_PKW},
+        trace::TraceEvent,
+        uncertainty::COVERAGE_FACTOR,
+        units::XQuantity,
+        validation::{self, Warning, MAX_CONCENTRATION},
+        watch::{Debounce, EventFilter, WatchedFolder, WatcherBackend},
+        Output, Region, Response, SaveRequest, Signal, Worker, WorkerError,
+    },
 };
 
 use self::{
+    annotation::{AnnotationField, AnnotationForm, AnnotationKind},
+    burette::Burette,
+    calculator::{CalculatorField, CalculatorForm},
+    data::DataEditor,
+    diagram::{
+        Axis, AxisFormat, CrosshairPlot, DiagramFrame, ExportSize, GraphState, LegendPosition,
+        Plot, SECONDARY_PLOTS,
+    },
+    examples::Example,
+    form::parse_number,
+    generator::{GeneratorField, GeneratorForm},
     options::Options,
-    strings::{BUTTON_SELECT_FILE, MESSAGE_NO_CONTENT, OPTION_COLORED, OPTION_DARK, WINDOW_TITLE},
+    playback::{Playback, PLAYBACK_INTERVAL},
+    profile::{Feature, Profile},
+    render_cache::RenderCache,
+    session::Session,
+    simulation::{SimulationField, SimulationForm},
+    strings::*,
+    style::{ColorPalette, DiagramStyle, StyleColor, StyleForm},
+    toast::Toasts,
+    tutorial::TutorialStep,
 };
 
+/// The minimum time between two restarts of the worker.
+///
+/// This prevents restarting a worker which crashes right away in a loop.
+const RESTART_INTERVAL: Duration = Duration::from_secs(10);
+/// The time a notification about a restart of the worker is shown.
+const RESTART_NOTICE_DURATION: Duration = Duration::from_secs(5);
+/// The number of trace entries shown in the debug overlay.
+const DEBUG_ENTRIES: usize = 60;
+/// The pseudo path of the simulated titration in the file list.
+const SIMULATION_PATH: &str = "simulation";
+/// The pseudo path of the titration run with the virtual burette in the file list.
+const BURETTE_PATH: &str = "burette";
+/// The pseudo path of the titration measured with a pH meter in the file list.
+#[cfg(feature = "live")]
+const LIVE_PATH: &str = "live";
+/// The number of log entries shown in the log panel.
+const LOG_ENTRIES: usize = 200;
+/// The time each loaded file is shown while presentation mode cycles through them.
+const PRESENTATION_INTERVAL: Duration = Duration::from_secs(8);
+/// The maximum time the app waits for the worker to save pending work when it exits.
+const STOP_TIMEOUT: Duration = Duration::from_secs(2);
+/// The time between two checks of the theme of the operating system.
+const SYSTEM_THEME_INTERVAL: Duration = Duration::from_secs(2);
+/// The width of the control sidebar in px until it is resized.
+const SIDEBAR_WIDTH: f32 = 160.0;
+/// The range the width of the control sidebar can be dragged in px.
+const SIDEBAR_WIDTH_RANGE: (f32, f32) = (110.0, 480.0);
+/// The width of the handle at the edge of the sidebar that resizes it in px.
+const SIDEBAR_HANDLE_WIDTH: f32 = 6.0;
+
 #[derive(Clone, Debug)]
 pub enum Message {
     /// Sets the `dark` option.
     SetDark(bool),
+    /// Sets the `system_theme` option.
+    SetSystemTheme(bool),
+    /// Applies the theme preferred by the operating system, if the `system_theme` option is set.
+    SystemTheme { dark: bool },
     /// Sets the `colored` option.
     SetColored(bool),
+    /// Sets the `overlay` option.
+    SetOverlay(bool),
+    /// Sets the `ladder` option.
+    SetLadder(bool),
+    /// Sets the `smooth` option.
+    SetSmooth(bool),
+    /// Sets the `crosshair` option.
+    SetCrosshair(bool),
+    /// Sets the `sort_volumes` option.
+    SetSortVolumes(bool),
+    /// Changes the number of points of a curve drawn at most.
+    MaxPointsInput(String),
+    /// Sets the `explain` option.
+    SetExplain(bool),
+    /// Sets the `usage` option.
+    SetUsage(bool),
+    /// Sets the `fit` option.
+    SetFit(bool),
+    /// Sets the `activity` option.
+    SetActivity(bool),
+    /// Sets the `summary` option.
+    SetSummary(bool),
+    /// Selects the ratio of analyte and measuring solution in the result summary, or `None` for
+    /// the ratio of the file.
+    SetStoichiometry(Option<Stoichiometry>),
+    /// Selects the indicator whose titration error is shown in the result summary.
+    SetIndicator(usize),
+    /// Selects the data point to be explained.
+    ExplainPoint(u32),
+    /// Sets the volume under the cursor, if the cursor is over the diagram.
+    HoverGraph(Option<f32>),
+    /// Starts selecting a range of volumes at the cursor.
+    StartSelection,
+    /// Finishes selecting a range of volumes.
+    EndSelection,
+    /// Hides or shows the control sidebar.
+    ToggleSidebar,
+    /// Starts dragging the edge of the control sidebar.
+    StartSidebarResize,
+    /// Sets the width of the control sidebar to the position of the cursor in px.
+    ResizeSidebar(f32),
+    /// Finishes dragging the edge of the control sidebar.
+    EndSidebarResize,
     /// Opens a file dialog.
     SelectFile,
-    /// Processes the response queue.
-    Update(Instant),
+    /// Shows the content of a loaded file.
+    ShowFile(PathBuf),
+    /// Unloads a file.
+    UnloadFile(PathBuf),
+    /// Sets the `compare` option.
+    SetCompare(bool),
+    /// Shows a loaded file next to the selected one.
+    CompareFile(PathBuf),
+    /// Selects the plot shown in the diagram.
+    SelectPlot(Plot),
+    /// Selects the quantity of the added measuring solution on the x-axis.
+    SetXQuantity(XQuantity),
+    /// Selects the plot drawn against a second y-axis over the pH curve, or removes the axis.
+    SetSecondaryPlot(Option<Plot>),
+    /// Selects the acid of the conductivity plot by its index.
+    SetConductivityAcid(usize),
+    /// Selects the base of the conductivity plot by its index.
+    SetConductivityBase(usize),
+    /// Selects the salt of the precipitation plot by its index.
+    SetPrecipitationSalt(usize),
+    /// Plots the anion instead of the cation in the precipitation plot.
+    SetPrecipitationAnion(bool),
+    /// Selects the metal ion of the complexometry plot by its index.
+    SetComplexometryMetal(usize),
+    /// Sets the pH of the complexometry plot.
+    SetBufferPh(BufferPh),
+    /// Changes the number of decimals of the tick labels of an axis.
+    ///
+    /// An empty text shows as many decimals as needed.
+    AxisDecimalsInput(Axis, String),
+    /// Changes the number of decimals of reported volumes.
+    RoundingVolumeInput(String),
+    /// Changes the number of significant digits of reported concentrations and derived results.
+    RoundingDigitsInput(String),
+    /// Sets the notation of the number format.
+    SetNotation(Notation),
+    /// Changes the number of decimals or significant digits of the number format.
+    NumberDigitsInput(String),
+    /// Changes the unit of an axis.
+    AxisUnitInput(Axis, String),
+    /// Writes the unit of an axis after every tick label instead of once.
+    SetUnitOnTicks(Axis, bool),
+    /// Changes the title of an axis.
+    ///
+    /// An empty text derives the title from the plot.
+    AxisTitleInput(Axis, String),
+    /// Copies the rendered diagram to the clipboard.
+    CopyDiagram,
+    /// Exports the rendered diagram as an SVG file.
+    ExportDiagram,
+    /// Selects the size of exported diagrams.
+    SetExportSize(ExportSize),
+    /// Selects the corner of the legend, or hides it.
+    SetLegend(LegendPosition),
+    /// Sets the `legend_file_names` option.
+    SetLegendFileNames(bool),
+    /// Changes the name of the selected series in the legend.
+    SeriesNameInput(String),
+    /// Exports a worked solution for the selected file.
+    ExportSolution,
+    /// Exports the selected file as an HTML page with an interactive chart.
+    ExportHtml,
+    /// Exports the calculated values of the selected file as a table.
+    ExportTable(TableFormat),
+    /// Exports a locked PDF report of the selected file with the hash of its printed data.
+    ExportReport,
+    /// Sets the `generator` option.
+    SetGenerator(bool),
+    /// Changes a field of the problem generator.
+    GeneratorInput(GeneratorField, String),
+    /// Shows or hides the simulation.
+    SetSimulation(bool),
+    /// Changes a field of the simulation form.
+    SimulationInput(SimulationField, String),
+    /// Shows or hides the virtual burette.
+    SetBurette(bool),
+    /// Adds the given volume in mL from the virtual burette.
+    BuretteAdd(f32),
+    /// Removes the last data point added with the virtual burette.
+    BuretteUndo,
+    /// Starts over with a full virtual burette.
+    BuretteReset,
+    /// Shows or hides the live input from a pH meter.
+    #[cfg(feature = "live")]
+    SetLive(bool),
+    /// Changes a field of the live input.
+    #[cfg(feature = "live")]
+    LiveInput(live::LiveField, String),
+    /// Connects to the pH meter.
+    #[cfg(feature = "live")]
+    Connect,
+    /// Closes the connection to the pH meter.
+    #[cfg(feature = "live")]
+    Disconnect,
+    /// Removes the points measured with the pH meter.
+    #[cfg(feature = "live")]
+    ClearLive,
+    /// Shows or hides the calibration of the electrode.
+    #[cfg(feature = "live")]
+    SetCalibrating(bool),
+    /// Changes a field of a buffer of the calibration.
+    #[cfg(feature = "live")]
+    CalibrationInput(usize, calibration::CalibrationField, String),
+    /// Enters the last reading of the meter as the potential of a buffer.
+    #[cfg(feature = "live")]
+    TakeCalibrationReading(usize),
+    /// Reads the potential in mV from the meter and converts it with the entered calibration.
+    #[cfg(feature = "live")]
+    ApplyCalibration,
+    /// Reads the pH from the meter again.
+    #[cfg(feature = "live")]
+    RemoveCalibration,
+    /// Shows or hides the dilution calculator.
+    SetCalculator(bool),
+    /// Changes a field of the dilution calculator.
+    CalculatorInput(CalculatorField, String),
+    /// Shows or hides the style editor.
+    SetStyleEditor(bool),
+    /// Changes a color of the diagram style.
+    StyleColorInput(StyleColor, String),
+    /// Sets the radius of data points.
+    SetPointSize(f32),
+    /// Shows or hides the grid lines.
+    SetGrid(bool),
+    /// Selects the preset of the series and background colors.
+    SelectPalette(ColorPalette),
+    /// Draws the diagram with thicker lines and larger points.
+    SetHighContrast(bool),
+    /// Sets the font size of the diagram.
+    SetFontSize(f32),
+    /// Restores the default diagram style.
+    ResetStyle,
+    /// Sets the `outliers` option.
+    SetOutliers(bool),
+    /// Excludes the point at the given volume from the curve of the selected file, or includes it
+    /// again.
+    ToggleExcluded(f32),
+    /// Excludes the point nearest to the cursor from the curve of the selected file, or includes
+    /// it again.
+    ToggleHoveredPoint,
+    /// Shows or hides the editor of the data points of the selected file.
+    SetDataEditor(bool),
+    /// Changes the volume of the data point at the given index.
+    DataPointInput(usize, String),
+    /// Changes the measured pH of the data point at the given index.
+    DataPointPhInput(usize, String),
+    /// Adds an empty data point to the edited file.
+    AddDataPoint,
+    /// Removes the data point at the given index from the edited file.
+    RemoveDataPoint(usize),
+    /// Discards the edits of the data points.
+    ResetData,
+    /// Shows or hides the annotation editor.
+    SetAnnotating(bool),
+    /// Selects the kind of annotation to add.
+    SetAnnotationKind(AnnotationKind),
+    /// Changes a field of the annotation form.
+    AnnotationInput(AnnotationField, String),
+    /// Adds the annotation described by the form to the diagram.
+    AddAnnotation,
+    /// Removes the annotation at the given index.
+    RemoveAnnotation(usize),
+    /// Uses the target concentration of the dilution calculator as the measuring solution of the
+    /// simulation.
+    ApplyCalculator,
+    /// Exports the spreadsheet of the generated problem.
+    ExportProblem,
+    /// Exports the answer key of the generated problem.
+    ExportAnswerKey,
+    /// Sets the language of the UI.
+    SetLanguage(Language),
+    /// Sets the locale numbers are parsed and displayed in.
+    SetNumberLocale(NumberLocale),
+    /// Sets the `projector` option.
+    SetProjector(bool),
+    /// Enters or leaves presentation mode, which shows only the diagram on the full screen.
+    TogglePresentation,
+    /// Leaves presentation mode.
+    EndPresentation,
+    /// Sets the `presentation_cycle` option.
+    SetPresentationCycle(bool),
+    /// Shows the next loaded file while presenting.
+    PresentNext,
+    /// Pauses or resumes watching the loaded files.
+    SetPaused(bool),
+    /// Reloads all loaded files, even while watching is paused.
+    ReloadNow,
+    /// Sets the `event_filter` option.
+    SetEventFilter(EventFilter),
+    /// Sets the `debounce` option.
+    SetDebounce(u32),
+    /// Sets the `tail` option.
+    SetTail(bool),
+    /// Changes the pattern of the watched folder, which applies right away.
+    FolderPatternInput(String),
+    /// Requests a folder dialog for the folder whose newest table is loaded.
+    WatchFolder,
+    /// Stops watching the folder.
+    UnwatchFolder,
+    /// Changes the text of the working temperature, which sets the `temperature` option if valid.
+    TemperatureInput(String),
+    /// Selects the model the files are calculated with by its index.
+    SetModel(usize),
+    /// Requests a file dialog for the script of the scripted model.
+    #[cfg(feature = "scripting")]
+    LoadScript,
+    /// Reveals the next region of the curve.
+    RevealNext,
+    /// Hides the last revealed region of the curve.
+    RevealPrevious,
+    /// Sets the `playback` option, which starts again from the first data point.
+    SetPlayback(bool),
+    /// Starts or stops playing back the curve.
+    TogglePlaying,
+    /// Shows the given number of data points in the playback.
+    SeekPlayback(u32),
+    /// Reveals the next data point while playing.
+    PlaybackTick,
+    /// Loads a built-in example and selects it.
+    LoadExample(Example),
+    /// Starts the tutorial with the sample dataset.
+    StartTutorial,
+    /// Continues with the next step of the tutorial.
+    NextTutorialStep,
+    /// Ends the tutorial.
+    EndTutorial,
+    /// Shows or hides the debug overlay.
+    ToggleDebug,
+    /// Shows or hides the log panel.
+    SetLogPanel(bool),
+    /// Switches to a profile and stores it.
+    SetProfile(Profile),
+    /// Processes a response of the worker.
+    Response(Arc<Response>),
+    /// Removes the notification with the given id.
+    DismissToast(u64),
+    /// Updates the time-dependent parts of the view.
+    Tick(Instant),
+    /// Stops the worker and closes the window, or only closes the window of the diagram.
+    CloseRequested(window::Id),
+    /// Shows the diagram in its own window, e.g. on a projector.
+    DetachDiagram,
+    /// Closes the window of the diagram and shows it in the main window again.
+    AttachDiagram,
+    /// Loads the files passed to a later launch of the app.
+    OpenForwarded(Vec<PathBuf>),
+    /// Stops parsing the file whose progress is shown.
+    CancelLoading,
+}
+
+/// The flags the app is started with.
+#[derive(Clone, Debug, Default)]
+pub struct StartupFlags {
+    /// Starts with default preferences, without restoring the session and with the poll watcher.
+    ///
+    /// This is meant for users whose configuration or watcher setup is broken.
+    pub safe_mode: bool,
+    /// The files loaded at the start.
+    pub files: Vec<PathBuf>,
+    /// The paths forwarded by later launches of the app, if this is the running instance.
+    pub forwarded: Option<Arc<Mutex<UnboundedReceiver<Vec<PathBuf>>>>>,
+}
+
+impl StartupFlags {
+    /// Returns the preferred watcher backend.
+    fn watcher_backend(&self) -> WatcherBackend {
+        if self.safe_mode {
+            WatcherBackend::Poll
+        } else {
+            WatcherBackend::Native
+        }
+    }
+}
+
+pub struct TitrationCurve {
+    flags: StartupFlags,
+    options: Options,
+    worker: Arc<Worker>,
+    response_receiver: Arc<Mutex<UnboundedReceiver<Response>>>,
+    /// The number of times the worker was spawned.
+    ///
+    /// Identifies the subscription to the responses of the current worker.
+    worker_generation: usize,
+    /// The loaded files.
+    ///
+    /// Each file maps to either its output or an error message.
+    files: BTreeMap<PathBuf, Either<Arc<Output>, String>>,
+    /// The file whose content is shown in the window.
+    selected: Option<PathBuf>,
+    /// The file shown next to the selected one while comparing.
+    compared: Option<PathBuf>,
+    /// The index of the data point explained in the explain panel.
+    explained_point: u32,
+    /// The volume under the cursor in mL, if the cursor is over the diagram.
+    hovered_volume: Option<f32>,
+    /// The selected range of volumes in mL, in the order it was dragged.
+    selection: Option<(f32, f32)>,
+    /// Whether the selection is being dragged.
+    selecting: bool,
+    /// Whether the edge of the control sidebar is being dragged.
+    resizing_sidebar: bool,
+    /// The notifications about errors and finished actions.
+    toasts: Toasts,
+    /// The form of the problem generator.
+    generator: GeneratorForm,
+    /// The form of the simulation.
+    simulation: SimulationForm,
+    /// The titration run by hand with the virtual burette.
+    burette: Burette,
+    /// The titration measured with a pH meter.
+    #[cfg(feature = "live")]
+    live: live::LiveForm,
+    /// The buffers calibrating the electrode of the pH meter.
+    #[cfg(feature = "live")]
+    calibration: calibration::CalibrationForm,
+    /// The form of the dilution calculator.
+    calculator: CalculatorForm,
+    /// The form adding annotations to the diagram.
+    annotation: AnnotationForm,
+    /// The texts of the style editor.
+    style: StyleForm,
+    /// The recently rendered diagrams.
+    render_cache: RenderCache,
+    /// The edited data points of a loaded file.
+    data_editor: DataEditor,
+    /// The volumes of the points excluded from the curve of every file.
+    excluded: BTreeMap<PathBuf, Vec<f32>>,
+    /// The names of the series in the legend entered by the user.
+    series_names: BTreeMap<PathBuf, String>,
+    /// The errors of files which failed to reload, mapped from their paths.
+    ///
+    /// The last output of such a file is still shown until the file can be read again.
+    stale: BTreeMap<PathBuf, String>,
+    /// The text of the working temperature.
+    temperature: String,
+    /// The pattern the tables of the watched folder have to match.
+    folder_pattern: String,
+    /// The folder whose newest table is loaded, once the worker watches it.
+    watched_folder: Option<PathBuf>,
+    /// The last region of the curve revealed in projector mode.
+    reveal: Region,
+    /// The playback revealing the curve point by point.
+    playback: Playback,
+    /// The plot shown in the diagram.
+    plot: Plot,
+    /// Shows the trace of the worker for debugging.
+    debug: bool,
+    /// Shows the recent log entries.
+    log_panel: bool,
+    /// Whether watching the loaded files is paused.
+    paused: bool,
+    /// The number of changes to the loaded files while paused.
+    pending_changes: u32,
+    /// The time the worker was last restarted after it died.
+    restarted_at: Option<Instant>,
+    /// Whether the window is closing, so the stopped worker is not restarted.
+    closing: bool,
+    /// Whether the diagram fills the screen in presentation mode.
+    presenting: bool,
+    /// The window the diagram is shown in, if it was detached from the main window.
+    diagram_window: Option<window::Id>,
+    /// The file being parsed with the number of read rows and of all rows.
+    progress: Option<(PathBuf, u32, u32)>,
+    /// The current step of the tutorial, while it is shown.
+    tutorial: Option<TutorialStep>,
+    /// The time of the last successful parse of every loaded file.
+    loaded_at: BTreeMap<PathBuf, SystemTime>,
+    /// The preset of the shown features.
+    profile: Profile,
+}
+
+impl TitrationCurve {
+    /// Returns the outputs drawn in the diagram.
+    ///
+    /// If the `overlay` option is set, all loaded outputs are drawn, otherwise only the output of
+    /// the selected file.
+    fn shown_outputs(&self) -> Vec<&Output> {
+        self.shown_files()
+            .into_iter()
+            .map(|(_, output)| output.as_ref())
+            .collect()
+    }
+
+    /// Returns the names of the series drawn in the diagram, in the order of their outputs.
+    fn shown_names(&self) -> Vec<String> {
+        self.shown_files()
+            .into_iter()
+            .map(|(path, output)| self.series_name(path, output))
+            .collect()
+    }
+
+    /// Returns the files drawn in the diagram with their outputs.
+    fn shown_files(&self) -> Vec<(&Path, &Arc<Output>)> {
+        if self.options.overlay {
+            return self
+                .files
+                .iter()
+                .filter_map(|(path, it)| match it {
+                    Left(output) => Some((path.as_path(), output)),
+                    Right(_) => None,
+                })
+                .collect();
+        }
+        let Some(path) = &self.selected else {
+            return Vec::new();
+        };
+        match self.files.get(path) {
+            Some(Left(output)) => vec![(path.as_path(), output)],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns the name of the series of a file in the legend.
+    ///
+    /// A name entered by the user comes first, then the name of the worksheet, unless the files
+    /// name the series, and finally the name of the file.
+    fn series_name(&self, path: &Path, output: &Output) -> String {
+        if let Some(name) = self
+            .series_names
+            .get(path)
+            .filter(|it| !it.trim().is_empty())
+        {
+            return name.trim().to_owned();
+        }
+        match &output.sheet {
+            Some(sheet) if !self.options.legend_file_names => sheet.clone(),
+            _ => file_stem(path),
+        }
+    }
+
+    /// Returns the output of the selected file.
+    fn selected_output(&self) -> Option<&Arc<Output>> {
+        match self.selected.as_ref().and_then(|it| self.files.get(it)) {
+            Some(Left(output)) => Some(output),
+            _ => None,
+        }
+    }
+
+    /// Returns the series of the selected file in the diagram.
+    fn selected_series(&self) -> Option<usize> {
+        if !self.options.overlay {
+            return self.selected_output().map(|_| 0);
+        }
+        let selected = self.selected.as_ref()?;
+        self.files
+            .iter()
+            .filter(|(_, it)| matches!(it, Left(_)))
+            .position(|(path, _)| path == selected)
+    }
+
+    /// Returns the index of the explained data point, if the explain panel is shown.
+    fn explained_index(&self) -> Option<usize> {
+        if !self.options.explain {
+            return None;
+        }
+        let count = self.selected_output()?.items.len();
+        if count == 0 {
+            return None;
+        }
+        Some((self.explained_point as usize).min(count - 1))
+    }
+
+    /// Returns the state of the diagram.
+    fn graph_state(&self) -> GraphState {
+        let highlighted = match self.tutorial {
+            Some(TutorialStep::ReadEquivalence) => self
+                .selected_output()
+                .map(|it| it.equivalence_index().saturating_sub(1)),
+            _ => self.explained_index(),
+        };
+        GraphState {
+            plot: self.plot,
+            highlight: self.selected_series().zip(highlighted),
+            reveal: self.options.projector.then_some(self.reveal),
+            playback: self.options.playback.then_some(self.playback.shown),
+            selection: self.selection,
+            size: ExportSize::Screen,
+            only: None,
+        }
+    }
+
+    /// Returns the outputs of the selected and the compared file, if both are loaded and differ.
+    fn compared_outputs(&self) -> Option<[&Arc<Output>; 2]> {
+        if !self.options.compare || self.compared == self.selected {
+            return None;
+        }
+        let output = |path: &Option<PathBuf>| match self.files.get(path.as_ref()?) {
+            Some(Left(output)) => Some(output),
+            _ => None,
+        };
+        Some([output(&self.selected)?, output(&self.compared)?])
+    }
+
+    /// Shows the curves of the selected and the compared file side by side.
+    ///
+    /// Both diagrams are scaled to both files, so their axes match, and the equivalence point of
+    /// every file is summarized below its diagram.
+    fn comparison_view(&self, outputs: [&Arc<Output>; 2]) -> Row<'_, Message> {
+        let paths = [&self.selected, &self.compared];
+        let names = outputs
+            .iter()
+            .zip(paths)
+            .map(|(output, path)| match path {
+                Some(path) => self.series_name(path, output),
+                None => String::new(),
+            })
+            .collect::<Vec<_>>();
+        let sides = outputs
+            .iter()
+            .zip(paths)
+            .enumerate()
+            .map(|(index, (output, path))| {
+                let name = path
+                    .as_ref()
+                    .and_then(|it| it.file_name())
+                    .map(|it| it.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let state = GraphState {
+                    only: Some(index),
+                    ..self.graph_state()
+                };
+                let handle = self
+                    .render_cache
+                    .get(&self.options, &outputs, &names, state, || {
+                        let plain = outputs.map(|it| it.as_ref());
+                        diagram::render_graph(&self.options, &plain, &names, state)
+                    });
+                let graph = svg(handle)
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .content_fit(ContentFit::Contain);
+                let rounding = self.options.rounding;
+                let summary =
+                    match analysis::analyte_concentration(output, self.stoichiometry(output)) {
+                        Some(result) => format!(
+                            "V(eq) = {} mL, {SUMMARY_ANALYTE} = {} mol/L",
+                            rounding.volume(result.equivalence_volume),
+                            rounding.value(result.concentration)
+                        ),
+                        None => SUMMARY_NO_EQUIVALENCE.into(),
+                    };
+                column![text(name), graph, text(summary).size(12)]
+                    .spacing(5)
+                    .width(Length::Fill)
+                    .into()
+            });
+        Row::with_children(sides.collect::<Vec<Element<Message>>>()).spacing(10)
+    }
+
+    /// Creates the buttons stepping through the regions of the curve.
+    fn reveal_controls(&self) -> Row<'_, Message> {
+        let mut previous_button = button(BUTTON_REVEAL_PREVIOUS.get());
+        if self.reveal.previous().is_some() {
+            previous_button = previous_button.on_press(Message::RevealPrevious);
+        }
+        let mut next_button = button(BUTTON_REVEAL_NEXT.get());
+        if self.reveal.next().is_some() {
+            next_button = next_button.on_press(Message::RevealNext);
+        }
+        row![
+            previous_button,
+            next_button,
+            text(diagram::region_name(self.reveal))
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center)
+    }
+
+    /// Returns the number of data points of the selected file played back.
+    fn playback_len(&self) -> usize {
+        self.selected_output().map_or(0, |it| it.items.len())
+    }
+
+    /// Creates the controls of the playback with the flask colored like the universal indicator
+    /// at the last revealed data point.
+    fn playback_controls(&self) -> Option<Row<'_, Message>> {
+        let output = self.selected_output()?;
+        let count = output.items.len();
+        let item = output
+            .items
+            .get(self.playback.shown.min(count).checked_sub(1)?)?;
+        let play_label = if self.playback.playing {
+            BUTTON_PAUSE
+        } else {
+            BUTTON_PLAY
+        };
+        let shown = self.playback.shown as u32;
+        let mut previous_button = button(BUTTON_REVEAL_PREVIOUS.get());
+        if shown > 1 {
+            previous_button = previous_button.on_press(Message::SeekPlayback(shown - 1));
+        }
+        let mut next_button = button(BUTTON_REVEAL_NEXT.get());
+        if (shown as usize) < count {
+            next_button = next_button.on_press(Message::SeekPlayback(shown + 1));
+        }
+        let controls = column![
+            row![
+                button(play_label.get()).on_press(Message::TogglePlaying),
+                previous_button,
+                next_button,
+            ]
+            .spacing(5),
+            slider(1..=count as u32, shown, Message::SeekPlayback).width(Length::Fixed(300.0)),
+            text(format!(
+                "V(m) = {} mL, pH = {:.2}",
+                number(item.m_v),
+                item.ph
+            )),
+        ]
+        .spacing(5);
+        Some(
+            row![indicator_swatch(item.ph, 40.0, 60.0), controls]
+                .spacing(10)
+                .align_items(Alignment::Center),
+        )
+    }
+
+    fn axis_format(&mut self, axis: Axis) -> &mut AxisFormat {
+        match axis {
+            Axis::X => &mut self.options.x_axis,
+            Axis::Y => &mut self.options.y_axis,
+        }
+    }
+
+    /// Creates the selection of the quantity on the x-axis.
+    fn x_quantity_select(&self) -> Column<'_, Message> {
+        let quantities = XQuantity::ALL.map(|it| {
+            radio(
+                x_quantity_name(it),
+                it,
+                Some(self.options.x_quantity),
+                Message::SetXQuantity,
+            )
+            .size(12)
+            .text_size(12)
+            .into()
+        });
+        column![
+            text(X_QUANTITY_TITLE).size(12),
+            Column::with_children(quantities.into_iter().collect::<Vec<_>>()).spacing(2),
+        ]
+        .spacing(3)
+    }
+
+    /// Creates the inputs for the tick labels of an axis.
+    fn axis_controls(&self, axis: Axis) -> Column<'_, Message> {
+        let (label, format) = match axis {
+            Axis::X => ("x", &self.options.x_axis),
+            Axis::Y => ("y", &self.options.y_axis),
+        };
+        let decimals = format.decimals.map(|it| it.to_string()).unwrap_or_default();
+        column![
+            row![
+                text(label),
+                text_input(AXIS_AUTO_DECIMALS.get(), &decimals)
+                    .on_input(move |it| Message::AxisDecimalsInput(axis, it))
+                    .width(Length::Fixed(50.0)),
+                text_input(AXIS_UNIT.get(), &format.unit)
+                    .on_input(move |it| Message::AxisUnitInput(axis, it))
+                    .width(Length::Fixed(70.0)),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center),
+            text_input(AXIS_TITLE_INPUT.get(), &format.title)
+                .on_input(move |it| Message::AxisTitleInput(axis, it))
+                .size(12),
+            checkbox(AXIS_UNIT_ON_TICKS, format.unit_on_ticks, move |it| {
+                Message::SetUnitOnTicks(axis, it)
+            })
+            .size(12)
+            .text_size(12),
+        ]
+        .spacing(2)
+    }
+
+    /// Creates the position of the legend and the name of the selected series.
+    fn legend_controls(&self) -> Column<'_, Message> {
+        let positions = LegendPosition::ALL.map(|it| {
+            radio(
+                it.label(),
+                it,
+                Some(self.options.legend),
+                Message::SetLegend,
+            )
+            .size(12)
+            .text_size(12)
+            .into()
+        });
+        let name = self
+            .selected
+            .as_ref()
+            .and_then(|it| self.series_names.get(it))
+            .map_or("", String::as_str);
+        let mut name_input = text_input(LEGEND_SERIES_NAME.get(), name).size(12);
+        if self.selected_output().is_some() {
+            name_input = name_input.on_input(Message::SeriesNameInput);
+        }
+        column![
+            text(LEGEND_TITLE),
+            Column::with_children(positions.into_iter().collect::<Vec<_>>()).spacing(2),
+            checkbox(
+                LEGEND_FILE_NAMES,
+                self.options.legend_file_names,
+                Message::SetLegendFileNames
+            )
+            .size(12)
+            .text_size(12),
+            name_input,
+        ]
+        .spacing(3)
+    }
+
+    /// Creates the inputs of the rounding rules of reported results.
+    fn rounding_controls(&self) -> Column<'_, Message> {
+        let rounding = self.options.rounding;
+        let input = |placeholder: Localized, value: Option<u8>, message: fn(String) -> Message| {
+            let value = value.map(|it| it.to_string()).unwrap_or_default();
+            row![
+                text(placeholder).size(12).width(Length::Fill),
+                text_input(AXIS_AUTO_DECIMALS.get(), &value)
+                    .on_input(message)
+                    .width(Length::Fixed(50.0)),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        };
+        column![
+            text(ROUNDING_TITLE),
+            input(
+                ROUNDING_VOLUME_DECIMALS,
+                rounding.volume_decimals,
+                Message::RoundingVolumeInput
+            ),
+            input(
+                ROUNDING_SIGNIFICANT_DIGITS,
+                rounding.significant_digits,
+                Message::RoundingDigitsInput
+            ),
+        ]
+        .spacing(2)
+    }
+
+    /// Creates the selection of the notation of numbers and the input of its digits.
+    fn number_format_controls(&self) -> Column<'_, Message> {
+        let format = self.options.number_format;
+        let notations = Notation::ALL.map(|it| {
+            radio(
+                notation_label(it),
+                it,
+                Some(format.notation),
+                Message::SetNotation,
+            )
+            .size(12)
+            .text_size(12)
+            .into()
+        });
+        let mut controls = column![
+            text(NUMBER_FORMAT_TITLE),
+            Column::with_children(notations).spacing(2),
+        ]
+        .spacing(2);
+        if format.notation != Notation::Auto {
+            controls = controls.push(
+                row![
+                    text(NUMBER_FORMAT_DIGITS).size(12).width(Length::Fill),
+                    text_input("", &format.digits.to_string())
+                        .on_input(Message::NumberDigitsInput)
+                        .width(Length::Fixed(50.0)),
+                ]
+                .spacing(5)
+                .align_items(Alignment::Center),
+            );
+        }
+        controls
+    }
+
+    /// Creates the selection of the plot drawn against a second y-axis over the pH curve, with
+    /// the settings of the selected plot.
+    fn secondary_controls(&self) -> Column<'_, Message> {
+        let plots = [None]
+            .into_iter()
+            .chain(SECONDARY_PLOTS.map(Some))
+            .map(|it| {
+                let label = match it {
+                    None => SECONDARY_AXIS_NONE,
+                    Some(Plot::Derivative) => PLOT_DERIVATIVE,
+                    Some(Plot::Conductivity) => PLOT_CONDUCTIVITY,
+                    Some(Plot::Precipitation) => PLOT_PRECIPITATION,
+                    Some(Plot::Complexometry) => PLOT_COMPLEXOMETRY,
+                    Some(_) => PLOT_TEMPERATURE,
+                };
+                radio(
+                    label,
+                    it,
+                    Some(self.options.secondary_plot),
+                    Message::SetSecondaryPlot,
+                )
+                .size(12)
+                .text_size(12)
+                .into()
+            })
+            .collect::<Vec<_>>();
+        let controls = column![
+            text(SECONDARY_AXIS_TITLE).size(12),
+            Column::with_children(plots).spacing(2),
+        ]
+        .spacing(3);
+        match self.options.secondary_plot {
+            Some(Plot::Conductivity) => controls.push(self.conductivity_controls()),
+            Some(Plot::Precipitation) => controls.push(self.precipitation_controls()),
+            Some(Plot::Complexometry) => controls.push(self.complexometry_controls()),
+            _ => controls,
+        }
+    }
+
+    /// Creates the selection of the acid and base whose ions conduct in the conductivity plot.
+    fn conductivity_controls(&self) -> Column<'_, Message> {
+        let substance_radio = |substance: &Substance, index, selected, message: fn(usize) -> _| {
+            radio(substance.formula, index, Some(selected), message)
+                .size(12)
+                .text_size(12)
+        };
+        let acids = STRONG_ACIDS.iter().enumerate().map(|(index, it)| {
+            substance_radio(
+                it,
+                index,
+                self.options.conductivity_acid,
+                Message::SetConductivityAcid,
+            )
+            .into()
+        });
+        let bases = STRONG_BASES.iter().enumerate().map(|(index, it)| {
+            substance_radio(
+                it,
+                index,
+                self.options.conductivity_base,
+                Message::SetConductivityBase,
+            )
+            .into()
+        });
+        column![
+            text(PLOT_CONDUCTIVITY_ACID).size(12),
+            Column::with_children(acids).spacing(2),
+            text(PLOT_CONDUCTIVITY_BASE).size(12),
+            Column::with_children(bases).spacing(2),
+        ]
+        .spacing(3)
+    }
+
+    /// Creates the selection of the salt and ion of the precipitation plot.
+    fn precipitation_controls(&self) -> Column<'_, Message> {
+        let salts = SILVER_SALTS.iter().enumerate().map(|(index, it)| {
+            radio(
+                it.formula,
+                index,
+                Some(self.options.precipitation_salt),
+                Message::SetPrecipitationSalt,
+            )
+            .size(12)
+            .text_size(12)
+            .into()
+        });
+        column![
+            Column::with_children(salts).spacing(2),
+            checkbox(
+                PLOT_PRECIPITATION_ANION,
+                self.options.precipitation_anion,
+                Message::SetPrecipitationAnion
+            )
+            .size(12)
+            .text_size(12),
+        ]
+        .spacing(3)
+    }
+
+    /// Creates the selection of the metal ion and the pH of the complexometry plot.
+    fn complexometry_controls(&self) -> Column<'_, Message> {
+        let metals = EDTA_METALS.iter().enumerate().map(|(index, it)| {
+            radio(
+                it.formula,
+                index,
+                Some(self.options.complexometry_metal),
+                Message::SetComplexometryMetal,
+            )
+            .size(12)
+            .text_size(12)
+            .into()
+        });
+        let ph = self.options.buffer_ph.0;
+        column![
+            Column::with_children(metals).spacing(2),
+            text(format!("{PLOT_COMPLEXOMETRY_PH}: {ph:.1}")).size(12),
+            slider(7.0..=13.0, ph, |it| Message::SetBufferPh(BufferPh(it))).step(0.5),
+        ]
+        .spacing(3)
+    }
+
+    /// Creates the panel next to the graph with the concentration determined from the selected
+    /// file.
+    fn summary_panel(&self) -> Column<'_, Message> {
+        let output = self.selected_output();
+        let ratios = iter::once(None)
+            .chain(Stoichiometry::COMMON.map(Some))
+            .map(|it| {
+                let label = match (it, output) {
+                    (Some(ratio), _) => format!("{} : {}", ratio.analyte, ratio.titrant),
+                    (None, Some(output)) => {
+                        let ratio = output.input.stoichiometry;
+                        format!(
+                            "{SUMMARY_STOICHIOMETRY_FILE} ({} : {})",
+                            ratio.analyte, ratio.titrant
+                        )
+                    }
+                    (None, None) => SUMMARY_STOICHIOMETRY_FILE.into(),
+                };
+                radio(
+                    label,
+                    it,
+                    Some(self.options.stoichiometry),
+                    Message::SetStoichiometry,
+                )
+                .size(12)
+                .text_size(12)
+                .into()
+            });
+        let mut panel = column![
+            text(SUMMARY_TITLE),
+            text(SUMMARY_STOICHIOMETRY).size(12),
+            Column::with_children(ratios.collect::<Vec<_>>()).spacing(2),
+        ]
+        .spacing(5)
+        .width(Length::Fixed(260.0));
+        let result = output.and_then(|output| {
+            let stoichiometry = self.stoichiometry(output);
+            Some((
+                output,
+                stoichiometry,
+                analysis::analyte_concentration(output, stoichiometry)?,
+            ))
+        });
+        let Some((output, stoichiometry, result)) = result else {
+            let panel = panel.push(text(SUMMARY_NO_EQUIVALENCE).size(12));
+            return match output {
+                Some(output) => panel
+                    .push(self.indicator_panel(output))
+                    .push(self.titration_error_panel(output)),
+                None => panel,
+            };
+        };
+        let rounding = self.options.rounding;
+        let input = &output.input;
+        let mut concentration = rounding.value(result.concentration);
+        if result.uncertainty > 0.0 {
+            _ = write!(
+                concentration,
+                " ± {}",
+                rounding.value(COVERAGE_FACTOR * result.uncertainty)
+            );
+        }
+        let lines = [
+            format!("V(eq) = {} mL", rounding.volume(result.equivalence_volume)),
+            format!("f = {} : {}", stoichiometry.analyte, stoichiometry.titrant),
+            "c(t) = f · c(m) · V(eq) / V(t)".to_string(),
+            format!(
+                "= {} · {} mol/L · {} mL / {} mL",
+                number(stoichiometry.factor()),
+                number(input.m_c),
+                rounding.volume(result.equivalence_volume),
+                rounding.volume(input.t_v * 1000.0)
+            ),
+            format!("= {concentration} mol/L"),
+        ];
+        for line in lines {
+            panel = panel.push(text(line).size(12));
+        }
+        panel
+            .push(text(format!("{SUMMARY_ANALYTE}: {concentration} mol/L")))
+            .push(self.indicator_panel(output))
+            .push(self.titration_error_panel(output))
+    }
+
+    /// Recommends the indicators changing their color at the equivalence point of a file.
+    fn indicator_panel(&self, output: &Output) -> Column<'_, Message> {
+        let panel = column![text(INDICATOR_TITLE)].spacing(2);
+        let Some(recommendation) = indicator::recommend(output) else {
+            return panel.push(text(INDICATOR_NOT_REACHED).size(12));
+        };
+        let ph = recommendation.equivalence_ph;
+        let range = |it: &Indicator| {
+            format!(
+                "{} ({:.1} – {:.1})",
+                it.name, it.transition.0, it.transition.1
+            )
+        };
+        let mut lines = vec![format!("pH(eq) = {ph:.2}")];
+        match recommendation.nearest {
+            Some(nearest) => {
+                lines.push(format!("{INDICATOR_NONE} {ph:.2}"));
+                lines.push(format!("{INDICATOR_NEAREST}: {}", range(nearest)));
+            }
+            None => {
+                lines.push(format!("{INDICATOR_REASON} {ph:.2}:"));
+                for (index, indicator) in recommendation.suitable.iter().enumerate() {
+                    let mut line = range(indicator);
+                    if index == 0 && recommendation.suitable.len() > 1 {
+                        _ = write!(line, ", {INDICATOR_BEST}");
+                    }
+                    lines.push(line);
+                }
+            }
+        }
+        lines
+            .into_iter()
+            .fold(panel, |panel, line| panel.push(text(line).size(12)))
+    }
+
+    /// Creates the selection of an indicator with the titration error it causes for a file.
+    fn titration_error_panel(&self, output: &Output) -> Column<'_, Message> {
+        let radios = INDICATORS.iter().enumerate().map(|(index, it)| {
+            radio(
+                it.name,
+                index,
+                self.options.indicator,
+                Message::SetIndicator,
+            )
+            .size(12)
+            .text_size(12)
+            .into()
+        });
+        let panel = column![
+            text(INDICATOR_ERROR_TITLE).size(12),
+            Column::with_children(radios.collect::<Vec<_>>()).spacing(2),
+        ]
+        .spacing(2);
+        let Some(indicator) = self.options.indicator.and_then(|it| INDICATORS.get(it)) else {
+            return panel;
+        };
+        let Some(error) = indicator::titration_error(output, indicator) else {
+            return panel.push(text(INDICATOR_ERROR_UNREACHED).size(12));
+        };
+        let rounding = self.options.rounding;
+        let lines = [
+            format!(
+                "V(pH {:.1}) = {} mL",
+                error.endpoint_ph,
+                rounding.volume(error.endpoint_volume)
+            ),
+            format!(
+                "{INDICATOR_ERROR}: {}{} mL ({:+.2} %)",
+                if error.volume() >= 0.0 { "+" } else { "" },
+                rounding.volume(error.volume()),
+                error.relative() * 100.0
+            ),
+        ];
+        lines
+            .into_iter()
+            .fold(panel, |panel, line| panel.push(text(line).size(12)))
+    }
+
+    /// Reports the parameters of the model fitted to the selected file and its residuals.
+    fn fit_panel(&self) -> Option<Column<'_, Message>> {
+        let output = self.selected_output()?;
+        let panel = column![text(FIT_TITLE).size(12)].spacing(2);
+        let Some(fit) = fit::fit(output) else {
+            return Some(panel.push(text(FIT_TOO_FEW_POINTS).size(12)));
+        };
+        // The parameters of a fit which did not converge are no result.
+        if !fit.converged {
+            let line = format!("{FIT_ITERATIONS}: {} ({FIT_NOT_CONVERGED})", fit.iterations);
+            return Some(panel.push(text(line).size(12)));
+        }
+        let rounding = self.options.rounding;
+        let mut lines = vec![
+            format!(
+                "c(t) = {} mol/L, pKa = {:.2}",
+                rounding.value(fit.model.test_concentration),
+                fit.model.pka
+            ),
+            format!("{FIT_RMS}: {:.3}", fit.rms),
+        ];
+        if let Some((volume, residual)) = fit.largest_residual() {
+            lines.push(format!(
+                "{FIT_LARGEST_RESIDUAL}: {residual:+.3} (V(m) = {} mL)",
+                rounding.volume(volume)
+            ));
+        }
+        lines.push(format!("{FIT_ITERATIONS}: {}", fit.iterations));
+        Some(
+            lines
+                .into_iter()
+                .fold(panel, |panel, line| panel.push(text(line).size(12))),
+        )
+    }
+
+    /// Reports how much the activities of the ions shift the curve of the selected file.
+    fn activity_panel(&self) -> Option<Column<'_, Message>> {
+        let output = self.selected_output()?;
+        let rounding = self.options.rounding;
+        let mut panel = column![text(ACTIVITY_TITLE).size(12)].spacing(2);
+        let ionic_strength = output
+            .items
+            .iter()
+            .filter_map(|it| activity::ionic_strength(&output.input, it))
+            .filter(|it| it.is_finite())
+            .max_by(f32::total_cmp);
+        if let Some(ionic_strength) = ionic_strength {
+            panel = panel.push(
+                text(format!(
+                    "{ACTIVITY_IONIC_STRENGTH}: I = {} mol/L",
+                    rounding.value(ionic_strength)
+                ))
+                .size(12),
+            );
+        }
+        let shift = activity::corrected_curve(output)
+            .into_iter()
+            .zip(&output.items)
+            .map(|((m_v, ph), item)| (m_v, ph - item.ph))
+            .filter(|(_, shift)| shift.is_finite())
+            .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()));
+        if let Some((volume, shift)) = shift {
+            panel = panel.push(
+                text(format!(
+                    "{ACTIVITY_LARGEST_SHIFT}: ΔpH = {shift:+.3} (V(m) = {} mL)",
+                    rounding.volume(volume)
+                ))
+                .size(12),
+            );
+        }
+        Some(panel)
+    }
+
+    /// Lists the implausible values of the selected file, if there are any.
+    ///
+    /// Data which is not loaded from a file is left out, since it is checked while it is entered.
+    fn warnings_panel(&self) -> Option<Column<'_, Message>> {
+        if !self.selected.as_ref()?.is_file() {
+            return None;
+        }
+        let warnings = validation::validate(&self.selected_output()?.input);
+        if warnings.is_empty() {
+            return None;
+        }
+        let mut panel = column![text(WARNINGS_TITLE).size(12)].spacing(2);
+        for warning in warnings {
+            panel = panel.push(text(warning_message(&warning)).size(12));
+        }
+        Some(panel)
+    }
+
+    /// Reports the equivalence volume extrapolated from the Gran plot of the selected file.
+    fn gran_report(&self) -> Option<Text<'_>> {
+        let volume = gran::gran(self.selected_output()?).equivalence_volume()?;
+        Some(
+            text(format!(
+                "{PLOT_GRAN_EQUIVALENCE} = {} mL",
+                self.options.rounding.volume(volume)
+            ))
+            .size(12),
+        )
+    }
+
+    /// Stores the session, unless the app runs in safe mode.
+    fn save_session(&self) {
+        if self.flags.safe_mode {
+            return;
+        }
+        session::save(&Session {
+            annotations: self.options.annotations.clone(),
+            #[cfg(feature = "live")]
+            calibration: self.options.calibration.clone(),
+        });
+    }
+
+    /// Shows the simulated titration like a loaded file, or removes it if the simulation is off.
+    fn update_simulation(&mut self) {
+        let path = PathBuf::from(SIMULATION_PATH);
+        if !self.options.simulation {
+            self.files.remove(&path);
+            if self.selected.as_ref() == Some(&path) {
+                self.selected = self.files.keys().next().cloned();
+            }
+            return;
+        }
+        let content = match self.simulation.simulation() {
+            Some(simulation) => Left(Arc::new(simulation.output())),
+            None => Right(GENERATOR_INVALID.into()),
+        };
+        self.files.insert(path.clone(), content);
+        self.selected = Some(path);
+    }
+
+    /// Shows the titration run with the virtual burette like a loaded file, or removes it if the
+    /// burette is off.
+    ///
+    /// The parameters of the titration are taken from the simulation form.
+    fn update_burette(&mut self) {
+        let path = PathBuf::from(BURETTE_PATH);
+        if !self.options.burette {
+            self.files.remove(&path);
+            if self.selected.as_ref() == Some(&path) {
+                self.selected = self.files.keys().next().cloned();
+            }
+            return;
+        }
+        let content = match self.simulation.simulation() {
+            Some(simulation) => Left(Arc::new(self.burette.output(&simulation))),
+            None => Right(GENERATOR_INVALID.into()),
+        };
+        self.files.insert(path.clone(), content);
+        self.selected = Some(path);
+    }
+
+    /// Shows the titration measured with a pH meter like a loaded file, or removes it if the live
+    /// input is off.
+    #[cfg(feature = "live")]
+    fn update_live(&mut self) {
+        let path = PathBuf::from(LIVE_PATH);
+        if !self.options.live {
+            self.files.remove(&path);
+            if self.selected.as_ref() == Some(&path) {
+                self.selected = self.files.keys().next().cloned();
+            }
+            return;
+        }
+        let temperature = self.options.temperature.unwrap_or(STANDARD_TEMPERATURE);
+        let output = crate::worker::live::output(self.live.points(), temperature);
+        let output = self.prepared(&path, Arc::new(output));
+        self.files.insert(path.clone(), Left(output));
+        self.selected = Some(path);
+    }
+
+    /// Returns the ratio of analyte and measuring solution used for the result of an output.
+    ///
+    /// The ratio chosen in the summary overrides the one of the file.
+    fn stoichiometry(&self, output: &Output) -> Stoichiometry {
+        self.options
+            .stoichiometry
+            .unwrap_or(output.input.stoichiometry)
+    }
+
+    /// Loads a built-in example like a file and selects it.
+    fn load_example(&mut self, example: Example) {
+        let (path, output) = example.load();
+        info!(path = %path.display(), "Loaded an example");
+        let output = self.prepared(&path, output);
+        self.files.insert(path.clone(), Left(output));
+        self.selected = Some(path);
+    }
+
+    /// Starts the tutorial and shows the sample dataset.
+    fn start_tutorial(&mut self) {
+        let (path, output) = tutorial::sample();
+        self.files.insert(path.clone(), Left(output));
+        self.selected = Some(path);
+        self.tutorial = Some(TutorialStep::FIRST);
+    }
+
+    /// Continues with the next step of the tutorial, or ends it after the last step.
+    fn next_tutorial_step(&mut self) {
+        self.tutorial = self.tutorial.and_then(TutorialStep::next);
+        if self.tutorial.is_none() {
+            tutorial::mark_done();
+        }
+    }
+
+    /// Returns the diagram of the shown outputs, which reports the volume under the cursor and
+    /// selects ranges or excludes points by clicking.
+    fn graph<'a>(&'a self, outputs: &[&Output]) -> Element<'a, Message> {
+        let presentation;
+        let options = if self.presenting {
+            presentation = Options {
+                style: self.options.style.presentation(),
+                ..self.options.clone()
+            };
+            &presentation
+        } else {
+            &self.options
+        };
+        let names = self.shown_names();
+        let shown: Vec<_> = self.shown_files().into_iter().map(|(_, it)| it).collect();
+        let state = self.graph_state();
+        let handle = self.render_cache.get(options, &shown, &names, state, || {
+            let started = Instant::now();
+            let svg_text = diagram::render_graph(options, outputs, &names, state);
+            let elapsed = started.elapsed();
+            self.worker.trace().record(|| TraceEvent::Render(elapsed));
+            svg_text
+        });
+        let frame = DiagramFrame::new(options, outputs, self.plot);
+        // While outliers are shown, a click excludes a point instead of selecting.
+        let excluding = options.outliers && self.plot == Plot::Curve;
+        let crosshair = (options.crosshair && self.plot == Plot::Curve)
+            .then(|| CrosshairPlot::new(options, &shown));
+        responsive(move |bounds| {
+            let graph: Element<Message> = match &crosshair {
+                Some(plot) => canvas(plot.clone())
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .into(),
+                None => svg(handle.clone())
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .content_fit(ContentFit::Contain)
+                    .into(),
+            };
+            let area = mouse_area(graph)
+                .on_move(move |position| Message::HoverGraph(frame.volume_at(position, bounds)))
+                .on_exit(Message::HoverGraph(None));
+            if excluding {
+                area.on_press(Message::ToggleHoveredPoint).into()
+            } else {
+                area.on_press(Message::StartSelection)
+                    .on_release(Message::EndSelection)
+                    .into()
+            }
+        })
+        .into()
+    }
+
+    /// Enters or leaves presentation mode, switching the main window to or from full screen.
+    fn set_presenting(&mut self, presenting: bool) -> Command<Message> {
+        self.presenting = presenting;
+        let mode = if presenting {
+            window::Mode::Fullscreen
+        } else {
+            window::Mode::Windowed
+        };
+        window::change_mode(window::Id::MAIN, mode)
+    }
+
+    /// Selects the loaded file after the selected one, or the first after the last.
+    fn present_next(&mut self) {
+        let paths: Vec<&PathBuf> = self
+            .files
+            .iter()
+            .filter(|(_, it)| matches!(it, Left(_)))
+            .map(|(path, _)| path)
+            .collect();
+        let next = self
+            .selected
+            .as_ref()
+            .and_then(|selected| paths.iter().position(|it| *it == selected))
+            .map_or(0, |it| (it + 1) % paths.len().max(1));
+        if let Some(path) = paths.get(next) {
+            self.selected = Some(PathBuf::clone(path));
+        }
+    }
+
+    /// Wraps the controls into the sidebar, with a button hiding it and a handle at its edge that
+    /// resizes it.
+    ///
+    /// A hidden sidebar only keeps the button that shows it again.
+    fn sidebar<'a>(&self, controls: Column<'a, Message>) -> Element<'a, Message> {
+        if self.options.sidebar_collapsed {
+            let show_button = button(text("»")).on_press(Message::ToggleSidebar);
+            return container(show_button)
+                .padding(10)
+                .height(Length::Fill)
+                .into();
+        }
+        let hide_button = button(text(format!("« {BUTTON_HIDE_SIDEBAR}")).size(12))
+            .on_press(Message::ToggleSidebar);
+        let width = self.options.sidebar_width.unwrap_or(SIDEBAR_WIDTH);
+        let controls = column![container(hide_button).padding([10, 10, 0, 10]), controls];
+        let handle = mouse_area(
+            container(vertical_rule(1))
+                .width(Length::Fixed(SIDEBAR_HANDLE_WIDTH))
+                .height(Length::Fill)
+                .center_x(),
+        )
+        .on_press(Message::StartSidebarResize);
+        row![
+            container(controls)
+                .width(Length::Fixed(width - SIDEBAR_HANDLE_WIDTH))
+                .height(Length::Fill),
+            handle
+        ]
+        .into()
+    }
+
+    /// Returns the label of a control, marked if the tutorial points to it.
+    fn tutorial_label(&self, step: TutorialStep, label: Localized) -> String {
+        if self.tutorial == Some(step) {
+            format!("▶ {label}")
+        } else {
+            label.into()
+        }
+    }
+
+    /// Creates the panel showing the current step of the tutorial.
+    fn tutorial_panel(&self, step: TutorialStep) -> Column<'_, Message> {
+        let mut buttons = row![].spacing(5);
+        if step.next().is_some() {
+            buttons = buttons
+                .push(button(BUTTON_TUTORIAL_NEXT.get()).on_press(Message::NextTutorialStep));
+        }
+        buttons = buttons.push(button(BUTTON_TUTORIAL_SKIP.get()).on_press(Message::EndTutorial));
+        column![
+            text(TUTORIAL_TITLE).size(16),
+            text(step.instruction()),
+            buttons
+        ]
+        .spacing(5)
+    }
+
+    /// Checks if the profile shows a feature.
+    fn allows(&self, feature: Feature) -> bool {
+        self.profile.allows(feature)
+    }
+
+    /// Switches to a profile and turns off everything it hides.
+    fn set_profile(&mut self, profile: Profile) {
+        self.profile = profile;
+        if !self.allows(Feature::Explain) {
+            self.options.explain = false;
+        }
+        if !self.allows(Feature::Generator) {
+            self.options.generator = false;
+        }
+        if !self.allows(Feature::Projector) {
+            self.options.projector = false;
+            self.reveal = Region::Initial;
+        }
+        if !self.allows(Feature::Simulation) && self.options.simulation {
+            self.options.simulation = false;
+            self.update_simulation();
+        }
+        if !self.allows(Feature::Simulation) && self.options.burette {
+            self.options.burette = false;
+            self.update_burette();
+        }
+        if !self.allows(Feature::Fitting) && self.plot == Plot::Gran {
+            self.plot = Plot::Curve;
+        }
+        if !self.allows(Feature::Usage) {
+            self.options.usage = false;
+        }
+        if !self.allows(Feature::Rounding) {
+            self.options.rounding = Rounding::default();
+        }
+        if !self.allows(Feature::Diagnostics) {
+            self.log_panel = false;
+            self.debug = false;
+            self.worker.trace().set_enabled(false);
+        }
+        if !self.allows(Feature::Tutorial) {
+            self.tutorial = None;
+        }
+    }
+
+    /// Checks if the view shows anything which changes over time.
+    ///
+    /// Otherwise the app does no work while idle.
+    fn needs_tick(&self) -> bool {
+        self.debug
+            || self.log_panel
+            || self.toasts.expiring()
+            || !self.worker.is_alive()
+            || self
+                .restarted_at
+                .is_some_and(|it| it.elapsed() < RESTART_NOTICE_DURATION)
+    }
+
+    /// Replaces a dead worker with a new one.
+    ///
+    /// The new worker gets the settings of the old one and loads all files again.
+    fn restart_worker(&mut self) {
+        if self.closing
+            || self
+                .restarted_at
+                .is_some_and(|it| it.elapsed() < RESTART_INTERVAL)
+        {
+            return;
+        }
+        warn!("Restarting the dead worker");
+        let (worker, response_receiver) = Worker::spawn(self.flags.watcher_backend());
+        worker.set_event_filter(self.options.event_filter);
+        worker.set_debounce(self.options.debounce);
+        worker.set_tail(self.options.tail);
+        worker.set_temperature(self.options.temperature.unwrap_or(STANDARD_TEMPERATURE));
+        worker.set_model(MODELS[self.options.model % MODELS.len()]);
+        worker.trace().set_enabled(self.debug);
+        if self.paused {
+            worker.send_signal(Signal::Pause);
+        }
+        // The tutorial sample is not backed by a file.
+        for path in self.files.keys().filter(|it| it.is_file()) {
+            worker.send_signal(Signal::LoadPath(path.clone()));
+        }
+        if let Some(path) = self.watched_folder.clone() {
+            worker.send_signal(Signal::WatchFolder(WatchedFolder {
+                path,
+                pattern: self.folder_pattern.clone(),
+            }));
+        }
+        self.worker = worker;
+        self.pending_changes = 0;
+        self.progress = None;
+        self.response_receiver = Arc::new(Mutex::new(response_receiver));
+        self.worker_generation += 1;
+        self.restarted_at = Some(Instant::now());
+    }
+
+    /// Creates the status bar showing the selected file and the state of the worker.
+    fn status_bar(&self) -> Container<'_, Message> {
+        let mut status = row![].spacing(15);
+        match &self.selected {
+            Some(path) => {
+                status = status.push(text(path.display()).size(12));
+                if let Some(time) = self.loaded_at.get(path) {
+                    let time = DateTime::<Local>::from(*time).format("%H:%M:%S");
+                    status = status.push(text(format!("{STATUS_LOADED} {time}")).size(12));
+                }
+                if let Some(output) = self.selected_output() {
+                    status = status
+                        .push(text(format!("{} {STATUS_POINTS}", output.items.len())).size(12));
+                }
+            }
+            None => status = status.push(text(STATUS_NO_FILE).size(12)),
+        }
+        if self.flags.safe_mode {
+            status = status.push(text(SAFE_MODE).size(12));
+        }
+        if let Some(backend) = self.worker.watcher_backend() {
+            let backend = match backend {
+                WatcherBackend::Native => STATUS_WATCHER_NATIVE,
+                WatcherBackend::Poll => STATUS_WATCHER_POLL,
+            };
+            status = status.push(text(format!("{STATUS_WATCHER}: {backend}")).size(12));
+        }
+        if self.paused {
+            status = status.push(text(WATCH_PAUSED).size(12));
+        }
+        if let Some((path, rows_done, rows_total)) = &self.progress {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            status = status
+                .push(text(format!("{STATUS_READING} {name}")).size(12))
+                .push(
+                    progress_bar(0.0..=*rows_total as f32, *rows_done as f32)
+                        .width(Length::Fixed(120.0))
+                        .height(Length::Fixed(12.0)),
+                )
+                .push(
+                    button(text(BUTTON_CANCEL_LOADING).size(12))
+                        .on_press(Message::CancelLoading)
+                        .padding([0, 6]),
+                );
+        }
+        if self
+            .restarted_at
+            .is_some_and(|it| it.elapsed() < RESTART_NOTICE_DURATION)
+        {
+            status = status.push(text(STATUS_WORKER_RESTARTED).size(12));
+        }
+        if !self.worker.is_alive() {
+            status = status.push(text(format!("⚠ {STATUS_WORKER_DEAD}")).size(12));
+        }
+        container(status).width(Length::Fill).padding([2, 10])
+    }
+
+    /// Creates the debug overlay showing the most recent entries of the trace.
+    fn debug_overlay(&self) -> Container<'_, Message> {
+        let entries: Vec<Element<Message>> = self
+            .worker
+            .trace()
+            .recent(DEBUG_ENTRIES)
+            .into_iter()
+            .map(|entry| {
+                let event = match entry.event {
+                    TraceEvent::Signal(signal) => format!("-> {signal}"),
+                    TraceEvent::SkippedSignal(signal) => format!("-x {signal}"),
+                    TraceEvent::Response(response) => format!("<- {response}"),
+                    TraceEvent::Watcher(event) => format!("watcher: {event}"),
+                    TraceEvent::Unchanged(path) => format!("unchanged: {path}"),
+                    TraceEvent::Render(duration) => format!("render: {duration:?}"),
+                };
+                text(format!("{:.3} {event}", entry.elapsed.as_secs_f32()))
+                    .size(10)
+                    .into()
+            })
+            .collect();
+        container(scrollable(Column::with_children(entries).spacing(1)))
+            .width(Length::Fixed(280.0))
+            .height(Length::Fill)
+            .padding(5)
+    }
+
+    /// Creates the readout of the pH at the hovered volume, colored like the universal indicator.
+    fn ph_readout(&self) -> Option<Row<'_, Message>> {
+        let volume = self.hovered_volume?;
+        let item = self
+            .selected_output()?
+            .items
+            .iter()
+            .min_by(|a, b| (a.m_v - volume).abs().total_cmp(&(b.m_v - volume).abs()))?;
+        let swatch = indicator_swatch(item.ph, 20.0, 20.0);
+        let format = self.options.number_format;
+        let readout = text(format!(
+            "V(m) = {} mL, pH = {}",
+            format.format(item.m_v),
+            format.format_or(item.ph, |it| localized(format!("{it:.2}")))
+        ));
+        Some(
+            row![swatch, readout]
+                .spacing(5)
+                .align_items(Alignment::Center),
+        )
+    }
+
+    /// Lists the suspicious and the excluded points of the selected file with buttons to exclude
+    /// or include them.
+    fn outlier_panel(&self) -> Option<Column<'_, Message>> {
+        let output = self.selected_output()?;
+        let entry = |label: Localized, volume: f32, ph: f32| {
+            row![
+                button(text(label).size(12)).on_press(Message::ToggleExcluded(volume)),
+                text(format!("V(m) = {} mL, pH = {:.2}", number(volume), ph)).size(12)
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+            .into()
+        };
+        let suspicious = analysis::outliers(output).into_iter().map(|index| {
+            let item = &output.items[index];
+            entry(BUTTON_EXCLUDE_POINT, item.m_v, item.ph)
+        });
+        let excluded = output
+            .excluded
+            .iter()
+            .map(|(_, item)| entry(BUTTON_INCLUDE_POINT, item.m_v, item.ph));
+        let entries: Vec<Element<'_, Message>> = suspicious.chain(excluded).collect();
+        let title = if entries.is_empty() {
+            OUTLIERS_NONE
+        } else {
+            OUTLIERS_TITLE
+        };
+        Some(
+            column![
+                text(title).size(12),
+                Column::with_children(entries).spacing(2)
+            ]
+            .spacing(2),
+        )
+    }
+
+    /// Reports the buffering between the pH values at both ends of the selection.
+    fn buffering_report(&self) -> Option<Text<'_>> {
+        let (from, to) = self.selection?;
+        let output = self.selected_output()?;
+        let from_ph = analysis::ph_at(output, from.min(to))?;
+        let to_ph = analysis::ph_at(output, from.max(to))?;
+        let amount = analysis::integrate_buffer_capacity(output, from_ph, to_ph);
+        Some(text(format!(
+            "{BUFFERING_TITLE} pH {from_ph:.2} – {to_ph:.2}: {} mol",
+            self.options.rounding.value(amount)
+        )))
+    }
+
+    /// Creates the panel showing the recent log entries.
+    fn log_panel(&self) -> Container<'_, Message> {
+        let entries: Vec<Element<Message>> = log::recent(LOG_ENTRIES)
+            .into_iter()
+            .map(|entry| {
+                let target = entry.target.trim_start_matches("titration_curve::");
+                let mut line = format!("{:<5} {target}", entry.level);
+                if !entry.spans.is_empty() {
+                    _ = write!(line, " {}", entry.spans);
+                }
+                _ = write!(line, ": {}", entry.message);
+                text(line).size(10).into()
+            })
+            .collect();
+        container(scrollable(Column::with_children(entries).spacing(1)))
+            .width(Length::Fixed(320.0))
+            .height(Length::Fill)
+            .padding(5)
+    }
+
+    /// Creates the panel comparing the used measuring solution of all loaded files.
+    ///
+    /// Listing every file lets students compare how far they over-titrated across their runs.
+    fn usage_panel(&self) -> Column<'_, Message> {
+        let rows = self.files.iter().filter_map(|(path, content)| {
+            let Left(output) = content else {
+                return None;
+            };
+            let usage = analysis::titrant_usage(output);
+            let rounding = self.options.rounding;
+            let mut line = format!(
+                "{}: {USAGE_EQUIVALENCE} {}",
+                file_stem(path),
+                rounding.volume(usage.equivalence_volume)
+            );
+            if usage.equivalence_uncertainty > 0.0 {
+                _ = write!(
+                    line,
+                    " ± {}",
+                    rounding.volume(COVERAGE_FACTOR * usage.equivalence_uncertainty)
+                );
+            }
+            line.push_str(" mL, ");
+            // A mixture of acids breaks once for every acid.
+            let breaks = output.input.equivalence_volumes();
+            if breaks.len() > 1 {
+                let breaks: Vec<String> = breaks.iter().map(|it| rounding.volume(*it)).collect();
+                _ = write!(line, "{USAGE_BREAKS} {} mL, ", breaks.join(" / "));
+            }
+            if let Some(endpoint) = usage.endpoint_volume {
+                _ = write!(line, "{USAGE_ENDPOINT} {} mL, ", rounding.volume(endpoint));
+            }
+            _ = write!(
+                line,
+                "{USAGE_TOTAL} {} mL, {:.0} % {USAGE_CONSUMED}",
+                rounding.volume(usage.total_volume),
+                usage.consumed_fraction() * 100.0
+            );
+            if let Some(overshoot) = usage.overshoot() {
+                _ = write!(
+                    line,
+                    ", {} mL {USAGE_OVERSHOOT}",
+                    rounding.volume(overshoot)
+                );
+            }
+            Some(text(line).size(12).into())
+        });
+        column![
+            text(OPTION_USAGE),
+            Column::with_children(rows.collect::<Vec<_>>()).spacing(2)
+        ]
+        .spacing(5)
+    }
+
+    /// Creates the panel explaining the calculation of the selected data point.
+    fn explain_panel(&self, output: &Output, index: usize) -> Column<'_, Message> {
+        let item = &output.items[index];
+        let mut panel = column![
+            text(format!(
+                "{EXPLAIN_POINT} {}: V(m) = {} mL",
+                index + 1,
+                number(item.m_v)
+            )),
+            slider(
+                0..=(output.items.len() - 1) as u32,
+                index as u32,
+                Message::ExplainPoint
+            ),
+        ];
+        for step in output.input.explain(index) {
+            panel = panel.push(
+                text(format!(
+                    "{}: {}",
+                    quantity_name(step.quantity),
+                    step.formula
+                ))
+                .size(12),
+            );
+        }
+        panel.spacing(3)
+    }
+
+    /// Creates the list of loaded files.
+    ///
+    /// The selected file is shown as a disabled button.
+    fn file_list(&self) -> Column<'_, Message> {
+        let items: Vec<Element<Message>> = self
+            .files
+            .keys()
+            .map(|path| {
+                let mut name = path
+                    .file_name()
+                    .map(|it| it.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                if self.stale.contains_key(path) {
+                    name.insert_str(0, "⚠ ");
+                }
+                let mut show_button = button(text(name).size(12)).width(Length::Fill);
+                if self.selected.as_ref() != Some(path) {
+                    show_button = show_button.on_press(Message::ShowFile(path.clone()));
+                }
+                let unload_button = button(text(BUTTON_UNLOAD_FILE).size(12))
+                    .on_press(Message::UnloadFile(path.clone()));
+                let mut item = row![show_button].spacing(2);
+                if self.options.compare {
+                    let mut compare_button = button(text(BUTTON_COMPARE_FILE).size(12));
+                    if self.compared.as_ref() != Some(path) {
+                        compare_button =
+                            compare_button.on_press(Message::CompareFile(path.clone()));
+                    }
+                    item = item.push(compare_button);
+                }
+                item.push(unload_button).into()
+            })
+            .collect();
+        Column::with_children(items).spacing(2)
+    }
+
+    /// Starts editing the data points of the selected file, unless they are edited already.
+    fn open_data_editor(&mut self) {
+        let Some(path) = &self.selected else {
+            return;
+        };
+        if !self.options.data_editor || self.data_editor.path() == Some(path.as_path()) {
+            return;
+        }
+        if let Some(Left(output)) = self.files.get(path) {
+            self.data_editor.open(path.clone(), output.clone());
+        }
+    }
+
+    /// Recalculates the edited file from the data points of the data editor.
+    ///
+    /// Invalid values keep the last valid curve.
+    fn apply_data_edits(&mut self) {
+        let (Some(path), Some(output)) = (self.data_editor.path(), self.data_editor.output())
+        else {
+            return;
+        };
+        let (sheet, measured_conductivity) = match self.files.get(path) {
+            Some(Left(output)) => (output.sheet.clone(), output.measured_conductivity.clone()),
+            _ => (None, None),
+        };
+        let output = Output {
+            sheet,
+            measured_conductivity,
+            ..output
+        };
+        let output = self.prepared(path, Arc::new(output));
+        if let Some(content) = self.files.get_mut(path) {
+            *content = Left(output);
+        }
+    }
+
+    /// Sorts the volumes of an output if enabled and applies the excluded points of its file.
+    fn prepared(&self, path: &Path, output: Arc<Output>) -> Arc<Output> {
+        let output = if self.options.sort_volumes {
+            Arc::new(output.monotonic())
+        } else {
+            output
+        };
+        match self.excluded.get(path) {
+            Some(volumes) if !volumes.is_empty() => Arc::new(output.excluding(volumes)),
+            _ => output,
+        }
+    }
+
+    /// Excludes the point nearest to a volume from the curve of the selected file, or includes it
+    /// again.
+    fn toggle_excluded(&mut self, volume: f32) {
+        let Some(path) = self.selected.clone() else {
+            return;
+        };
+        let Some(Left(output)) = self.files.get(&path) else {
+            return;
+        };
+        let nearest = output
+            .items
+            .iter()
+            .chain(output.excluded.iter().map(|(_, it)| it))
+            .map(|it| it.m_v)
+            .min_by(|a, b| (a - volume).abs().total_cmp(&(b - volume).abs()));
+        let Some(nearest) = nearest else {
+            return;
+        };
+        let volumes = self.excluded.entry(path.clone()).or_default();
+        match volumes.iter().position(|it| *it == nearest) {
+            Some(position) => _ = volumes.remove(position),
+            None => volumes.push(nearest),
+        }
+        let output = Arc::new(output.excluding(volumes));
+        self.files.insert(path, Left(output));
+    }
+
+    /// Inserts the content of a file and selects it, if it was not loaded before.
+    fn insert_file(&mut self, path: PathBuf, content: Either<Arc<Output>, String>) {
+        if self.files.insert(path.clone(), content).is_none() {
+            self.selected = Some(path);
+        }
+    }
 }
 
-pub struct TitrationCurve {
-    options: Options,
-    worker: Arc<Worker>,
-    response_receiver: Receiver<Response>,
-    /// The content of the window.
-    ///
-    /// Either a graph of the output or a message.
-    content: Either<Arc<Output>, String>,
+impl Drop for TitrationCurve {
+    /// Lets the worker finish a pending export after the windows are closed, instead of blocking
+    /// the window while it stops.
+    fn drop(&mut self) {
+        self.worker.stop();
+        self.worker.join(STOP_TIMEOUT);
+    }
 }
 
 impl Application for TitrationCurve {
     type Executor = executor::Default;
-    type Flags = ();
+    type Flags = StartupFlags;
     type Message = Message;
     type Theme = Theme;
 
-    fn new(_flags: Self::Flags) -> (Self, Command<Self::Message>) {
-        let (worker, response_receiver) = Worker::spawn();
-        let app = Self {
+    fn new(flags: Self::Flags) -> (Self, Command<Self::Message>) {
+        if flags.safe_mode {
+            info!("Starting in safe mode");
+        }
+        let (worker, response_receiver) = Worker::spawn(flags.watcher_backend());
+        let mut app = Self {
+            flags,
             options: Options::default(),
             worker,
-            response_receiver,
-            content: Right(MESSAGE_NO_CONTENT.into()),
+            response_receiver: Arc::new(Mutex::new(response_receiver)),
+            worker_generation: 0,
+            files: BTreeMap::new(),
+            selected: None,
+            compared: None,
+            explained_point: 0,
+            hovered_volume: None,
+            selection: None,
+            selecting: false,
+            resizing_sidebar: false,
+            toasts: Toasts::default(),
+            generator: GeneratorForm::default(),
+            simulation: SimulationForm::default(),
+            burette: Burette::default(),
+            #[cfg(feature = "live")]
+            live: live::LiveForm::default(),
+            #[cfg(feature = "live")]
+            calibration: calibration::CalibrationForm::default(),
+            calculator: CalculatorForm::default(),
+            data_editor: DataEditor::default(),
+            excluded: BTreeMap::new(),
+            series_names: BTreeMap::new(),
+            stale: BTreeMap::new(),
+            temperature: String::new(),
+            folder_pattern: String::new(),
+            watched_folder: None,
+            annotation: AnnotationForm::default(),
+            style: StyleForm::default(),
+            render_cache: RenderCache::default(),
+            reveal: Region::Initial,
+            playback: Playback::default(),
+            plot: Plot::default(),
+            debug: false,
+            log_panel: false,
+            paused: false,
+            pending_changes: 0,
+            restarted_at: None,
+            closing: false,
+            presenting: false,
+            diagram_window: None,
+            progress: None,
+            tutorial: None,
+            loaded_at: BTreeMap::new(),
+            profile: Profile::default(),
         };
+        // Safe mode ignores the stored settings.
+        if !app.flags.safe_mode {
+            app.set_profile(profile::load());
+            let session = session::load();
+            app.options.annotations = session.annotations;
+            #[cfg(feature = "live")]
+            if let Some(calibration) = session.calibration {
+                app.calibration.load(&calibration);
+                app.options.calibration = Some(calibration);
+            }
+            if app.allows(Feature::Tutorial) && tutorial::is_first_run() {
+                app.start_tutorial();
+            }
+        }
+        for path in &app.flags.files {
+            app.worker.send_signal(Signal::LoadPath(path.clone()));
+        }
         (app, Command::none())
     }
 
-    fn title(&self) -> String {
-        WINDOW_TITLE.into()
+    fn title(&self, window: window::Id) -> String {
+        let title = if self.flags.safe_mode {
+            format!("{WINDOW_TITLE} ({SAFE_MODE})")
+        } else {
+            WINDOW_TITLE.into()
+        };
+        if self.diagram_window == Some(window) {
+            format!("{title} – {DIAGRAM_WINDOW_TITLE}")
+        } else {
+            title
+        }
     }
 
     fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
+        if Feature::of(&message).is_some_and(|it| !self.allows(it)) {
+            return Command::none();
+        }
+        if self.tutorial.is_some_and(|it| it.is_completed_by(&message)) {
+            self.next_tutorial_step();
+        }
         match message {
-            Message::SetDark(dark) => self.options.dark = dark,
+            Message::SetDark(dark) => {
+                // Choosing a theme stops following the system.
+                self.options.dark = dark;
+                self.options.system_theme = false;
+            }
+            // The subscription sends the current theme as soon as it starts.
+            Message::SetSystemTheme(system_theme) => self.options.system_theme = system_theme,
+            Message::SystemTheme { dark } => {
+                if self.options.system_theme {
+                    self.options.dark = dark;
+                }
+            }
             Message::SetColored(colored) => self.options.colored = colored,
-            Message::SelectFile => self.worker.send_signal(Signal::FileDialog),
-            Message::Update(_) => {
-                while let Ok(response) = self.response_receiver.try_recv() {
-                    match response {
-                        Response::Unload => self.content = Right(MESSAGE_NO_CONTENT.into()),
-                        Response::Output(output) => self.content = Left(output),
-                        Response::Error(err) => {
-                            self.content = Right(format!("Ein Fehler ist aufgetreten: {err}"))
+            Message::SetOverlay(overlay) => self.options.overlay = overlay,
+            Message::SetLadder(ladder) => self.options.ladder = ladder,
+            Message::SetSmooth(smooth) => self.options.smooth = smooth,
+            Message::SetCrosshair(crosshair) => self.options.crosshair = crosshair,
+            Message::SetSortVolumes(sort_volumes) => {
+                self.options.sort_volumes = sort_volumes;
+                // The shown outputs are already prepared, so they are loaded again.
+                self.worker.send_signal(Signal::Reload);
+                // The worker does not load the examples.
+                for example in Example::ALL {
+                    let (path, output) = example.load();
+                    if self.files.contains_key(&path) {
+                        let output = self.prepared(&path, output);
+                        self.files.insert(path, Left(output));
+                    }
+                }
+                #[cfg(feature = "live")]
+                self.update_live();
+            }
+            Message::MaxPointsInput(value) => {
+                // Empty input draws all points, invalid input is ignored.
+                let value = value.trim();
+                if value.is_empty() {
+                    self.options.max_points = None;
+                } else if let Some(max_points) = value.parse().ok().filter(|it| *it >= 2) {
+                    self.options.max_points = Some(max_points);
+                }
+            }
+            Message::SetExplain(explain) => self.options.explain = explain,
+            Message::SetUsage(usage) => self.options.usage = usage,
+            Message::SetSummary(summary) => self.options.summary = summary,
+            Message::SetFit(fit) => self.options.fit = fit,
+            Message::SetActivity(activity) => self.options.activity = activity,
+            Message::SetStoichiometry(stoichiometry) => self.options.stoichiometry = stoichiometry,
+            Message::SetIndicator(index) => self.options.indicator = Some(index),
+            Message::ExplainPoint(index) => self.explained_point = index,
+            Message::HoverGraph(volume) => {
+                self.hovered_volume = volume;
+                if let (true, Some(volume), Some(selection)) =
+                    (self.selecting, volume, &mut self.selection)
+                {
+                    selection.1 = volume;
+                }
+            }
+            Message::StartSelection => {
+                self.selection = self.hovered_volume.map(|it| (it, it));
+                self.selecting = self.selection.is_some();
+            }
+            Message::EndSelection => {
+                self.selecting = false;
+                // A click without dragging clears the selection.
+                if self.selection.is_some_and(|(from, to)| from == to) {
+                    self.selection = None;
+                }
+            }
+            Message::ToggleSidebar => {
+                self.options.sidebar_collapsed = !self.options.sidebar_collapsed;
+            }
+            Message::StartSidebarResize => self.resizing_sidebar = true,
+            Message::ResizeSidebar(width) => {
+                if self.resizing_sidebar {
+                    let (min, max) = SIDEBAR_WIDTH_RANGE;
+                    self.options.sidebar_width = Some(width.clamp(min, max));
+                }
+            }
+            Message::EndSidebarResize => self.resizing_sidebar = false,
+            Message::SelectFile => self
+                .worker
+                .send_signal(Signal::FileDialog(FILTER_TABLE.get())),
+            Message::ShowFile(path) => {
+                self.selected = Some(path);
+                self.open_data_editor();
+            }
+            Message::UnloadFile(path) => self.worker.send_signal(Signal::Unload(path)),
+            Message::SetCompare(compare) => self.options.compare = compare,
+            Message::CompareFile(path) => self.compared = Some(path),
+            Message::CopyDiagram => {
+                let outputs = self.shown_outputs();
+                if !outputs.is_empty() {
+                    return clipboard::write(diagram::render_graph(
+                        &self.options,
+                        &outputs,
+                        &self.shown_names(),
+                        self.graph_state(),
+                    ));
+                }
+            }
+            Message::SelectPlot(plot) => self.plot = plot,
+            Message::SetXQuantity(quantity) => self.options.x_quantity = quantity,
+            Message::SetSecondaryPlot(plot) => self.options.secondary_plot = plot,
+            Message::AxisDecimalsInput(axis, value) => {
+                if let Some(decimals) = parse_limit(&value, 6) {
+                    self.axis_format(axis).decimals = decimals;
+                }
+            }
+            Message::RoundingVolumeInput(value) => {
+                if let Some(decimals) = parse_limit(&value, 6) {
+                    self.options.rounding.volume_decimals = decimals;
+                }
+            }
+            Message::RoundingDigitsInput(value) => {
+                if let Some(digits) = parse_limit(&value, 8) {
+                    self.options.rounding.significant_digits = digits.filter(|it| *it > 0);
+                }
+            }
+            Message::SetNotation(notation) => self.options.number_format.notation = notation,
+            Message::NumberDigitsInput(value) => {
+                if let Some(Some(digits)) = parse_limit(&value, 8) {
+                    self.options.number_format.digits = digits;
+                }
+            }
+            Message::SetConductivityAcid(index) => self.options.conductivity_acid = index,
+            Message::SetConductivityBase(index) => self.options.conductivity_base = index,
+            Message::SetPrecipitationSalt(index) => self.options.precipitation_salt = index,
+            Message::SetPrecipitationAnion(anion) => self.options.precipitation_anion = anion,
+            Message::SetComplexometryMetal(index) => self.options.complexometry_metal = index,
+            Message::SetBufferPh(ph) => self.options.buffer_ph = ph,
+            Message::AxisUnitInput(axis, unit) => self.axis_format(axis).unit = unit,
+            Message::SetUnitOnTicks(axis, unit_on_ticks) => {
+                self.axis_format(axis).unit_on_ticks = unit_on_ticks;
+            }
+            Message::AxisTitleInput(axis, title) => self.axis_format(axis).title = title,
+            Message::ExportDiagram => {
+                let outputs = self.shown_outputs();
+                if !outputs.is_empty() {
+                    let title = match &self.selected {
+                        Some(path) if !self.options.overlay => file_stem(path),
+                        _ => PLOT_DEFAULT_FILE_NAME.into(),
+                    };
+                    let suffix = match self.plot {
+                        Plot::Curve => PLOT_CURVE_FILE_SUFFIX,
+                        Plot::Derivative => PLOT_DERIVATIVE_FILE_SUFFIX,
+                        Plot::Conductivity => PLOT_CONDUCTIVITY_FILE_SUFFIX,
+                        Plot::Correlation => PLOT_CORRELATION_FILE_SUFFIX,
+                        Plot::Precipitation => PLOT_PRECIPITATION_FILE_SUFFIX,
+                        Plot::Complexometry => PLOT_COMPLEXOMETRY_FILE_SUFFIX,
+                        Plot::Temperature => PLOT_TEMPERATURE_FILE_SUFFIX,
+                        Plot::Gran => PLOT_GRAN_FILE_SUFFIX,
+                        Plot::BufferCapacity => PLOT_BUFFER_CAPACITY_FILE_SUFFIX,
+                        Plot::Hagg => PLOT_HAGG_FILE_SUFFIX,
+                    };
+                    let state = GraphState {
+                        size: self.options.export_size,
+                        ..self.graph_state()
+                    };
+                    let names = self.shown_names();
+                    let content = diagram::render_export(&self.options, &outputs, &names, state);
+                    self.worker.send_signal(Signal::Save(SaveRequest {
+                        filter_name: FILTER_SVG.get(),
+                        extension: "svg",
+                        file_name: format!("{title}-{suffix}.svg"),
+                        content: content.into_bytes(),
+                    }));
+                }
+            }
+            Message::SetExportSize(size) => self.options.export_size = size,
+            Message::SetLegend(legend) => self.options.legend = legend,
+            Message::SetLegendFileNames(file_names) => self.options.legend_file_names = file_names,
+            Message::SeriesNameInput(name) => {
+                if let Some(path) = self.selected.clone() {
+                    self.series_names.insert(path, name);
+                }
+            }
+            Message::ExportSolution => {
+                if let (Some(path), Some(output)) = (&self.selected, self.selected_output()) {
+                    let title = file_stem(path);
+                    let content = solution::worked_solution(&title, output, self.options.rounding);
+                    self.worker.send_signal(Signal::Save(SaveRequest {
+                        filter_name: FILTER_MARKDOWN.get(),
+                        extension: "md",
+                        file_name: format!("{title}-{SOLUTION_FILE_SUFFIX}.md"),
+                        content: content.into_bytes(),
+                    }));
+                }
+            }
+            Message::ExportHtml => {
+                if let (Some(path), Some(output)) = (&self.selected, self.selected_output()) {
+                    let title = file_stem(path);
+                    let content = html::interactive_plot(&title, output, &self.options);
+                    self.worker.send_signal(Signal::Save(SaveRequest {
+                        filter_name: FILTER_HTML.get(),
+                        extension: "html",
+                        file_name: format!("{title}.html"),
+                        content: content.into_bytes(),
+                    }));
+                }
+            }
+            Message::ExportTable(format) => {
+                if let (Some(path), Some(output)) = (&self.selected, self.selected_output()) {
+                    self.worker.send_signal(Signal::ExportTable(TableExport {
+                        output: output.clone(),
+                        format,
+                        filter_name: FILTER_TABLE.get(),
+                        file_name: format!("{}.{}", file_stem(path), format.extension()),
+                        number_format: self.options.number_format,
+                        rounding: self.options.rounding,
+                    }));
+                }
+            }
+            Message::ExportReport => {
+                if let (Some(path), Some(output)) = (&self.selected, self.selected_output()) {
+                    let title = file_stem(path);
+                    self.worker.send_signal(Signal::Save(SaveRequest {
+                        filter_name: FILTER_PDF.get(),
+                        extension: "pdf",
+                        file_name: format!("{title}-{REPORT_FILE_SUFFIX}.pdf"),
+                        content: report::locked_report(&title, output, self.options.rounding),
+                    }));
+                }
+            }
+            Message::SetGenerator(generator) => self.options.generator = generator,
+            Message::GeneratorInput(field, value) => self.generator.set(field, value),
+            Message::SetSimulation(simulation) => {
+                self.options.simulation = simulation;
+                self.update_simulation();
+            }
+            Message::SimulationInput(field, value) => {
+                self.simulation.set(field, value);
+                self.update_simulation();
+                self.update_burette();
+            }
+            Message::SetBurette(burette) => {
+                self.options.burette = burette;
+                self.burette.reset();
+                self.update_burette();
+            }
+            Message::BuretteAdd(volume) => {
+                self.burette.add(volume);
+                self.update_burette();
+            }
+            Message::BuretteUndo => {
+                self.burette.undo();
+                self.update_burette();
+            }
+            Message::BuretteReset => {
+                self.burette.reset();
+                self.update_burette();
+            }
+            #[cfg(feature = "live")]
+            Message::SetLive(live) => {
+                self.options.live = live;
+                if !live && self.live.connected {
+                    self.live.connected = false;
+                    self.worker.send_signal(Signal::Disconnect);
+                }
+                self.update_live();
+            }
+            #[cfg(feature = "live")]
+            Message::LiveInput(field, value) => self.live.set(field, value),
+            #[cfg(feature = "live")]
+            Message::Connect => {
+                if let Some(config) = self.live.config() {
+                    self.live.connected = true;
+                    self.worker.send_signal(Signal::Connect(config));
+                }
+            }
+            #[cfg(feature = "live")]
+            Message::Disconnect => {
+                self.live.connected = false;
+                self.worker.send_signal(Signal::Disconnect);
+            }
+            #[cfg(feature = "live")]
+            Message::ClearLive => {
+                self.live.clear();
+                self.update_live();
+            }
+            #[cfg(feature = "live")]
+            Message::SetCalibrating(calibrating) => self.options.calibrating = calibrating,
+            #[cfg(feature = "live")]
+            Message::CalibrationInput(buffer, field, value) => {
+                self.calibration.set(buffer, field, value);
+            }
+            #[cfg(feature = "live")]
+            Message::TakeCalibrationReading(buffer) => self.calibration.take_reading(buffer),
+            #[cfg(feature = "live")]
+            Message::ApplyCalibration => {
+                let temperature = self.options.temperature.unwrap_or(STANDARD_TEMPERATURE);
+                if let Some(calibration) = self.calibration.calibration(temperature) {
+                    self.options.calibration = Some(calibration);
+                    self.save_session();
+                }
+            }
+            #[cfg(feature = "live")]
+            Message::RemoveCalibration => {
+                self.options.calibration = None;
+                self.save_session();
+            }
+            Message::SetCalculator(calculator) => self.options.calculator = calculator,
+            Message::CalculatorInput(field, value) => self.calculator.set(field, value),
+            Message::SetStyleEditor(style_editor) => self.options.style_editor = style_editor,
+            Message::StyleColorInput(color, value) => {
+                self.style.set(color, value, &mut self.options.style);
+            }
+            Message::SetPointSize(size) => self.options.style.point_size = size,
+            Message::SetGrid(grid) => self.options.style.grid = grid,
+            Message::SelectPalette(palette) => {
+                self.style.select_palette(palette, &mut self.options.style);
+            }
+            Message::SetHighContrast(high_contrast) => {
+                self.options.style.high_contrast = high_contrast;
+            }
+            Message::SetFontSize(size) => self.options.style.font_size = size,
+            Message::ResetStyle => {
+                self.options.style = DiagramStyle::default();
+                self.style = StyleForm::default();
+            }
+            Message::SetOutliers(outliers) => self.options.outliers = outliers,
+            Message::ToggleExcluded(volume) => self.toggle_excluded(volume),
+            Message::ToggleHoveredPoint => {
+                if let Some(volume) = self.hovered_volume {
+                    self.toggle_excluded(volume);
+                }
+            }
+            Message::SetDataEditor(data_editor) => {
+                self.options.data_editor = data_editor;
+                self.open_data_editor();
+            }
+            Message::DataPointInput(index, value) => {
+                self.data_editor.set(index, value);
+                self.apply_data_edits();
+            }
+            Message::DataPointPhInput(index, value) => {
+                self.data_editor.set_ph(index, value);
+                self.apply_data_edits();
+            }
+            Message::AddDataPoint => self.data_editor.add(),
+            Message::RemoveDataPoint(index) => {
+                self.data_editor.remove(index);
+                self.apply_data_edits();
+            }
+            Message::ResetData => {
+                if let Some((path, output)) = self.data_editor.reset() {
+                    let output = self.prepared(&path, output);
+                    if let Some(content) = self.files.get_mut(&path) {
+                        *content = Left(output);
+                    }
+                }
+            }
+            Message::SetAnnotating(annotating) => self.options.annotating = annotating,
+            Message::SetAnnotationKind(kind) => self.annotation.set_kind(kind),
+            Message::AnnotationInput(field, value) => self.annotation.set(field, value),
+            Message::AddAnnotation => {
+                if let Some(annotation) = self.annotation.annotation() {
+                    self.options.annotations.push(annotation);
+                    self.save_session();
+                }
+            }
+            Message::RemoveAnnotation(index) => {
+                if index < self.options.annotations.len() {
+                    self.options.annotations.remove(index);
+                    self.save_session();
+                }
+            }
+            Message::ApplyCalculator => {
+                if self.calculator.dilution().is_none() || !self.allows(Feature::Simulation) {
+                    return Command::none();
+                }
+                self.simulation.set(
+                    SimulationField::MeasuringConcentration,
+                    self.calculator.target_concentration().to_string(),
+                );
+                self.options.simulation = true;
+                self.update_simulation();
+            }
+            Message::ExportProblem => {
+                let Some(problem) = self.generator.problem() else {
+                    return Command::none();
+                };
+                match problem.write_worksheet() {
+                    Ok(content) => self.worker.send_signal(Signal::Save(SaveRequest {
+                        filter_name: FILTER_TABLE.get(),
+                        extension: "xlsx",
+                        file_name: format!("{GENERATOR_FILE_PREFIX}-{}.xlsx", problem.seed),
+                        content,
+                    })),
+                    Err(err) => self.toasts.error(format!("{ERROR_OCCURRED}: {err}")),
+                }
+            }
+            Message::ExportAnswerKey => {
+                if let Some(problem) = self.generator.problem() {
+                    self.worker.send_signal(Signal::Save(SaveRequest {
+                        filter_name: FILTER_MARKDOWN.get(),
+                        extension: "md",
+                        file_name: format!(
+                            "{GENERATOR_FILE_PREFIX}-{}-{GENERATOR_KEY_FILE_SUFFIX}.md",
+                            problem.seed
+                        ),
+                        content: generator::answer_key(&problem, self.options.rounding)
+                            .into_bytes(),
+                    }));
+                }
+            }
+            Message::SetLanguage(language) => strings::set_language(language),
+            Message::SetNumberLocale(locale) => {
+                locale::set_number_locale(locale);
+                // Numbers stored as text in the tables may be read differently now.
+                self.worker.send_signal(Signal::Reload);
+            }
+            Message::SetProjector(projector) => {
+                self.options.projector = projector;
+                self.reveal = Region::Initial;
+            }
+            Message::SetPaused(paused) => {
+                self.paused = paused;
+                self.worker.send_signal(if paused {
+                    Signal::Pause
+                } else {
+                    Signal::Resume
+                });
+            }
+            Message::ReloadNow => self.worker.send_signal(Signal::Reload),
+            Message::SetDebounce(millis) => {
+                self.options.debounce = Debounce(millis);
+                self.worker.set_debounce(self.options.debounce);
+            }
+            Message::SetTail(tail) => {
+                self.options.tail = tail;
+                self.worker.set_tail(tail);
+            }
+            Message::FolderPatternInput(pattern) => {
+                self.folder_pattern = pattern;
+                if let Some(path) = self.watched_folder.clone() {
+                    self.worker.send_signal(Signal::WatchFolder(WatchedFolder {
+                        path,
+                        pattern: self.folder_pattern.clone(),
+                    }));
+                }
+            }
+            Message::WatchFolder => self
+                .worker
+                .send_signal(Signal::FolderDialog(self.folder_pattern.clone())),
+            Message::UnwatchFolder => self.worker.send_signal(Signal::UnwatchFolder),
+            Message::TemperatureInput(input) => {
+                // An empty text resets the standard temperature, while invalid text is kept for
+                // editing without changing the option.
+                let temperature = if input.trim().is_empty() {
+                    Some(None)
+                } else {
+                    let (min, max) = WATER_PKW.range().unwrap_or_default();
+                    parse_number(&input)
+                        .filter(|it| (min..=max).contains(it))
+                        .map(Some)
+                };
+                self.temperature = input;
+                if let Some(temperature) = temperature {
+                    if temperature != self.options.temperature {
+                        self.options.temperature = temperature;
+                        self.worker
+                            .set_temperature(temperature.unwrap_or(STANDARD_TEMPERATURE));
+                        self.worker.send_signal(Signal::Reload);
+                    }
+                }
+            }
+            Message::SetModel(index) => {
+                self.options.model = index;
+                self.worker.set_model(MODELS[index % MODELS.len()]);
+                self.worker.send_signal(Signal::Reload);
+            }
+            #[cfg(feature = "scripting")]
+            Message::LoadScript => self
+                .worker
+                .send_signal(Signal::ScriptDialog(FILTER_SCRIPT.get())),
+            Message::SetEventFilter(filter) => {
+                self.options.event_filter = filter;
+                self.worker.set_event_filter(filter);
+            }
+            Message::RevealNext if self.options.projector => {
+                if let Some(region) = self.reveal.next() {
+                    self.reveal = region;
+                }
+            }
+            Message::RevealPrevious if self.options.projector => {
+                if let Some(region) = self.reveal.previous() {
+                    self.reveal = region;
+                }
+            }
+            Message::RevealNext | Message::RevealPrevious => {}
+            Message::TogglePresentation => return self.set_presenting(!self.presenting),
+            Message::EndPresentation => {
+                if self.presenting {
+                    return self.set_presenting(false);
+                }
+            }
+            Message::SetPresentationCycle(cycle) => self.options.presentation_cycle = cycle,
+            Message::PresentNext => self.present_next(),
+            Message::SetPlayback(playback) => {
+                self.options.playback = playback;
+                self.playback = Playback::default();
+            }
+            Message::TogglePlaying => self.playback.toggle(self.playback_len()),
+            Message::SeekPlayback(shown) => {
+                self.playback.seek(shown as usize, self.playback_len());
+            }
+            Message::PlaybackTick => self.playback.tick(self.playback_len()),
+            Message::LoadExample(example) => self.load_example(example),
+            Message::StartTutorial => self.start_tutorial(),
+            Message::NextTutorialStep => self.next_tutorial_step(),
+            Message::EndTutorial => {
+                self.tutorial = None;
+                tutorial::mark_done();
+            }
+            Message::ToggleDebug => {
+                self.debug = !self.debug;
+                self.worker.trace().set_enabled(self.debug);
+            }
+            Message::SetLogPanel(log_panel) => self.log_panel = log_panel,
+            Message::SetProfile(profile) => {
+                self.set_profile(profile);
+                profile::save(profile);
+            }
+            Message::Response(response) => match response.as_ref() {
+                Response::Unload(path) => {
+                    self.files.remove(path);
+                    if self.compared.as_ref() == Some(path) {
+                        self.compared = None;
+                    }
+                    self.loaded_at.remove(path);
+                    self.excluded.remove(path);
+                    self.series_names.remove(path);
+                    self.stale.remove(path);
+                    if self.selected.as_ref() == Some(path) {
+                        self.selected = self.files.keys().next().cloned();
+                    }
+                }
+                Response::Output(path, output, time) => {
+                    self.progress = None;
+                    self.loaded_at.insert(path.clone(), *time);
+                    if self.stale.remove(path).is_some() {
+                        info!(path = %path.display(), "Recovered from a failed reload");
+                    }
+                    let shown = self.prepared(path, output.clone());
+                    self.insert_file(path.clone(), Left(shown));
+                    // A change of the file on disk replaces the edits.
+                    if self.data_editor.path() == Some(path.as_path()) {
+                        self.data_editor.open(path.clone(), output.clone());
+                    }
+                    self.open_data_editor();
+                }
+                Response::Error(path, err) => {
+                    self.progress = None;
+                    let message = error_message(err);
+                    self.toasts.error(format!("{}: {message}", path.display()));
+                    // A file saved while it is written is read again after the next change, so
+                    // the last output is kept until then.
+                    if let Some(Left(_)) = self.files.get(path) {
+                        self.stale.insert(path.clone(), message);
+                    } else {
+                        self.insert_file(path.clone(), Right(message));
+                    }
+                }
+                Response::Saved(path) => self
+                    .toasts
+                    .info(format!("{NOTICE_SAVED}: {}", path.display())),
+                Response::SaveFailed(_, err) => self.toasts.error(error_message(err)),
+                Response::PendingChanges(count) => self.pending_changes = *count,
+                Response::WatchedFolder(path) => self.watched_folder = path.clone(),
+                Response::Progress {
+                    path,
+                    rows_done,
+                    rows_total,
+                } => self.progress = Some((path.clone(), *rows_done, *rows_total)),
+                Response::Cancelled(path) => {
+                    self.progress = None;
+                    self.toasts
+                        .info(format!("{NOTICE_CANCELLED}: {}", path.display()));
+                }
+                #[cfg(feature = "live")]
+                Response::Reading(reading, temperature) => {
+                    // With a calibration, the meter sends the potential of the electrode in mV.
+                    let ph = match self
+                        .options
+                        .calibration
+                        .as_ref()
+                        .and_then(Calibration::line)
+                    {
+                        Some(line) => {
+                            self.calibration.set_reading(*reading);
+                            let working = self.options.temperature.unwrap_or(STANDARD_TEMPERATURE);
+                            line.ph(*reading, temperature.unwrap_or(working))
                         }
+                        None => *reading,
+                    };
+                    if self.options.live && self.live.record(ph, *temperature) {
+                        self.update_live();
+                    }
+                }
+                #[cfg(feature = "live")]
+                Response::LiveFailed(err) => {
+                    self.live.connected = false;
+                    self.toasts.error(error_message(err));
+                }
+                #[cfg(feature = "scripting")]
+                Response::ScriptLoaded(path) => {
+                    // The files were calculated with the script, so its model is selected.
+                    if let Some(index) = MODELS.iter().position(|it| it.id() == "script") {
+                        self.options.model = index;
+                        self.worker.set_model(MODELS[index]);
+                        self.worker.send_signal(Signal::Reload);
                     }
+                    self.toasts
+                        .info(format!("{NOTICE_SCRIPT_LOADED}: {}", path.display()));
+                }
+                #[cfg(feature = "scripting")]
+                Response::ScriptFailed(err) => self.toasts.error(error_message(err)),
+                Response::Stopped => self.restart_worker(),
+            },
+            Message::DismissToast(id) => self.toasts.dismiss(id),
+            Message::OpenForwarded(paths) => {
+                for path in paths {
+                    self.worker.send_signal(Signal::LoadPath(path));
+                }
+                return window::gain_focus(window::Id::MAIN);
+            }
+            Message::CancelLoading => self.worker.send_signal(Signal::Cancel),
+            Message::CloseRequested(id) if self.diagram_window == Some(id) => {
+                self.diagram_window = None;
+                return window::close(id);
+            }
+            Message::CloseRequested(id) => {
+                info!("Closing the window");
+                self.closing = true;
+                self.worker.stop();
+                let diagram = self.diagram_window.take().map(window::close);
+                return Command::batch([window::close(id)].into_iter().chain(diagram));
+            }
+            Message::DetachDiagram => {
+                if self.diagram_window.is_none() {
+                    let id = window::Id::unique();
+                    self.diagram_window = Some(id);
+                    return window::spawn(
+                        id,
+                        window::Settings {
+                            size: Size::new(800.0, 600.0),
+                            // Closing the window only brings the diagram back.
+                            exit_on_close_request: false,
+                            ..Default::default()
+                        },
+                    );
+                }
+            }
+            Message::AttachDiagram => {
+                if let Some(id) = self.diagram_window.take() {
+                    return window::close(id);
+                }
+            }
+            Message::Tick(_) => {
+                self.toasts.expire();
+                if !self.worker.is_alive() {
+                    self.restart_worker();
                 }
             }
         }
         Command::none()
     }
 
-    fn view(&self) -> Element<Self::Message> {
+    fn view(&self, window: window::Id) -> Element<'_, Self::Message> {
+        let outputs = self.shown_outputs();
+        if self.diagram_window == Some(window) || self.presenting {
+            let content = if outputs.is_empty() {
+                container(text(MESSAGE_NO_CONTENT))
+            } else {
+                container(self.graph(&outputs))
+            };
+            return content
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .center_x()
+                .center_y()
+                .padding(10)
+                .into();
+        }
         let controls = {
-            let dark_toggle = checkbox(OPTION_DARK, self.options.dark, Message::SetDark);
+            let dark_toggle = checkbox(
+                self.tutorial_label(TutorialStep::ToggleTheme, OPTION_DARK),
+                self.options.dark,
+                Message::SetDark,
+            );
+            let system_theme_toggle = checkbox(
+                OPTION_SYSTEM_THEME,
+                self.options.system_theme,
+                Message::SetSystemTheme,
+            );
             let colored_toggle =
                 checkbox(OPTION_COLORED, self.options.colored, Message::SetColored);
-            let file_button = button(BUTTON_SELECT_FILE).on_press(Message::SelectFile);
-            container(
-                column![dark_toggle, colored_toggle, file_button]
-                    .spacing(5)
-                    .padding(10),
+            let overlay_toggle =
+                checkbox(OPTION_OVERLAY, self.options.overlay, Message::SetOverlay);
+            let compare_toggle =
+                checkbox(OPTION_COMPARE, self.options.compare, Message::SetCompare);
+            let ladder_toggle = checkbox(OPTION_LADDER, self.options.ladder, Message::SetLadder);
+            let smooth_toggle = checkbox(OPTION_SMOOTH, self.options.smooth, Message::SetSmooth);
+            let sort_volumes_toggle = checkbox(
+                OPTION_SORT_VOLUMES,
+                self.options.sort_volumes,
+                Message::SetSortVolumes,
+            );
+            let crosshair_toggle = checkbox(
+                OPTION_CROSSHAIR,
+                self.options.crosshair,
+                Message::SetCrosshair,
+            );
+            let max_points = self
+                .options
+                .max_points
+                .map(|it| it.to_string())
+                .unwrap_or_default();
+            let max_points_input = row![
+                text(OPTION_MAX_POINTS).size(12).width(Length::Fill),
+                text_input(OPTION_ALL_POINTS.get(), &max_points)
+                    .on_input(Message::MaxPointsInput)
+                    .width(Length::Fixed(60.0)),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center);
+            let generator_toggle = checkbox(
+                OPTION_GENERATOR,
+                self.options.generator,
+                Message::SetGenerator,
+            );
+            let simulation_toggle = checkbox(
+                OPTION_SIMULATION,
+                self.options.simulation,
+                Message::SetSimulation,
+            );
+            let burette_toggle =
+                checkbox(OPTION_BURETTE, self.options.burette, Message::SetBurette);
+            #[cfg(feature = "live")]
+            let live_toggle =
+                Some(checkbox(OPTION_LIVE, self.options.live, Message::SetLive).into());
+            #[cfg(not(feature = "live"))]
+            let live_toggle = None;
+            #[cfg(feature = "live")]
+            let calibration_toggle = Some(
+                checkbox(
+                    OPTION_CALIBRATION,
+                    self.options.calibrating,
+                    Message::SetCalibrating,
+                )
+                .into(),
+            );
+            #[cfg(not(feature = "live"))]
+            let calibration_toggle = None;
+            let calculator_toggle = checkbox(
+                OPTION_CALCULATOR,
+                self.options.calculator,
+                Message::SetCalculator,
+            );
+            let style_toggle = checkbox(
+                OPTION_STYLE_EDITOR,
+                self.options.style_editor,
+                Message::SetStyleEditor,
+            );
+            let outliers_toggle =
+                checkbox(OPTION_OUTLIERS, self.options.outliers, Message::SetOutliers);
+            let data_editor_toggle = checkbox(
+                OPTION_DATA_EDITOR,
+                self.options.data_editor,
+                Message::SetDataEditor,
+            );
+            let annotation_toggle = checkbox(
+                OPTION_ANNOTATIONS,
+                self.options.annotating,
+                Message::SetAnnotating,
+            );
+            let playback_toggle =
+                checkbox(OPTION_PLAYBACK, self.options.playback, Message::SetPlayback);
+            let projector_toggle = checkbox(
+                OPTION_PROJECTOR,
+                self.options.projector,
+                Message::SetProjector,
+            );
+            let present_button = button(BUTTON_PRESENT.get()).on_press(Message::TogglePresentation);
+            let presentation_cycle_toggle = checkbox(
+                OPTION_PRESENTATION_CYCLE,
+                self.options.presentation_cycle,
+                Message::SetPresentationCycle,
+            );
+            let log_toggle = checkbox(OPTION_LOG_PANEL, self.log_panel, Message::SetLogPanel);
+            let explain_toggle =
+                checkbox(OPTION_EXPLAIN, self.options.explain, Message::SetExplain);
+            let usage_toggle = checkbox(OPTION_USAGE, self.options.usage, Message::SetUsage);
+            let fit_toggle = checkbox(OPTION_FIT, self.options.fit, Message::SetFit);
+            let activity_toggle =
+                checkbox(OPTION_ACTIVITY, self.options.activity, Message::SetActivity);
+            let summary_toggle =
+                checkbox(OPTION_SUMMARY, self.options.summary, Message::SetSummary);
+            let file_button = button(text(
+                self.tutorial_label(TutorialStep::SelectFile, BUTTON_SELECT_FILE),
+            ))
+            .on_press(Message::SelectFile);
+            let example_select =
+                pick_list(&Example::ALL[..], None::<Example>, Message::LoadExample)
+                    .placeholder(EXAMPLE_LOAD.get())
+                    .text_size(12);
+            let language_select = column![
+                radio(
+                    LANGUAGE_GERMAN,
+                    Language::German,
+                    Some(strings::language()),
+                    Message::SetLanguage
+                ),
+                radio(
+                    LANGUAGE_ENGLISH,
+                    Language::English,
+                    Some(strings::language()),
+                    Message::SetLanguage
+                ),
+            ]
+            .spacing(5);
+            let number_locale_select = column![
+                text(NUMBER_LOCALE_TITLE),
+                radio(
+                    NUMBER_LOCALE_POINT,
+                    NumberLocale::Point,
+                    Some(locale::number_locale()),
+                    Message::SetNumberLocale
+                ),
+                radio(
+                    NUMBER_LOCALE_COMMA,
+                    NumberLocale::Comma,
+                    Some(locale::number_locale()),
+                    Message::SetNumberLocale
+                ),
+            ]
+            .spacing(5);
+            let pause_toggle = checkbox(OPTION_PAUSED, self.paused, Message::SetPaused);
+            let mut reload_button = button(BUTTON_RELOAD.get());
+            if !self.files.is_empty() {
+                reload_button = reload_button.on_press(Message::ReloadNow);
+            }
+            let temperature = self.options.temperature.unwrap_or(STANDARD_TEMPERATURE);
+            let temperature_input = column![
+                text(OPTION_TEMPERATURE),
+                row![
+                    text_input(&STANDARD_TEMPERATURE.to_string(), &self.temperature)
+                        .on_input(Message::TemperatureInput)
+                        .width(Length::Fixed(60.0)),
+                    text(format!("pKw = {:.2}", temperature::pkw(temperature))).size(12),
+                ]
+                .spacing(5)
+                .align_items(Alignment::Center),
+            ]
+            .spacing(3);
+            let models = MODELS.iter().enumerate().map(|(index, it)| {
+                radio(
+                    model_name(*it),
+                    index,
+                    Some(self.options.model),
+                    Message::SetModel,
+                )
+                .size(14)
+                .text_size(12)
+                .into()
+            });
+            let model_select = column![
+                text(OPTION_MODEL),
+                Column::with_children(models.collect::<Vec<_>>()).spacing(3),
+            ]
+            .spacing(3);
+            #[cfg(feature = "scripting")]
+            let model_select = model_select.push(
+                row![
+                    button(text(BUTTON_LOAD_SCRIPT).size(12)).on_press(Message::LoadScript),
+                    text(
+                        script::path().map_or_else(|| SCRIPT_NONE.to_string(), |it| file_stem(&it))
+                    )
+                    .size(12),
+                ]
+                .spacing(5)
+                .align_items(Alignment::Center),
+            );
+            let event_filter = Some(self.options.event_filter);
+            let filter_select = column![
+                text(WATCH_TITLE).size(12),
+                radio(
+                    WATCH_ANY_MODIFY,
+                    EventFilter::AnyModify,
+                    event_filter,
+                    Message::SetEventFilter
+                )
+                .size(14)
+                .text_size(12),
+                radio(
+                    WATCH_IGNORE_ATTRIBUTES,
+                    EventFilter::IgnoreAttributes,
+                    event_filter,
+                    Message::SetEventFilter
+                )
+                .size(14)
+                .text_size(12),
+                radio(
+                    WATCH_DATA_ONLY,
+                    EventFilter::DataOnly,
+                    event_filter,
+                    Message::SetEventFilter
+                )
+                .size(14)
+                .text_size(12),
+                text(format!("{WATCH_DEBOUNCE}: {} ms", self.options.debounce.0)).size(12),
+                slider(0..=2000, self.options.debounce.0, Message::SetDebounce).step(100u32),
+                checkbox(WATCH_TAIL, self.options.tail, Message::SetTail)
+                    .size(14)
+                    .text_size(12),
+            ]
+            .spacing(3);
+            let mut folder_button = button(text(BUTTON_WATCH_FOLDER).size(12));
+            let mut unwatch_button = button(text(BUTTON_UNWATCH_FOLDER).size(12));
+            if self.watched_folder.is_some() {
+                unwatch_button = unwatch_button.on_press(Message::UnwatchFolder);
+            } else {
+                folder_button = folder_button.on_press(Message::WatchFolder);
+            }
+            let folder_select = column![
+                text(WATCH_FOLDER_TITLE).size(12),
+                text_input(WATCH_FOLDER_PATTERN.get(), &self.folder_pattern)
+                    .on_input(Message::FolderPatternInput)
+                    .size(12),
+                row![folder_button, unwatch_button].spacing(5),
+                text(match &self.watched_folder {
+                    Some(path) => path.display().to_string(),
+                    None => WATCH_FOLDER_NONE.to_string(),
+                })
+                .size(12),
+            ]
+            .spacing(3);
+            let plot_select = column![
+                radio(
+                    PLOT_CURVE,
+                    Plot::Curve,
+                    Some(self.plot),
+                    Message::SelectPlot
+                ),
+                radio(
+                    PLOT_DERIVATIVE,
+                    Plot::Derivative,
+                    Some(self.plot),
+                    Message::SelectPlot
+                ),
+                radio(
+                    PLOT_CONDUCTIVITY,
+                    Plot::Conductivity,
+                    Some(self.plot),
+                    Message::SelectPlot
+                ),
+                radio(
+                    PLOT_CORRELATION,
+                    Plot::Correlation,
+                    Some(self.plot),
+                    Message::SelectPlot
+                ),
+                radio(
+                    PLOT_PRECIPITATION,
+                    Plot::Precipitation,
+                    Some(self.plot),
+                    Message::SelectPlot
+                ),
+                radio(
+                    PLOT_COMPLEXOMETRY,
+                    Plot::Complexometry,
+                    Some(self.plot),
+                    Message::SelectPlot
+                ),
+                radio(
+                    PLOT_TEMPERATURE,
+                    Plot::Temperature,
+                    Some(self.plot),
+                    Message::SelectPlot
+                ),
+                radio(
+                    PLOT_BUFFER_CAPACITY,
+                    Plot::BufferCapacity,
+                    Some(self.plot),
+                    Message::SelectPlot
+                ),
+                radio(PLOT_HAGG, Plot::Hagg, Some(self.plot), Message::SelectPlot),
+            ]
+            .spacing(5);
+            let plot_select = if self.allows(Feature::Fitting) {
+                plot_select.push(radio(
+                    PLOT_GRAN,
+                    Plot::Gran,
+                    Some(self.plot),
+                    Message::SelectPlot,
+                ))
+            } else {
+                plot_select
+            };
+            let plot_select = match self.plot {
+                Plot::Conductivity | Plot::Correlation => {
+                    plot_select.push(self.conductivity_controls())
+                }
+                Plot::Precipitation => plot_select.push(self.precipitation_controls()),
+                Plot::Complexometry => plot_select.push(self.complexometry_controls()),
+                Plot::Gran => match self.gran_report() {
+                    Some(report) => plot_select.push(report),
+                    None => plot_select,
+                },
+                Plot::Curve => plot_select.push(self.secondary_controls()),
+                _ => plot_select,
+            };
+            let mut copy_button = button(text(
+                self.tutorial_label(TutorialStep::Export, BUTTON_COPY_DIAGRAM),
+            ));
+            let mut export_button = button(text(
+                self.tutorial_label(TutorialStep::Export, BUTTON_EXPORT_DIAGRAM),
+            ));
+            if !outputs.is_empty() {
+                copy_button = copy_button.on_press(Message::CopyDiagram);
+                export_button = export_button.on_press(Message::ExportDiagram);
+            }
+            let detach_button = if self.diagram_window.is_some() {
+                button(BUTTON_ATTACH_DIAGRAM.get()).on_press(Message::AttachDiagram)
+            } else {
+                button(BUTTON_DETACH_DIAGRAM.get()).on_press(Message::DetachDiagram)
+            };
+            let sizes = ExportSize::ALL.map(|it| {
+                radio(
+                    it.label(),
+                    it,
+                    Some(self.options.export_size),
+                    Message::SetExportSize,
+                )
+                .size(12)
+                .text_size(12)
+                .into()
+            });
+            let export_size_select = column![
+                text(EXPORT_SIZE_TITLE).size(12),
+                Column::with_children(sizes.into_iter().collect::<Vec<_>>()).spacing(2),
+            ]
+            .spacing(3);
+            let mut solution_button = button(text(
+                self.tutorial_label(TutorialStep::Export, BUTTON_EXPORT_SOLUTION),
+            ));
+            let mut html_button = button(BUTTON_EXPORT_HTML.get());
+            let mut csv_button = button(BUTTON_EXPORT_CSV.get());
+            let mut xlsx_button = button(BUTTON_EXPORT_XLSX.get());
+            let mut report_button = button(BUTTON_EXPORT_REPORT.get());
+            let tutorial_button = button(BUTTON_TUTORIAL.get()).on_press(Message::StartTutorial);
+            if self.selected_output().is_some() {
+                solution_button = solution_button.on_press(Message::ExportSolution);
+                html_button = html_button.on_press(Message::ExportHtml);
+                csv_button = csv_button.on_press(Message::ExportTable(TableFormat::Csv));
+                xlsx_button = xlsx_button.on_press(Message::ExportTable(TableFormat::Xlsx));
+                report_button = report_button.on_press(Message::ExportReport);
+            }
+            let file_list = self.file_list();
+            let profile_select = Column::with_children(
+                Profile::ALL
+                    .into_iter()
+                    .map(|it| {
+                        radio(it.label(), it, Some(self.profile), Message::SetProfile)
+                            .size(14)
+                            .text_size(12)
+                            .into()
+                    })
+                    .collect::<Vec<_>>(),
             )
-            .width(Length::Fixed(110.0))
-            .height(Length::Fill)
+            .spacing(3);
+            // Controls of features hidden by the profile are left out.
+            let gated = |feature, element: Element<'static, Message>| {
+                self.allows(feature).then_some(element)
+            };
+            let controls: Vec<Element<Message>> = [
+                Some(language_select.into()),
+                Some(number_locale_select.into()),
+                Some(
+                    column![text(PROFILE_TITLE).size(12), profile_select]
+                        .spacing(3)
+                        .into(),
+                ),
+                Some(dark_toggle.into()),
+                Some(system_theme_toggle.into()),
+                Some(colored_toggle.into()),
+                Some(overlay_toggle.into()),
+                Some(compare_toggle.into()),
+                Some(ladder_toggle.into()),
+                Some(smooth_toggle.into()),
+                Some(crosshair_toggle.into()),
+                Some(sort_volumes_toggle.into()),
+                Some(max_points_input.into()),
+                gated(Feature::Explain, explain_toggle.into()),
+                gated(Feature::Usage, usage_toggle.into()),
+                Some(summary_toggle.into()),
+                gated(Feature::Fitting, fit_toggle.into()),
+                Some(activity_toggle.into()),
+                Some(model_select.into()),
+                Some(temperature_input.into()),
+                gated(Feature::Generator, generator_toggle.into()),
+                gated(Feature::Simulation, simulation_toggle.into()),
+                gated(Feature::Simulation, burette_toggle.into()),
+                live_toggle,
+                calibration_toggle,
+                Some(calculator_toggle.into()),
+                Some(outliers_toggle.into()),
+                Some(data_editor_toggle.into()),
+                Some(annotation_toggle.into()),
+                Some(style_toggle.into()),
+                gated(Feature::Projector, projector_toggle.into()),
+                Some(present_button.into()),
+                Some(presentation_cycle_toggle.into()),
+                gated(Feature::Projector, playback_toggle.into()),
+                gated(Feature::Diagnostics, log_toggle.into()),
+                Some(plot_select.into()),
+                Some(self.x_quantity_select().into()),
+                Some(text(AXIS_TITLE).into()),
+                Some(self.axis_controls(Axis::X).into()),
+                Some(self.axis_controls(Axis::Y).into()),
+                Some(self.legend_controls().into()),
+                self.allows(Feature::Rounding)
+                    .then(|| self.rounding_controls().into()),
+                Some(self.number_format_controls().into()),
+                gated(Feature::Diagnostics, filter_select.into()),
+                Some(folder_select.into()),
+                Some(pause_toggle.into()),
+                Some(file_button.into()),
+                Some(example_select.into()),
+                Some(reload_button.into()),
+                Some(copy_button.into()),
+                Some(export_button.into()),
+                Some(detach_button.into()),
+                Some(export_size_select.into()),
+                gated(Feature::Solution, solution_button.into()),
+                Some(html_button.into()),
+                Some(csv_button.into()),
+                Some(xlsx_button.into()),
+                gated(Feature::Report, report_button.into()),
+                gated(Feature::Tutorial, tutorial_button.into()),
+                Some(file_list.into()),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            self.sidebar(Column::with_children(controls).spacing(5).padding(10))
         };
-        let content = match &self.content {
-            Left(output) => {
-                let svg_text = diagram::render_graph(&self.options, &output);
-                let handle = Handle::from_memory(svg_text.into_bytes());
-                container(
-                    svg(handle)
-                        .width(Length::Fill)
-                        .height(Length::Fill)
-                        .content_fit(ContentFit::Contain),
-                )
+        let selected = self.selected.as_ref().and_then(|it| self.files.get(it));
+        let content = match (&outputs[..], selected, self.compared_outputs()) {
+            _ if self.options.generator => container(self.generator.view(self.options.rounding)),
+            ([], Some(Right(message)), _) => container(text(message)),
+            ([], _, _) => container(text(MESSAGE_NO_CONTENT)),
+            (_, _, Some(compared)) => container(self.comparison_view(compared)),
+            (outputs, _, None) => {
+                let graph: Element<Message> = if self.diagram_window.is_some() {
+                    column![
+                        text(MESSAGE_DIAGRAM_DETACHED),
+                        button(BUTTON_ATTACH_DIAGRAM.get()).on_press(Message::AttachDiagram),
+                    ]
+                    .spacing(5)
+                    .into()
+                } else {
+                    self.graph(outputs)
+                };
+                let mut content = column![].spacing(10);
+                if self.pending_changes > 0 {
+                    let label = if self.pending_changes == 1 {
+                        BADGE_PENDING_CHANGE
+                    } else {
+                        BADGE_PENDING_CHANGES
+                    };
+                    let badge = button(text(format!("{} {label}", self.pending_changes)).size(12))
+                        .on_press(Message::ReloadNow);
+                    content = content.push(badge);
+                }
+                if let Some(message) = self.selected.as_ref().and_then(|it| self.stale.get(it)) {
+                    content = content.push(text(format!("⚠ {BADGE_STALE}: {message}")).size(12));
+                }
+                if let Some(panel) = self.warnings_panel() {
+                    content = content.push(panel);
+                }
+                if self.options.summary {
+                    content = content.push(row![graph, self.summary_panel()].spacing(10));
+                } else {
+                    content = content.push(graph);
+                }
+                if self.plot == Plot::Curve {
+                    if let Some(readout) = self.ph_readout() {
+                        content = content.push(readout);
+                    }
+                    if let Some(report) = self.buffering_report() {
+                        content = content.push(report);
+                    }
+                    if self.options.outliers {
+                        if let Some(panel) = self.outlier_panel() {
+                            content = content.push(panel);
+                        }
+                    }
+                    if self.options.fit {
+                        if let Some(panel) = self.fit_panel() {
+                            content = content.push(panel);
+                        }
+                    }
+                    if self.options.activity {
+                        if let Some(panel) = self.activity_panel() {
+                            content = content.push(panel);
+                        }
+                    }
+                }
+                if let (Some(output), Some(index)) =
+                    (self.selected_output(), self.explained_index())
+                {
+                    content = content.push(self.explain_panel(output, index));
+                }
+                if self.options.usage {
+                    content = content.push(self.usage_panel());
+                }
+                if self.options.projector {
+                    content = content.push(self.reveal_controls());
+                }
+                if self.options.playback && self.plot == Plot::Curve {
+                    if let Some(controls) = self.playback_controls() {
+                        content = content.push(controls);
+                    }
+                }
+                container(content)
             }
-            Right(message) => container(text(message)),
         }
         .width(Length::Fill)
         .height(Length::Fill)
         .center_x()
         .center_y()
         .padding(10);
-        row![controls, content].into()
+        let mut content = column![content];
+        if (self.options.simulation || self.options.burette) && !self.options.generator {
+            content = column![self.simulation.view(self.options.rounding), content].padding(10);
+        }
+        if self.options.burette && !self.options.generator {
+            let output = match self.files.get(Path::new(BURETTE_PATH)) {
+                Some(Left(output)) => Some(&**output),
+                _ => None,
+            };
+            content = column![self.burette.view(output), content].padding(10);
+        }
+        #[cfg(feature = "live")]
+        if self.options.live && !self.options.generator {
+            content = column![self.live.view(), content].padding(10);
+        }
+        #[cfg(feature = "live")]
+        if self.options.calibrating && !self.options.generator {
+            let temperature = self.options.temperature.unwrap_or(STANDARD_TEMPERATURE);
+            let applied = self.options.calibration.is_some();
+            content = column![self.calibration.view(applied, temperature), content].padding(10);
+        }
+        if self.options.style_editor && !self.options.generator {
+            let editor = self.style.view(&self.options.style, self.options.dark);
+            content = column![editor, content].padding(10);
+        }
+        let editing_selected = self.data_editor.path().is_some()
+            && self.data_editor.path() == self.selected.as_deref();
+        if self.options.data_editor && editing_selected && !self.options.generator {
+            content = column![self.data_editor.view(), content].padding(10);
+        }
+        if self.options.annotating && !self.options.generator {
+            let editor = self.annotation.view(&self.options.annotations);
+            content = column![editor, content].padding(10);
+        }
+        if self.options.calculator && !self.options.generator {
+            let calculator = self
+                .calculator
+                .view(self.options.rounding, self.allows(Feature::Simulation));
+            content = column![calculator, content].padding(10);
+        }
+        if let Some(step) = self.tutorial {
+            content = column![self.tutorial_panel(step).padding(10), content];
+        }
+        if let Some(toasts) = self.toasts.view() {
+            content = column![container(toasts).padding([10, 10, 0, 10]), content];
+        }
+        let mut main = row![controls, content];
+        if self.log_panel {
+            main = main.push(self.log_panel());
+        }
+        if self.debug {
+            main = main.push(self.debug_overlay());
+        }
+        column![main.height(Length::Fill), self.status_bar()].into()
     }
 
-    fn theme(&self) -> Self::Theme {
+    fn theme(&self, _window: window::Id) -> Self::Theme {
         if self.options.dark {
             Theme::Dark
         } else {
@@ -131,6 +3489,269 @@ impl Application for TitrationCurve {
     }
 
     fn subscription(&self) -> Subscription<Self::Message> {
-        Subscription::batch([every(Duration::from_millis(500)).map(Message::Update)])
+        let mut subscriptions = vec![
+            responses(self.worker_generation, self.response_receiver.clone()),
+            keys::key_presses(),
+            events::close_requests(),
+        ];
+        if let Some(receiver) = &self.flags.forwarded {
+            subscriptions.push(forwarded_paths(receiver.clone()));
+        }
+        if self.needs_tick() {
+            subscriptions.push(every(Duration::from_secs(1)).map(Message::Tick));
+        }
+        if self.options.playback && self.playback.playing {
+            subscriptions.push(every(PLAYBACK_INTERVAL).map(|_| Message::PlaybackTick));
+        }
+        if self.presenting && self.options.presentation_cycle {
+            subscriptions.push(every(PRESENTATION_INTERVAL).map(|_| Message::PresentNext));
+        }
+        if self.resizing_sidebar {
+            subscriptions.push(events::sidebar_drags());
+        }
+        if self.options.system_theme {
+            subscriptions.push(system_theme());
+        }
+        Subscription::batch(subscriptions)
+    }
+}
+
+/// Subscribes to the paths forwarded by later launches of the app.
+fn forwarded_paths(receiver: Arc<Mutex<UnboundedReceiver<Vec<PathBuf>>>>) -> Subscription<Message> {
+    struct Forwarded;
+    subscription::unfold(
+        std::any::TypeId::of::<Forwarded>(),
+        receiver,
+        |receiver| async move {
+            let paths = receiver.lock().await.next().await;
+            match paths {
+                Some(paths) => (Message::OpenForwarded(paths), receiver),
+                // The listener thread stopped, so no more paths will be forwarded.
+                None => future::pending().await,
+            }
+        },
+    )
+}
+
+/// Subscribes to the theme of the operating system, which is sent when the subscription starts
+/// and whenever it changes.
+///
+/// The detection may block, so it runs on its own thread instead of the executor.
+fn system_theme() -> Subscription<Message> {
+    struct SystemTheme;
+    subscription::channel(
+        std::any::TypeId::of::<SystemTheme>(),
+        1,
+        |mut output| async move {
+            let (sender, mut receiver) = unbounded();
+            thread::Builder::new()
+                .name("theme".into())
+                .spawn(move || {
+                    let mut last = None;
+                    // The receiver is dropped with the subscription, which ends the thread.
+                    while !sender.is_closed() {
+                        let dark = system_prefers_dark();
+                        if last != Some(dark) {
+                            last = Some(dark);
+                            _ = sender.unbounded_send(dark);
+                        }
+                        thread::sleep(SYSTEM_THEME_INTERVAL);
+                    }
+                })
+                .expect("spawn theme thread");
+            while let Some(dark) = receiver.next().await {
+                _ = output.send(Message::SystemTheme { dark }).await;
+            }
+            future::pending().await
+        },
+    )
+}
+
+/// Subscribes to the responses of a worker.
+///
+/// The id has to change whenever the worker is replaced, so the subscription is restarted.
+fn responses(
+    id: usize,
+    receiver: Arc<Mutex<UnboundedReceiver<Response>>>,
+) -> Subscription<Message> {
+    subscription::unfold(id, receiver, |receiver| async move {
+        let response = receiver.lock().await.next().await;
+        match response {
+            Some(response) => (Message::Response(Arc::new(response)), receiver),
+            // The worker was dropped, so there will be no more responses.
+            None => future::pending().await,
+        }
+    })
+}
+
+/// Creates a swatch colored like the universal indicator at a pH.
+fn indicator_swatch(ph: f32, width: f32, height: f32) -> Container<'static, Message> {
+    let [r, g, b] = diagram::indicator_color(ph);
+    let color = Color::from_rgb8(r, g, b);
+    container(text(""))
+        .width(Length::Fixed(width))
+        .height(Length::Fixed(height))
+        .style(move |_: &Theme| container::Appearance {
+            background: Some(color.into()),
+            ..Default::default()
+        })
+}
+
+/// Checks if the operating system prefers a dark theme.
+///
+/// Systems without a preference get the light theme.
+fn system_prefers_dark() -> bool {
+    matches!(dark_light::detect(), dark_light::Mode::Dark)
+}
+
+/// Returns the name of a file without its extension.
+/// Parses an optional number up to a limit.
+///
+/// Empty input clears the number, invalid input is ignored.
+fn parse_limit(value: &str, limit: u8) -> Option<Option<u8>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Some(None);
+    }
+    value.parse().ok().filter(|it| *it <= limit).map(Some)
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|it| it.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Returns the display name of a quantity on the x-axis.
+fn x_quantity_name(quantity: XQuantity) -> Localized {
+    match quantity {
+        XQuantity::Volume => X_QUANTITY_VOLUME,
+        XQuantity::Degree => X_QUANTITY_DEGREE,
+        XQuantity::Amount => X_QUANTITY_AMOUNT,
+    }
+}
+
+/// Returns the display name of a titration model.
+///
+/// Models without a translated name are shown by their identifier.
+fn model_name(model: &dyn TitrationModel) -> Localized {
+    match model.id() {
+        "strong" => MODEL_STRONG,
+        "weak-acid" => MODEL_WEAK_ACID,
+        #[cfg(feature = "scripting")]
+        "script" => MODEL_SCRIPT,
+        id => Localized::new(id, id),
+    }
+}
+
+/// Returns the display name of a calculated quantity.
+fn quantity_name(quantity: Quantity) -> Localized {
+    match quantity {
+        Quantity::TotalVolume => EXPLAIN_TOTAL_VOLUME,
+        Quantity::AddedAmount => EXPLAIN_ADDED_AMOUNT,
+        Quantity::RemainingAmount => EXPLAIN_REMAINING_AMOUNT,
+        Quantity::TestConcentration => EXPLAIN_TEST_CONCENTRATION,
+        Quantity::ExcessConcentration => EXPLAIN_EXCESS_CONCENTRATION,
+        Quantity::Ph => EXPLAIN_PH,
+        Quantity::Poh => EXPLAIN_POH,
+    }
+}
+
+/// Returns the name of a notation of numbers.
+fn notation_label(notation: Notation) -> Localized {
+    match notation {
+        Notation::Auto => NUMBER_FORMAT_AUTO,
+        Notation::Fixed => NUMBER_FORMAT_FIXED,
+        Notation::Significant => NUMBER_FORMAT_SIGNIFICANT,
+        Notation::Scientific => NUMBER_FORMAT_SCIENTIFIC,
+    }
+}
+
+/// Returns the display name of a field of the table.
+fn field_name(field: Field) -> Localized {
+    match field {
+        Field::TestVolume => FIELD_TEST_VOLUME,
+        Field::TestConcentration => FIELD_TEST_CONCENTRATION,
+        Field::MeasuringConcentration => FIELD_MEASURING_CONCENTRATION,
+        Field::MeasuringVolume => FIELD_MEASURING_VOLUME,
+        Field::SecondConcentration => FIELD_SECOND_CONCENTRATION,
+        Field::SecondPka => FIELD_SECOND_PKA,
+        Field::Temperature => FIELD_TEMPERATURE,
+    }
+}
+
+/// Returns the message shown for a warning about an implausible value.
+fn warning_message(warning: &Warning) -> String {
+    match *warning {
+        Warning::Negative(field, value) => {
+            format!(
+                "{}: {WARNING_NEGATIVE} ({})",
+                field_name(field),
+                number(value)
+            )
+        }
+        Warning::Zero(field) => format!("{}: {WARNING_ZERO}", field_name(field)),
+        Warning::HighConcentration(field, value) => format!(
+            "{}: {WARNING_HIGH_CONCENTRATION} ({} mol/L > {} mol/L)",
+            field_name(field),
+            number(value),
+            number(MAX_CONCENTRATION)
+        ),
+        Warning::Decreasing { index, volume } => format!(
+            "{}: {WARNING_DECREASING} ({} mL, #{})",
+            field_name(Field::MeasuringVolume),
+            number(volume),
+            index + 1
+        ),
+        Warning::Duplicate { index, volume } => format!(
+            "{}: {WARNING_DUPLICATE} ({} mL, #{})",
+            field_name(Field::MeasuringVolume),
+            number(volume),
+            index + 1
+        ),
     }
 }
+
+/// Returns the message shown for a worker error.
+fn error_message(err: &WorkerError) -> String {
+    let message = match err {
+        WorkerError::FileDoesNotExist => ERROR_FILE_DOES_NOT_EXIST.into(),
+        WorkerError::TableError(err) => format!("{ERROR_TABLE}: {err}"),
+        WorkerError::NoTableInWorkbook => ERROR_NO_TABLE_IN_WORKBOOK.into(),
+        WorkerError::InvalidMapping(path, err) => {
+            format!("{ERROR_MAPPING} {}: {err}", path.display())
+        }
+        WorkerError::Io(err) => format!("{ERROR_IO}: {err}"),
+        WorkerError::Xlsx(err) => format!("{ERROR_XLSX}: {err}"),
+        WorkerError::NoMetadata => ERROR_NO_METADATA.into(),
+        WorkerError::InvalidMetadata(err) => format!("{ERROR_METADATA}: {err}"),
+        WorkerError::Cancelled => NOTICE_CANCELLED.into(),
+        #[cfg(feature = "scripting")]
+        WorkerError::Script(err) => format!("{ERROR_SCRIPT}: {err}"),
+        WorkerError::NotAWeakAcid(analyte) => {
+            format!("{ERROR_NOT_A_WEAK_ACID}: {}", analyte.formula)
+        }
+        WorkerError::NoConductivity(path) => {
+            format!("{ERROR_NO_CONDUCTIVITY}: {}", path.display())
+        }
+        WorkerError::UnknownMolarMass(field) => {
+            format!("{ERROR_UNKNOWN_MOLAR_MASS} {}", field_name(*field))
+        }
+        WorkerError::MissingField(field) => {
+            format!("{ERROR_MISSING_FIELD} {}", field_name(*field))
+        }
+        WorkerError::InvalidCell(err) => {
+            let found = match &err.found {
+                Some(found) => format!("{ERROR_CELL_FOUND} '{found}'"),
+                None => ERROR_CELL_EMPTY.into(),
+            };
+            format!(
+                "{} {}: {ERROR_CELL_NUMBER} {}, {found}",
+                ERROR_CELL,
+                err.cell_name(),
+                field_name(err.field)
+            )
+        }
+    };
+    format!("{ERROR_OCCURRED}: {message}")
+}
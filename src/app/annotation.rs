@@ -0,0 +1,186 @@
+use iced::{
+    widget::{button, column, radio, row, text, text_input, Column, Row},
+    Element, Length,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::worker::explain::number;
+
+use super::{
+    form::{FormField, TextFields},
+    strings::*,
+    Message,
+};
+
+/// A note added to the diagram by the user.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Annotation {
+    /// A text at a point of the diagram.
+    Label { volume: f32, ph: f32, text: String },
+    /// A vertical line at an added volume in mL.
+    VolumeMarker { volume: f32, text: String },
+    /// A horizontal line at a pH.
+    PhLine { ph: f32, text: String },
+}
+
+impl Annotation {
+    /// Returns the text of the annotation, which may be empty for lines.
+    pub fn text(&self) -> &str {
+        match self {
+            Self::Label { text, .. }
+            | Self::VolumeMarker { text, .. }
+            | Self::PhLine { text, .. } => text,
+        }
+    }
+
+    /// Checks if the annotation can be drawn into a plot without a pH axis.
+    pub fn needs_ph_axis(&self) -> bool {
+        !matches!(self, Self::VolumeMarker { .. })
+    }
+
+    /// Returns a short description for the list of annotations.
+    fn summary(&self) -> String {
+        let position = match self {
+            Self::Label { volume, ph, .. } => {
+                format!("{} mL, pH {}", number(*volume), number(*ph))
+            }
+            Self::VolumeMarker { volume, .. } => format!("V = {} mL", number(*volume)),
+            Self::PhLine { ph, .. } => format!("pH = {}", number(*ph)),
+        };
+        match self.text() {
+            "" => position,
+            text => format!("{position}: {text}"),
+        }
+    }
+}
+
+/// The kind of annotation created by the form.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AnnotationKind {
+    #[default]
+    Label,
+    VolumeMarker,
+    PhLine,
+}
+
+impl AnnotationKind {
+    pub const ALL: [Self; 3] = [Self::Label, Self::VolumeMarker, Self::PhLine];
+
+    pub fn label(self) -> Localized {
+        match self {
+            Self::Label => ANNOTATION_LABEL,
+            Self::VolumeMarker => ANNOTATION_VOLUME_MARKER,
+            Self::PhLine => ANNOTATION_PH_LINE,
+        }
+    }
+}
+
+/// A field of the annotation form.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnnotationField {
+    Volume,
+    Ph,
+    Text,
+}
+
+impl FormField for AnnotationField {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// The state of the form adding annotations.
+#[derive(Default)]
+pub struct AnnotationForm {
+    kind: AnnotationKind,
+    fields: TextFields<AnnotationField>,
+}
+
+impl AnnotationForm {
+    pub fn set(&mut self, field: AnnotationField, value: String) {
+        self.fields.set(field, value);
+    }
+
+    pub fn set_kind(&mut self, kind: AnnotationKind) {
+        self.kind = kind;
+    }
+
+    /// Returns the annotation described by the form, if the needed fields are valid.
+    ///
+    /// Labels need a text, while lines may be drawn without one.
+    pub fn annotation(&self) -> Option<Annotation> {
+        let fields = &self.fields;
+        let volume = || {
+            fields
+                .number(AnnotationField::Volume)
+                .filter(|it| *it >= 0.0)
+        };
+        let ph = || {
+            let ph = fields.number(AnnotationField::Ph);
+            ph.filter(|it| (0.0..=14.0).contains(it))
+        };
+        let text = fields.get(AnnotationField::Text).trim().to_string();
+        Some(match self.kind {
+            AnnotationKind::Label if text.is_empty() => return None,
+            AnnotationKind::Label => Annotation::Label {
+                volume: volume()?,
+                ph: ph()?,
+                text,
+            },
+            AnnotationKind::VolumeMarker => Annotation::VolumeMarker {
+                volume: volume()?,
+                text,
+            },
+            AnnotationKind::PhLine => Annotation::PhLine { ph: ph()?, text },
+        })
+    }
+
+    /// Creates the form and the list of existing annotations with buttons to remove them.
+    pub fn view<'a>(&'a self, annotations: &'a [Annotation]) -> Element<'a, Message> {
+        let kinds = AnnotationKind::ALL.map(|it| {
+            radio(it.label(), it, Some(self.kind), Message::SetAnnotationKind)
+                .size(12)
+                .text_size(12)
+                .into()
+        });
+        let field_row = |label, field, width| {
+            row![
+                text(label).width(Length::Fixed(80.0)),
+                text_input("", self.fields.get(field))
+                    .on_input(move |it| Message::AnnotationInput(field, it))
+                    .width(Length::Fixed(width))
+            ]
+            .spacing(5)
+        };
+        let mut fields = column![].spacing(5);
+        if self.kind != AnnotationKind::PhLine {
+            fields = fields.push(field_row(ANNOTATION_VOLUME, AnnotationField::Volume, 80.0));
+        }
+        if self.kind != AnnotationKind::VolumeMarker {
+            fields = fields.push(field_row(ANNOTATION_PH, AnnotationField::Ph, 80.0));
+        }
+        fields = fields.push(field_row(ANNOTATION_TEXT, AnnotationField::Text, 200.0));
+        let mut add_button = button(BUTTON_ADD_ANNOTATION.get());
+        if self.annotation().is_some() {
+            add_button = add_button.on_press(Message::AddAnnotation);
+        }
+        let entries = annotations.iter().enumerate().map(|(index, it)| {
+            row![
+                button(BUTTON_UNLOAD_FILE.get()).on_press(Message::RemoveAnnotation(index)),
+                text(it.summary()).size(12)
+            ]
+            .spacing(5)
+            .into()
+        });
+        column![
+            text(ANNOTATION_TITLE).size(20),
+            Row::with_children(kinds.into_iter().collect::<Vec<_>>()).spacing(10),
+            fields,
+            add_button,
+            Column::with_children(entries.collect::<Vec<_>>()).spacing(2),
+        ]
+        .spacing(5)
+        .into()
+    }
+}
@@ -0,0 +1,100 @@
+use iced::{
+    widget::{button, column, row, text},
+    Alignment, Element,
+};
+
+use crate::worker::{explain::number, simulation::Simulation, Input, Output};
+
+use super::{indicator_swatch, strings::*, Message};
+
+/// The volumes added with the buttons of the burette in mL.
+pub const BURETTE_STEPS: [f32; 3] = [0.1, 0.5, 1.0];
+
+/// The state of a titration run by hand with the virtual burette.
+///
+/// The readings start at no added volume and only grow, like those of a real burette.
+pub struct Burette {
+    readings: Vec<f32>,
+}
+
+impl Default for Burette {
+    fn default() -> Self {
+        Self {
+            readings: vec![0.0],
+        }
+    }
+}
+
+impl Burette {
+    /// Returns the current reading of the burette in mL.
+    pub fn reading(&self) -> f32 {
+        self.readings.last().copied().unwrap_or(0.0)
+    }
+
+    /// Adds a volume of measuring solution in mL, which records a new data point.
+    pub fn add(&mut self, volume: f32) {
+        let reading = self.reading() + volume;
+        // Rounding keeps repeated additions of 0.1 mL on the marks of the burette.
+        self.readings.push((reading * 1000.0).round() / 1000.0);
+    }
+
+    /// Removes the last data point, but never the start of the titration.
+    pub fn undo(&mut self) {
+        if self.readings.len() > 1 {
+            self.readings.pop();
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Calculates the curve of the data points recorded so far with the parameters of a
+    /// simulation.
+    pub fn output(&self, simulation: &Simulation) -> Output {
+        Input {
+            m_v: self.readings.clone(),
+            ..simulation.input()
+        }
+        .calculate_output()
+    }
+
+    /// Creates the buttons of the burette with the reading and the color of the flask.
+    pub fn view(&self, output: Option<&Output>) -> Element<'_, Message> {
+        let mut buttons = row![].spacing(5);
+        for step in BURETTE_STEPS {
+            let mut add_button = button(text(format!("+ {} mL", number(step))));
+            if output.is_some() {
+                add_button = add_button.on_press(Message::BuretteAdd(step));
+            }
+            buttons = buttons.push(add_button);
+        }
+        let mut undo_button = button(BUTTON_BURETTE_UNDO.get());
+        if self.readings.len() > 1 {
+            undo_button = undo_button.on_press(Message::BuretteUndo);
+        }
+        buttons = buttons
+            .push(undo_button)
+            .push(button(BUTTON_BURETTE_RESET.get()).on_press(Message::BuretteReset));
+        let last = output.and_then(|it| it.items.last());
+        let reading = match last {
+            Some(item) => format!(
+                "{BURETTE_READING}: {} mL, pH = {:.2}",
+                number(self.reading()),
+                item.ph
+            ),
+            None => GENERATOR_INVALID.into(),
+        };
+        let mut status = row![].spacing(10).align_items(Alignment::Center);
+        if let Some(item) = last {
+            status = status.push(indicator_swatch(item.ph, 20.0, 30.0));
+        }
+        column![
+            text(BURETTE_TITLE).size(20),
+            buttons,
+            status.push(text(reading)),
+        ]
+        .spacing(5)
+        .into()
+    }
+}
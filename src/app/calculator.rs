@@ -0,0 +1,124 @@
+use iced::{
+    widget::{button, column, text},
+    Element,
+};
+
+use crate::worker::explain::Rounding;
+
+use super::{
+    form::{field_row, FormField, TextFields},
+    strings::*,
+    Message,
+};
+
+/// A field of the dilution calculator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CalculatorField {
+    StockConcentration,
+    TargetConcentration,
+    TargetVolume,
+}
+
+impl FormField for CalculatorField {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// A dilution of a stock solution to the measuring solution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Dilution {
+    /// The concentration of the stock solution in mol/L.
+    pub stock_concentration: f32,
+    /// The concentration of the prepared solution in mol/L.
+    pub target_concentration: f32,
+    /// The volume of the prepared solution in mL.
+    pub target_volume: f32,
+}
+
+impl Dilution {
+    /// Returns the volume of stock solution to dilute in mL.
+    ///
+    /// The amount of substance stays the same, so c₁ · V₁ = c₂ · V₂.
+    pub fn aliquot(&self) -> f32 {
+        self.target_concentration * self.target_volume / self.stock_concentration
+    }
+
+    /// Returns the volume of water to add in mL.
+    pub fn water(&self) -> f32 {
+        self.target_volume - self.aliquot()
+    }
+}
+
+/// The state of the dilution calculator.
+pub struct CalculatorForm {
+    fields: TextFields<CalculatorField>,
+}
+
+impl Default for CalculatorForm {
+    fn default() -> Self {
+        Self {
+            fields: TextFields::new(&["1", "0.1", "1000"]),
+        }
+    }
+}
+
+impl CalculatorForm {
+    pub fn set(&mut self, field: CalculatorField, value: String) {
+        self.fields.set(field, value);
+    }
+
+    /// Returns the dilution described by the form, if all fields are valid.
+    ///
+    /// A solution can only be diluted, so the target must not be more concentrated than the stock.
+    pub fn dilution(&self) -> Option<Dilution> {
+        let dilution = Dilution {
+            stock_concentration: self.fields.positive(CalculatorField::StockConcentration)?,
+            target_concentration: self.fields.positive(CalculatorField::TargetConcentration)?,
+            target_volume: self.fields.positive(CalculatorField::TargetVolume)?,
+        };
+        (dilution.target_concentration <= dilution.stock_concentration).then_some(dilution)
+    }
+
+    /// Returns the entered target concentration, to be used as the measuring solution.
+    pub fn target_concentration(&self) -> &str {
+        self.fields.get(CalculatorField::TargetConcentration)
+    }
+
+    /// Creates the calculator.
+    ///
+    /// The result can only be applied to the simulation if it is shown by the profile.
+    pub fn view(&self, rounding: Rounding, can_apply: bool) -> Element<'_, Message> {
+        let field_row =
+            |label, field| field_row(label, &self.fields, field, Message::CalculatorInput, 80.0);
+        let dilution = self.dilution();
+        let summary = match &dilution {
+            Some(dilution) => format!(
+                "{CALCULATOR_ALIQUOT}: {} mL, {CALCULATOR_WATER}: {} mL",
+                rounding.volume(dilution.aliquot()),
+                rounding.volume(dilution.water())
+            ),
+            None => CALCULATOR_INVALID.into(),
+        };
+        let mut apply_button = button(BUTTON_APPLY_CALCULATOR.get());
+        if dilution.is_some() && can_apply {
+            apply_button = apply_button.on_press(Message::ApplyCalculator);
+        }
+        column![
+            text(CALCULATOR_TITLE).size(20),
+            field_row(
+                CALCULATOR_STOCK_CONCENTRATION,
+                CalculatorField::StockConcentration
+            ),
+            field_row(
+                CALCULATOR_TARGET_CONCENTRATION,
+                CalculatorField::TargetConcentration
+            ),
+            field_row(CALCULATOR_TARGET_VOLUME, CalculatorField::TargetVolume),
+            text(summary).size(12),
+            apply_button,
+        ]
+        .spacing(5)
+        .into()
+    }
+}
@@ -0,0 +1,153 @@
+use iced::{
+    widget::{button, column, row, text, text_input, Column},
+    Alignment, Element, Length,
+};
+
+use crate::worker::{
+    calibration::{Calibration, CalibrationPoint, MAX_BUFFERS},
+    locale::localized,
+};
+
+use super::{form::parse_number, strings::*, Message};
+
+/// A field of a buffer in the calibration form.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CalibrationField {
+    Ph,
+    Potential,
+}
+
+/// The form calibrating the electrode with two or three buffers.
+///
+/// Buffers with both fields empty are left out.
+#[derive(Default)]
+pub struct CalibrationForm {
+    /// The pH and the potential in mV of every buffer.
+    buffers: [(String, String); MAX_BUFFERS],
+    /// The last potential sent by the meter in mV.
+    reading: Option<f32>,
+}
+
+impl CalibrationForm {
+    pub fn set(&mut self, buffer: usize, field: CalibrationField, value: String) {
+        let Some((ph, potential)) = self.buffers.get_mut(buffer) else {
+            return;
+        };
+        match field {
+            CalibrationField::Ph => *ph = value,
+            CalibrationField::Potential => *potential = value,
+        }
+    }
+
+    /// Remembers the last reading of the meter, which can be taken as the potential of a buffer.
+    pub fn set_reading(&mut self, mv: f32) {
+        self.reading = Some(mv);
+    }
+
+    /// Enters the last reading of the meter as the potential of a buffer.
+    pub fn take_reading(&mut self, buffer: usize) {
+        if let (Some(mv), Some((_, potential))) = (self.reading, self.buffers.get_mut(buffer)) {
+            *potential = localized(mv.to_string());
+        }
+    }
+
+    /// Fills the form with a stored calibration.
+    pub fn load(&mut self, calibration: &Calibration) {
+        for (index, buffer) in self.buffers.iter_mut().enumerate() {
+            *buffer = calibration
+                .points
+                .get(index)
+                .map(|it| (localized(it.ph.to_string()), localized(it.mv.to_string())))
+                .unwrap_or_default();
+        }
+    }
+
+    /// Returns the calibration described by the form with buffers at a temperature in °C, if all
+    /// entered buffers are valid and it defines a calibration line.
+    pub fn calibration(&self, temperature: f32) -> Option<Calibration> {
+        let points = self
+            .buffers
+            .iter()
+            .filter(|(ph, potential)| !(ph.trim().is_empty() && potential.trim().is_empty()))
+            .map(|(ph, potential)| {
+                Some(CalibrationPoint {
+                    ph: parse_number(ph).filter(|it| (0.0..=14.0).contains(it))?,
+                    mv: parse_number(potential)?,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let calibration = Calibration {
+            points,
+            temperature,
+        };
+        calibration.line().map(|_| calibration)
+    }
+
+    /// Shows the buffers and the calibration line of the entered buffers.
+    ///
+    /// The buffers are taken at the given temperature in °C, which the efficiency refers to.
+    pub fn view(&self, applied: bool, temperature: f32) -> Element<'_, Message> {
+        let buffers = self
+            .buffers
+            .iter()
+            .enumerate()
+            .map(|(index, (ph, potential))| {
+                let mut take_button = button(text(BUTTON_TAKE_READING).size(12));
+                if self.reading.is_some() {
+                    take_button = take_button.on_press(Message::TakeCalibrationReading(index));
+                }
+                row![
+                    text(format!("{} {}", CALIBRATION_BUFFER, index + 1))
+                        .width(Length::Fixed(80.0)),
+                    text_input("pH", ph)
+                        .on_input(move |it| Message::CalibrationInput(
+                            index,
+                            CalibrationField::Ph,
+                            it
+                        ))
+                        .width(Length::Fixed(60.0)),
+                    text_input("mV", potential)
+                        .on_input(move |it| {
+                            Message::CalibrationInput(index, CalibrationField::Potential, it)
+                        })
+                        .width(Length::Fixed(70.0)),
+                    take_button,
+                ]
+                .spacing(5)
+                .align_items(Alignment::Center)
+                .into()
+            });
+        let line = self
+            .calibration(temperature)
+            .as_ref()
+            .and_then(Calibration::line);
+        let mut summary = match line {
+            Some(line) => format!(
+                "{CALIBRATION_SLOPE}: {:.2} mV/pH ({:.1} %), {CALIBRATION_OFFSET}: {:.1} mV",
+                line.slope,
+                line.efficiency() * 100.0,
+                line.offset
+            ),
+            None => CALIBRATION_INCOMPLETE.to_string(),
+        };
+        let mut apply_button = button(BUTTON_APPLY_CALIBRATION.get());
+        if line.is_some() {
+            apply_button = apply_button.on_press(Message::ApplyCalibration);
+        }
+        let mut remove_button = button(BUTTON_REMOVE_CALIBRATION.get());
+        if applied {
+            remove_button = remove_button.on_press(Message::RemoveCalibration);
+        }
+        if let Some(mv) = self.reading {
+            summary = format!("{summary}, {CALIBRATION_READING}: {mv:.1} mV");
+        }
+        column![
+            text(CALIBRATION_TITLE).size(20),
+            Column::with_children(buffers.collect::<Vec<_>>()).spacing(2),
+            text(summary).size(12),
+            row![apply_button, remove_button].spacing(5),
+        ]
+        .spacing(5)
+        .into()
+    }
+}
@@ -0,0 +1,178 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use iced::{
+    widget::{button, column, row, scrollable, text, text_input, Column, Space},
+    Element, Length,
+};
+
+use crate::worker::{locale::localized, temperature, Input, Output};
+
+use super::{form::parse_number, strings::*, Message};
+
+/// The state of the editor of the data points of a loaded file.
+///
+/// The edited volumes replace the volumes of the file, while its concentrations are kept. A pH
+/// entered for a data point replaces its calculated pH as a measured value.
+#[derive(Default)]
+pub struct DataEditor {
+    /// The edited file with its output as loaded.
+    file: Option<(PathBuf, Arc<Output>)>,
+    volumes: Vec<String>,
+    /// The measured pH of every data point, which is empty if the calculated pH is kept.
+    phs: Vec<String>,
+}
+
+impl DataEditor {
+    /// Starts editing the data points of a file, discarding all previous edits.
+    ///
+    /// Excluded data points are edited as well, they stay excluded by their volume.
+    pub fn open(&mut self, path: PathBuf, output: Arc<Output>) {
+        let mut volumes: Vec<f32> = output
+            .items
+            .iter()
+            .chain(output.excluded.iter().map(|(_, it)| it))
+            .map(|it| it.m_v)
+            .collect();
+        volumes.sort_by(f32::total_cmp);
+        self.volumes = volumes.iter().map(|it| localized(it.to_string())).collect();
+        self.phs = vec![String::new(); volumes.len()];
+        self.file = Some((path, output));
+    }
+
+    /// Returns the path of the edited file, if any.
+    pub fn path(&self) -> Option<&Path> {
+        self.file.as_ref().map(|(path, _)| path.as_path())
+    }
+
+    pub fn set(&mut self, index: usize, value: String) {
+        if let Some(volume) = self.volumes.get_mut(index) {
+            *volume = value;
+        }
+    }
+
+    /// Sets the measured pH of a data point, where an empty value keeps the calculated pH.
+    pub fn set_ph(&mut self, index: usize, value: String) {
+        if let Some(ph) = self.phs.get_mut(index) {
+            *ph = value;
+        }
+    }
+
+    /// Adds an empty data point at the end, which is skipped until a volume is entered.
+    pub fn add(&mut self) {
+        self.volumes.push(String::new());
+        self.phs.push(String::new());
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.volumes.len() {
+            self.volumes.remove(index);
+            self.phs.remove(index);
+        }
+    }
+
+    /// Discards all edits and returns the edited file with its output as loaded.
+    pub fn reset(&mut self) -> Option<(PathBuf, Arc<Output>)> {
+        let (path, output) = self.file.clone()?;
+        self.open(path.clone(), output.clone());
+        Some((path, output))
+    }
+
+    /// Returns the input of the edited file with the edited volumes, if they are valid.
+    ///
+    /// Empty rows are skipped. The volumes have to be ascending, since the data points are
+    /// entered in the order of the titration.
+    pub fn input(&self) -> Option<Input> {
+        let (_, output) = self.file.as_ref()?;
+        let mut m_v: Vec<f32> = Vec::new();
+        for value in self.volumes.iter().filter(|it| !it.trim().is_empty()) {
+            let volume = parse_number(value).filter(|it| *it >= 0.0)?;
+            if m_v.last().is_some_and(|last| volume < *last) {
+                return None;
+            }
+            m_v.push(volume);
+        }
+        // The temperatures of the table belong to its rows, which the edited volumes replace.
+        (!m_v.is_empty()).then(|| Input {
+            m_v,
+            temperatures: Vec::new(),
+            ..output.input.clone()
+        })
+    }
+
+    /// Calculates the output of the edited file with the measured pH values, if all values are
+    /// valid.
+    ///
+    /// Only the pH of a data point is measured, so its amounts and concentrations are still
+    /// calculated.
+    pub fn output(&self) -> Option<Output> {
+        let mut output = self.input()?.calculate_output();
+        let phs = self
+            .volumes
+            .iter()
+            .zip(&self.phs)
+            .filter(|(volume, _)| !volume.trim().is_empty())
+            .map(|(_, ph)| ph);
+        for (item, ph) in output.items.iter_mut().zip(phs) {
+            if ph.trim().is_empty() {
+                continue;
+            }
+            item.ph = parse_number(ph).filter(|it| it.is_finite())?;
+            item.poh = temperature::pkw(item.temperature) - item.ph;
+            item.ph_uncertainty = 0.0;
+        }
+        Some(output)
+    }
+
+    /// Creates the editor with a row for every data point.
+    ///
+    /// The file is shown by its name, since the full path rarely fits.
+    pub fn view(&self) -> Element<'_, Message> {
+        let name = self
+            .path()
+            .and_then(Path::file_name)
+            .map(|it| it.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let rows = self.volumes.iter().zip(&self.phs).enumerate();
+        let rows = rows.map(|(index, (volume, ph))| {
+            row![
+                text(format!("{}", index + 1)).width(Length::Fixed(30.0)),
+                text_input("", volume)
+                    .on_input(move |it| Message::DataPointInput(index, it))
+                    .width(Length::Fixed(80.0)),
+                text_input(DATA_EDITOR_CALCULATED.get(), ph)
+                    .on_input(move |it| Message::DataPointPhInput(index, it))
+                    .width(Length::Fixed(80.0)),
+                button(BUTTON_UNLOAD_FILE.get()).on_press(Message::RemoveDataPoint(index)),
+            ]
+            .spacing(5)
+            .into()
+        });
+        let status = if self.output().is_some() {
+            DATA_EDITOR_HINT
+        } else {
+            DATA_EDITOR_INVALID
+        };
+        column![
+            text(format!("{DATA_EDITOR_TITLE}: {name}")).size(20),
+            row![
+                Space::with_width(Length::Fixed(30.0)),
+                text(DATA_EDITOR_VOLUME).size(12).width(Length::Fixed(80.0)),
+                text(DATA_EDITOR_PH).size(12),
+            ]
+            .spacing(5),
+            scrollable(Column::with_children(rows.collect::<Vec<_>>()).spacing(2))
+                .height(Length::Fixed(150.0)),
+            row![
+                button(BUTTON_ADD_DATA_POINT.get()).on_press(Message::AddDataPoint),
+                button(BUTTON_RESET_DATA.get()).on_press(Message::ResetData),
+            ]
+            .spacing(5),
+            text(status).size(12),
+        ]
+        .spacing(5)
+        .into()
+    }
+}
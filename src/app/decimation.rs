@@ -0,0 +1,71 @@
+/// The share of the steepest slope from which a point counts as part of an equivalence region.
+const STEEP_SHARE: f32 = 0.01;
+
+/// Selects the points of a curve drawn when it should have at most about `max_points` points.
+///
+/// Points where the curve is steep, like around the equivalence points, are all kept. The other
+/// points are split into bins of neighbouring points, of which the ones with the lowest and the
+/// highest value are kept, so the flat parts keep their shape. Curves with more steep points than
+/// `max_points` keep all of them.
+///
+/// Returns the indices of the kept points in order, including the first and the last point.
+pub fn decimate(points: &[(f32, f32)], max_points: usize) -> Vec<usize> {
+    let len = points.len();
+    if len <= max_points.max(2) {
+        return (0..len).collect();
+    }
+    let slopes: Vec<f32> = (0..len)
+        .map(|index| {
+            let (x0, y0) = points[index.saturating_sub(1)];
+            let (x1, y1) = points[(index + 1).min(len - 1)];
+            if x1 > x0 {
+                ((y1 - y0) / (x1 - x0)).abs()
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    let steepest = slopes.iter().copied().fold(0.0, f32::max);
+    let steep: Vec<bool> = slopes
+        .iter()
+        .map(|it| steepest > 0.0 && *it >= STEEP_SHARE * steepest)
+        .collect();
+    let steep_count = steep.iter().filter(|it| **it).count();
+    // Every bin keeps two points.
+    let bins = (max_points.saturating_sub(steep_count) / 2).max(1);
+    let bin_size = (len - steep_count).div_ceil(bins).max(1);
+    let mut kept = Vec::with_capacity(max_points + 2);
+    let mut bin = Vec::with_capacity(bin_size);
+    let flush = |bin: &mut Vec<usize>, kept: &mut Vec<usize>| {
+        let value = |index: &&usize| points[**index].1;
+        let lowest = bin.iter().min_by(|a, b| value(a).total_cmp(&value(b)));
+        let highest = bin.iter().max_by(|a, b| value(a).total_cmp(&value(b)));
+        if let (Some(&lowest), Some(&highest)) = (lowest, highest) {
+            kept.push(lowest.min(highest));
+            if lowest != highest {
+                kept.push(lowest.max(highest));
+            }
+        }
+        bin.clear();
+    };
+    for (index, steep) in steep.into_iter().enumerate() {
+        if steep {
+            flush(&mut bin, &mut kept);
+            kept.push(index);
+            continue;
+        }
+        bin.push(index);
+        if bin.len() == bin_size {
+            flush(&mut bin, &mut kept);
+        }
+    }
+    flush(&mut bin, &mut kept);
+    // The ends of the curve stay in place.
+    if kept.first() != Some(&0) {
+        kept.insert(0, 0);
+    }
+    if kept.last() != Some(&(len - 1)) {
+        kept.push(len - 1);
+    }
+    kept
+}
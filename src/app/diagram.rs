@@ -1,183 +1,1715 @@
 #![allow(unused)]
 
+use std::{fmt::Write, iter};
+
+use iced::{Point, Size, Vector};
 use svg::{
-    node::element::{
-        tag::LinearGradient, Circle, Definitions, Line, LinearGradient, Polyline, Rectangle, Stop,
-        Style, Text,
+    node::{
+        element::{
+            tag::LinearGradient, Circle, Definitions, Element, Line, LinearGradient, Path,
+            Polyline, Rectangle, Stop, Style, Text,
+        },
+        Blob,
     },
     Document, Node,
 };
 
-use crate::worker::Output;
+use crate::worker::{
+    activity, analysis, buffer, complexometry, conductivity, fit,
+    format::NumberFormat,
+    gran::{self, GranLine},
+    hagg::{self, HaggLine},
+    locale::localized,
+    metadata::{self, METADATA_ID},
+    precipitation,
+    substances::{Salt, Substance, EDTA_METALS, SILVER_SALTS, STRONG_ACIDS, STRONG_BASES},
+    uncertainty::COVERAGE_FACTOR,
+    units::XQuantity,
+    AcidBasePair, Output, Region,
+};
+
+use super::{
+    annotation::Annotation,
+    decimation,
+    layout::{DiagramLayout, DEFAULT_FRAME_HEIGHT},
+    options::Options,
+    strings::*,
+};
+
+mod crosshair;
+#[cfg(test)]
+mod tests;
 
-use super::options::Options;
+pub use self::crosshair::CrosshairPlot;
 
-const DIAGRAM_FRAME_WIDTH: f32 = 400.0;
-const DIAGRAM_FRAME_HEIGHT: f32 = 300.0;
-const DIAGRAM_MARGIN: f32 = 20.0;
-const DIAGRAM_WIDTH: f32 = DIAGRAM_FRAME_WIDTH - 2.0 * DIAGRAM_MARGIN;
-const DIAGRAM_HEIGHT: f32 = DIAGRAM_FRAME_HEIGHT - 2.0 * DIAGRAM_MARGIN;
-const DIAGRAM_TOP: f32 = DIAGRAM_MARGIN;
-const DIAGRAM_BOTTOM: f32 = DIAGRAM_TOP + DIAGRAM_HEIGHT;
-const DIAGRAM_LEFT: f32 = DIAGRAM_MARGIN;
-const DIAGRAM_RIGHT: f32 = DIAGRAM_LEFT + DIAGRAM_WIDTH;
 const DIAGRAM_X_GAPS: f32 = 5.0;
+/// Approximate number of gaps on the x-axis of quantities other than the volume
+const DIAGRAM_X_QUANTITY_GAPS: f32 = 10.0;
 /// Maximum pH
 const DIAGRAM_MAX_Y: f32 = 14.0;
+/// Number of gaps on the y-axis of analysis plots
+const DIAGRAM_Y_GAPS: usize = 10;
+/// Number of distinct series styles in the stylesheets
+pub const SERIES_STYLES: usize = 5;
+/// Width of the ladder diagram next to the graph
+const LADDER_WIDTH: f32 = 90.0;
+/// Width of the labels of the second y-axis
+const SECONDARY_AXIS_WIDTH: f32 = 30.0;
+/// Width of the legend of the two y-axes
+const LEGEND_WIDTH: f32 = 90.0;
+/// Approximate width of a character in the legend of the series
+const LEGEND_CHAR_WIDTH: f32 = 4.5;
+/// Minimum width of the formula overlay shown in projector mode
+const OVERLAY_WIDTH: f32 = 170.0;
+const OVERLAY_LINE_HEIGHT: f32 = 12.0;
+/// Number of decimals of the coordinates, a hundredth of a unit being far below a pixel
+const SVG_DECIMALS: usize = 2;
 
-const STYLE_LIGHT: &str = include_str!("style/light.css");
-const STYLE_DARK: &str = include_str!("style/dark.css");
+/// The colors of the universal indicator at pH 0, 2, 4, …, 14.
+const INDICATOR_COLORS: [[u8; 3]; 8] = [
+    [0xd7, 0x19, 0x1c],
+    [0xf4, 0x6d, 0x23],
+    [0xfd, 0xc8, 0x2f],
+    [0x9a, 0xcd, 0x32],
+    [0x1a, 0x96, 0x41],
+    [0x2b, 0x83, 0xba],
+    [0x4b, 0x2c, 0x9a],
+    [0x6a, 0x1b, 0x7a],
+];
+
+/// A plot which can be rendered from the outputs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Plot {
+    /// The pH over the added volume.
+    #[default]
+    Curve,
+    /// The change of the pH per added volume.
+    Derivative,
+    /// The conductivity of the solution over the added volume.
+    Conductivity,
+    /// The negative logarithm of an ion concentration in a precipitation titration.
+    Precipitation,
+    /// The negative logarithm of the free metal ion concentration in an EDTA titration.
+    Complexometry,
+    /// The temperature of the solution over the added volume.
+    Temperature,
+    /// The pH and the conductivity over a shared volume axis.
+    Correlation,
+    /// The Gran functions before and after the equivalence point with their regression lines.
+    Gran,
+    /// The buffer capacity over the pH.
+    BufferCapacity,
+    /// The logarithms of the concentrations of all species in the test solution over the pH.
+    Hagg,
+}
+
+/// The plots which can be drawn against a second y-axis over the pH curve.
+pub const SECONDARY_PLOTS: [Plot; 5] = [
+    Plot::Derivative,
+    Plot::Conductivity,
+    Plot::Precipitation,
+    Plot::Complexometry,
+    Plot::Temperature,
+];
+
+/// An axis of the diagram.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+}
+
+/// How the tick labels of an axis are written.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AxisFormat {
+    /// The number of decimals, or `None` for as many as the step between two ticks needs.
+    pub decimals: Option<u8>,
+    /// The unit of the values, which may be empty.
+    pub unit: String,
+    /// Writes the unit after every tick label instead of once at the end of the axis.
+    pub unit_on_ticks: bool,
+    /// The title of the axis, or an empty text for the title derived from the plot.
+    ///
+    /// Only the volume axis and the pH axis of the titration curve take it.
+    pub title: String,
+}
+
+impl AxisFormat {
+    /// Formats the label of a tick.
+    ///
+    /// The decimals of the axis take precedence over the number format. Without either, the label
+    /// has the given decimals, which the step between two ticks needs.
+    fn tick_label(&self, value: f32, auto_decimals: usize, number_format: NumberFormat) -> String {
+        let value = match self.decimals {
+            Some(decimals) => localized(format!("{value:.*}", usize::from(decimals))),
+            None => number_format.format_or(value, |it| localized(format!("{it:.auto_decimals$}"))),
+        };
+        if self.unit_on_ticks && !self.unit.is_empty() {
+            format!("{value} {}", self.unit)
+        } else {
+            value
+        }
+    }
+
+    /// Returns the unit written once at the end of the axis, if any.
+    fn end_unit(&self) -> Option<&str> {
+        (!self.unit_on_ticks && !self.unit.is_empty()).then_some(self.unit.as_str())
+    }
+}
+
+/// The state of a rendered diagram which is not covered by the options.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GraphState {
+    pub plot: Plot,
+    /// The highlighted point as the index of its series and item.
+    ///
+    /// Only used for the titration curve.
+    pub highlight: Option<(usize, usize)>,
+    /// The last revealed region, if the curve is revealed region by region.
+    ///
+    /// Only used for the titration curve.
+    pub reveal: Option<Region>,
+    /// The number of data points revealed by the playback, if it is running.
+    ///
+    /// Only used for the titration curve.
+    pub playback: Option<usize>,
+    /// The selected range of added volumes in mL.
+    ///
+    /// Only used for the titration curve.
+    pub selection: Option<(f32, f32)>,
+    /// The size of the rendered SVG.
+    pub size: ExportSize,
+    /// The index of the only series drawn, while the axes are still scaled to all outputs, so
+    /// diagrams drawn side by side share their axes.
+    pub only: Option<usize>,
+}
 
-pub fn render_graph(options: &Options, output: &Output) -> String {
-    diagram(options, output).to_string()
+/// The size of an exported diagram.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportSize {
+    /// The size of the diagram shown in the app.
+    #[default]
+    Screen,
+    Medium,
+    Large,
+    /// A landscape A4 page printed at 300 DPI.
+    A4Landscape,
 }
 
-fn diagram(options: &Options, output: &Output) -> impl Node {
-    let max_m_v = output.max_m_v();
-    let x_steps = (max_m_v / DIAGRAM_X_GAPS).ceil() as usize;
-    let scale = (
-        DIAGRAM_WIDTH / DIAGRAM_X_GAPS / x_steps as f32,
-        DIAGRAM_HEIGHT / DIAGRAM_MAX_Y,
+impl ExportSize {
+    pub const ALL: [Self; 4] = [Self::Screen, Self::Medium, Self::Large, Self::A4Landscape];
+
+    /// Returns the width and height in pixels.
+    ///
+    /// The diagram shown in the app has no fixed size, it is scaled to fit the window.
+    pub fn pixels(self) -> Option<(u32, u32)> {
+        match self {
+            Self::Screen => None,
+            Self::Medium => Some((800, 600)),
+            Self::Large => Some((1600, 1200)),
+            // 297 mm × 210 mm at 300 DPI.
+            Self::A4Landscape => Some((3508, 2480)),
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            Self::Screen => EXPORT_SIZE_SCREEN.into(),
+            Self::A4Landscape => EXPORT_SIZE_A4_LANDSCAPE.into(),
+            size => {
+                let (width, height) = size.pixels().unwrap_or_default();
+                format!("{width} × {height}")
+            }
+        }
+    }
+}
+
+/// The corner of the plot area the legend of the series is drawn in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LegendPosition {
+    /// No legend is drawn.
+    #[default]
+    Hidden,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl LegendPosition {
+    pub const ALL: [Self; 5] = [
+        Self::Hidden,
+        Self::TopLeft,
+        Self::TopRight,
+        Self::BottomLeft,
+        Self::BottomRight,
+    ];
+
+    pub fn label(self) -> Localized {
+        match self {
+            Self::Hidden => LEGEND_HIDDEN,
+            Self::TopLeft => LEGEND_TOP_LEFT,
+            Self::TopRight => LEGEND_TOP_RIGHT,
+            Self::BottomLeft => LEGEND_BOTTOM_LEFT,
+            Self::BottomRight => LEGEND_BOTTOM_RIGHT,
+        }
+    }
+}
+
+/// The scale of the x-axis.
+struct XAxis {
+    max: f32,
+    gaps: usize,
+    /// The quantity of the added measuring solution shown on the axis, which is formatted by the
+    /// axis options, or `None` if the axis shows the pH.
+    quantity: Option<XQuantity>,
+}
+
+impl XAxis {
+    /// Returns the x-axis of a plot.
+    ///
+    /// The buffer capacity and the logarithmic concentrations are drawn over the pH, all other
+    /// plots over the added measuring solution in the given quantity. Volumes are labeled every
+    /// 5 mL, the other quantities in steps of 1, 2 or 5 times a power of ten.
+    fn of(plot: Plot, outputs: &[&Output], quantity: XQuantity) -> Self {
+        match plot {
+            Plot::BufferCapacity | Plot::Hagg => Self {
+                max: DIAGRAM_MAX_Y,
+                gaps: DIAGRAM_MAX_Y as usize,
+                quantity: None,
+            },
+            _ => {
+                let max = outputs
+                    .iter()
+                    .filter_map(|it| Some(it.max_m_v() * quantity.per_milliliter(&it.input)?))
+                    .fold(0.0, f32::max);
+                let step = match quantity {
+                    XQuantity::Volume => DIAGRAM_X_GAPS,
+                    _ => nice_step(max / DIAGRAM_X_QUANTITY_GAPS),
+                };
+                // Curves without volumes, e.g. of a single point, still get an axis to draw on.
+                let gaps = ((max / step).ceil() as usize).max(1);
+                Self {
+                    max: gaps as f32 * step,
+                    gaps,
+                    quantity: Some(quantity),
+                }
+            }
+        }
+    }
+
+    /// Returns whether the axis shows the added measuring solution.
+    fn volume(&self) -> bool {
+        self.quantity.is_some()
+    }
+}
+
+/// The scale of the y-axis.
+struct YAxis {
+    max: f32,
+    gaps: usize,
+    label: String,
+    /// The value at the bottom of the axis, e.g. for logarithms of concentrations.
+    ///
+    /// The values are drawn relative to it, so `max` is the range of the axis.
+    offset: f32,
+}
+
+/// The placement of the diagram in the rendered SVG.
+///
+/// Used to map the cursor position over the diagram back to a volume, and to draw the crosshair
+/// plot at the same place as the SVG. The y-axis is the pH axis of the titration curve.
+#[derive(Clone, Copy, Debug)]
+pub struct DiagramFrame {
+    layout: DiagramLayout,
+    /// Whether the x-axis shows the added volume.
+    volume: bool,
+}
+
+impl DiagramFrame {
+    /// Creates the frame of a diagram, whose volumes are converted like those of the first output.
+    pub fn new(options: &Options, outputs: &[&Output], plot: Plot) -> Self {
+        let x_axis = XAxis::of(plot, outputs, options.x_quantity);
+        let layout = diagram_layout(options, plot, ExportSize::Screen)
+            .with_x_max(x_axis.max)
+            .with_y_max(DIAGRAM_MAX_Y);
+        let factor = outputs
+            .first()
+            .and_then(|it| x_factor(&x_axis, it))
+            .unwrap_or(1.0);
+        Self {
+            layout: layout.with_x_factor(factor),
+            volume: x_axis.volume(),
+        }
+    }
+
+    /// Returns the volume at a position in a widget of the given size, if it is inside the
+    /// diagram.
+    ///
+    /// The SVG is expected to be scaled to fit the widget and centered in it.
+    pub fn volume_at(&self, position: Point, bounds: Size) -> Option<f32> {
+        if !self.volume {
+            return None;
+        }
+        let (scale, offset) = self.placement(bounds)?;
+        let position = position - offset;
+        self.layout
+            .x_value_at(position.x / scale, position.y / scale)
+    }
+
+    /// Returns the scale and the offset of the diagram fitted into a widget of the given size.
+    ///
+    /// The diagram keeps its aspect ratio and is centered, like an SVG scaled to fit.
+    pub fn placement(&self, bounds: Size) -> Option<(f32, Vector)> {
+        let (width, height) = (self.layout.frame_width, self.layout.frame_height);
+        let scale = (bounds.width / width).min(bounds.height / height);
+        (scale > 0.0).then(|| {
+            let offset = Vector::new(
+                (bounds.width - width * scale) / 2.0,
+                (bounds.height - height * scale) / 2.0,
+            );
+            (scale, offset)
+        })
+    }
+
+    pub fn layout(&self) -> DiagramLayout {
+        self.layout
+    }
+}
+
+/// Returns the color of the universal indicator at a pH.
+pub fn indicator_color(ph: f32) -> [u8; 3] {
+    let position = (ph.clamp(0.0, 14.0) / 2.0).min(INDICATOR_COLORS.len() as f32 - 1.0);
+    let index = (position as usize).min(INDICATOR_COLORS.len() - 2);
+    let fraction = position - index as f32;
+    let (from, to) = (INDICATOR_COLORS[index], INDICATOR_COLORS[index + 1]);
+    [0, 1, 2].map(|it| (from[it] as f32 + (to[it] as f32 - from[it] as f32) * fraction) as u8)
+}
+
+/// Renders the given outputs into a single diagram.
+///
+/// Every output is drawn as its own series. All plots are rendered here, so exports and the
+/// clipboard share the same styles.
+///
+/// The names of the series are shown in the legend, which numbers series without a name.
+pub fn render_graph(
+    options: &Options,
+    outputs: &[&Output],
+    names: &[String],
+    state: GraphState,
+) -> String {
+    diagram(options, outputs, names, state).to_string()
+}
+
+/// Renders the graph like [`render_graph`] with the inputs and the calculated tables of the
+/// outputs embedded as metadata, so the exported file can be loaded again.
+pub fn render_export(
+    options: &Options,
+    outputs: &[&Output],
+    names: &[String],
+    state: GraphState,
+) -> String {
+    let mut element = Element::new("metadata");
+    element.assign("id", METADATA_ID);
+    element.append(Blob::new(metadata::svg_metadata(outputs)));
+    let mut doc = diagram(options, outputs, names, state);
+    doc.append(element);
+    doc.to_string()
+}
+
+fn diagram(
+    options: &Options,
+    outputs: &[&Output],
+    names: &[String],
+    state: GraphState,
+) -> impl Node {
+    let x_axis = XAxis::of(state.plot, outputs, options.x_quantity);
+    let secondary = secondary_plot(options, state.plot);
+    // The series of the plots which are calculated from the outputs.
+    let derived = derived_series(state.plot, options, outputs);
+    let y_axis = match state.plot {
+        // With a second y-axis, both axes are explained by the legend.
+        Plot::Curve | Plot::Correlation => YAxis {
+            max: DIAGRAM_MAX_Y,
+            gaps: DIAGRAM_MAX_Y as usize,
+            label: if secondary.is_some() {
+                String::new()
+            } else if !options.y_axis.title.is_empty() {
+                options.y_axis.title.clone()
+            } else {
+                "pH".into()
+            },
+            offset: 0.0,
+        },
+        Plot::Derivative
+        | Plot::Conductivity
+        | Plot::Precipitation
+        | Plot::Complexometry
+        | Plot::Temperature
+        | Plot::BufferCapacity => derived_axis(&derived, derived_label(state.plot, options)),
+        Plot::Gran => YAxis {
+            max: 1.0,
+            gaps: DIAGRAM_Y_GAPS,
+            label: PLOT_GRAN_AXIS.into(),
+            offset: 0.0,
+        },
+        Plot::Hagg => YAxis {
+            max: DIAGRAM_MAX_Y,
+            gaps: DIAGRAM_MAX_Y as usize,
+            label: PLOT_HAGG_AXIS.into(),
+            offset: -DIAGRAM_MAX_Y,
+        },
+    };
+    let layout = diagram_layout(options, state.plot, state.size)
+        .with_x_max(x_axis.max)
+        .with_y_max(y_axis.max);
+    // The outputs drawn as series with their index and the layout converting their volumes.
+    //
+    // Outputs whose volumes cannot be converted into the quantity of the x-axis are left out.
+    let drawn: Vec<(usize, &Output, DiagramLayout)> = outputs
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| state.only.is_none_or(|it| it == *index))
+        .filter_map(|(index, output)| {
+            let factor = x_factor(&x_axis, output)?;
+            Some((index, *output, layout.with_x_factor(factor)))
+        })
+        .collect();
+    // Annotations and the selection are placed at the volumes of the first series.
+    let layout = drawn.first().map_or(layout, |it| it.2);
+    // The ladder diagram takes the place of the second y-axis, so it is only drawn without one.
+    let ladder = options.ladder && state.plot == Plot::Curve && secondary.is_none();
+    let mut doc = Document::new().set(
+        "viewBox",
+        format!(
+            "0 0 {} {}",
+            number(layout.frame_width),
+            number(layout.frame_height)
+        ),
     );
-    let mut doc = Document::new()
-        .set(
-            "viewBox",
-            format!("0 0 {DIAGRAM_FRAME_WIDTH} {DIAGRAM_FRAME_HEIGHT}"),
-        )
-        .add(style(options));
-    diagram_frame(options, &mut doc, x_steps);
-    diagram_graph(options, output, &mut doc, scale);
+    if let Some((width, height)) = state.size.pixels() {
+        doc = doc.set("width", width).set("height", height);
+    }
+    doc = doc.add(style(options));
+    if options.colored && state.plot == Plot::Curve {
+        colored_background(&mut doc, options, layout);
+    }
+    let titrant = outputs.first().and_then(|it| it.input.titrant);
+    let x_title = x_title(options, x_axis.quantity, titrant);
+    diagram_frame(&mut doc, options, &x_axis, &y_axis, &x_title, layout);
+    match state.plot {
+        Plot::Curve => {
+            if let Some(selection) = state.selection {
+                selection_box(&mut doc, selection, layout);
+            }
+            for &(_, output, layout) in &drawn {
+                let usage = analysis::titrant_usage(output);
+                if usage.equivalence_uncertainty > 0.0 {
+                    let margin = COVERAGE_FACTOR * usage.equivalence_uncertainty;
+                    let volume = usage.equivalence_volume;
+                    confidence_band(&mut doc, (volume - margin, volume + margin), layout);
+                }
+            }
+            for &(index, output, layout) in &drawn {
+                let highlight = state
+                    .highlight
+                    .and_then(|(series, item)| (series == index).then_some(item));
+                let shown = shown_items(output, state);
+                diagram_graph(output, index, highlight, shown, options, &mut doc, layout);
+            }
+            if options.fit {
+                for &(index, output, layout) in &drawn {
+                    // A fit which did not converge has no valid parameters to draw.
+                    if let Some(fit) = fit::fit(output).filter(|it| it.converged) {
+                        model_curve(&mut doc, index, "fit", fit.curve(output.max_m_v()), layout);
+                    }
+                }
+            }
+            if options.activity {
+                for &(index, output, layout) in &drawn {
+                    let points = activity::corrected_curve(output);
+                    model_curve(&mut doc, index, "activity", points, layout);
+                }
+            }
+            if let (Some(region), Some(&(_, output, _))) = (state.reveal, drawn.first()) {
+                formula_overlay(&mut doc, output, region, layout);
+            }
+        }
+        Plot::Derivative
+        | Plot::Conductivity
+        | Plot::Precipitation
+        | Plot::Complexometry
+        | Plot::Temperature
+        | Plot::BufferCapacity => {
+            for &(index, _, layout) in &drawn {
+                let points = &derived[index];
+                diagram_series(&mut doc, index, points, None, false, options.smooth, layout);
+            }
+        }
+        Plot::Correlation => {
+            for &(index, output, layout) in &drawn {
+                diagram_graph(output, index, None, None, options, &mut doc, layout);
+                // The endpoints of both curves show whether they line up.
+                let endpoints = [
+                    analysis::titrant_usage(output).endpoint_volume,
+                    conductivity::endpoint(
+                        &conductivity_series(options, output),
+                        output.input.t_v * 1000.0,
+                    ),
+                ];
+                for (endpoint, secondary) in endpoints.into_iter().zip([false, true]) {
+                    if let Some(volume) = endpoint {
+                        endpoint_line(&mut doc, index, volume, secondary, layout);
+                    }
+                }
+            }
+        }
+        Plot::Hagg => {
+            for (_, output, _) in &drawn {
+                for (index, line) in hagg::hagg(&output.input).iter().enumerate() {
+                    hagg_line(&mut doc, index, line, &y_axis, layout);
+                }
+            }
+        }
+        Plot::Gran => {
+            for &(index, output, layout) in &drawn {
+                let plot = gran::gran(output);
+                diagram_series(&mut doc, index, &plot.before, None, false, false, layout);
+                diagram_series(&mut doc, index, &plot.after, None, true, false, layout);
+                let fits = [
+                    (plot.before_line, plot.before.first()),
+                    (plot.after_line, plot.after.last()),
+                ];
+                for (part, (line, outer)) in fits.into_iter().enumerate() {
+                    let (Some(line), Some(outer)) = (line, outer) else {
+                        continue;
+                    };
+                    let Some(root) = line.root().filter(|it| it.is_finite() && *it >= 0.0) else {
+                        continue;
+                    };
+                    gran_line(&mut doc, index, line, (outer.0, root), layout);
+                    let row = 2 * index + part;
+                    gran_intercept(&mut doc, index, row, root, options, layout);
+                }
+            }
+        }
+    }
+    if let Some(plot) = secondary {
+        let series = derived_series(plot, options, outputs);
+        let secondary_y_axis = derived_axis(&series, derived_label(plot, options));
+        secondary_axis(&mut doc, &secondary_y_axis, layout);
+        // Series on the second y-axis are dashed.
+        for &(index, _, layout) in &drawn {
+            let secondary_layout = layout.with_y_max(secondary_y_axis.max);
+            let points = &series[index];
+            diagram_series(
+                &mut doc,
+                index,
+                points,
+                None,
+                true,
+                options.smooth,
+                secondary_layout,
+            );
+        }
+        axis_legend(&mut doc, &secondary_y_axis.label, layout);
+    }
+    // Only the curve and the correlation plot have a pH axis for labels and pH lines.
+    let ph_axis = matches!(state.plot, Plot::Curve | Plot::Correlation);
+    // Annotations are placed at volumes, which the buffer capacity plot does not show.
+    for annotation in options.annotations.iter().filter(|_| x_axis.volume()) {
+        if ph_axis || !annotation.needs_ph_axis() {
+            draw_annotation(&mut doc, annotation, layout);
+        }
+    }
+    if ladder {
+        let mut pairs: Vec<&AcidBasePair> = Vec::new();
+        for pair in drawn.iter().flat_map(|(_, it, _)| &it.pairs) {
+            if !pairs.contains(&pair) {
+                pairs.push(pair);
+            }
+        }
+        pairs.sort_by(|a, b| a.pka.total_cmp(&b.pka));
+        diagram_ladder(&mut doc, &pairs, layout);
+    }
+    if options.legend != LegendPosition::Hidden {
+        let entries: Vec<(usize, String)> = drawn
+            .iter()
+            .map(|&(index, _, _)| {
+                let name = names.get(index).filter(|it| !it.is_empty());
+                (
+                    index,
+                    name.cloned().unwrap_or_else(|| (index + 1).to_string()),
+                )
+            })
+            .collect();
+        series_legend(&mut doc, &entries, options.legend, layout);
+    }
     doc
 }
 
-fn style(options: &Options) -> Style {
-    Style::new(if options.dark {
-        STYLE_DARK
+/// Returns the plot drawn against the second y-axis, if any.
+///
+/// The correlation plot always draws the measured or calculated conductivity over the pH curve,
+/// while the pH curve may draw any of the [`SECONDARY_PLOTS`] chosen in the options.
+fn secondary_plot(options: &Options, plot: Plot) -> Option<Plot> {
+    match plot {
+        Plot::Correlation => Some(Plot::Conductivity),
+        Plot::Curve => options.secondary_plot,
+        _ => None,
+    }
+}
+
+/// Calculates the series of a plot which is derived from the outputs, with one series per output.
+///
+/// The other plots are drawn from the outputs directly and have no derived series.
+fn derived_series(plot: Plot, options: &Options, outputs: &[&Output]) -> Vec<Vec<(f32, f32)>> {
+    match plot {
+        Plot::Curve | Plot::Gran | Plot::Hagg | Plot::Correlation => Vec::new(),
+        Plot::Derivative => outputs.iter().map(|it| analysis::derivative(it)).collect(),
+        Plot::BufferCapacity => outputs
+            .iter()
+            .map(|it| buffer::buffer_capacity(it))
+            .collect(),
+        Plot::Conductivity => outputs
+            .iter()
+            .map(|it| conductivity_series(options, it))
+            .collect(),
+        Plot::Complexometry => {
+            let metal = &EDTA_METALS[options.complexometry_metal % EDTA_METALS.len()];
+            outputs
+                .iter()
+                .map(|it| complexometry::complexometry(&it.input, metal, options.buffer_ph))
+                .collect()
+        }
+        Plot::Temperature => outputs
+            .iter()
+            .map(|it| it.items.iter().map(|it| (it.m_v, it.temperature)).collect())
+            .collect(),
+        Plot::Precipitation => {
+            let salt = precipitation_salt(options);
+            outputs
+                .iter()
+                .map(|it| {
+                    precipitation::precipitation(&it.input, salt)
+                        .into_iter()
+                        .map(|point| {
+                            let p = if options.precipitation_anion {
+                                point.p_anion
+                            } else {
+                                point.p_cation
+                            };
+                            (point.m_v, p)
+                        })
+                        .collect()
+                })
+                .collect()
+        }
+    }
+}
+
+/// Returns the conductivity of an output at the volumes of its pH curve.
+///
+/// A conductivity measured in the same run is paired with the pH by volume. Without one, the
+/// conductivity is calculated from the ions of the chosen acid and base.
+fn conductivity_series(options: &Options, output: &Output) -> Vec<(f32, f32)> {
+    if let Some(measured) = &output.measured_conductivity {
+        let volumes: Vec<f32> = output.items.iter().map(|it| it.m_v).collect();
+        return conductivity::paired(measured, &volumes);
+    }
+    let acid = &STRONG_ACIDS[options.conductivity_acid % STRONG_ACIDS.len()];
+    let base = &STRONG_BASES[options.conductivity_base % STRONG_BASES.len()];
+    conductivity::conductivity(&output.input, acid, base)
+}
+
+/// Returns the label of the y-axis of a derived plot.
+fn derived_label(plot: Plot, options: &Options) -> String {
+    match plot {
+        Plot::Derivative => PLOT_DERIVATIVE_AXIS.into(),
+        Plot::Conductivity => PLOT_CONDUCTIVITY_AXIS.into(),
+        Plot::Precipitation => precipitation_label(options),
+        Plot::Complexometry => "pM".into(),
+        Plot::Temperature => PLOT_TEMPERATURE_AXIS.into(),
+        Plot::BufferCapacity => PLOT_BUFFER_CAPACITY_AXIS.into(),
+        _ => String::new(),
+    }
+}
+
+/// Returns a y-axis from zero to a round value above all derived series.
+fn derived_axis(derived: &[Vec<(f32, f32)>], label: String) -> YAxis {
+    let max = derived.iter().flatten().map(|it| it.1).fold(0.0, f32::max);
+    YAxis {
+        max: nice_step(max / DIAGRAM_Y_GAPS as f32) * DIAGRAM_Y_GAPS as f32,
+        gaps: DIAGRAM_Y_GAPS,
+        label,
+        offset: 0.0,
+    }
+}
+
+/// Returns the salt selected for the precipitation plot.
+fn precipitation_salt(options: &Options) -> &'static Salt {
+    &SILVER_SALTS[options.precipitation_salt % SILVER_SALTS.len()]
+}
+
+/// Returns the label of the y-axis of the precipitation plot, e.g. `pAg`.
+fn precipitation_label(options: &Options) -> String {
+    let salt = precipitation_salt(options);
+    let ion = if options.precipitation_anion {
+        salt.anion
     } else {
-        STYLE_LIGHT
-    })
+        salt.cation
+    };
+    // The charge is not part of the name of the quantity.
+    format!("p{}", ion.trim_end_matches(['⁺', '⁻']))
 }
 
-fn diagram_frame(options: &Options, doc: &mut Document, x_steps: usize) {
-    if options.colored {
-        colored_background(doc);
+/// Returns the factor converting the volumes of an output into values of the x-axis.
+///
+/// Axes of the pH take the x values of the series as they are.
+fn x_factor(x_axis: &XAxis, output: &Output) -> Option<f32> {
+    match x_axis.quantity {
+        Some(quantity) => quantity.per_milliliter(&output.input),
+        None => Some(1.0),
     }
+}
+
+/// Returns the layout of a plot without the ranges of its axes.
+///
+/// Exports are stretched to their aspect ratio by widening the plot area.
+fn diagram_layout(options: &Options, plot: Plot, size: ExportSize) -> DiagramLayout {
+    let side_width = side_width(options, plot);
+    match size.pixels() {
+        Some((width, height)) => DiagramLayout::new(
+            DEFAULT_FRAME_HEIGHT * width as f32 / height as f32,
+            DEFAULT_FRAME_HEIGHT,
+            side_width,
+        ),
+        None => DiagramLayout::with_default_plot_area(side_width),
+    }
+}
+
+/// Returns the width of the parts drawn to the right of the plot area.
+fn side_width(options: &Options, plot: Plot) -> f32 {
+    if secondary_plot(options, plot).is_some() {
+        return SECONDARY_AXIS_WIDTH;
+    }
+    match plot {
+        Plot::Curve if options.ladder => LADDER_WIDTH,
+        _ => 0.0,
+    }
+}
+
+/// Rounds a step of an axis up to 1, 2 or 5 times a power of ten.
+fn nice_step(value: f32) -> f32 {
+    if value <= 0.0 || !value.is_finite() {
+        return 1.0;
+    }
+    let magnitude = 10f32.powf(value.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .find(|it| it * magnitude >= value)
+        .unwrap_or(10.0);
+    step * magnitude
+}
+
+fn style(options: &Options) -> Style {
+    Style::new(options.style.css(options.dark))
+}
+
+fn diagram_frame(
+    doc: &mut Document,
+    options: &Options,
+    x_axis: &XAxis,
+    y_axis: &YAxis,
+    x_title: &str,
+    layout: DiagramLayout,
+) {
+    let y_step = y_axis.max / y_axis.gaps as f32;
+    // Show as many decimals as the step needs.
+    let decimals = (-y_step.log10().floor()).max(0.0) as usize;
+    let (left, right, top, bottom) = (layout.left(), layout.right(), layout.top(), layout.bottom());
     // y-Axis
-    for ph in 0..=14 {
-        let y = DIAGRAM_BOTTOM - DIAGRAM_HEIGHT / DIAGRAM_MAX_Y * ph as f32;
+    for step in 0..=y_axis.gaps {
+        let y = bottom - layout.height() / y_axis.gaps as f32 * step as f32;
         doc.append(
             Line::new()
                 .set("class", "grid")
-                .set("x1", DIAGRAM_LEFT)
-                .set("y1", y)
-                .set("x2", DIAGRAM_RIGHT)
-                .set("y2", y),
+                .set("x1", number(left))
+                .set("y1", number(y))
+                .set("x2", number(right))
+                .set("y2", number(y)),
         );
         doc.append(
             Line::new()
                 .set("class", "axis")
-                .set("x1", DIAGRAM_LEFT - 3.0)
-                .set("y1", y)
-                .set("x2", DIAGRAM_LEFT + 3.0)
-                .set("y2", y),
+                .set("x1", number(left - 3.0))
+                .set("y1", number(y))
+                .set("x2", number(left + 3.0))
+                .set("y2", number(y)),
         );
         doc.append(
             Text::new()
                 .set("class", "axis-number anchor-end")
-                .set("x", DIAGRAM_LEFT - 5.0)
-                .set("y", y)
-                .add(text(ph.to_string())),
+                .set("x", number(left - 5.0))
+                .set("y", number(y))
+                .add(text(options.y_axis.tick_label(
+                    y_axis.offset + step as f32 * y_step,
+                    decimals,
+                    options.number_format,
+                ))),
+        );
+    }
+    if let Some(unit) = options.y_axis.end_unit() {
+        doc.append(
+            Text::new()
+                .set("class", "axis-number anchor-end")
+                .set("x", number(left - 5.0))
+                .set("y", number(top - 8.0))
+                .add(text(unit)),
         );
     }
     // x-Axis
-    for step in 0..=x_steps {
-        let x = DIAGRAM_MARGIN + DIAGRAM_WIDTH / x_steps as f32 * step as f32;
+    let x_step = x_axis.max / x_axis.gaps as f32;
+    let x_decimals = (-x_step.log10().floor()).max(0.0) as usize;
+    for step in 0..=x_axis.gaps {
+        let x = left + layout.width() / x_axis.gaps as f32 * step as f32;
         doc.append(
             Line::new()
                 .set("class", "grid")
-                .set("x1", x)
-                .set("y1", DIAGRAM_BOTTOM)
-                .set("x2", x)
-                .set("y2", DIAGRAM_TOP),
+                .set("x1", number(x))
+                .set("y1", number(bottom))
+                .set("x2", number(x))
+                .set("y2", number(top)),
         );
         doc.append(
             Line::new()
                 .set("class", "axis")
-                .set("x1", x)
-                .set("y1", DIAGRAM_BOTTOM - 3.0)
-                .set("x2", x)
-                .set("y2", DIAGRAM_BOTTOM + 3.0),
+                .set("x1", number(x))
+                .set("y1", number(bottom - 3.0))
+                .set("x2", number(x))
+                .set("y2", number(bottom + 3.0)),
         );
+        let label = if x_axis.volume() {
+            options
+                .x_axis
+                .tick_label(step as f32 * x_step, x_decimals, options.number_format)
+        } else {
+            format!("{:.x_decimals$}", step as f32 * x_step)
+        };
         doc.append(
             Text::new()
                 .set("class", "axis-number anchor-middle")
-                .set("x", x)
-                .set("y", DIAGRAM_BOTTOM + 10.0)
-                .add(text((step as f32 * DIAGRAM_X_GAPS).to_string())),
+                .set("x", number(x))
+                .set("y", number(bottom + 10.0))
+                .add(text(label)),
         );
     }
-    doc.append(
-        Polyline::new()
-            .set("class", "axis")
-            .set("points", format!("{DIAGRAM_MARGIN},{DIAGRAM_MARGIN} {DIAGRAM_MARGIN},{DIAGRAM_BOTTOM} {DIAGRAM_RIGHT},{DIAGRAM_BOTTOM}"))
-    );
+    let end_unit = x_axis.volume().then(|| options.x_axis.end_unit()).flatten();
+    if let Some(unit) = end_unit {
+        doc.append(
+            Text::new()
+                .set("class", "axis-number")
+                .set("x", number(right + 6.0))
+                .set("y", number(bottom + 10.0))
+                .add(text(unit)),
+        );
+    }
+    doc.append(Polyline::new().set("class", "axis").set(
+        "points",
+        format!(
+            "{left},{top} {left},{bottom} {right},{bottom}",
+            left = number(left),
+            top = number(top),
+            right = number(right),
+            bottom = number(bottom),
+        ),
+    ));
+    // The title of the y-axis runs upwards along it.
+    let (title_x, title_y) = (layout.y_title_width / 2.0, top + layout.height() / 2.0);
     doc.append(
         Text::new()
             .set("class", "text anchor-middle")
-            .set("x", DIAGRAM_RIGHT + 10.0)
-            .set("y", DIAGRAM_TOP + DIAGRAM_HEIGHT / 2.0)
-            .add(text("pH")),
+            .set("x", number(title_x))
+            .set("y", number(title_y))
+            .set(
+                "transform",
+                format!("rotate(-90 {} {})", number(title_x), number(title_y)),
+            )
+            .add(text(&y_axis.label)),
     );
     doc.append(
         Text::new()
             .set("class", "text anchor-middle")
-            .set("x", DIAGRAM_LEFT + DIAGRAM_WIDTH / 2.0)
-            .set("y", DIAGRAM_TOP - 10.0)
-            .add(text("Volumen")),
+            .set("x", number(left + layout.width() / 2.0))
+            .set("y", number(top - 10.0))
+            .add(text(x_title)),
     );
 }
 
-fn diagram_graph(
+/// Returns the title of the x-axis.
+///
+/// The title set for the axis replaces the title of the volume axis. Without one, the volume is
+/// named after the measuring solution, with the unit unless the axis shows it.
+pub fn x_title(
     options: &Options,
+    quantity: Option<XQuantity>,
+    titrant: Option<&Substance>,
+) -> String {
+    match quantity {
+        Some(XQuantity::Volume) if !options.x_axis.title.is_empty() => options.x_axis.title.clone(),
+        Some(XQuantity::Volume) => {
+            let name = match titrant {
+                Some(titrant) => format!("V({})", titrant.formula),
+                None => AXIS_VOLUME.into(),
+            };
+            if options.x_axis.unit.is_empty() {
+                format!("{name} / mL")
+            } else {
+                name
+            }
+        }
+        Some(XQuantity::Degree) => AXIS_DEGREE.into(),
+        Some(XQuantity::Amount) => AXIS_AMOUNT.into(),
+        None => "pH".into(),
+    }
+}
+
+/// Returns the number of items of an output in the revealed regions and revealed by the
+/// playback, or `None` if the whole curve is shown.
+fn shown_items(output: &Output, state: GraphState) -> Option<usize> {
+    let revealed = state.reveal.map(|region| {
+        (0..output.items.len())
+            .take_while(|&index| output.region(index) <= region)
+            .count()
+    });
+    match (revealed, state.playback) {
+        (Some(revealed), Some(playback)) => Some(revealed.min(playback)),
+        (revealed, playback) => revealed.or(playback),
+    }
+}
+
+/// Draws the graph of an output with its excluded items and, if enabled, its suspicious items.
+///
+/// If a number of shown items is given, only the items up to it are drawn.
+fn diagram_graph(
     output: &Output,
+    series: usize,
+    highlight: Option<usize>,
+    shown: Option<usize>,
+    options: &Options,
     doc: &mut Document,
-    (scale_x, scale_y): (f32, f32),
+    layout: DiagramLayout,
 ) {
-    // Lines
-    for items in output.items.windows(2) {
+    let count = shown.map_or(output.items.len(), |it| it.min(output.items.len()));
+    let points: Vec<(f32, f32)> = output.items[..count]
+        .iter()
+        .map(|it| (it.m_v, it.ph))
+        .collect();
+    // Large datasets are decimated, but the highlighted point is always drawn.
+    let mut kept = match options.max_points {
+        Some(max_points) => decimation::decimate(&points, max_points as usize),
+        None => (0..count).collect(),
+    };
+    if let Some(index) = highlight.filter(|it| *it < count) {
+        if let Err(position) = kept.binary_search(&index) {
+            kept.insert(position, index);
+        }
+    }
+    let drawn: Vec<(f32, f32)> = kept.iter().map(|it| points[*it]).collect();
+    diagram_series(
+        doc,
+        series,
+        &drawn,
+        highlight.and_then(|it| kept.binary_search(&it).ok()),
+        false,
+        options.smooth,
+        layout,
+    );
+    for item in kept.iter().map(|it| &output.items[*it]) {
+        if item.ph_uncertainty > 0.0 {
+            error_bar(doc, series, item.m_v, item.ph, item.ph_uncertainty, layout);
+        }
+    }
+    let last_m_v = match shown {
+        Some(_) => points.last().map_or(f32::NEG_INFINITY, |it| it.0),
+        None => f32::INFINITY,
+    };
+    for (_, item) in output.excluded.iter().filter(|(_, it)| it.m_v <= last_m_v) {
+        doc.append(
+            Circle::new()
+                .set(
+                    "class",
+                    format!("graph-point series-{} excluded", series % SERIES_STYLES),
+                )
+                .set("cx", number(layout.x(item.m_v)))
+                .set("cy", number(layout.y(item.ph))),
+        );
+    }
+    if options.outliers {
+        for index in analysis::outliers(output)
+            .into_iter()
+            .filter(|it| *it < count)
+        {
+            let item = &output.items[index];
+            doc.append(
+                Circle::new()
+                    .set("class", "outlier-mark")
+                    .set("cx", number(layout.x(item.m_v)))
+                    .set("cy", number(layout.y(item.ph))),
+            );
+        }
+    }
+}
+
+/// Draws the confidence interval of a pH value as a vertical bar.
+fn error_bar(
+    doc: &mut Document,
+    series: usize,
+    m_v: f32,
+    ph: f32,
+    uncertainty: f32,
+    layout: DiagramLayout,
+) {
+    let margin = COVERAGE_FACTOR * uncertainty;
+    let x = layout.x(m_v);
+    let clamp = |ph: f32| layout.y(ph).clamp(layout.top(), layout.bottom());
+    doc.append(
+        Line::new()
+            .set(
+                "class",
+                format!("graph-line series-{} error-bar", series % SERIES_STYLES),
+            )
+            .set("x1", number(x))
+            .set("y1", number(clamp(ph - margin)))
+            .set("x2", number(x))
+            .set("y2", number(clamp(ph + margin))),
+    );
+}
+
+/// Shades the confidence interval of the equivalence volume.
+fn confidence_band(doc: &mut Document, (from, to): (f32, f32), layout: DiagramLayout) {
+    let left = layout.x(from).max(layout.left());
+    let right = layout.x(to).min(layout.right());
+    doc.append(
+        Rectangle::new()
+            .set("class", "confidence")
+            .set("x", number(left))
+            .set("y", number(layout.top()))
+            .set("width", number((right - left).max(0.0)))
+            .set("height", number(layout.height())),
+    );
+}
+
+/// Shades the selected range of volumes.
+fn selection_box(doc: &mut Document, (from, to): (f32, f32), layout: DiagramLayout) {
+    let left = layout.x(from.min(to));
+    let right = layout.x(from.max(to)).min(layout.right());
+    doc.append(
+        Rectangle::new()
+            .set("class", "selection")
+            .set("x", number(left))
+            .set("y", number(layout.top()))
+            .set("width", number((right - left).max(0.0)))
+            .set("height", number(layout.height())),
+    );
+}
+
+/// Draws the ticks and the label of the second y-axis on the right side of the diagram.
+///
+/// The ticks are labeled with as many decimals as the step between two of them needs.
+fn secondary_axis(doc: &mut Document, y_axis: &YAxis, layout: DiagramLayout) {
+    let right = layout.right();
+    let y_step = y_axis.max / y_axis.gaps as f32;
+    let decimals = (-y_step.log10().floor()).max(0.0) as usize;
+    for step in 0..=y_axis.gaps {
+        let y = layout.bottom() - layout.height() / y_axis.gaps as f32 * step as f32;
+        doc.append(
+            Line::new()
+                .set("class", "axis")
+                .set("x1", number(right - 3.0))
+                .set("y1", number(y))
+                .set("x2", number(right + 3.0))
+                .set("y2", number(y)),
+        );
+        doc.append(
+            Text::new()
+                .set("class", "axis-number")
+                .set("x", number(right + 5.0))
+                .set("y", number(y))
+                .add(text(localized(format!(
+                    "{:.*}",
+                    decimals,
+                    step as f32 * y_step
+                )))),
+        );
+    }
+    doc.append(
+        Line::new()
+            .set("class", "axis")
+            .set("x1", number(right))
+            .set("y1", number(layout.top()))
+            .set("x2", number(right))
+            .set("y2", number(layout.bottom())),
+    );
+    doc.append(
+        Text::new()
+            .set("class", "axis-number")
+            .set("x", number(right + 5.0))
+            .set("y", number(layout.top() - 8.0))
+            .add(text(&y_axis.label)),
+    );
+}
+
+/// Draws a regression line of a Gran function between two volumes.
+///
+/// The line is cut off where it leaves the diagram at the top.
+fn gran_line(
+    doc: &mut Document,
+    series: usize,
+    line: GranLine,
+    (from, to): (f32, f32),
+    layout: DiagramLayout,
+) {
+    let clip = |m_v: f32| match line.root() {
+        Some(_) if line.at(m_v) > 1.0 => (1.0 - line.intercept) / line.slope,
+        _ => m_v,
+    };
+    let (from, to) = (clip(from), clip(to));
+    doc.append(
+        Line::new()
+            .set(
+                "class",
+                format!("graph-line series-{} fit", series % SERIES_STYLES),
+            )
+            .set("x1", number(layout.x(from)))
+            .set("y1", number(layout.y(line.at(from).max(0.0))))
+            .set("x2", number(layout.x(to)))
+            .set("y2", number(layout.y(line.at(to).max(0.0)))),
+    );
+}
+
+/// Draws the curve of a model without points, like the fitted curve or the curve calculated
+/// with activities.
+///
+/// The class tells the models apart, since they share the style of the series.
+fn model_curve(
+    doc: &mut Document,
+    series: usize,
+    class: &str,
+    points: Vec<(f32, f32)>,
+    layout: DiagramLayout,
+) {
+    let points: Vec<String> = points
+        .into_iter()
+        .filter(|(_, ph)| ph.is_finite())
+        .map(|(m_v, ph)| {
+            format!(
+                "{},{}",
+                layout.x(m_v),
+                layout.y(ph.clamp(0.0, DIAGRAM_MAX_Y))
+            )
+        })
+        .collect();
+    doc.append(
+        Polyline::new()
+            .set(
+                "class",
+                format!("graph-line series-{} {class}", series % SERIES_STYLES),
+            )
+            .set("points", points.join(" ")),
+    );
+}
+
+/// Marks the extrapolated equivalence volume of a Gran plot on the volume axis.
+///
+/// Every regression line gets its own row of labels, so they do not overlap.
+fn gran_intercept(
+    doc: &mut Document,
+    series: usize,
+    row: usize,
+    volume: f32,
+    options: &Options,
+    layout: DiagramLayout,
+) {
+    let x = layout.x(volume);
+    doc.append(
+        Circle::new()
+            .set(
+                "class",
+                format!("graph-point series-{} highlight", series % SERIES_STYLES),
+            )
+            .set("cx", number(x))
+            .set("cy", number(layout.bottom())),
+    );
+    doc.append(
+        Text::new()
+            .set("class", "overlay-text anchor-middle")
+            .set("x", number(x))
+            .set(
+                "y",
+                number(layout.bottom() - 10.0 - OVERLAY_LINE_HEIGHT * row as f32),
+            )
+            .add(text(format!("V = {} mL", options.rounding.volume(volume)))),
+    );
+}
+
+/// Draws an annotation added by the user.
+///
+/// Lines are labeled at their upper or right end, labels are centered on their point.
+fn draw_annotation(doc: &mut Document, annotation: &Annotation, layout: DiagramLayout) {
+    let (x, y, class) = match annotation {
+        Annotation::Label { volume, ph, .. } => (
+            layout.x(*volume),
+            layout.y(*ph),
+            "annotation-text anchor-middle",
+        ),
+        Annotation::VolumeMarker { volume, .. } => {
+            let x = layout.x(*volume);
+            if x > layout.right() {
+                return;
+            }
+            doc.append(
+                Line::new()
+                    .set("class", "annotation-line")
+                    .set("x1", number(x))
+                    .set("y1", number(layout.top()))
+                    .set("x2", number(x))
+                    .set("y2", number(layout.bottom())),
+            );
+            (x + 2.0, layout.top() + 5.0, "annotation-text")
+        }
+        Annotation::PhLine { ph, .. } => {
+            let y = layout.y(*ph);
+            doc.append(
+                Line::new()
+                    .set("class", "annotation-line")
+                    .set("x1", number(layout.left()))
+                    .set("y1", number(y))
+                    .set("x2", number(layout.right()))
+                    .set("y2", number(y)),
+            );
+            (layout.right() - 2.0, y - 5.0, "annotation-text anchor-end")
+        }
+    };
+    if !annotation.text().is_empty() {
+        doc.append(
+            Text::new()
+                .set("class", class)
+                .set("x", number(x))
+                .set("y", number(y))
+                .add(text(annotation.text())),
+        );
+    }
+}
+
+/// Draws a line of the logarithmic concentration diagram, labeled with its species at its
+/// highest point.
+///
+/// Only the part of the line inside the range of the y-axis is drawn.
+fn hagg_line(
+    doc: &mut Document,
+    series: usize,
+    line: &HaggLine,
+    y_axis: &YAxis,
+    layout: DiagramLayout,
+) {
+    let points: Vec<(f32, f32)> = line
+        .points
+        .iter()
+        .map(|&(ph, lg)| (ph, lg - y_axis.offset))
+        .filter(|(_, y)| (0.0..=y_axis.max).contains(y))
+        .collect();
+    let Some(&(top_x, top_y)) = points.iter().max_by(|a, b| a.1.total_cmp(&b.1)) else {
+        return;
+    };
+    let coordinates: Vec<String> = points
+        .iter()
+        .map(|&(x, y)| format!("{},{}", number(layout.x(x)), number(layout.y(y))))
+        .collect();
+    doc.append(
+        Polyline::new()
+            .set(
+                "class",
+                format!("graph-line series-{}", series % SERIES_STYLES),
+            )
+            .set("points", coordinates.join(" ")),
+    );
+    doc.append(
+        Text::new()
+            .set("class", "axis-number anchor-middle")
+            .set(
+                "x",
+                number(
+                    layout
+                        .x(top_x)
+                        .clamp(layout.left() + 8.0, layout.right() - 8.0),
+                ),
+            )
+            .set("y", number(layout.y(top_y) - 4.0))
+            .add(text(&line.species)),
+    );
+}
+
+/// Draws the legend naming the series with a sample of their lines and points.
+///
+/// Every entry is the index of a series and its name.
+fn series_legend(
+    doc: &mut Document,
+    entries: &[(usize, String)],
+    position: LegendPosition,
+    layout: DiagramLayout,
+) {
+    if entries.is_empty() {
+        return;
+    }
+    let longest = entries
+        .iter()
+        .map(|(_, name)| name.chars().count())
+        .max()
+        .unwrap_or_default();
+    let width = 30.0 + LEGEND_CHAR_WIDTH * longest as f32;
+    let height = entries.len() as f32 * OVERLAY_LINE_HEIGHT + 6.0;
+    let x = match position {
+        LegendPosition::TopLeft | LegendPosition::BottomLeft => layout.left() + 5.0,
+        _ => layout.right() - width - 5.0,
+    };
+    let y = match position {
+        LegendPosition::BottomLeft | LegendPosition::BottomRight => layout.bottom() - height - 5.0,
+        _ => layout.top() + 5.0,
+    };
+    doc.append(
+        Rectangle::new()
+            .set("class", "overlay-box")
+            .set("x", number(x))
+            .set("y", number(y))
+            .set("width", number(width))
+            .set("height", number(height)),
+    );
+    for (line, (series, name)) in entries.iter().enumerate() {
+        let series = series % SERIES_STYLES;
+        let line_y = y + 3.0 + OVERLAY_LINE_HEIGHT * (line as f32 + 0.5);
+        doc.append(
+            Line::new()
+                .set("class", format!("graph-line series-{series}"))
+                .set("x1", number(x + 4.0))
+                .set("y1", number(line_y))
+                .set("x2", number(x + 20.0))
+                .set("y2", number(line_y)),
+        );
+        doc.append(
+            Circle::new()
+                .set("class", format!("graph-point series-{series}"))
+                .set("cx", number(x + 12.0))
+                .set("cy", number(line_y)),
+        );
+        doc.append(
+            Text::new()
+                .set("class", "overlay-text")
+                .set("x", number(x + 24.0))
+                .set("y", number(line_y + 3.0))
+                .add(text(name)),
+        );
+    }
+}
+
+/// Draws the legend telling the pH and the series on the second y-axis apart.
+fn axis_legend(doc: &mut Document, secondary_label: &str, layout: DiagramLayout) {
+    let x = layout.right() - LEGEND_WIDTH - 5.0;
+    let y = layout.top() + 5.0;
+    doc.append(
+        Rectangle::new()
+            .set("class", "overlay-box")
+            .set("x", number(x))
+            .set("y", number(y))
+            .set("width", number(LEGEND_WIDTH))
+            .set("height", number(2.0 * OVERLAY_LINE_HEIGHT + 6.0)),
+    );
+    for (line, (label, secondary)) in [("pH", false), (secondary_label, true)]
+        .into_iter()
+        .enumerate()
+    {
+        let line_y = y + 3.0 + OVERLAY_LINE_HEIGHT * (line as f32 + 0.5);
+        let class = if secondary {
+            "graph-line series-0 secondary"
+        } else {
+            "graph-line series-0"
+        };
         doc.append(
             Line::new()
-                .set("class", "graph-line")
-                .set("x1", DIAGRAM_LEFT + items[0].m_v * scale_x)
-                .set("y1", DIAGRAM_BOTTOM - items[0].ph * scale_y)
-                .set("x2", DIAGRAM_LEFT + items[1].m_v * scale_x)
-                .set("y2", DIAGRAM_BOTTOM - items[1].ph * scale_y),
+                .set("class", class)
+                .set("x1", number(x + 4.0))
+                .set("y1", number(line_y))
+                .set("x2", number(x + 20.0))
+                .set("y2", number(line_y)),
+        );
+        doc.append(
+            Text::new()
+                .set("class", "overlay-text")
+                .set("x", number(x + 24.0))
+                .set("y", number(line_y + 3.0))
+                .add(text(label)),
         );
     }
+}
+
+/// Draws a vertical line at the endpoint of a series, dashed for a series on the second y-axis.
+fn endpoint_line(
+    doc: &mut Document,
+    series: usize,
+    volume: f32,
+    secondary: bool,
+    layout: DiagramLayout,
+) {
+    let x = layout.x(volume);
+    if x > layout.right() {
+        return;
+    }
+    let series = series % SERIES_STYLES;
+    let class = if secondary {
+        format!("graph-line endpoint series-{series} secondary")
+    } else {
+        format!("graph-line endpoint series-{series}")
+    };
+    doc.append(
+        Line::new()
+            .set("class", class)
+            .set("x1", number(x))
+            .set("y1", number(layout.top()))
+            .set("x2", number(x))
+            .set("y2", number(layout.bottom())),
+    );
+}
+
+/// Draws a series of points connected by lines, or by a smooth curve.
+fn diagram_series(
+    doc: &mut Document,
+    series: usize,
+    points: &[(f32, f32)],
+    highlight: Option<usize>,
+    secondary: bool,
+    smooth: bool,
+    layout: DiagramLayout,
+) {
+    let series = series % SERIES_STYLES;
+    // Series on the second y-axis are dashed.
+    let series = if secondary {
+        format!("{series} secondary")
+    } else {
+        series.to_string()
+    };
+    // Lines
+    if smooth && points.len() > 2 {
+        doc.append(
+            Path::new()
+                .set("class", format!("graph-line series-{series}"))
+                .set("d", smooth_path(points, layout)),
+        );
+    } else {
+        for points in points.windows(2) {
+            doc.append(
+                Line::new()
+                    .set("class", format!("graph-line series-{series}"))
+                    .set("x1", number(layout.x(points[0].0)))
+                    .set("y1", number(layout.y(points[0].1)))
+                    .set("x2", number(layout.x(points[1].0)))
+                    .set("y2", number(layout.y(points[1].1))),
+            );
+        }
+    }
     // Points
-    for item in &output.items {
+    for (index, (x, y)) in points.iter().enumerate() {
+        let class = if highlight == Some(index) {
+            format!("graph-point series-{series} highlight")
+        } else {
+            format!("graph-point series-{series}")
+        };
         doc.append(
             Circle::new()
-                .set("class", "graph-point")
-                .set("cx", DIAGRAM_LEFT + item.m_v * scale_x)
-                .set("cy", DIAGRAM_BOTTOM - item.ph * scale_y),
+                .set("class", class)
+                .set("cx", number(layout.x(*x)))
+                .set("cy", number(layout.y(*y))),
         );
     }
 }
 
-fn colored_background(doc: &mut Document) {
-    color_gradient(doc);
+/// Creates the path data of a monotone cubic spline through the points.
+///
+/// The slopes are chosen with the Fritsch–Butland formula, so the curve does not overshoot
+/// between two points, e.g. above pH 14 next to the equivalence point.
+fn smooth_path(points: &[(f32, f32)], layout: DiagramLayout) -> String {
+    let to_svg = |(x, y): (f32, f32)| (number(layout.x(x)), number(layout.y(y)));
+    let widths: Vec<f32> = points.windows(2).map(|it| it[1].0 - it[0].0).collect();
+    let slopes: Vec<f32> = points
+        .windows(2)
+        .zip(&widths)
+        .map(|(it, &width)| {
+            if width > 0.0 {
+                (it[1].1 - it[0].1) / width
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    let mut tangents = vec![0.0; points.len()];
+    tangents[0] = slopes[0];
+    tangents[points.len() - 1] = slopes[slopes.len() - 1];
+    for index in 1..points.len() - 1 {
+        let (before, after) = (slopes[index - 1], slopes[index]);
+        if before * after <= 0.0 {
+            continue;
+        }
+        let (h_before, h_after) = (widths[index - 1], widths[index]);
+        tangents[index] = 3.0 * (h_before + h_after)
+            / ((2.0 * h_after + h_before) / before + (h_after + 2.0 * h_before) / after);
+    }
+    let (x, y) = to_svg(points[0]);
+    let mut path = format!("M {x} {y}");
+    for (index, &width) in widths.iter().enumerate() {
+        let (from, to) = (points[index], points[index + 1]);
+        let third = width / 3.0;
+        let (x1, y1) = to_svg((from.0 + third, from.1 + tangents[index] * third));
+        let (x2, y2) = to_svg((to.0 - third, to.1 - tangents[index + 1] * third));
+        let (x, y) = to_svg(to);
+        _ = write!(path, " C {x1} {y1} {x2} {y2} {x} {y}");
+    }
+    path
+}
+
+/// Draws a box with the name of the last revealed region and the calculation of its last point.
+fn formula_overlay(doc: &mut Document, output: &Output, region: Region, layout: DiagramLayout) {
+    let lines: Vec<String> = iter::once(region_name(region).get().to_owned())
+        .chain(region_formulas(output, region))
+        .collect();
+    let longest = lines
+        .iter()
+        .map(|it| it.chars().count())
+        .max()
+        .unwrap_or_default();
+    let width = (10.0 + LEGEND_CHAR_WIDTH * longest as f32).max(OVERLAY_WIDTH);
+    let x = layout.left() + 5.0;
+    let y = layout.top() + 5.0;
+    doc.append(
+        Rectangle::new()
+            .set("class", "overlay-box")
+            .set("x", number(x))
+            .set("y", number(y))
+            .set("width", number(width))
+            .set(
+                "height",
+                number(lines.len() as f32 * OVERLAY_LINE_HEIGHT + 6.0),
+            ),
+    );
+    for (line, content) in lines.into_iter().enumerate() {
+        let class = if line == 0 {
+            "overlay-title"
+        } else {
+            "overlay-text"
+        };
+        doc.append(
+            Text::new()
+                .set("class", class)
+                .set("x", number(x + 5.0))
+                .set(
+                    "y",
+                    number(y + 3.0 + (line as f32 + 0.5) * OVERLAY_LINE_HEIGHT),
+                )
+                .add(text(content)),
+        );
+    }
+}
+
+/// Returns the display name of a region of the curve.
+pub fn region_name(region: Region) -> Localized {
+    match region {
+        Region::Initial => REGION_INITIAL,
+        Region::Buffer => REGION_BUFFER,
+        Region::Equivalence => REGION_EQUIVALENCE,
+        Region::Excess => REGION_EXCESS,
+    }
+}
+
+/// Returns the formulas used to calculate the pH at the last point of a region of the curve.
+///
+/// The formulas are taken from the explainer of the model, so they follow the temperature, the
+/// stoichiometry and a second acid. A formula is broken into lines at its conclusions.
+fn region_formulas(output: &Output, region: Region) -> Vec<String> {
+    let Some(index) = (0..output.items.len()).rfind(|&index| output.region(index) == region) else {
+        return Vec::new();
+    };
+    output
+        .input
+        .explain(index)
+        .iter()
+        .flat_map(|step| {
+            let formula = step.formula.replace(" ⇒ ", "; ⇒ ");
+            formula.split("; ").map(str::to_owned).collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Draws a ladder diagram with a rung for the pKa of every pair.
+///
+/// The predominant species are written between the rungs.
+fn diagram_ladder(doc: &mut Document, pairs: &[&AcidBasePair], layout: DiagramLayout) {
+    let bar_x = layout.side_left() + 10.0;
+    doc.append(
+        Line::new()
+            .set("class", "axis")
+            .set("x1", number(bar_x))
+            .set("y1", number(layout.top()))
+            .set("x2", number(bar_x))
+            .set("y2", number(layout.bottom())),
+    );
+    let mut last_y = layout.bottom();
+    for pair in pairs {
+        let y = layout.y(pair.pka.clamp(0.0, DIAGRAM_MAX_Y));
+        doc.append(
+            Line::new()
+                .set("class", "ladder-rung")
+                .set("x1", number(bar_x - 5.0))
+                .set("y1", number(y))
+                .set("x2", number(bar_x + 5.0))
+                .set("y2", number(y)),
+        );
+        doc.append(
+            Text::new()
+                .set("class", "ladder-text")
+                .set("x", number(bar_x + 8.0))
+                .set("y", number(y))
+                .add(text(format!("pKa {}", pair.pka))),
+        );
+        ladder_species(doc, &pair.acid, bar_x, (last_y + y) / 2.0);
+        last_y = y;
+    }
+    if let Some(pair) = pairs.last() {
+        ladder_species(doc, &pair.base, bar_x, (last_y + layout.top()) / 2.0);
+    }
+}
+
+fn ladder_species(doc: &mut Document, species: &str, bar_x: f32, y: f32) {
+    doc.append(
+        Text::new()
+            .set("class", "ladder-species")
+            .set("x", number(bar_x + 8.0))
+            .set("y", number(y))
+            .add(text(species)),
+    );
+}
+
+fn colored_background(doc: &mut Document, options: &Options, layout: DiagramLayout) {
+    color_gradient(doc, options);
     doc.append(
         Rectangle::new()
             .set("fill", "url(#color-gradient)")
-            .set("x", DIAGRAM_LEFT)
-            .set("y", DIAGRAM_TOP)
-            .set("width", DIAGRAM_WIDTH)
-            .set("height", DIAGRAM_HEIGHT),
+            .set("x", number(layout.left()))
+            .set("y", number(layout.top()))
+            .set("width", number(layout.width()))
+            .set("height", number(layout.height())),
     );
 }
 
-fn color_gradient(doc: &mut Document) {
+fn color_gradient(doc: &mut Document, options: &Options) {
+    let [low, middle, high] = options.style.gradient.map(|it| it.to_string());
     doc.append(
         Definitions::new().add(
             LinearGradient::new()
@@ -186,13 +1718,25 @@ fn color_gradient(doc: &mut Document) {
                 .set("y1", 1)
                 .set("x2", 0)
                 .set("y2", 0)
-                .add(Stop::new().set("stop-color", "red").set("offset", "0%"))
-                .add(Stop::new().set("stop-color", "yellow").set("offset", "50%"))
-                .add(Stop::new().set("stop-color", "green").set("offset", "100%")),
+                .add(Stop::new().set("stop-color", low).set("offset", "0%"))
+                .add(Stop::new().set("stop-color", middle).set("offset", "50%"))
+                .add(Stop::new().set("stop-color", high).set("offset", "100%")),
         ),
     )
 }
 
+/// Formats a coordinate with at most [`SVG_DECIMALS`] decimals.
+///
+/// The shortest form of a float depends on its last bits, which differ between platforms after
+/// the same calculation, so the diagrams would not be the same everywhere.
+fn number(value: f32) -> String {
+    let formatted = format!("{value:.SVG_DECIMALS$}");
+    match formatted.trim_end_matches('0').trim_end_matches('.') {
+        "-0" => "0".into(),
+        trimmed => trimmed.into(),
+    }
+}
+
 fn text(content: impl Into<String>) -> svg::node::Text {
     svg::node::Text::new(content)
 }
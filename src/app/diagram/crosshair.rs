@@ -0,0 +1,287 @@
+use std::sync::Arc;
+
+use iced::{
+    alignment::{Horizontal, Vertical},
+    mouse::{self, Cursor},
+    widget::canvas::{self, Frame, Geometry, LineDash, Path, Stroke, Text},
+    Color, Point, Rectangle, Renderer, Theme,
+};
+
+use crate::worker::{analysis, format::NumberFormat, locale::localized, Output};
+
+use super::{
+    super::{options::Options, style::Rgb, Message},
+    x_factor, DiagramFrame, DiagramLayout, Plot, XAxis, DIAGRAM_MAX_Y,
+};
+
+/// The radius of the marker at the crossing of the guide lines with the curve in SVG units.
+const MARKER_RADIUS: f32 = 2.5;
+
+/// The titration curve drawn on a canvas, with guide lines following the cursor.
+///
+/// The vertical guide reads the interpolated pH of the first curve at the volume under the
+/// cursor, and the horizontal one reads the volume at which the curve reaches the pH under the
+/// cursor. Everything is placed with the layout of the SVG diagram, so the frame maps the cursor
+/// to the same volumes as over the SVG.
+#[derive(Clone)]
+pub struct CrosshairPlot {
+    frame: DiagramFrame,
+    /// The labels of the evenly spaced ticks of the x-axis, written like those of the SVG.
+    x_labels: Vec<String>,
+    /// The labels of the ticks of the pH axis.
+    y_labels: Vec<String>,
+    /// The drawn outputs with the factor converting their volumes into values of the x-axis.
+    curves: Vec<(Arc<Output>, f32)>,
+    foreground: Color,
+    /// The font size of the tick labels and the readout, scaled like the texts of the SVG.
+    font_size: f32,
+    /// The factor the widths of lines are scaled with.
+    weight: f32,
+    /// The format of the values in the readout.
+    number_format: NumberFormat,
+    /// The line colors of the series.
+    colors: Vec<Color>,
+}
+
+impl CrosshairPlot {
+    /// Creates the plot of the titration curves of the outputs.
+    ///
+    /// Outputs whose volumes cannot be converted into the quantity of the x-axis are left out.
+    pub fn new(options: &Options, outputs: &[&Arc<Output>]) -> Self {
+        let plain: Vec<&Output> = outputs.iter().map(|it| it.as_ref()).collect();
+        let x_axis = XAxis::of(Plot::Curve, &plain, options.x_quantity);
+        let curves: Vec<(Arc<Output>, f32)> = outputs
+            .iter()
+            .filter_map(|it| Some(((*it).clone(), x_factor(&x_axis, it)?)))
+            .collect();
+        let x_step = x_axis.max / x_axis.gaps as f32;
+        let x_decimals = (-x_step.log10().floor()).max(0.0) as usize;
+        let x_labels = (0..=x_axis.gaps)
+            .map(|it| {
+                let value = it as f32 * x_step;
+                options
+                    .x_axis
+                    .tick_label(value, x_decimals, options.number_format)
+            })
+            .collect();
+        let y_labels = (0..=DIAGRAM_MAX_Y as usize)
+            .map(|it| {
+                options
+                    .y_axis
+                    .tick_label(it as f32, 0, options.number_format)
+            })
+            .collect();
+        let colors = (0..curves.len())
+            .map(|it| color(options.style.line_color(it, options.dark)))
+            .collect();
+        Self {
+            frame: DiagramFrame::new(options, &plain, Plot::Curve),
+            x_labels,
+            y_labels,
+            curves,
+            foreground: color(options.style.foreground_color(options.dark)),
+            font_size: options.style.font_size,
+            weight: options.style.line_weight(),
+            number_format: options.number_format,
+            colors,
+        }
+    }
+
+    /// Draws the axes and the curves into the plot area of the layout.
+    fn draw_plot(
+        &self,
+        frame: &mut Frame,
+        layout: DiagramLayout,
+        to_screen: &impl Fn(f32, f32) -> Point,
+    ) {
+        let stroke = Stroke::default()
+            .with_color(self.foreground)
+            .with_width(self.weight);
+        let (left, right, top, bottom) =
+            (layout.left(), layout.right(), layout.top(), layout.bottom());
+        frame.stroke(
+            &Path::new(|path| {
+                path.move_to(to_screen(left, top));
+                path.line_to(to_screen(left, bottom));
+                path.line_to(to_screen(right, bottom));
+            }),
+            stroke.clone(),
+        );
+        let y_gaps = self.y_labels.len().saturating_sub(1).max(1);
+        for (step, label) in self.y_labels.iter().enumerate() {
+            let y = bottom - layout.height() / y_gaps as f32 * step as f32;
+            frame.stroke(
+                &Path::line(to_screen(left - 3.0, y), to_screen(left + 3.0, y)),
+                stroke.clone(),
+            );
+            self.label(
+                frame,
+                label.clone(),
+                to_screen(left - 5.0, y),
+                Horizontal::Right,
+            );
+        }
+        let x_gaps = self.x_labels.len().saturating_sub(1).max(1);
+        for (step, label) in self.x_labels.iter().enumerate() {
+            let x = left + layout.width() / x_gaps as f32 * step as f32;
+            frame.stroke(
+                &Path::line(to_screen(x, bottom - 3.0), to_screen(x, bottom + 3.0)),
+                stroke.clone(),
+            );
+            self.label(
+                frame,
+                label.clone(),
+                to_screen(x, bottom + 10.0),
+                Horizontal::Center,
+            );
+        }
+        for ((output, factor), color) in self.curves.iter().zip(&self.colors) {
+            let layout = layout.with_x_factor(*factor);
+            let path = Path::new(|path| {
+                for (index, item) in output.items.iter().enumerate() {
+                    let point = to_screen(layout.x(item.m_v), layout.y(item.ph));
+                    if index == 0 {
+                        path.move_to(point);
+                    } else {
+                        path.line_to(point);
+                    }
+                }
+            });
+            frame.stroke(
+                &path,
+                Stroke::default().with_color(*color).with_width(self.weight),
+            );
+        }
+    }
+
+    /// Draws the guide lines through the volume and the pH under the cursor.
+    fn draw_crosshair(
+        &self,
+        frame: &mut Frame,
+        layout: DiagramLayout,
+        to_screen: &impl Fn(f32, f32) -> Point,
+        (x, y): (f32, f32),
+    ) {
+        let Some((output, factor)) = self.curves.first() else {
+            return;
+        };
+        let layout = layout.with_x_factor(*factor);
+        let (left, right, top, bottom) =
+            (layout.left(), layout.right(), layout.top(), layout.bottom());
+        let Some(volume) = layout.x_value_at(x, y) else {
+            return;
+        };
+        let guide = Stroke {
+            line_dash: LineDash {
+                segments: &[4.0, 2.0],
+                offset: 0,
+            },
+            ..Stroke::default()
+                .with_color(self.foreground)
+                .with_width(0.5 * self.weight)
+        };
+        // The volume under the cursor and the pH of the curve there.
+        if let Some(ph) = analysis::ph_at(output, volume) {
+            let (curve_x, curve_y) = (layout.x(volume), layout.y(ph));
+            frame.stroke(
+                &Path::line(to_screen(curve_x, bottom), to_screen(curve_x, top)),
+                guide.clone(),
+            );
+            frame.stroke(
+                &Path::line(to_screen(left, curve_y), to_screen(curve_x, curve_y)),
+                guide.clone(),
+            );
+            let marker = to_screen(curve_x, curve_y);
+            let radius = to_screen(curve_x + MARKER_RADIUS, curve_y).x - marker.x;
+            frame.fill(&Path::circle(marker, radius), self.foreground);
+            self.label(
+                frame,
+                format!("V = {} mL, pH = {}", self.readout(volume), self.readout(ph)),
+                to_screen(curve_x + 4.0, curve_y - 8.0),
+                Horizontal::Left,
+            );
+        }
+        // The pH under the cursor and the volume at which the curve reaches it.
+        let ph = (bottom - y) / layout.scale_y();
+        if let Some(volume) = analysis::volume_at(output, ph) {
+            let curve_x = layout.x(volume);
+            frame.stroke(
+                &Path::line(to_screen(curve_x, y), to_screen(right, y)),
+                guide,
+            );
+            self.label(
+                frame,
+                format!(
+                    "pH = {} ⇒ V = {} mL",
+                    self.readout(ph),
+                    self.readout(volume)
+                ),
+                to_screen(right, y - 6.0),
+                Horizontal::Right,
+            );
+        }
+    }
+
+    /// Formats a value of the readout, with two decimals by default.
+    fn readout(&self, value: f32) -> String {
+        self.number_format
+            .format_or(value, |it| localized(format!("{it:.2}")))
+    }
+
+    fn label(&self, frame: &mut Frame, content: String, position: Point, alignment: Horizontal) {
+        frame.fill_text(Text {
+            content,
+            position,
+            color: self.foreground,
+            size: self.font_size.into(),
+            horizontal_alignment: alignment,
+            vertical_alignment: Vertical::Center,
+            ..Text::default()
+        });
+    }
+}
+
+impl canvas::Program<Message> for CrosshairPlot {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        cursor: Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let Some((scale, offset)) = self.frame.placement(bounds.size()) else {
+            return vec![frame.into_geometry()];
+        };
+        let layout = self.frame.layout();
+        let to_screen = |x: f32, y: f32| Point::new(x * scale, y * scale) + offset;
+        self.draw_plot(&mut frame, layout, &to_screen);
+        if let Some(position) = cursor.position_in(bounds) {
+            let position = position - offset;
+            let at = (position.x / scale, position.y / scale);
+            self.draw_crosshair(&mut frame, layout, &to_screen, at);
+        }
+        vec![frame.into_geometry()]
+    }
+
+    fn mouse_interaction(
+        &self,
+        _state: &Self::State,
+        bounds: Rectangle,
+        cursor: Cursor,
+    ) -> mouse::Interaction {
+        if cursor.is_over(bounds) {
+            mouse::Interaction::Crosshair
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
+
+fn color(rgb: Rgb) -> Color {
+    let [r, g, b] = rgb.0;
+    Color::from_rgb8(r, g, b)
+}
@@ -0,0 +1,228 @@
+//! Snapshots of the rendered diagrams, which show every change of the SVG in review.
+//!
+//! Run `cargo insta review` after an intended change to accept the new snapshots.
+
+use std::{
+    cell::RefCell,
+    sync::{Arc, RwLock, RwLockReadGuard},
+};
+
+use insta::assert_snapshot;
+
+use crate::worker::{
+    analysis::Stoichiometry, model::StrongModel, uncertainty::Uncertainty, Input, Output,
+};
+
+use super::{
+    super::{
+        render_cache::RenderCache,
+        strings::{self, Language},
+    },
+    *,
+};
+
+/// Guards the language, which is global, so no snapshot is rendered while a test switches it.
+static LANGUAGE: RwLock<()> = RwLock::new(());
+
+/// Keeps the language unchanged while the returned guard lives.
+fn same_language() -> RwLockReadGuard<'static, ()> {
+    LANGUAGE.read().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Calculates the titration of 20 mL of 0.1 mol/L acid with 0.1 mol/L base up to 40 mL in the
+/// given number of steps.
+fn output(points: usize, uncertainty: Uncertainty) -> Output {
+    Input {
+        t_v: 0.02,
+        t_c: 0.1,
+        m_c: 0.1,
+        m_v: (0..points)
+            .map(|it| it as f32 * 40.0 / points as f32)
+            .collect(),
+        temperature: 25.0,
+        temperatures: Vec::new(),
+        uncertainty,
+        stoichiometry: Stoichiometry::default(),
+        second_acid: None,
+        titrant: None,
+        analyte: None,
+        model: &StrongModel,
+    }
+    .calculate_output()
+}
+
+fn render(options: &Options, outputs: &[&Output], plot: Plot) -> String {
+    let _language = same_language();
+    let state = GraphState {
+        plot,
+        ..Default::default()
+    };
+    render_graph(options, outputs, &[], state)
+}
+
+#[test]
+fn curve_light() {
+    let output = output(16, Uncertainty::default());
+    assert_snapshot!(render(&Options::default(), &[&output], Plot::Curve));
+}
+
+#[test]
+fn curve_dark() {
+    let output = output(16, Uncertainty::default());
+    let options = Options {
+        dark: true,
+        ..Default::default()
+    };
+    assert_snapshot!(render(&options, &[&output], Plot::Curve));
+}
+
+#[test]
+fn curve_colored() {
+    let output = output(16, Uncertainty::default());
+    let options = Options {
+        colored: true,
+        ..Default::default()
+    };
+    assert_snapshot!(render(&options, &[&output], Plot::Curve));
+}
+
+#[test]
+fn curve_smooth() {
+    let output = output(16, Uncertainty::default());
+    let options = Options {
+        smooth: true,
+        ..Default::default()
+    };
+    assert_snapshot!(render(&options, &[&output], Plot::Curve));
+}
+
+#[test]
+fn curve_with_uncertainty() {
+    let uncertainty = Uncertainty {
+        t_v: 0.0001,
+        t_c: 0.001,
+        m_c: 0.001,
+        m_v: 0.05,
+    };
+    let output = output(16, uncertainty);
+    assert_snapshot!(render(&Options::default(), &[&output], Plot::Curve));
+}
+
+#[test]
+fn curve_decimated() {
+    let output = output(400, Uncertainty::default());
+    let options = Options {
+        max_points: Some(40),
+        ..Default::default()
+    };
+    assert_snapshot!(render(&options, &[&output], Plot::Curve));
+}
+
+#[test]
+fn curve_single_point() {
+    let output = output(1, Uncertainty::default());
+    assert_snapshot!(render(&Options::default(), &[&output], Plot::Curve));
+}
+
+#[test]
+fn curve_without_points() {
+    let output = output(0, Uncertainty::default());
+    assert_snapshot!(render(&Options::default(), &[&output], Plot::Curve));
+}
+
+#[test]
+fn curve_titrant_title() {
+    let mut output = output(16, Uncertainty::default());
+    output.input.titrant = Some(&STRONG_BASES[0]);
+    assert_snapshot!(render(&Options::default(), &[&output], Plot::Curve));
+}
+
+#[test]
+fn curve_custom_titles() {
+    let output = output(16, Uncertainty::default());
+    let mut options = Options::default();
+    options.x_axis.title = "V(NaOH) / mL".into();
+    options.y_axis.title = "pH-Wert".into();
+    assert_snapshot!(render(&options, &[&output], Plot::Curve));
+}
+
+#[test]
+fn overlay() {
+    let (coarse, fine) = (
+        output(8, Uncertainty::default()),
+        output(16, Uncertainty::default()),
+    );
+    let names = ["coarse".to_string(), "fine".to_string()];
+    let _language = same_language();
+    let svg = render_graph(
+        &Options::default(),
+        &[&coarse, &fine],
+        &names,
+        GraphState::default(),
+    );
+    assert_snapshot!(svg);
+}
+
+#[test]
+fn derivative() {
+    let output = output(16, Uncertainty::default());
+    assert_snapshot!(render(&Options::default(), &[&output], Plot::Derivative));
+}
+
+#[test]
+fn buffer_capacity() {
+    let output = output(16, Uncertainty::default());
+    assert_snapshot!(render(
+        &Options::default(),
+        &[&output],
+        Plot::BufferCapacity
+    ));
+}
+
+/// An exported diagram carries the input of its curve, which is read back unchanged.
+#[test]
+fn export_metadata_round_trip() {
+    let output = output(16, Uncertainty::default());
+    let state = GraphState::default();
+    let svg = render_export(&Options::default(), &[&output], &[], state);
+    let (input, sheet) = crate::worker::metadata::read_svg(&svg).unwrap();
+    assert_eq!(input.m_v, output.input.m_v);
+    assert_eq!((input.t_v, input.t_c, input.m_c), (0.02, 0.1, 0.1));
+    assert_eq!(input.model.id(), "strong");
+    assert_eq!(sheet, None);
+}
+
+/// The cached diagram is rendered again after switching the language, so the axes are titled in
+/// the new language.
+#[test]
+fn render_cache_follows_language() {
+    let _language = LANGUAGE.write().unwrap_or_else(|err| err.into_inner());
+    let output = Arc::new(output(16, Uncertainty::default()));
+    let cache = RenderCache::default();
+    let rendered = RefCell::new(Vec::new());
+    let get = || {
+        cache.get(
+            &Options::default(),
+            &[&output],
+            &[],
+            GraphState::default(),
+            || {
+                let svg = render_graph(&Options::default(), &[&output], &[], GraphState::default());
+                rendered.borrow_mut().push(svg.clone());
+                svg
+            },
+        )
+    };
+    strings::set_language(Language::German);
+    let german = get();
+    assert_eq!(get(), german);
+    strings::set_language(Language::English);
+    let english = get();
+    strings::set_language(Language::German);
+    assert_ne!(english, german);
+    let rendered = rendered.into_inner();
+    assert_eq!(rendered.len(), 2);
+    assert!(rendered[0].contains(AXIS_VOLUME.german));
+    assert!(!rendered[1].contains(AXIS_VOLUME.german));
+    assert!(rendered[1].contains(AXIS_VOLUME.english));
+}
@@ -0,0 +1,30 @@
+use iced::{mouse, subscription, window, Event, Subscription};
+
+use super::Message;
+
+/// Subscribes to the requests to close a window.
+///
+/// The app exits itself once the worker is stopped, so closing the window does not exit right
+/// away.
+pub fn close_requests() -> Subscription<Message> {
+    subscription::events_with(|event, _| match event {
+        Event::Window(id, window::Event::CloseRequested) => Some(Message::CloseRequested(id)),
+        _ => None,
+    })
+}
+
+/// Subscribes to the movements of the cursor while the edge of the sidebar is dragged.
+///
+/// The sidebar starts at the left edge of the window, so the horizontal position of the cursor is
+/// its new width.
+pub fn sidebar_drags() -> Subscription<Message> {
+    subscription::events_with(|event, _| match event {
+        Event::Mouse(mouse::Event::CursorMoved { position }) => {
+            Some(Message::ResizeSidebar(position.x))
+        }
+        Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+            Some(Message::EndSidebarResize)
+        }
+        _ => None,
+    })
+}
@@ -0,0 +1,160 @@
+use std::{fmt, path::PathBuf, sync::Arc};
+
+use crate::{
+    util::Rng,
+    worker::{
+        analysis::Stoichiometry,
+        equilibrium::{Component, Solution},
+        mixture::SecondAcid,
+        model::StrongModel,
+        temperature::{self, STANDARD_TEMPERATURE},
+        uncertainty::Uncertainty,
+        AcidBasePair, Input, Output,
+    },
+};
+
+use super::strings::*;
+
+/// The seed of the measurement errors, so the noisy example looks the same every time.
+const NOISE_SEED: u64 = 1592;
+/// The largest error of a pH reading in the noisy example.
+const PH_NOISE: f32 = 0.08;
+/// The largest error of a volume reading in mL in the noisy example.
+const VOLUME_NOISE: f32 = 0.05;
+
+/// A built-in titration, so a curve can be shown without preparing a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Example {
+    /// Hydrochloric acid titrated with sodium hydroxide.
+    StrongAcid,
+    /// Acetic acid titrated with sodium hydroxide, which buffers before the equivalence point.
+    WeakAcid,
+    /// Maleic acid titrated with sodium hydroxide, which has two equivalence points.
+    Diprotic,
+    /// Hydrochloric and acetic acid titrated together, which breaks once for every acid.
+    Mixture,
+    /// A titration of hydrochloric acid with the errors of real readings.
+    Noisy,
+}
+
+impl Example {
+    pub const ALL: [Self; 5] = [
+        Self::StrongAcid,
+        Self::WeakAcid,
+        Self::Diprotic,
+        Self::Mixture,
+        Self::Noisy,
+    ];
+
+    pub fn label(self) -> Localized {
+        match self {
+            Self::StrongAcid => EXAMPLE_STRONG_ACID,
+            Self::WeakAcid => EXAMPLE_WEAK_ACID,
+            Self::Diprotic => EXAMPLE_DIPROTIC,
+            Self::Mixture => EXAMPLE_MIXTURE,
+            Self::Noisy => EXAMPLE_NOISY,
+        }
+    }
+
+    /// Calculates the example with the name it is listed under.
+    ///
+    /// It is not backed by a file, so the worker never reloads it.
+    pub fn load(self) -> (PathBuf, Arc<Output>) {
+        let output = match self {
+            Self::StrongAcid => input(0.1, 0.1, volumes(40.0, 0.5)).calculate_output(),
+            Self::WeakAcid => with_acid(
+                input(0.1, 0.1, volumes(40.0, 0.5)),
+                &[4.756],
+                vec![AcidBasePair::new("CH₃COOH", "CH₃COO⁻", 4.756)],
+            ),
+            Self::Diprotic => with_acid(
+                input(0.05, 0.1, volumes(30.0, 0.25)),
+                &[1.92, 6.23],
+                vec![
+                    AcidBasePair::new("H₂C₄H₂O₄", "HC₄H₂O₄⁻", 1.92),
+                    AcidBasePair::new("HC₄H₂O₄⁻", "C₄H₂O₄²⁻", 6.23),
+                ],
+            ),
+            Self::Mixture => Input {
+                second_acid: Some(SecondAcid {
+                    concentration: 0.05,
+                    pkas: vec![4.756],
+                }),
+                ..input(0.05, 0.1, volumes(30.0, 0.25))
+            }
+            .calculate_output(),
+            Self::Noisy => noisy(),
+        };
+        (PathBuf::from(self.label().get()), Arc::new(output))
+    }
+}
+
+/// Shows the examples by their label in the menu.
+impl fmt::Display for Example {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.label().fmt(f)
+    }
+}
+
+/// Creates the titration of 20 mL of acid with sodium hydroxide.
+fn input(t_c: f32, m_c: f32, m_v: Vec<f32>) -> Input {
+    Input {
+        t_v: 0.02,
+        t_c,
+        m_c,
+        m_v,
+        temperature: STANDARD_TEMPERATURE,
+        temperatures: Vec::new(),
+        uncertainty: Uncertainty::default(),
+        stoichiometry: Stoichiometry::default(),
+        second_acid: None,
+        titrant: None,
+        analyte: None,
+        model: &StrongModel,
+    }
+}
+
+/// Returns the added volumes in mL from zero up to the maximum in equal steps.
+fn volumes(max: f32, step: f32) -> Vec<f32> {
+    let steps = (max / step).round() as usize;
+    (0..=steps).map(|it| it as f32 * step).collect()
+}
+
+/// Calculates the titration of a weak acid, which is described by its pKa values.
+///
+/// The amounts are the ones of a strong acid, while the pH is solved from the charge balance of
+/// the weak acid and the sodium ions.
+fn with_acid(input: Input, pkas: &[f64], pairs: Vec<AcidBasePair>) -> Output {
+    let mut output = input.calculate_output();
+    let pkw = temperature::pkw(input.temperature);
+    let kw = 10f64.powf(-pkw as f64);
+    for item in &mut output.items {
+        let liters = item.m_v as f64 / 1000.0 + input.t_v as f64;
+        let acid = input.t_c as f64 * input.t_v as f64 / liters;
+        let sodium = input.m_c as f64 * item.m_v as f64 / 1000.0 / liters;
+        let solution = Solution::new(kw)
+            .with(Component::acid(acid, pkas, 0))
+            .with(Component::ion(sodium, 1));
+        item.ph = solution.ph() as f32;
+        item.poh = pkw - item.ph;
+    }
+    output.pairs.extend(pairs);
+    output.pairs.sort_by(|a, b| a.pka.total_cmp(&b.pka));
+    output
+}
+
+/// Calculates a titration of hydrochloric acid whose volumes and pH values are read with errors.
+fn noisy() -> Output {
+    let mut rng = Rng::new(NOISE_SEED);
+    let m_v = volumes(40.0, 1.0)
+        .into_iter()
+        .map(|it| (it + rng.range(-VOLUME_NOISE..=VOLUME_NOISE)).max(0.0))
+        .collect();
+    let mut output = input(0.1, 0.1, m_v).calculate_output();
+    let pkw = temperature::pkw(output.input.temperature);
+    for item in &mut output.items {
+        item.ph += rng.range(-PH_NOISE..=PH_NOISE);
+        item.poh = pkw - item.ph;
+    }
+    output
+}
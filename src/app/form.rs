@@ -0,0 +1,95 @@
+//! The text fields of the forms.
+//!
+//! A form keeps the text of every field as it was entered instead of the parsed value, so
+//! unfinished or invalid input like `0,` stays in the field and can still be edited. The values
+//! are parsed whenever the form is read, and a form with an invalid field describes nothing.
+
+use std::marker::PhantomData;
+
+use iced::{
+    widget::{row, text, text_input, Row},
+    Length,
+};
+
+use crate::worker::locale::number_locale;
+
+use super::Message;
+
+/// A field of a form, which selects one of its texts.
+pub trait FormField: Copy {
+    /// Returns the position of the field in the form.
+    fn index(self) -> usize;
+}
+
+/// The entered texts of the fields of a form.
+///
+/// Fields which were never entered are empty.
+#[derive(Clone, Debug)]
+pub struct TextFields<F> {
+    texts: Vec<String>,
+    field: PhantomData<F>,
+}
+
+impl<F> Default for TextFields<F> {
+    fn default() -> Self {
+        Self {
+            texts: Vec::new(),
+            field: PhantomData,
+        }
+    }
+}
+
+impl<F: FormField> TextFields<F> {
+    /// Creates the fields with their initial texts, in the order of the form.
+    pub fn new(texts: &[&str]) -> Self {
+        Self {
+            texts: texts.iter().map(|it| it.to_string()).collect(),
+            field: PhantomData,
+        }
+    }
+
+    pub fn get(&self, field: F) -> &str {
+        self.texts.get(field.index()).map_or("", String::as_str)
+    }
+
+    pub fn set(&mut self, field: F, value: String) {
+        let index = field.index();
+        if index >= self.texts.len() {
+            self.texts.resize(index + 1, String::new());
+        }
+        self.texts[index] = value;
+    }
+
+    /// Parses the number entered in a field.
+    pub fn number(&self, field: F) -> Option<f32> {
+        parse_number(self.get(field))
+    }
+
+    /// Parses the number entered in a field, if it is positive.
+    pub fn positive(&self, field: F) -> Option<f32> {
+        self.number(field).filter(|it| *it > 0.0)
+    }
+}
+
+/// Parses a number in the format of the locale, accepting a comma as decimal separator.
+pub fn parse_number(text: &str) -> Option<f32> {
+    number_locale().parse(text).map(|it| it as f32)
+}
+
+/// Creates a row with the label and the text input of a field, whose input is sent with the
+/// message.
+pub fn field_row<'a, F: FormField + 'a>(
+    label: impl ToString,
+    fields: &'a TextFields<F>,
+    field: F,
+    message: fn(F, String) -> Message,
+    width: f32,
+) -> Row<'a, Message> {
+    row![
+        text(label).width(Length::Fixed(220.0)),
+        text_input("", fields.get(field))
+            .on_input(move |it| message(field, it))
+            .width(Length::Fixed(width))
+    ]
+    .spacing(5)
+}
@@ -0,0 +1,194 @@
+use std::fmt::Write;
+
+use iced::{
+    widget::{button, column, row, text, text_input},
+    Element, Length,
+};
+
+use crate::worker::{
+    explain::{number, Rounding},
+    generator::{Problem, ProblemRanges},
+};
+
+use super::{
+    form::{field_row, FormField, TextFields},
+    strings::*,
+    Message,
+};
+
+/// A field of the problem generator form.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GeneratorField {
+    Seed,
+    TestVolumeMin,
+    TestVolumeMax,
+    TestConcentrationMin,
+    TestConcentrationMax,
+    MeasuringConcentrationMin,
+    MeasuringConcentrationMax,
+    Step,
+}
+
+impl FormField for GeneratorField {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// The state of the problem generator form.
+pub struct GeneratorForm {
+    fields: TextFields<GeneratorField>,
+}
+
+impl Default for GeneratorForm {
+    fn default() -> Self {
+        let ranges = ProblemRanges::default();
+        let [test_volume, test_concentration, measuring_concentration] = [
+            &ranges.test_volume,
+            &ranges.test_concentration,
+            &ranges.measuring_concentration,
+        ]
+        .map(|it| (it.start().to_string(), it.end().to_string()));
+        Self {
+            fields: TextFields::new(&[
+                "1",
+                &test_volume.0,
+                &test_volume.1,
+                &test_concentration.0,
+                &test_concentration.1,
+                &measuring_concentration.0,
+                &measuring_concentration.1,
+                &ranges.step.to_string(),
+            ]),
+        }
+    }
+}
+
+impl GeneratorForm {
+    pub fn set(&mut self, field: GeneratorField, value: String) {
+        self.fields.set(field, value);
+    }
+
+    /// Generates the problem described by the form, if all fields are valid.
+    pub fn problem(&self) -> Option<Problem> {
+        let fields = &self.fields;
+        let seed = fields.get(GeneratorField::Seed).trim().parse().ok()?;
+        let range = |min, max| {
+            let min = fields.number(min)?;
+            let max = fields.number(max)?;
+            (min > 0.0 && min <= max).then_some(min..=max)
+        };
+        let ranges = ProblemRanges {
+            test_volume: range(GeneratorField::TestVolumeMin, GeneratorField::TestVolumeMax)?,
+            test_concentration: range(
+                GeneratorField::TestConcentrationMin,
+                GeneratorField::TestConcentrationMax,
+            )?,
+            measuring_concentration: range(
+                GeneratorField::MeasuringConcentrationMin,
+                GeneratorField::MeasuringConcentrationMax,
+            )?,
+            step: fields.positive(GeneratorField::Step)?,
+        };
+        Some(Problem::generate(seed, &ranges))
+    }
+
+    pub fn view(&self, rounding: Rounding) -> Element<'_, Message> {
+        let input = |field| {
+            text_input("", self.fields.get(field))
+                .on_input(move |it| Message::GeneratorInput(field, it))
+                .width(Length::Fixed(80.0))
+        };
+        let range_row = |label, (min, max)| {
+            row![
+                text(label).width(Length::Fixed(220.0)),
+                input(min),
+                text("–"),
+                input(max)
+            ]
+            .spacing(5)
+        };
+        let field_row =
+            |label, field| field_row(label, &self.fields, field, Message::GeneratorInput, 80.0);
+        let problem = self.problem();
+        let mut problem_button = button(BUTTON_EXPORT_PROBLEM.get());
+        let mut key_button = button(BUTTON_EXPORT_ANSWER_KEY.get());
+        if problem.is_some() {
+            problem_button = problem_button.on_press(Message::ExportProblem);
+            key_button = key_button.on_press(Message::ExportAnswerKey);
+        }
+        let summary = match &problem {
+            Some(problem) => answer_key(problem, rounding),
+            None => GENERATOR_INVALID.into(),
+        };
+        column![
+            text(GENERATOR_TITLE).size(20),
+            field_row(GENERATOR_SEED, GeneratorField::Seed),
+            range_row(
+                GENERATOR_TEST_VOLUME,
+                (GeneratorField::TestVolumeMin, GeneratorField::TestVolumeMax)
+            ),
+            range_row(
+                GENERATOR_TEST_CONCENTRATION,
+                (
+                    GeneratorField::TestConcentrationMin,
+                    GeneratorField::TestConcentrationMax
+                )
+            ),
+            range_row(
+                GENERATOR_MEASURING_CONCENTRATION,
+                (
+                    GeneratorField::MeasuringConcentrationMin,
+                    GeneratorField::MeasuringConcentrationMax
+                )
+            ),
+            field_row(GENERATOR_STEP, GeneratorField::Step),
+            row![problem_button, key_button].spacing(5),
+            text(summary).size(12),
+        ]
+        .spacing(5)
+        .into()
+    }
+}
+
+/// Writes the answer key of a problem as a markdown document.
+///
+/// The concentrations and the equivalence volume are rounded by the given rules.
+pub fn answer_key(problem: &Problem, rounding: Rounding) -> String {
+    let mut doc = String::new();
+    let input = &problem.input;
+    _ = writeln!(
+        doc,
+        "# {GENERATOR_ANSWER_KEY} ({GENERATOR_SEED} {})\n",
+        problem.seed
+    );
+    _ = writeln!(
+        doc,
+        "- {GENERATOR_ACID}: {} ({}, pKa = {})",
+        problem.acid.name,
+        problem.acid.formula,
+        number(problem.acid.pka)
+    );
+    _ = writeln!(
+        doc,
+        "- {GENERATOR_BASE}: {} ({})",
+        problem.base.name, problem.base.formula
+    );
+    _ = writeln!(doc, "- {FIELD_TEST_VOLUME} = {} L", number(input.t_v));
+    _ = writeln!(
+        doc,
+        "- {FIELD_TEST_CONCENTRATION} = {} mol/L",
+        rounding.value(input.t_c)
+    );
+    _ = writeln!(
+        doc,
+        "- {FIELD_MEASURING_CONCENTRATION} = {} mol/L",
+        rounding.value(input.m_c)
+    );
+    _ = writeln!(
+        doc,
+        "- {GENERATOR_EQUIVALENCE_VOLUME} = {} mL",
+        rounding.volume(problem.equivalence_volume())
+    );
+    doc
+}
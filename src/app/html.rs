@@ -0,0 +1,248 @@
+use std::fmt::Write;
+
+use crate::worker::{
+    format::{Notation, NumberFormat},
+    locale::number_locale,
+    units::XQuantity,
+    Output,
+};
+
+use super::{
+    diagram::{x_title, AxisFormat},
+    options::Options,
+    strings::*,
+};
+
+/// The script drawing the chart from the `DATA` array.
+///
+/// Hovering shows the values of the nearest point, the mouse wheel zooms into the volume axis,
+/// dragging pans and a double click resets the view.
+const SCRIPT: &str = r#"
+const canvas = document.getElementById("chart");
+const context = canvas.getContext("2d");
+const tooltip = document.getElementById("tooltip");
+const margin = 40;
+const maxVolume = Math.max(...DATA.map(it => it[0]), 1);
+let view = [0, maxVolume];
+let hovered = null;
+let drag = null;
+
+function toX(volume) {
+    return margin + (volume - view[0]) / (view[1] - view[0]) * (canvas.width - 2 * margin);
+}
+
+function toY(ph) {
+    return canvas.height - margin - ph / 14 * (canvas.height - 2 * margin);
+}
+
+function toVolume(x) {
+    return view[0] + (x - margin) / (canvas.width - 2 * margin) * (view[1] - view[0]);
+}
+
+function formatNumber(value, autoDecimals) {
+    let label;
+    switch (FORMAT.notation) {
+        case "fixed": label = value.toFixed(FORMAT.digits); break;
+        case "significant": label = value.toPrecision(Math.max(FORMAT.digits, 1)); break;
+        case "scientific": label = value.toExponential(FORMAT.digits); break;
+        default: label = value.toFixed(autoDecimals);
+    }
+    return label.replace(".", FORMAT.separator);
+}
+
+function tickLabel(axis, value, autoDecimals) {
+    const label = axis.decimals === null
+        ? formatNumber(value, autoDecimals)
+        : value.toFixed(axis.decimals).replace(".", FORMAT.separator);
+    return axis.onTicks && axis.unit ? `${label} ${axis.unit}` : label;
+}
+
+function draw() {
+    context.clearRect(0, 0, canvas.width, canvas.height);
+    context.strokeStyle = COLORS.grid;
+    context.fillStyle = COLORS.text;
+    context.font = "12px sans-serif";
+    for (let ph = 0; ph <= 14; ph += 2) {
+        context.beginPath();
+        context.moveTo(margin, toY(ph));
+        context.lineTo(canvas.width - margin, toY(ph));
+        context.stroke();
+        context.fillText(tickLabel(AXES.y, ph, 0), 4, toY(ph) + 4);
+    }
+    for (let step = 0; step <= 5; step++) {
+        const volume = view[0] + (view[1] - view[0]) * step / 5;
+        context.fillText(tickLabel(AXES.x, volume, 2), toX(volume) - 12, canvas.height - margin + 16);
+    }
+    if (!AXES.x.onTicks && AXES.x.unit) {
+        context.fillText(AXES.x.unit, canvas.width - margin + 4, canvas.height - margin + 16);
+    }
+    if (!AXES.y.onTicks && AXES.y.unit) {
+        context.fillText(AXES.y.unit, 4, margin - 4);
+    }
+    context.fillText(LABELS.x, canvas.width / 2 - 40, canvas.height - 6);
+    context.save();
+    context.translate(12, canvas.height / 2);
+    context.rotate(-Math.PI / 2);
+    context.textAlign = "center";
+    context.fillText(LABELS.y, 0, 0);
+    context.restore();
+    context.save();
+    context.beginPath();
+    context.rect(margin, margin, canvas.width - 2 * margin, canvas.height - 2 * margin);
+    context.clip();
+    context.strokeStyle = COLORS.curve;
+    context.beginPath();
+    DATA.forEach(([volume, ph], index) => {
+        if (index === 0) context.moveTo(toX(volume), toY(ph));
+        else context.lineTo(toX(volume), toY(ph));
+    });
+    context.stroke();
+    context.fillStyle = COLORS.curve;
+    for (const [volume, ph] of DATA) {
+        context.beginPath();
+        context.arc(toX(volume), toY(ph), hovered && hovered[0] === volume ? 5 : 2, 0, 2 * Math.PI);
+        context.fill();
+    }
+    context.restore();
+}
+
+canvas.addEventListener("mousemove", event => {
+    if (drag !== null) {
+        const shift = toVolume(drag) - toVolume(event.offsetX);
+        view = [view[0] + shift, view[1] + shift];
+        drag = event.offsetX;
+    }
+    const volume = toVolume(event.offsetX);
+    hovered = DATA.reduce((best, it) =>
+        best === null || Math.abs(it[0] - volume) < Math.abs(best[0] - volume) ? it : best, null);
+    if (hovered) {
+        const volume = formatNumber(hovered[0], 3);
+        tooltip.textContent = `${LABELS.x} = ${volume}, pH = ${formatNumber(hovered[1], 3)}`;
+        tooltip.style.left = `${event.pageX + 12}px`;
+        tooltip.style.top = `${event.pageY + 12}px`;
+        tooltip.hidden = false;
+    }
+    draw();
+});
+canvas.addEventListener("mouseleave", () => {
+    hovered = null;
+    drag = null;
+    tooltip.hidden = true;
+    draw();
+});
+canvas.addEventListener("mousedown", event => drag = event.offsetX);
+canvas.addEventListener("mouseup", () => drag = null);
+canvas.addEventListener("wheel", event => {
+    event.preventDefault();
+    const center = toVolume(event.offsetX);
+    const factor = event.deltaY < 0 ? 0.8 : 1.25;
+    view = [center - (center - view[0]) * factor, center + (view[1] - center) * factor];
+    draw();
+});
+canvas.addEventListener("dblclick", () => {
+    view = [0, maxVolume];
+    draw();
+});
+draw();
+"#;
+
+/// Writes an HTML page with an interactive chart of the output.
+///
+/// The page is self-contained, so it can be opened without the app or an internet connection.
+pub fn interactive_plot(title: &str, output: &Output, options: &Options) -> String {
+    let mut data = String::new();
+    for item in output.items.iter().filter(|it| it.ph.is_finite()) {
+        if !data.is_empty() {
+            data.push(',');
+        }
+        _ = write!(data, "[{},{}]", item.m_v, item.ph);
+    }
+    let (background, text, grid, curve) = if options.dark {
+        ("#1e1e1e", "#e0e0e0", "#444444", "#6cb4ff")
+    } else {
+        ("#ffffff", "#202020", "#dddddd", "#1f5fbf")
+    };
+    let title = escape_html(title);
+    let hint = escape_html(HTML_HINT.get());
+    let x_label = escape_script(&x_title(
+        options,
+        Some(XQuantity::Volume),
+        output.input.titrant,
+    ));
+    let y_label = escape_script(if options.y_axis.title.is_empty() {
+        "pH"
+    } else {
+        &options.y_axis.title
+    });
+    let x_axis = axis_object(&options.x_axis);
+    let y_axis = axis_object(&options.y_axis);
+    let number_format = format_object(options.number_format);
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+body {{ background: {background}; color: {text}; font-family: sans-serif; }}
+#tooltip {{ position: absolute; padding: 4px; background: {background}; border: 1px solid {grid}; font-size: 12px; pointer-events: none; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+<p>{hint}</p>
+<canvas id="chart" width="800" height="500"></canvas>
+<div id="tooltip" hidden></div>
+<script>
+const DATA = [{data}];
+const LABELS = {{ x: "{x_label}", y: "{y_label}" }};
+const AXES = {{ x: {x_axis}, y: {y_axis} }};
+const FORMAT = {number_format};
+const COLORS = {{ text: "{text}", grid: "{grid}", curve: "{curve}" }};
+{SCRIPT}
+</script>
+</body>
+</html>
+"#
+    )
+}
+
+/// Writes the tick label format of an axis as a JavaScript object.
+fn axis_object(format: &AxisFormat) -> String {
+    let decimals = format
+        .decimals
+        .map_or_else(|| "null".into(), |it| it.to_string());
+    format!(
+        r#"{{ decimals: {decimals}, unit: "{}", onTicks: {} }}"#,
+        escape_script(&format.unit),
+        format.unit_on_ticks
+    )
+}
+
+/// Writes the number format as a JavaScript object.
+fn format_object(format: NumberFormat) -> String {
+    let notation = match format.notation {
+        Notation::Auto => "auto",
+        Notation::Fixed => "fixed",
+        Notation::Significant => "significant",
+        Notation::Scientific => "scientific",
+    };
+    format!(
+        r#"{{ notation: "{notation}", digits: {}, separator: "{}" }}"#,
+        format.digits,
+        number_locale().decimal_separator()
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Escapes text for a string literal inside a script element.
+fn escape_script(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace("</", "<\\/")
+}
@@ -0,0 +1,40 @@
+use iced::{
+    event,
+    keyboard::{self, KeyCode, Modifiers},
+    subscription, Event, Subscription,
+};
+
+use super::Message;
+
+/// Subscribes to the key presses handled by the app.
+///
+/// Key presses captured by a widget (e.g. a text input) are ignored.
+pub fn key_presses() -> Subscription<Message> {
+    subscription::events_with(|event, status| {
+        if status == event::Status::Captured {
+            return None;
+        }
+        let Event::Keyboard(keyboard::Event::KeyPressed {
+            key_code,
+            modifiers,
+        }) = event
+        else {
+            return None;
+        };
+        key_message(key_code, modifiers)
+    })
+}
+
+fn key_message(key_code: KeyCode, modifiers: Modifiers) -> Option<Message> {
+    match key_code {
+        KeyCode::D if modifiers.control() && modifiers.shift() => Some(Message::ToggleDebug),
+        // Network shares may not report changes, so a reload can be forced like in a browser.
+        KeyCode::F5 => Some(Message::ReloadNow),
+        KeyCode::F11 => Some(Message::TogglePresentation),
+        KeyCode::Escape => Some(Message::EndPresentation),
+        // Presentation remotes send page keys.
+        KeyCode::Right | KeyCode::Space | KeyCode::PageDown => Some(Message::RevealNext),
+        KeyCode::Left | KeyCode::PageUp => Some(Message::RevealPrevious),
+        _ => None,
+    }
+}
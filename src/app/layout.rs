@@ -0,0 +1,135 @@
+/// Width of the diagram shown in the app
+pub const DEFAULT_FRAME_WIDTH: f32 = 400.0;
+/// Height of the diagram in every size, so texts and margins scale with the exported size
+pub const DEFAULT_FRAME_HEIGHT: f32 = 300.0;
+/// Space between the frame and the plot area, which holds the axis labels
+pub const DEFAULT_MARGIN: f32 = 20.0;
+/// Space left of the margin, which holds the rotated title of the y-axis
+pub const DEFAULT_Y_TITLE_WIDTH: f32 = 12.0;
+
+/// The geometry of a diagram.
+///
+/// The frame is split into the plot area, the margins around it, the title of the y-axis to the
+/// left of it and the parts drawn to the right of it, like the ladder diagram. Values are mapped
+/// into the plot area with the ranges of the axes, which start at zero.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DiagramLayout {
+    /// The width of the whole diagram in SVG units.
+    pub frame_width: f32,
+    /// The height of the whole diagram in SVG units.
+    pub frame_height: f32,
+    pub margin: f32,
+    /// The width left of the margin holding the title of the y-axis.
+    pub y_title_width: f32,
+    /// The width of the parts drawn to the right of the plot area.
+    pub side_width: f32,
+    /// The value at the right end of the x-axis.
+    pub x_max: f32,
+    /// The value at the top of the y-axis.
+    pub y_max: f32,
+    /// The factor converting the x values of a series into values of the x-axis, e.g. added
+    /// volumes into the titration degree.
+    pub x_factor: f32,
+}
+
+impl DiagramLayout {
+    /// Creates the layout of a frame with the default margin and unit ranges.
+    ///
+    /// The parts next to the plot area are taken from the width of the frame.
+    pub fn new(frame_width: f32, frame_height: f32, side_width: f32) -> Self {
+        Self {
+            frame_width,
+            frame_height,
+            margin: DEFAULT_MARGIN,
+            y_title_width: DEFAULT_Y_TITLE_WIDTH,
+            side_width,
+            x_max: 1.0,
+            y_max: 1.0,
+            x_factor: 1.0,
+        }
+    }
+
+    /// Creates the layout of a frame whose plot area has the default size of the app.
+    ///
+    /// The frame grows by the parts next to the plot area instead.
+    pub fn with_default_plot_area(side_width: f32) -> Self {
+        Self::new(
+            DEFAULT_FRAME_WIDTH + side_width,
+            DEFAULT_FRAME_HEIGHT,
+            side_width,
+        )
+    }
+
+    /// Returns the layout with another range of the x-axis.
+    pub fn with_x_max(self, x_max: f32) -> Self {
+        Self { x_max, ..self }
+    }
+
+    /// Returns the layout with another conversion of the x values of a series.
+    pub fn with_x_factor(self, x_factor: f32) -> Self {
+        Self { x_factor, ..self }
+    }
+
+    /// Returns the layout with another range of the y-axis, e.g. for a second y-axis.
+    pub fn with_y_max(self, y_max: f32) -> Self {
+        Self { y_max, ..self }
+    }
+
+    pub fn left(&self) -> f32 {
+        self.y_title_width + self.margin
+    }
+
+    pub fn right(&self) -> f32 {
+        self.frame_width - self.margin - self.side_width
+    }
+
+    pub fn top(&self) -> f32 {
+        self.margin
+    }
+
+    pub fn bottom(&self) -> f32 {
+        self.frame_height - self.margin
+    }
+
+    /// Returns the width of the plot area.
+    pub fn width(&self) -> f32 {
+        self.right() - self.left()
+    }
+
+    /// Returns the height of the plot area.
+    pub fn height(&self) -> f32 {
+        self.bottom() - self.top()
+    }
+
+    /// Returns the position of the parts drawn to the right of the plot area.
+    pub fn side_left(&self) -> f32 {
+        self.right() + self.margin
+    }
+
+    /// Returns the horizontal distance of one unit on the x-axis.
+    pub fn scale_x(&self) -> f32 {
+        self.width() / self.x_max
+    }
+
+    /// Returns the vertical distance of one unit on the y-axis.
+    pub fn scale_y(&self) -> f32 {
+        self.height() / self.y_max
+    }
+
+    /// Maps an x value of a series to its horizontal position.
+    pub fn x(&self, value: f32) -> f32 {
+        self.left() + value * self.x_factor * self.scale_x()
+    }
+
+    /// Maps a value on the y-axis to its vertical position.
+    pub fn y(&self, value: f32) -> f32 {
+        self.bottom() - value * self.scale_y()
+    }
+
+    /// Maps a position back to the x value of a series, if it is inside the plot area.
+    pub fn x_value_at(&self, x: f32, y: f32) -> Option<f32> {
+        let inside =
+            (self.left()..=self.right()).contains(&x) && (self.top()..=self.bottom()).contains(&y);
+        inside.then(|| (x - self.left()) / self.scale_x() / self.x_factor)
+    }
+}
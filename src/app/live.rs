@@ -0,0 +1,140 @@
+use iced::{
+    widget::{button, column, row, text},
+    Alignment, Element,
+};
+
+use crate::worker::live::LiveConfig;
+
+use super::{
+    form::{field_row, FormField, TextFields},
+    indicator_swatch,
+    strings::*,
+    Message,
+};
+
+/// A field of the form of the live input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LiveField {
+    Port,
+    BaudRate,
+    /// The reading of the burette, which the next pH values are plotted against.
+    Volume,
+}
+
+impl FormField for LiveField {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// The state of a titration measured with a pH meter.
+///
+/// Every reading replaces the pH at the volume entered last, so the curve follows the meter while
+/// the pH settles, and entering a new volume starts the next point.
+pub struct LiveForm {
+    fields: TextFields<LiveField>,
+    /// The recorded volumes in mL with the pH and the temperature in °C, which is NaN if the
+    /// meter sends none.
+    points: Vec<(f32, f32, f32)>,
+    /// The last pH value sent by the meter.
+    reading: Option<f32>,
+    /// Whether the connection was requested and did not fail since.
+    pub connected: bool,
+}
+
+impl Default for LiveForm {
+    fn default() -> Self {
+        Self {
+            fields: TextFields::new(&["", "9600", "0"]),
+            points: Vec::new(),
+            reading: None,
+            connected: false,
+        }
+    }
+}
+
+impl LiveForm {
+    pub fn set(&mut self, field: LiveField, value: String) {
+        self.fields.set(field, value);
+    }
+
+    /// Returns the settings of the serial port, if all fields are valid.
+    pub fn config(&self) -> Option<LiveConfig> {
+        let port = self.fields.get(LiveField::Port).trim();
+        if port.is_empty() {
+            return None;
+        }
+        Some(LiveConfig {
+            port: port.to_owned(),
+            baud_rate: self
+                .fields
+                .get(LiveField::BaudRate)
+                .trim()
+                .parse()
+                .ok()
+                .filter(|it| *it > 0)?,
+        })
+    }
+
+    pub fn points(&self) -> &[(f32, f32, f32)] {
+        &self.points
+    }
+
+    /// Records a pH reading at the entered volume with the temperature in °C the meter sent.
+    ///
+    /// Returns whether the reading was recorded, which requires a valid volume.
+    pub fn record(&mut self, ph: f32, temperature: Option<f32>) -> bool {
+        self.reading = Some(ph);
+        let volume = self.fields.number(LiveField::Volume);
+        let Some(volume) = volume.filter(|it| *it >= 0.0) else {
+            return false;
+        };
+        let temperature = temperature.unwrap_or(f32::NAN);
+        match self.points.iter_mut().find(|(m_v, _, _)| *m_v == volume) {
+            Some(point) => *point = (volume, ph, temperature),
+            None => {
+                self.points.push((volume, ph, temperature));
+                self.points.sort_by(|a, b| a.0.total_cmp(&b.0));
+            }
+        }
+        true
+    }
+
+    pub fn clear(&mut self) {
+        self.points.clear();
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let field_row =
+            |label, field| field_row(label, &self.fields, field, Message::LiveInput, 120.0);
+        let connect_button = if self.connected {
+            button(BUTTON_DISCONNECT.get()).on_press(Message::Disconnect)
+        } else {
+            let mut connect_button = button(BUTTON_CONNECT.get());
+            if self.config().is_some() {
+                connect_button = connect_button.on_press(Message::Connect);
+            }
+            connect_button
+        };
+        let mut status = row![].spacing(10).align_items(Alignment::Center);
+        if let Some(ph) = self.reading {
+            status = status
+                .push(indicator_swatch(ph, 20.0, 30.0))
+                .push(text(format!("pH = {ph:.2}")));
+        }
+        column![
+            text(LIVE_TITLE).size(20),
+            field_row(LIVE_PORT, LiveField::Port),
+            field_row(LIVE_BAUD_RATE, LiveField::BaudRate),
+            field_row(LIVE_VOLUME, LiveField::Volume),
+            row![
+                connect_button,
+                button(BUTTON_LIVE_CLEAR.get()).on_press(Message::ClearLive),
+            ]
+            .spacing(5),
+            status.push(text(format!("{LIVE_POINTS}: {}", self.points.len())).size(12)),
+        ]
+        .spacing(5)
+        .into()
+    }
+}
@@ -1,5 +1,136 @@
-#[derive(Default)]
+#[cfg(feature = "live")]
+use crate::worker::calibration::Calibration;
+use crate::worker::{
+    analysis::Stoichiometry,
+    complexometry::BufferPh,
+    explain::Rounding,
+    format::NumberFormat,
+    units::XQuantity,
+    watch::{Debounce, EventFilter},
+};
+
+use super::{
+    annotation::Annotation,
+    diagram::{AxisFormat, ExportSize, LegendPosition, Plot},
+    style::DiagramStyle,
+};
+
+#[derive(Clone, Default, PartialEq)]
 pub struct Options {
     pub dark: bool,
+    /// Follows the light or dark theme of the operating system, which sets `dark`.
+    pub system_theme: bool,
     pub colored: bool,
+    /// Draws all loaded files into one diagram.
+    pub overlay: bool,
+    /// Shows a second file next to the selected one, with the same axes.
+    pub compare: bool,
+    /// Draws a ladder diagram next to the graph.
+    pub ladder: bool,
+    /// Connects the points with a smooth curve instead of straight lines.
+    pub smooth: bool,
+    /// Draws the titration curve on a canvas with guide lines reading the pH and the volume
+    /// under the cursor.
+    pub crosshair: bool,
+    /// Sorts the points by volume and averages the points at the same volume, so the curve does
+    /// not zig-zag.
+    pub sort_volumes: bool,
+    /// The number of points of a curve drawn at most, beyond which it is decimated.
+    pub max_points: Option<u32>,
+    /// Shows the calculation of a single data point step by step.
+    pub explain: bool,
+    /// Compares the used measuring solution with the needed amount for all loaded files.
+    pub usage: bool,
+    /// Fits the model of a monoprotic acid to the curve and draws the fitted curve.
+    pub fit: bool,
+    /// Draws the curve calculated with activities instead of concentrations for comparison.
+    pub activity: bool,
+    /// The working temperature in °C the files are calculated at, if it differs from the
+    /// standard temperature.
+    pub temperature: Option<f32>,
+    /// The index of the model in [`MODELS`](crate::worker::model::MODELS) the files are
+    /// calculated with.
+    pub model: usize,
+    /// Shows the concentration determined from the equivalence point next to the graph.
+    pub summary: bool,
+    /// The ratio of analyte and measuring solution used by the result summary, or `None` for the
+    /// ratio of the file.
+    pub stoichiometry: Option<Stoichiometry>,
+    /// The index of the indicator whose titration error is shown in the result summary.
+    pub indicator: Option<usize>,
+    /// Shows the problem generator instead of the diagram.
+    pub generator: bool,
+    /// Shows a titration simulated from its parameters.
+    pub simulation: bool,
+    /// Shows the virtual burette, which adds the measuring solution by hand.
+    pub burette: bool,
+    /// Shows the live input, which plots the readings of a pH meter.
+    #[cfg(feature = "live")]
+    pub live: bool,
+    /// Shows the calibration of the electrode.
+    #[cfg(feature = "live")]
+    pub calibrating: bool,
+    /// Converts the readings of the meter from mV into the pH, restored with the session.
+    #[cfg(feature = "live")]
+    pub calibration: Option<Calibration>,
+    /// Shows the calculator for diluting a stock solution.
+    pub calculator: bool,
+    /// Flags suspicious points, which are excluded from the curve with a click.
+    pub outliers: bool,
+    /// Reveals the curve point by point with the playback controls.
+    pub playback: bool,
+    /// Shows the editor of the data points of the selected file.
+    pub data_editor: bool,
+    /// Shows the editor of the annotations.
+    pub annotating: bool,
+    /// The annotations drawn into the diagram, restored with the session.
+    pub annotations: Vec<Annotation>,
+    /// Shows the editor of the diagram style.
+    pub style_editor: bool,
+    /// The colors, sizes and grid of the diagram.
+    pub style: DiagramStyle,
+    /// The size of exported diagrams.
+    pub export_size: ExportSize,
+    /// The corner the legend of the series is drawn in, if it is shown.
+    pub legend: LegendPosition,
+    /// Names the series in the legend after their files instead of their worksheets.
+    pub legend_file_names: bool,
+    /// Reveals the curve region by region for presentations.
+    pub projector: bool,
+    /// Shows the loaded files one after another in presentation mode.
+    pub presentation_cycle: bool,
+    /// The index of the titrated acid in the conductivity plot.
+    pub conductivity_acid: usize,
+    /// The index of the added base in the conductivity plot.
+    pub conductivity_base: usize,
+    /// The index of the precipitating salt in the precipitation plot.
+    pub precipitation_salt: usize,
+    /// Plots the concentration of the anion instead of the cation in the precipitation plot.
+    pub precipitation_anion: bool,
+    /// The index of the metal ion in the complexometry plot.
+    pub complexometry_metal: usize,
+    /// The pH of the solution in the complexometry plot.
+    pub buffer_ph: BufferPh,
+    /// The plot drawn against a second y-axis over the pH curve, e.g. the derivative.
+    pub secondary_plot: Option<Plot>,
+    /// The quantity of the added measuring solution on the x-axis.
+    pub x_quantity: XQuantity,
+    /// The tick labels of the volume axis.
+    pub x_axis: AxisFormat,
+    /// The tick labels of the pH or derivative axis.
+    pub y_axis: AxisFormat,
+    /// The rounding rules of reported results.
+    pub rounding: Rounding,
+    /// The format of the values on the axes, in readouts and in exported tables.
+    pub number_format: NumberFormat,
+    /// Decides which file changes cause a reload.
+    pub event_filter: EventFilter,
+    /// Coalesces bursts of file changes into a single reload.
+    pub debounce: Debounce,
+    /// Reads only the rows appended to CSV files, for data loggers writing during a titration.
+    pub tail: bool,
+    /// Hides the control sidebar, so the diagram can use the full window.
+    pub sidebar_collapsed: bool,
+    /// The width of the control sidebar in px, if it was resized by dragging its edge.
+    pub sidebar_width: Option<f32>,
 }
@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+/// The time between two data points revealed while playing.
+pub const PLAYBACK_INTERVAL: Duration = Duration::from_millis(400);
+
+/// The state of the playback revealing the curve point by point.
+#[derive(Debug)]
+pub struct Playback {
+    /// The number of revealed data points, at least one.
+    pub shown: usize,
+    pub playing: bool,
+}
+
+impl Default for Playback {
+    fn default() -> Self {
+        Self {
+            shown: 1,
+            playing: false,
+        }
+    }
+}
+
+impl Playback {
+    /// Reveals the next of the given number of data points.
+    ///
+    /// Playing stops after the last one.
+    pub fn tick(&mut self, count: usize) {
+        if self.shown < count {
+            self.shown += 1;
+        } else {
+            self.playing = false;
+        }
+    }
+
+    /// Starts or stops playing.
+    ///
+    /// Playing again after the last of the given number of data points starts from the first.
+    pub fn toggle(&mut self, count: usize) {
+        if !self.playing && self.shown >= count {
+            self.shown = 1;
+        }
+        self.playing = !self.playing;
+    }
+
+    /// Shows the given number of data points, which stops playing.
+    pub fn seek(&mut self, shown: usize, count: usize) {
+        self.shown = shown.clamp(1, count.max(1));
+        self.playing = false;
+    }
+}
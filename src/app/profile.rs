@@ -0,0 +1,163 @@
+use std::fs;
+
+use tracing::warn;
+
+use crate::util::config_dir;
+
+use super::{diagram::Plot, strings::*, Message};
+
+/// The file in the config directory storing the selected profile.
+const PROFILE_FILE_NAME: &str = "profile";
+
+/// A part of the UI which is shown or hidden by the profile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Feature {
+    /// The step by step explanation of data points.
+    Explain,
+    /// The problem generator with its answer keys.
+    Generator,
+    /// The export of worked solutions.
+    Solution,
+    /// The region by region reveal and the playback for presentations.
+    Projector,
+    Simulation,
+    /// The Gran plot with its regression lines.
+    Fitting,
+    /// The comparison of the used measuring solution.
+    Usage,
+    /// The PDF report with the hash of its dataset.
+    Report,
+    /// The rounding rules of reported results.
+    Rounding,
+    /// The log panel, the debug overlay and the settings of the file watcher.
+    Diagnostics,
+    Tutorial,
+}
+
+impl Feature {
+    /// Returns the feature a message belongs to, if any.
+    ///
+    /// Messages of features hidden by the profile are ignored, so they can not be reached
+    /// through keyboard shortcuts either.
+    pub fn of(message: &Message) -> Option<Self> {
+        Some(match message {
+            Message::SetExplain(_) | Message::ExplainPoint(_) => Self::Explain,
+            Message::SetGenerator(_)
+            | Message::GeneratorInput(..)
+            | Message::ExportProblem
+            | Message::ExportAnswerKey => Self::Generator,
+            Message::ExportSolution => Self::Solution,
+            Message::SetProjector(_)
+            | Message::RevealNext
+            | Message::RevealPrevious
+            | Message::SetPlayback(_)
+            | Message::TogglePlaying
+            | Message::SeekPlayback(_)
+            | Message::PlaybackTick => Self::Projector,
+            Message::SetSimulation(_)
+            | Message::SimulationInput(..)
+            | Message::SetBurette(_)
+            | Message::BuretteAdd(_)
+            | Message::BuretteUndo
+            | Message::BuretteReset => Self::Simulation,
+            Message::SelectPlot(Plot::Gran) | Message::SetFit(_) => Self::Fitting,
+            Message::SetUsage(_) => Self::Usage,
+            Message::ExportReport => Self::Report,
+            Message::RoundingVolumeInput(_) | Message::RoundingDigitsInput(_) => Self::Rounding,
+            Message::SetLogPanel(_)
+            | Message::ToggleDebug
+            | Message::SetEventFilter(_)
+            | Message::SetDebounce(_) => Self::Diagnostics,
+            Message::StartTutorial | Message::NextTutorialStep => Self::Tutorial,
+            _ => return None,
+        })
+    }
+}
+
+/// A preset of the features shown to an audience.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Profile {
+    /// Shows every feature.
+    #[default]
+    Teacher,
+    /// Hides answer keys, fitting internals and diagnostics.
+    Student,
+    /// Hides teaching aids and shows the reporting features of quality control.
+    QualityControl,
+}
+
+impl Profile {
+    pub const ALL: [Self; 3] = [Self::Teacher, Self::Student, Self::QualityControl];
+
+    /// Checks if the profile shows a feature.
+    pub fn allows(self, feature: Feature) -> bool {
+        match self {
+            Self::Teacher => true,
+            Self::Student => !matches!(
+                feature,
+                Feature::Generator
+                    | Feature::Solution
+                    | Feature::Fitting
+                    | Feature::Report
+                    | Feature::Rounding
+                    | Feature::Diagnostics
+            ),
+            Self::QualityControl => !matches!(
+                feature,
+                Feature::Explain
+                    | Feature::Generator
+                    | Feature::Solution
+                    | Feature::Projector
+                    | Feature::Simulation
+                    | Feature::Tutorial
+            ),
+        }
+    }
+
+    pub fn label(self) -> Localized {
+        match self {
+            Self::Teacher => PROFILE_TEACHER,
+            Self::Student => PROFILE_STUDENT,
+            Self::QualityControl => PROFILE_QUALITY_CONTROL,
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Self::Teacher => "teacher",
+            Self::Student => "student",
+            Self::QualityControl => "qc",
+        }
+    }
+}
+
+/// Loads the profile stored in the config directory.
+///
+/// Without a stored profile, every feature is shown.
+pub fn load() -> Profile {
+    let Some(content) =
+        config_dir().and_then(|it| fs::read_to_string(it.join(PROFILE_FILE_NAME)).ok())
+    else {
+        return Profile::default();
+    };
+    let key = content.trim();
+    Profile::ALL
+        .into_iter()
+        .find(|it| it.key() == key)
+        .unwrap_or_else(|| {
+            warn!("Unknown profile {key:?}, showing every feature");
+            Profile::default()
+        })
+}
+
+/// Stores the profile in the config directory.
+pub fn save(profile: Profile) {
+    let Some(dir) = config_dir() else {
+        return;
+    };
+    if let Err(err) = fs::create_dir_all(&dir)
+        .and_then(|()| fs::write(dir.join(PROFILE_FILE_NAME), profile.key()))
+    {
+        warn!("Could not store the profile: {err}");
+    }
+}
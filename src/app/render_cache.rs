@@ -0,0 +1,101 @@
+use std::{cell::RefCell, sync::Arc};
+
+use iced::widget::svg::Handle;
+
+use crate::worker::{
+    locale::{number_locale, NumberLocale},
+    Output,
+};
+
+use super::{
+    diagram::GraphState,
+    options::Options,
+    strings::{language, Language},
+};
+
+/// The number of diagrams kept, which covers the two diagrams of the comparison.
+const MAX_ENTRIES: usize = 2;
+
+/// A rendered diagram with the inputs it was rendered from.
+///
+/// The language and the number locale are part of the inputs, as they change the titles and the
+/// labels of the axes without changing the options.
+struct Entry {
+    language: Language,
+    locale: NumberLocale,
+    options: Options,
+    outputs: Vec<Arc<Output>>,
+    names: Vec<String>,
+    state: GraphState,
+    handle: Handle,
+}
+
+impl Entry {
+    fn matches(
+        &self,
+        options: &Options,
+        outputs: &[&Arc<Output>],
+        names: &[String],
+        state: GraphState,
+    ) -> bool {
+        self.language == language()
+            && self.locale == number_locale()
+            && self.state == state
+            && self.names == names
+            && self.outputs.len() == outputs.len()
+            && self
+                .outputs
+                .iter()
+                .zip(outputs)
+                .all(|(a, b)| Arc::ptr_eq(a, b))
+            && self.options == *options
+    }
+}
+
+/// The recently rendered diagrams, so the view only renders a diagram again if it changed.
+///
+/// The view is created after every message, e.g. on every move of the cursor, while rendering
+/// large curves takes long. The outputs are compared by identity, as every reload creates a new
+/// output.
+#[derive(Default)]
+pub struct RenderCache {
+    entries: RefCell<Vec<Entry>>,
+}
+
+impl RenderCache {
+    /// Returns the handle of a diagram, which is only rendered if it is not cached.
+    pub fn get(
+        &self,
+        options: &Options,
+        outputs: &[&Arc<Output>],
+        names: &[String],
+        state: GraphState,
+        render: impl FnOnce() -> String,
+    ) -> Handle {
+        let mut entries = self.entries.borrow_mut();
+        if let Some(index) = entries
+            .iter()
+            .position(|it| it.matches(options, outputs, names, state))
+        {
+            // The most recently used diagram is kept at the end.
+            let entry = entries.remove(index);
+            let handle = entry.handle.clone();
+            entries.push(entry);
+            return handle;
+        }
+        let handle = Handle::from_memory(render().into_bytes());
+        if entries.len() == MAX_ENTRIES {
+            entries.remove(0);
+        }
+        entries.push(Entry {
+            language: language(),
+            locale: number_locale(),
+            options: options.clone(),
+            outputs: outputs.iter().map(|it| Arc::clone(it)).collect(),
+            names: names.to_vec(),
+            state,
+            handle: handle.clone(),
+        });
+        handle
+    }
+}
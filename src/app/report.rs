@@ -0,0 +1,243 @@
+use std::fmt::Write;
+
+use sha2::{Digest, Sha256};
+
+use crate::worker::{analysis, explain::Rounding, Output};
+
+use self::encryption::{hex, Encryption};
+
+use super::strings::*;
+
+mod encryption;
+#[cfg(test)]
+mod tests;
+
+/// The width of an A4 page in points.
+const PAGE_WIDTH: f32 = 595.0;
+/// The height of an A4 page in points.
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 56.0;
+const FONT_SIZE: f32 = 10.0;
+const LINE_HEIGHT: f32 = 13.0;
+/// The number of lines fitting on a page above the footer.
+const LINES_PER_PAGE: usize = ((PAGE_HEIGHT - 3.0 * MARGIN) / LINE_HEIGHT) as usize;
+
+/// Writes a locked PDF report of a titration with the SHA-256 hash of its data.
+///
+/// The hash is taken over the given values, the results and the table exactly as they are
+/// printed, every line followed by a line feed. So it certifies the printed data, including
+/// edited, excluded and sorted points. It is printed on every page and stored in the document
+/// information, so reviewers can verify the report against its data.
+///
+/// The document opens without a password, but is encrypted with a random owner password, so
+/// viewers only permit printing it and copying its text. The permissions are enforced by the
+/// viewers, not by the encryption, so the hash remains the proof of the data.
+pub fn locked_report(title: &str, output: &Output, rounding: Rounding) -> Vec<u8> {
+    let data = report_data(output, rounding);
+    let mut hasher = Sha256::new();
+    for line in &data {
+        hasher.update(line);
+        hasher.update("\n");
+    }
+    let hash = format!("{:x}", hasher.finalize());
+    let mut lines = vec![
+        format!("{REPORT_TITLE}: {title}"),
+        String::new(),
+        format!("{REPORT_HASH}:"),
+        hash.clone(),
+        String::new(),
+    ];
+    lines.extend(data);
+    let pages: Vec<_> = lines.chunks(LINES_PER_PAGE).collect();
+    let mut pdf = Pdf::new();
+    // AES-256 encryption is an extension of PDF 1.7, which became part of PDF 2.0.
+    pdf.object(
+        "<< /Type /Catalog /Pages 2 0 R \
+         /Extensions << /ADBE << /BaseVersion /1.7 /ExtensionLevel 8 >> >> >>",
+    );
+    let kids: String = (0..pages.len())
+        .map(|index| format!("{} 0 R ", 5 + 2 * index))
+        .collect();
+    pdf.object(&format!(
+        "<< /Type /Pages /Kids [{kids}] /Count {} >>",
+        pages.len()
+    ));
+    pdf.object("<< /Type /Font /Subtype /Type1 /BaseFont /Courier /Encoding /WinAnsiEncoding >>");
+    pdf.object(&format!(
+        "<< /Title {} /Subject {} /Producer {} /DatasetSHA256 {} >>",
+        pdf.string(&format!("{REPORT_TITLE}: {title}")),
+        pdf.string(&format!("{REPORT_HASH}: {hash}")),
+        pdf.string("titration-curve"),
+        pdf.string(&hash),
+    ));
+    for (index, page) in pages.iter().enumerate() {
+        let mut content = format!(
+            "BT /F1 {FONT_SIZE} Tf {LINE_HEIGHT} TL {MARGIN} {} Td\n",
+            PAGE_HEIGHT - MARGIN
+        );
+        for line in *page {
+            _ = writeln!(content, "{} '", string(line));
+        }
+        _ = writeln!(
+            content,
+            "ET BT /F1 8 Tf {MARGIN} {} Td {} Tj ET",
+            MARGIN / 2.0,
+            string(&format!(
+                "SHA-256 {hash} - {REPORT_PAGE} {}/{}",
+                index + 1,
+                pages.len()
+            ))
+        );
+        pdf.object(&format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] \
+             /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            6 + 2 * index
+        ));
+        pdf.stream(&content);
+    }
+    pdf.finish(&hash)
+}
+
+/// Returns the lines of the report covered by its hash: the given values, the results and the
+/// table.
+fn report_data(output: &Output, rounding: Rounding) -> Vec<String> {
+    let input = &output.input;
+    let usage = analysis::titrant_usage(output);
+    let mut lines = vec![
+        format!("{SOLUTION_GIVEN}:"),
+        format!("  {FIELD_TEST_VOLUME} = {} L", input.t_v),
+        format!(
+            "  {FIELD_TEST_CONCENTRATION} = {} mol/L",
+            rounding.value(input.t_c)
+        ),
+        format!(
+            "  {FIELD_MEASURING_CONCENTRATION} = {} mol/L",
+            rounding.value(input.m_c)
+        ),
+        String::new(),
+        format!("{REPORT_RESULTS}:"),
+        format!(
+            "  {GENERATOR_EQUIVALENCE_VOLUME} = {} mL",
+            rounding.volume(usage.equivalence_volume)
+        ),
+    ];
+    if let Some(endpoint) = usage.endpoint_volume {
+        lines.push(format!(
+            "  {USAGE_ENDPOINT} = {} mL",
+            rounding.volume(endpoint)
+        ));
+    }
+    lines.push(String::new());
+    lines.push(format!("{SOLUTION_TABLE}:"));
+    lines.push(format!(
+        "  {:>12} {:>12} {:>8} {:>8}",
+        "V(m) / mL", "V / mL", "pH", "pOH"
+    ));
+    for item in &output.items {
+        lines.push(format!(
+            "  {:>12} {:>12} {:>8.2} {:>8.2}",
+            rounding.volume(item.m_v),
+            rounding.volume(item.total_v),
+            item.ph,
+            item.poh
+        ));
+    }
+    lines
+}
+
+/// An encrypted PDF document written object by object.
+struct Pdf {
+    bytes: Vec<u8>,
+    offsets: Vec<usize>,
+    encryption: Encryption,
+}
+
+impl Pdf {
+    fn new() -> Self {
+        Self {
+            bytes: b"%PDF-1.7\n%\xe2\xe3\xcf\xd3\n".to_vec(),
+            offsets: Vec::new(),
+            encryption: Encryption::new(),
+        }
+    }
+
+    /// Appends the next object, which is numbered from 1.
+    fn object(&mut self, body: &str) {
+        self.append(encode(body).collect());
+    }
+
+    /// Appends the next object as an encrypted stream with the given content.
+    fn stream(&mut self, content: &str) {
+        let data = self
+            .encryption
+            .encrypt(&encode(content).collect::<Vec<_>>());
+        let mut body = format!("<< /Length {} >>\nstream\n", data.len()).into_bytes();
+        body.extend(data);
+        body.extend_from_slice(b"\nendstream");
+        self.append(body);
+    }
+
+    fn append(&mut self, body: Vec<u8>) {
+        self.offsets.push(self.bytes.len());
+        let number = self.offsets.len();
+        self.bytes
+            .extend(format!("{number} 0 obj\n").bytes().chain(body));
+        self.bytes.extend_from_slice(b"\nendobj\n");
+    }
+
+    /// Writes an encrypted string.
+    fn string(&self, text: &str) -> String {
+        let data = self.encryption.encrypt(&encode(text).collect::<Vec<_>>());
+        format!("<{}>", hex(&data))
+    }
+
+    /// Writes the encryption dictionary, the cross-reference table and the trailer.
+    ///
+    /// The first half of the hash becomes the document ID.
+    fn finish(mut self, hash: &str) -> Vec<u8> {
+        let dictionary = self.encryption.dictionary();
+        self.object(&dictionary);
+        let xref = self.bytes.len();
+        let count = self.offsets.len() + 1;
+        let mut trailer = format!("xref\n0 {count}\n0000000000 65535 f \n");
+        for offset in &self.offsets {
+            _ = writeln!(trailer, "{offset:010} 00000 n ");
+        }
+        let id = &hash[..32];
+        _ = write!(
+            trailer,
+            "trailer\n<< /Size {count} /Root 1 0 R /Info 4 0 R /Encrypt {} 0 R /ID [<{id}> <{id}>] >>\nstartxref\n{xref}\n%%EOF\n",
+            count - 1
+        );
+        self.bytes.extend_from_slice(trailer.as_bytes());
+        self.bytes
+    }
+}
+
+/// Writes a PDF string literal, escaping its delimiters.
+fn string(text: &str) -> String {
+    let mut literal = String::from("(");
+    for char in text.chars() {
+        if matches!(char, '(' | ')' | '\\') {
+            literal.push('\\');
+        }
+        literal.push(char);
+    }
+    literal.push(')');
+    literal
+}
+
+/// Encodes text in the Windows-1252 encoding of the standard fonts.
+///
+/// Characters without an equivalent, like subscript digits, are replaced.
+fn encode(text: &str) -> impl Iterator<Item = u8> + '_ {
+    text.chars().map(|char| match char {
+        '–' => 0x96,
+        '—' => 0x97,
+        '·' => 0xb7,
+        '−' => b'-',
+        '₀'..='₉' => b'0' + (char as u32 - '₀' as u32) as u8,
+        _ if (char as u32) < 0x80 || (0xa0..=0xff).contains(&(char as u32)) => char as u8,
+        _ => b'?',
+    })
+}
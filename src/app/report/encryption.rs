@@ -0,0 +1,156 @@
+//! The standard security handler of PDF 2.0 with AES-256 (revision 6).
+//!
+//! The user password is empty, so every viewer opens the document. The owner password is random
+//! and discarded, so the permissions can not be lifted with it.
+
+use std::{
+    collections::hash_map::RandomState,
+    fmt::Write,
+    hash::{BuildHasher, Hasher},
+};
+
+use aes::{
+    cipher::{
+        block_padding::{NoPadding, Pkcs7},
+        BlockEncrypt, BlockEncryptMut, KeyInit, KeyIvInit,
+    },
+    Aes128, Aes256,
+};
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+/// The permissions of the document: printing, also in high quality, and copying the text.
+///
+/// Modifying the document, its annotations and forms and assembling it are not permitted. The
+/// reserved bits are set as required.
+const PERMISSIONS: i32 = !0b0101_0010_1011;
+
+/// Encrypts the strings and streams of a document with a random key.
+pub struct Encryption {
+    key: [u8; 32],
+    owner: [u8; 48],
+    owner_key: [u8; 32],
+    user: [u8; 48],
+    user_key: [u8; 32],
+    perms: [u8; 16],
+}
+
+impl Encryption {
+    pub fn new() -> Self {
+        let key = random();
+        let owner_password: [u8; 32] = random();
+        let owner_password = hex(&owner_password);
+        let [validation_salt, key_salt] = [random::<8>(), random::<8>()];
+        let mut user = [0; 48];
+        user[..32].copy_from_slice(&hash(b"", &validation_salt, &[]));
+        user[32..40].copy_from_slice(&validation_salt);
+        user[40..].copy_from_slice(&key_salt);
+        let user_key = wrap(&hash(b"", &key_salt, &[]), &key);
+        let [validation_salt, key_salt] = [random::<8>(), random::<8>()];
+        let mut owner = [0; 48];
+        owner[..32].copy_from_slice(&hash(owner_password.as_bytes(), &validation_salt, &user));
+        owner[32..40].copy_from_slice(&validation_salt);
+        owner[40..].copy_from_slice(&key_salt);
+        let owner_key = wrap(&hash(owner_password.as_bytes(), &key_salt, &user), &key);
+        let mut perms = [0xff; 16];
+        perms[..4].copy_from_slice(&PERMISSIONS.to_le_bytes());
+        perms[8..12].copy_from_slice(b"Tadb");
+        perms[12..].copy_from_slice(&random::<4>());
+        Aes256::new(&key.into()).encrypt_block((&mut perms).into());
+        Self {
+            key,
+            owner,
+            owner_key,
+            user,
+            user_key,
+            perms,
+        }
+    }
+
+    /// Returns the encryption dictionary, which is itself not encrypted.
+    pub fn dictionary(&self) -> String {
+        format!(
+            "<< /Filter /Standard /V 5 /R 6 /Length 256 \
+             /CF << /StdCF << /AuthEvent /DocOpen /CFM /AESV3 /Length 32 >> >> \
+             /StmF /StdCF /StrF /StdCF /O <{}> /U <{}> /OE <{}> /UE <{}> /P {PERMISSIONS} \
+             /Perms <{}> >>",
+            hex(&self.owner),
+            hex(&self.user),
+            hex(&self.owner_key),
+            hex(&self.user_key),
+            hex(&self.perms)
+        )
+    }
+
+    /// Encrypts the data of a string or stream, preceded by the random initialization vector.
+    pub fn encrypt(&self, data: &[u8]) -> Vec<u8> {
+        let iv: [u8; 16] = random();
+        let encrypted = cbc::Encryptor::<Aes256>::new(&self.key.into(), &iv.into())
+            .encrypt_padded_vec_mut::<Pkcs7>(data);
+        iv.into_iter().chain(encrypted).collect()
+    }
+}
+
+/// Hashes a password with a salt and the user key data (algorithm 2.B of ISO 32000-2).
+///
+/// The hash is repeated at least 64 times with SHA-256, SHA-384 or SHA-512, chosen by the
+/// previous round, which makes guessing passwords slow.
+pub(super) fn hash(password: &[u8], salt: &[u8], user: &[u8]) -> [u8; 32] {
+    let mut hash = Sha256::new()
+        .chain_update(password)
+        .chain_update(salt)
+        .chain_update(user)
+        .finalize()
+        .to_vec();
+    let mut round = 0;
+    loop {
+        let block: Vec<u8> = [password, &hash, user].concat();
+        let mut data = block.repeat(64);
+        let length = data.len();
+        let encrypted = cbc::Encryptor::<Aes128>::new(hash[..16].into(), hash[16..32].into())
+            .encrypt_padded_mut::<NoPadding>(&mut data, length)
+            .expect("64 repetitions fill whole blocks");
+        // The first 16 bytes taken as a number modulo 3 equal the sum of the bytes modulo 3.
+        hash = match encrypted[..16].iter().map(|it| u32::from(*it)).sum::<u32>() % 3 {
+            0 => Sha256::digest(encrypted).to_vec(),
+            1 => Sha384::digest(encrypted).to_vec(),
+            _ => Sha512::digest(encrypted).to_vec(),
+        };
+        round += 1;
+        let last = u32::from(encrypted[length - 1]);
+        if round >= 64 && last + 32 <= round {
+            break;
+        }
+    }
+    hash[..32]
+        .try_into()
+        .expect("every hash has at least 32 bytes")
+}
+
+/// Encrypts the file key with a key derived from a password, without an initialization vector.
+fn wrap(key: &[u8; 32], file_key: &[u8; 32]) -> [u8; 32] {
+    let mut wrapped = *file_key;
+    cbc::Encryptor::<Aes256>::new(key.into(), &[0; 16].into())
+        .encrypt_padded_mut::<NoPadding>(&mut wrapped, 32)
+        .expect("the key fills whole blocks");
+    wrapped
+}
+
+/// Returns random bytes, taken from the random keys of the standard library's hash maps.
+fn random<const N: usize>() -> [u8; N] {
+    let mut bytes = [0; N];
+    for (index, chunk) in bytes.chunks_mut(8).enumerate() {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_usize(index);
+        let value = hasher.finish().to_le_bytes();
+        chunk.copy_from_slice(&value[..chunk.len()]);
+    }
+    bytes
+}
+
+/// Writes bytes as a hex string.
+pub(super) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, it| {
+        _ = write!(hex, "{it:02x}");
+        hex
+    })
+}
@@ -0,0 +1,185 @@
+//! The structure of the written PDF, which viewers rely on to find the objects.
+
+use aes::{
+    cipher::{
+        block_padding::{NoPadding, Pkcs7},
+        BlockDecrypt, BlockDecryptMut, KeyInit, KeyIvInit,
+    },
+    Aes256,
+};
+
+use crate::worker::{
+    analysis::Stoichiometry, explain::Rounding, model::StrongModel, uncertainty::Uncertainty, Input,
+};
+
+use super::{encryption::hash, *};
+
+/// Writes the report of a titration of 20 mL of 0.1 mol/L acid in the given number of steps.
+///
+/// Returns the report with the hash of its data.
+fn report(points: usize) -> (Vec<u8>, String) {
+    let output = Input {
+        t_v: 0.02,
+        t_c: 0.1,
+        m_c: 0.1,
+        m_v: (0..points).map(|it| it as f32 * 0.5).collect(),
+        temperature: 25.0,
+        temperatures: Vec::new(),
+        uncertainty: Uncertainty::default(),
+        stoichiometry: Stoichiometry::default(),
+        second_acid: None,
+        titrant: None,
+        analyte: None,
+        model: &StrongModel,
+    }
+    .calculate_output();
+    let data: String = report_data(&output, Rounding::default())
+        .iter()
+        .map(|it| format!("{it}\n"))
+        .collect();
+    (
+        locked_report("(sample)", &output, Rounding::default()),
+        format!("{:x}", Sha256::digest(data)),
+    )
+}
+
+fn find(bytes: &[u8], pattern: &str) -> Option<usize> {
+    bytes
+        .windows(pattern.len())
+        .position(|it| it == pattern.as_bytes())
+}
+
+/// Returns the text of the line starting at the offset.
+fn line_at(bytes: &[u8], offset: usize) -> &str {
+    let end = bytes[offset..]
+        .iter()
+        .position(|&it| it == b'\n')
+        .map_or(bytes.len(), |it| offset + it);
+    std::str::from_utf8(&bytes[offset..end]).unwrap()
+}
+
+/// Every entry of the cross-reference table points to the start of its object, and the trailer
+/// points to the table.
+fn check_structure(pdf: &[u8], hash: &str) {
+    assert!(pdf.starts_with(b"%PDF-1.7\n"));
+    assert!(pdf.ends_with(b"%%EOF\n"));
+    let startxref = find(pdf, "startxref\n").unwrap();
+    let xref: usize = line_at(pdf, startxref + "startxref\n".len())
+        .parse()
+        .unwrap();
+    assert_eq!(line_at(pdf, xref), "xref");
+    let count: usize = line_at(pdf, xref + "xref\n".len())
+        .strip_prefix("0 ")
+        .unwrap()
+        .parse()
+        .unwrap();
+    // Every entry is exactly 20 bytes long, including the line break.
+    let entries = xref + "xref\n".len() + format!("0 {count}\n").len();
+    assert_eq!(line_at(pdf, entries), "0000000000 65535 f ");
+    for number in 1..count {
+        let entry = line_at(pdf, entries + 20 * number);
+        let offset: usize = entry.strip_suffix(" 00000 n ").unwrap().parse().unwrap();
+        assert_eq!(line_at(pdf, offset), format!("{number} 0 obj"));
+    }
+    let trailer = entries + 20 * count;
+    assert_eq!(line_at(pdf, trailer), "trailer");
+    assert_eq!(
+        line_at(pdf, trailer + "trailer\n".len()),
+        format!(
+            "<< /Size {count} /Root 1 0 R /Info 4 0 R /Encrypt {} 0 R /ID [<{id}> <{id}>] >>",
+            count - 1,
+            id = &hash[..32]
+        )
+    );
+}
+
+/// Returns the data of every content stream, whose length matches the bytes between its
+/// keywords.
+fn streams(pdf: &[u8]) -> Vec<&[u8]> {
+    let mut streams = Vec::new();
+    let mut rest = pdf;
+    while let Some(start) = find(rest, "<< /Length ") {
+        let length: usize = line_at(rest, start + "<< /Length ".len())
+            .strip_suffix(" >>")
+            .unwrap()
+            .parse()
+            .unwrap();
+        let data = start + find(&rest[start..], "stream\n").unwrap() + "stream\n".len();
+        assert_eq!(&rest[data + length..data + length + 10], b"\nendstream");
+        streams.push(&rest[data..data + length]);
+        rest = &rest[data + length..];
+    }
+    streams
+}
+
+/// Returns the hex string following a key of the encryption dictionary.
+fn hex_value(pdf: &[u8], key: &str) -> Vec<u8> {
+    let start = find(pdf, &format!("{key} <")).unwrap() + key.len() + 2;
+    let end = start + find(&pdf[start..], ">").unwrap();
+    let hex = std::str::from_utf8(&pdf[start..end]).unwrap();
+    (0..hex.len())
+        .step_by(2)
+        .map(|it| u8::from_str_radix(&hex[it..it + 2], 16).unwrap())
+        .collect()
+}
+
+/// Finds the file key with the empty user password, as a viewer opening the report does.
+fn file_key(pdf: &[u8]) -> [u8; 32] {
+    let user = hex_value(pdf, "/U");
+    assert_eq!(hash(b"", &user[32..40], &[]), user[..32]);
+    let mut key: [u8; 32] = hex_value(pdf, "/UE").try_into().unwrap();
+    cbc::Decryptor::<Aes256>::new(&hash(b"", &user[40..], &[]).into(), &[0; 16].into())
+        .decrypt_padded_mut::<NoPadding>(&mut key)
+        .unwrap();
+    key
+}
+
+fn decrypt(key: &[u8; 32], data: &[u8]) -> String {
+    let (iv, data) = data.split_at(16);
+    let decrypted = cbc::Decryptor::<Aes256>::new(key.into(), iv.into())
+        .decrypt_padded_vec_mut::<Pkcs7>(data)
+        .unwrap();
+    decrypted.into_iter().map(char::from).collect()
+}
+
+#[test]
+fn report_structure() {
+    let (pdf, hash) = report(8);
+    check_structure(&pdf, &hash);
+    let key = file_key(&pdf);
+    let content = decrypt(&key, streams(&pdf)[0]);
+    assert!(content.contains("\\(sample\\)"));
+    assert!(content.contains(&format!("({hash}) '")));
+    assert!(content.contains("0.500"));
+    let info = &hex_value(&pdf, "/DatasetSHA256");
+    assert_eq!(decrypt(&key, info), hash);
+}
+
+/// The permissions only allow printing and copying the text, which the viewer checks against
+/// the encrypted copy.
+#[test]
+fn report_permissions() {
+    let (pdf, _) = report(8);
+    let key = file_key(&pdf);
+    let mut perms: [u8; 16] = hex_value(&pdf, "/Perms").try_into().unwrap();
+    Aes256::new(&key.into()).decrypt_block((&mut perms).into());
+    assert_eq!(&perms[..12], b"\xd4\xfa\xff\xff\xff\xff\xff\xffTadb");
+    assert!(find(&pdf, "/P -1324 ").is_some());
+}
+
+/// A long table is split into several pages, which are all referenced.
+#[test]
+fn report_pages() {
+    let (pdf, hash) = report(3 * LINES_PER_PAGE);
+    check_structure(&pdf, &hash);
+    let key = file_key(&pdf);
+    for stream in streams(&pdf) {
+        assert!(decrypt(&key, stream).ends_with("ET\n"));
+    }
+    let pages = pdf
+        .windows(b"/Type /Page ".len())
+        .filter(|it| *it == b"/Type /Page ")
+        .count();
+    assert!(pages >= 3);
+    assert!(find(&pdf, &format!("/Count {pages} >>")).is_some());
+}
@@ -0,0 +1,58 @@
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::util::config_dir;
+#[cfg(feature = "live")]
+use crate::worker::calibration::Calibration;
+
+use super::annotation::Annotation;
+
+/// The file in the config directory storing the session.
+const SESSION_FILE_NAME: &str = "session.toml";
+
+/// The state of the app which is restored on the next start.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    /// The annotations drawn into the diagram.
+    pub annotations: Vec<Annotation>,
+    /// The calibration of the electrode of the pH meter.
+    #[cfg(feature = "live")]
+    pub calibration: Option<Calibration>,
+}
+
+/// Loads the session stored in the config directory.
+///
+/// A missing or broken session file starts an empty session.
+pub fn load() -> Session {
+    let Some(content) =
+        config_dir().and_then(|it| fs::read_to_string(it.join(SESSION_FILE_NAME)).ok())
+    else {
+        return Session::default();
+    };
+    toml::from_str(&content).unwrap_or_else(|err| {
+        warn!("Could not restore the session: {err}");
+        Session::default()
+    })
+}
+
+/// Stores the session in the config directory.
+pub fn save(session: &Session) {
+    let Some(dir) = config_dir() else {
+        return;
+    };
+    let content = match toml::to_string(session) {
+        Ok(content) => content,
+        Err(err) => {
+            warn!("Could not serialize the session: {err}");
+            return;
+        }
+    };
+    if let Err(err) =
+        fs::create_dir_all(&dir).and_then(|()| fs::write(dir.join(SESSION_FILE_NAME), content))
+    {
+        warn!("Could not store the session: {err}");
+    }
+}
@@ -0,0 +1,94 @@
+use iced::{
+    widget::{column, text},
+    Element,
+};
+
+use crate::worker::{explain::Rounding, simulation::Simulation};
+
+use super::{
+    form::{field_row, FormField, TextFields},
+    strings::*,
+    Message,
+};
+
+/// A field of the simulation form.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SimulationField {
+    TestVolume,
+    TestConcentration,
+    MeasuringConcentration,
+    /// The largest added volume, which may be left empty.
+    MaxVolume,
+    Step,
+}
+
+impl FormField for SimulationField {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// The state of the simulation form.
+pub struct SimulationForm {
+    fields: TextFields<SimulationField>,
+}
+
+impl Default for SimulationForm {
+    fn default() -> Self {
+        Self {
+            fields: TextFields::new(&["0.01", "0.1", "0.1", "", "1"]),
+        }
+    }
+}
+
+impl SimulationForm {
+    pub fn set(&mut self, field: SimulationField, value: String) {
+        self.fields.set(field, value);
+    }
+
+    /// Returns the simulation described by the form, if all fields are valid.
+    pub fn simulation(&self) -> Option<Simulation> {
+        let fields = &self.fields;
+        let max_volume = if fields.get(SimulationField::MaxVolume).trim().is_empty() {
+            None
+        } else {
+            Some(fields.positive(SimulationField::MaxVolume)?)
+        };
+        Some(Simulation {
+            test_volume: fields.positive(SimulationField::TestVolume)?,
+            test_concentration: fields.positive(SimulationField::TestConcentration)?,
+            measuring_concentration: fields.positive(SimulationField::MeasuringConcentration)?,
+            max_volume,
+            step: fields.positive(SimulationField::Step)?,
+        })
+    }
+
+    pub fn view(&self, rounding: Rounding) -> Element<'_, Message> {
+        let field_row =
+            |label, field| field_row(label, &self.fields, field, Message::SimulationInput, 80.0);
+        let summary = match self.simulation() {
+            Some(simulation) => format!(
+                "{GENERATOR_EQUIVALENCE_VOLUME} = {} mL",
+                rounding.volume(simulation.equivalence_volume())
+            ),
+            None => GENERATOR_INVALID.into(),
+        };
+        column![
+            text(SIMULATION_TITLE).size(20),
+            field_row(GENERATOR_TEST_VOLUME, SimulationField::TestVolume),
+            field_row(
+                GENERATOR_TEST_CONCENTRATION,
+                SimulationField::TestConcentration
+            ),
+            field_row(
+                GENERATOR_MEASURING_CONCENTRATION,
+                SimulationField::MeasuringConcentration
+            ),
+            field_row(SIMULATION_MAX_VOLUME, SimulationField::MaxVolume),
+            field_row(GENERATOR_STEP, SimulationField::Step),
+            text(summary).size(12),
+        ]
+        .spacing(5)
+        .into()
+    }
+}
@@ -0,0 +1,98 @@
+use std::fmt::Write;
+
+use crate::worker::{
+    analysis::Stoichiometry,
+    explain::{number, Rounding},
+    Output,
+};
+
+use super::{diagram::region_name, quantity_name, strings::*};
+
+/// Writes a worked solution for the whole titration as a markdown document.
+///
+/// The calculation of every region is shown at sample points and generated by the explainer of
+/// the model, so it matches the calculated values, e.g. with the pKw at the temperature of the
+/// point, the stoichiometry and a second acid. The reported values are rounded by the given rules,
+/// the sample calculations are not.
+pub fn worked_solution(title: &str, output: &Output, rounding: Rounding) -> String {
+    let mut doc = String::new();
+    let input = &output.input;
+    _ = writeln!(doc, "# {SOLUTION_TITLE}: {title}\n");
+    _ = writeln!(doc, "## {SOLUTION_GIVEN}\n");
+    _ = writeln!(doc, "- {FIELD_TEST_VOLUME} = {} L", number(input.t_v));
+    _ = writeln!(
+        doc,
+        "- {FIELD_TEST_CONCENTRATION} = {} mol/L",
+        rounding.value(input.t_c)
+    );
+    _ = writeln!(
+        doc,
+        "- {FIELD_MEASURING_CONCENTRATION} = {} mol/L",
+        rounding.value(input.m_c)
+    );
+    let stoichiometry = input.stoichiometry;
+    if stoichiometry != Stoichiometry::default() {
+        _ = writeln!(
+            doc,
+            "- {SUMMARY_STOICHIOMETRY} = {} : {}",
+            stoichiometry.analyte, stoichiometry.titrant
+        );
+    }
+    if let Some(second) = &input.second_acid {
+        let pkas: Vec<String> = second.pkas.iter().map(|it| number(*it)).collect();
+        _ = writeln!(
+            doc,
+            "- {FIELD_SECOND_CONCENTRATION} = {} mol/L",
+            rounding.value(second.concentration)
+        );
+        _ = writeln!(doc, "- {FIELD_SECOND_PKA} = {}", pkas.join("; "));
+    }
+    _ = writeln!(doc);
+    _ = writeln!(doc, "## {SOLUTION_REGIONS}\n");
+    let mut last_region = None;
+    for index in key_points(output) {
+        let region = output.region(index);
+        if last_region != Some(region) {
+            _ = writeln!(doc, "### {}\n", region_name(region));
+            last_region = Some(region);
+        }
+        let item = &output.items[index];
+        _ = writeln!(
+            doc,
+            "#### {EXPLAIN_POINT} {} (V(m) = {} mL)\n",
+            index + 1,
+            number(item.m_v)
+        );
+        for step in input.explain(index) {
+            _ = writeln!(doc, "- {}: {}", quantity_name(step.quantity), step.formula);
+        }
+        _ = writeln!(doc);
+    }
+    _ = writeln!(doc, "## {SOLUTION_TABLE}\n");
+    _ = writeln!(doc, "| V(m) / mL | V / mL | pH | pOH |");
+    _ = writeln!(doc, "|---|---|---|---|");
+    for item in &output.items {
+        _ = writeln!(
+            doc,
+            "| {} | {} | {} | {} |",
+            rounding.volume(item.m_v),
+            rounding.volume(item.total_v),
+            number(item.ph),
+            number(item.poh)
+        );
+    }
+    doc
+}
+
+/// Returns the indices of the points worth a sample calculation.
+///
+/// These are the first and last point of both sides of the equivalence point.
+fn key_points(output: &Output) -> Vec<usize> {
+    let Some(last) = output.items.len().checked_sub(1) else {
+        return Vec::new();
+    };
+    let split = output.equivalence_index();
+    let mut points = vec![0, split.saturating_sub(1), split.min(last), last];
+    points.dedup();
+    points
+}
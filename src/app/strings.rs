@@ -1,8 +1,646 @@
-pub const WINDOW_TITLE: &str = "Titrationskurve";
+//! The UI strings in all supported languages.
+//!
+//! Every string is a [`Localized`], which resolves to the current language when it is displayed.
 
-pub const BUTTON_SELECT_FILE: &str = "Datei auswählen";
+use std::{
+    fmt::{self, Display},
+    sync::atomic::{AtomicU8, Ordering},
+};
 
-pub const MESSAGE_NO_CONTENT: &str = "Kein Inhalt verfügbar";
+static LANGUAGE: AtomicU8 = AtomicU8::new(Language::German as u8);
 
-pub const OPTION_DARK: &str = "Dunkel";
-pub const OPTION_COLORED: &str = "Gefärbt";
+/// A language of the UI.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    German = 0,
+    English = 1,
+}
+
+/// Returns the current language.
+pub fn language() -> Language {
+    match LANGUAGE.load(Ordering::Relaxed) {
+        1 => Language::English,
+        _ => Language::German,
+    }
+}
+
+/// Sets the language all strings are resolved in.
+pub fn set_language(language: Language) {
+    LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+/// A UI string in all supported languages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Localized {
+    pub german: &'static str,
+    pub english: &'static str,
+}
+
+impl Localized {
+    pub const fn new(german: &'static str, english: &'static str) -> Self {
+        Self { german, english }
+    }
+
+    /// Returns the string in the current language.
+    pub fn get(self) -> &'static str {
+        match language() {
+            Language::German => self.german,
+            Language::English => self.english,
+        }
+    }
+}
+
+impl Display for Localized {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+impl From<Localized> for String {
+    fn from(value: Localized) -> Self {
+        value.get().into()
+    }
+}
+
+pub const WINDOW_TITLE: Localized = Localized::new("Titrationskurve", "Titration curve");
+pub const SAFE_MODE: Localized = Localized::new("Abgesicherter Modus", "Safe mode");
+pub const DIAGRAM_WINDOW_TITLE: Localized = Localized::new("Diagramm", "Diagram");
+
+pub const BUTTON_SELECT_FILE: Localized = Localized::new("Datei auswählen", "Select file");
+#[cfg(feature = "scripting")]
+pub const BUTTON_LOAD_SCRIPT: Localized = Localized::new("Skript laden", "Load script");
+pub const BUTTON_PRESENT: Localized = Localized::new("Präsentieren (F11)", "Present (F11)");
+pub const BUTTON_COPY_DIAGRAM: Localized = Localized::new("Diagramm kopieren", "Copy diagram");
+pub const BUTTON_DETACH_DIAGRAM: Localized =
+    Localized::new("Diagramm in eigenem Fenster", "Diagram in own window");
+pub const BUTTON_ATTACH_DIAGRAM: Localized =
+    Localized::new("Diagramm zurückholen", "Bring diagram back");
+pub const BUTTON_UNLOAD_FILE: Localized = Localized::new("x", "x");
+pub const BUTTON_COMPARE_FILE: Localized = Localized::new("vs.", "vs.");
+pub const BUTTON_EXPORT_HTML: Localized = Localized::new("Als HTML exportieren", "Export as HTML");
+pub const EXPORT_SIZE_TITLE: Localized = Localized::new("Exportgröße", "Export size");
+pub const EXPORT_SIZE_SCREEN: Localized = Localized::new("Wie angezeigt", "As shown");
+pub const EXPORT_SIZE_A4_LANDSCAPE: Localized =
+    Localized::new("A4 quer, 300 dpi", "A4 landscape, 300 DPI");
+pub const BUTTON_EXPORT_CSV: Localized =
+    Localized::new("Werte als CSV exportieren", "Export values as CSV");
+pub const BUTTON_EXPORT_XLSX: Localized =
+    Localized::new("Werte als Excel exportieren", "Export values as Excel");
+pub const BUTTON_EXPORT_REPORT: Localized =
+    Localized::new("Prüfbericht exportieren", "Export test report");
+pub const BUTTON_EXPORT_SOLUTION: Localized =
+    Localized::new("Musterlösung exportieren", "Export worked solution");
+pub const BUTTON_EXPORT_PROBLEM: Localized =
+    Localized::new("Aufgabe exportieren", "Export problem");
+pub const BUTTON_EXPORT_ANSWER_KEY: Localized =
+    Localized::new("Lösungsschlüssel exportieren", "Export answer key");
+pub const BUTTON_RELOAD: Localized = Localized::new("Jetzt neu laden", "Reload now");
+pub const BUTTON_HIDE_SIDEBAR: Localized = Localized::new("Ausblenden", "Hide");
+pub const BUTTON_CANCEL_LOADING: Localized = Localized::new("Abbrechen", "Cancel");
+pub const BUTTON_EXPORT_DIAGRAM: Localized =
+    Localized::new("Diagramm exportieren", "Export diagram");
+pub const BUTTON_REVEAL_PREVIOUS: Localized = Localized::new("Zurück", "Back");
+pub const BUTTON_REVEAL_NEXT: Localized = Localized::new("Weiter", "Next");
+pub const BUTTON_PLAY: Localized = Localized::new("Abspielen", "Play");
+pub const BUTTON_PAUSE: Localized = Localized::new("Anhalten", "Pause");
+
+pub const MESSAGE_NO_CONTENT: Localized =
+    Localized::new("Kein Inhalt verfügbar", "No content available");
+pub const MESSAGE_DIAGRAM_DETACHED: Localized = Localized::new(
+    "Das Diagramm wird in einem eigenen Fenster angezeigt.",
+    "The diagram is shown in its own window.",
+);
+
+pub const STATUS_NO_FILE: Localized = Localized::new("Keine Datei geladen", "No file loaded");
+pub const STATUS_LOADED: Localized = Localized::new("Geladen um", "Loaded at");
+pub const STATUS_POINTS: Localized = Localized::new("Messpunkte", "data points");
+pub const STATUS_WATCHER: Localized = Localized::new("Überwachung", "Watching");
+pub const STATUS_READING: Localized = Localized::new("Lese", "Reading");
+pub const STATUS_WATCHER_NATIVE: Localized = Localized::new("nativ", "native");
+pub const STATUS_WATCHER_POLL: Localized = Localized::new("Abfrage", "polling");
+pub const STATUS_WORKER_DEAD: Localized = Localized::new(
+    "Der Hintergrundprozess läuft nicht mehr",
+    "The background worker is not running",
+);
+pub const STATUS_WORKER_RESTARTED: Localized = Localized::new(
+    "Der Hintergrundprozess wurde neu gestartet",
+    "The background worker was restarted",
+);
+
+pub const NOTICE_SAVED: Localized = Localized::new("Gespeichert", "Saved");
+pub const NOTICE_CANCELLED: Localized = Localized::new("Laden abgebrochen", "Loading cancelled");
+#[cfg(feature = "scripting")]
+pub const NOTICE_SCRIPT_LOADED: Localized = Localized::new("Skript geladen", "Script loaded");
+pub const TOAST_INFO: Localized = Localized::new("Hinweis", "Info");
+pub const TOAST_ERROR: Localized = Localized::new("Fehler", "Error");
+
+pub const FILTER_MARKDOWN: Localized = Localized::new("Markdown", "Markdown");
+pub const FILTER_TABLE: Localized = Localized::new("Tabelle", "Table");
+pub const FILTER_SVG: Localized = Localized::new("SVG-Grafik", "SVG image");
+pub const FILTER_HTML: Localized = Localized::new("HTML-Seite", "HTML page");
+pub const FILTER_PDF: Localized = Localized::new("PDF-Dokument", "PDF document");
+#[cfg(feature = "scripting")]
+pub const FILTER_SCRIPT: Localized = Localized::new("Rhai-Skript", "Rhai script");
+
+pub const ERROR_OCCURRED: Localized =
+    Localized::new("Ein Fehler ist aufgetreten", "An error occurred");
+pub const ERROR_FILE_DOES_NOT_EXIST: Localized =
+    Localized::new("Die Datei existiert nicht", "The file does not exist");
+pub const ERROR_TABLE: Localized = Localized::new(
+    "Die Tabelle konnte nicht gelesen werden",
+    "The table could not be read",
+);
+pub const ERROR_NO_TABLE_IN_WORKBOOK: Localized = Localized::new(
+    "Die Arbeitsmappe enthält keine Tabelle",
+    "The workbook does not contain a table",
+);
+pub const ERROR_MAPPING: Localized =
+    Localized::new("Ungültige Zuordnungsdatei", "Invalid mapping file");
+pub const ERROR_IO: Localized = Localized::new(
+    "Die Datei konnte nicht geschrieben werden",
+    "The file could not be written",
+);
+pub const ERROR_XLSX: Localized = Localized::new(
+    "Die Arbeitsmappe konnte nicht erstellt werden",
+    "The workbook could not be created",
+);
+#[cfg(feature = "scripting")]
+pub const ERROR_SCRIPT: Localized = Localized::new(
+    "Das Skript konnte nicht geladen werden",
+    "The script could not be loaded",
+);
+pub const ERROR_NO_METADATA: Localized = Localized::new(
+    "Die SVG-Grafik enthält keine Kurvendaten",
+    "The SVG image contains no curve data",
+);
+pub const ERROR_METADATA: Localized = Localized::new(
+    "Die Kurvendaten der SVG-Grafik sind ungültig",
+    "The curve data of the SVG image is invalid",
+);
+pub const ERROR_NOT_A_WEAK_ACID: Localized = Localized::new(
+    "Das Modell für schwache Säuren gilt nicht für die Probelösung",
+    "The weak acid model does not apply to the test solution",
+);
+pub const ERROR_NO_CONDUCTIVITY: Localized = Localized::new(
+    "Die Leitfähigkeitsdatei enthält keine Messwerte",
+    "The conductivity file contains no measurements",
+);
+pub const ERROR_UNKNOWN_MOLAR_MASS: Localized = Localized::new(
+    "Unbekannte Stoffart für die Massenkonzentration",
+    "Unknown substance for the mass concentration",
+);
+pub const ERROR_MISSING_FIELD: Localized =
+    Localized::new("Die Tabelle beschriftet nicht", "The table does not label");
+pub const ERROR_CELL: Localized = Localized::new("Zelle", "Cell");
+pub const ERROR_CELL_NUMBER: Localized =
+    Localized::new("erwartet eine Zahl für", "expects a number for");
+pub const ERROR_CELL_FOUND: Localized = Localized::new("gefunden:", "found:");
+pub const ERROR_CELL_EMPTY: Localized = Localized::new("die Zelle ist leer", "the cell is empty");
+
+pub const FIELD_TEST_VOLUME: Localized = Localized::new("V(Probelösung)", "V(test solution)");
+pub const FIELD_TEST_CONCENTRATION: Localized =
+    Localized::new("c(Probelösung)", "c(test solution)");
+pub const FIELD_MEASURING_CONCENTRATION: Localized = Localized::new("c(Maßlösung)", "c(titrant)");
+pub const FIELD_MEASURING_VOLUME: Localized = Localized::new("V(Maßlösung)", "V(titrant)");
+pub const FIELD_SECOND_CONCENTRATION: Localized =
+    Localized::new("c(Probelösung 2)", "c(test solution 2)");
+pub const FIELD_SECOND_PKA: Localized =
+    Localized::new("pKs(Probelösung 2)", "pKa(test solution 2)");
+pub const FIELD_TEMPERATURE: Localized = Localized::new("T / °C", "T / °C");
+pub const WARNINGS_TITLE: Localized = Localized::new("Auffällige Werte", "Implausible values");
+pub const WARNING_NEGATIVE: Localized = Localized::new("negativ", "negative");
+pub const WARNING_ZERO: Localized = Localized::new("null", "zero");
+pub const WARNING_HIGH_CONCENTRATION: Localized =
+    Localized::new("unrealistisch hoch", "implausibly high");
+pub const WARNING_DECREASING: Localized = Localized::new(
+    "kleiner als der vorherige Wert",
+    "smaller than the previous value",
+);
+pub const WARNING_DUPLICATE: Localized =
+    Localized::new("doppelt vorhanden", "appears more than once");
+
+pub const OPTION_DARK: Localized = Localized::new("Dunkel", "Dark");
+pub const OPTION_SYSTEM_THEME: Localized = Localized::new("Wie System", "System theme");
+pub const OPTION_COLORED: Localized = Localized::new("Gefärbt", "Colored");
+pub const OPTION_OVERLAY: Localized = Localized::new("Überlagern", "Overlay");
+pub const OPTION_COMPARE: Localized =
+    Localized::new("Nebeneinander vergleichen", "Compare side by side");
+pub const OPTION_LADDER: Localized = Localized::new("Leiterdiagramm", "Ladder diagram");
+pub const OPTION_SMOOTH: Localized = Localized::new("Glatte Kurve", "Smooth curve");
+pub const OPTION_CROSSHAIR: Localized = Localized::new("Fadenkreuz", "Crosshair");
+pub const OPTION_SORT_VOLUMES: Localized = Localized::new("Volumina sortieren", "Sort volumes");
+pub const OPTION_MAX_POINTS: Localized = Localized::new("Max. Punkte", "Max. points");
+pub const OPTION_ALL_POINTS: Localized = Localized::new("alle", "all");
+pub const OPTION_EXPLAIN: Localized = Localized::new("Erklären", "Explain");
+pub const OPTION_USAGE: Localized = Localized::new("Maßlösungsverbrauch", "Titrant usage");
+pub const OPTION_SUMMARY: Localized = Localized::new("Ergebnis", "Result");
+pub const OPTION_FIT: Localized = Localized::new("Modell anpassen", "Fit model");
+pub const OPTION_ACTIVITY: Localized = Localized::new("Aktivitäten", "Activities");
+pub const OPTION_TEMPERATURE: Localized = Localized::new("Temperatur (°C)", "Temperature (°C)");
+pub const OPTION_MODEL: Localized = Localized::new("Modell", "Model");
+pub const MODEL_STRONG: Localized = Localized::new("Starke Säure", "Strong acid");
+pub const MODEL_WEAK_ACID: Localized = Localized::new("Schwache Säure", "Weak acid");
+#[cfg(feature = "scripting")]
+pub const MODEL_SCRIPT: Localized = Localized::new("Skript", "Script");
+#[cfg(feature = "scripting")]
+pub const SCRIPT_NONE: Localized = Localized::new("kein Skript geladen", "no script loaded");
+pub const OPTION_GENERATOR: Localized = Localized::new("Aufgabengenerator", "Problem generator");
+pub const OPTION_SIMULATION: Localized = Localized::new("Simulation", "Simulation");
+pub const OPTION_CALCULATOR: Localized =
+    Localized::new("Verdünnungsrechner", "Dilution calculator");
+pub const OPTION_OUTLIERS: Localized = Localized::new("Ausreißer", "Outliers");
+pub const OPTION_DATA_EDITOR: Localized =
+    Localized::new("Messwerte bearbeiten", "Edit data points");
+pub const OPTION_ANNOTATIONS: Localized = Localized::new("Anmerkungen", "Annotations");
+pub const OPTION_STYLE_EDITOR: Localized = Localized::new("Diagrammstil", "Diagram style");
+pub const OPTION_PLAYBACK: Localized = Localized::new("Wiedergabe", "Playback");
+pub const OPTION_BURETTE: Localized = Localized::new("Virtuelle Bürette", "Virtual burette");
+#[cfg(feature = "live")]
+pub const OPTION_LIVE: Localized = Localized::new("pH-Meter (live)", "pH meter (live)");
+#[cfg(feature = "live")]
+pub const OPTION_CALIBRATION: Localized =
+    Localized::new("Elektrode kalibrieren (mV)", "Calibrate electrode (mV)");
+pub const OPTION_PROJECTOR: Localized = Localized::new("Projektormodus", "Projector mode");
+pub const OPTION_PRESENTATION_CYCLE: Localized =
+    Localized::new("Dateien nacheinander zeigen", "Cycle through files");
+pub const OPTION_LOG_PANEL: Localized = Localized::new("Protokoll", "Log");
+pub const OPTION_PAUSED: Localized = Localized::new("Überwachung pausieren", "Pause watching");
+
+pub const WATCH_PAUSED: Localized = Localized::new("Überwachung pausiert", "Watching paused");
+pub const BADGE_PENDING_CHANGE: Localized = Localized::new("Änderung ausstehend", "change pending");
+pub const BADGE_PENDING_CHANGES: Localized =
+    Localized::new("Änderungen ausstehend", "changes pending");
+pub const BADGE_STALE: Localized = Localized::new(
+    "Veraltet, das Neuladen ist fehlgeschlagen",
+    "Outdated, the reload failed",
+);
+pub const WATCH_TITLE: Localized = Localized::new("Neu laden bei", "Reload on");
+pub const WATCH_ANY_MODIFY: Localized = Localized::new("Jeder Änderung", "Any change");
+pub const WATCH_IGNORE_ATTRIBUTES: Localized =
+    Localized::new("Änderung außer Attributen", "Change except attributes");
+pub const WATCH_DEBOUNCE: Localized = Localized::new("Verzögerung", "Delay");
+pub const WATCH_TAIL: Localized = Localized::new(
+    "Nur neue Zeilen lesen (CSV)",
+    "Read only appended rows (CSV)",
+);
+pub const WATCH_DATA_ONLY: Localized = Localized::new("Inhaltsänderung", "Content change");
+pub const WATCH_FOLDER_TITLE: Localized = Localized::new("Ordner überwachen", "Watch folder");
+pub const WATCH_FOLDER_PATTERN: Localized =
+    Localized::new("Muster, z. B. lauf-*.csv", "Pattern, e.g. run-*.csv");
+pub const WATCH_FOLDER_NONE: Localized =
+    Localized::new("Lädt die neueste Tabelle", "Loads the newest table");
+pub const BUTTON_WATCH_FOLDER: Localized = Localized::new("Ordner wählen", "Choose folder");
+pub const BUTTON_UNWATCH_FOLDER: Localized = Localized::new("Beenden", "Stop");
+
+pub const LANGUAGE_GERMAN: Localized = Localized::new("Deutsch", "Deutsch");
+pub const LANGUAGE_ENGLISH: Localized = Localized::new("English", "English");
+pub const NUMBER_LOCALE_TITLE: Localized =
+    Localized::new("Dezimaltrennzeichen", "Decimal separator");
+pub const NUMBER_LOCALE_POINT: Localized = Localized::new("Punkt (3.5)", "Point (3.5)");
+pub const NUMBER_LOCALE_COMMA: Localized = Localized::new("Komma (3,5)", "Comma (3,5)");
+
+pub const EXPLAIN_POINT: Localized = Localized::new("Messpunkt", "Data point");
+pub const EXPLAIN_TOTAL_VOLUME: Localized = Localized::new("Gesamtvolumen", "Total volume");
+pub const EXPLAIN_ADDED_AMOUNT: Localized =
+    Localized::new("Zugegebene Stoffmenge", "Added amount of substance");
+pub const EXPLAIN_REMAINING_AMOUNT: Localized =
+    Localized::new("Verbleibende Stoffmenge", "Remaining amount of substance");
+pub const EXPLAIN_TEST_CONCENTRATION: Localized = Localized::new(
+    "Konzentration der Probelösung",
+    "Concentration of the test solution",
+);
+pub const EXPLAIN_EXCESS_CONCENTRATION: Localized = Localized::new(
+    "Konzentration der überschüssigen Maßlösung",
+    "Concentration of the excess titrant",
+);
+pub const EXPLAIN_PH: Localized = Localized::new("pH-Wert", "pH value");
+pub const EXPLAIN_POH: Localized = Localized::new("pOH-Wert", "pOH value");
+
+pub const PLOT_CURVE: Localized = Localized::new("Titrationskurve", "Titration curve");
+pub const PLOT_CURVE_FILE_SUFFIX: Localized = Localized::new("kurve", "curve");
+pub const PLOT_DERIVATIVE: Localized = Localized::new("Ableitung", "Derivative");
+pub const SECONDARY_AXIS_TITLE: Localized = Localized::new("Zweite y-Achse", "Second y-axis");
+pub const SECONDARY_AXIS_NONE: Localized = Localized::new("Keine", "None");
+pub const PLOT_DERIVATIVE_FILE_SUFFIX: Localized = Localized::new("ableitung", "derivative");
+pub const AXIS_VOLUME: Localized = Localized::new("Volumen", "Volume");
+pub const AXIS_DEGREE: Localized = Localized::new("Titrationsgrad τ", "Titration degree τ");
+pub const AXIS_AMOUNT: Localized =
+    Localized::new("Stoffmenge Maßlösung / mmol", "Amount of titrant / mmol");
+pub const X_QUANTITY_TITLE: Localized = Localized::new("x-Achse", "x-axis");
+pub const X_QUANTITY_VOLUME: Localized = Localized::new("Volumen (mL)", "Volume (mL)");
+pub const X_QUANTITY_DEGREE: Localized = Localized::new("Titrationsgrad τ", "Titration degree τ");
+pub const X_QUANTITY_AMOUNT: Localized = Localized::new("Stoffmenge (mmol)", "Amount (mmol)");
+pub const AXIS_TITLE: Localized = Localized::new("Achsenbeschriftung", "Axis labels");
+pub const LEGEND_TITLE: Localized = Localized::new("Legende", "Legend");
+pub const LEGEND_HIDDEN: Localized = Localized::new("Keine", "None");
+pub const LEGEND_TOP_LEFT: Localized = Localized::new("Oben links", "Top left");
+pub const LEGEND_TOP_RIGHT: Localized = Localized::new("Oben rechts", "Top right");
+pub const LEGEND_BOTTOM_LEFT: Localized = Localized::new("Unten links", "Bottom left");
+pub const LEGEND_BOTTOM_RIGHT: Localized = Localized::new("Unten rechts", "Bottom right");
+pub const LEGEND_FILE_NAMES: Localized = Localized::new(
+    "Dateinamen statt Tabellennamen",
+    "File names instead of sheet names",
+);
+pub const LEGEND_SERIES_NAME: Localized =
+    Localized::new("Name der ausgewählten Reihe", "Name of the selected series");
+pub const AXIS_AUTO_DECIMALS: Localized = Localized::new("Stellen", "Decimals");
+pub const AXIS_UNIT: Localized = Localized::new("Einheit", "Unit");
+pub const AXIS_TITLE_INPUT: Localized = Localized::new("Titel (automatisch)", "Title (automatic)");
+pub const AXIS_UNIT_ON_TICKS: Localized =
+    Localized::new("Einheit an jedem Wert", "Unit on every tick");
+pub const PLOT_CONDUCTIVITY: Localized = Localized::new("Leitfähigkeit", "Conductivity");
+pub const PLOT_CONDUCTIVITY_FILE_SUFFIX: Localized =
+    Localized::new("leitfaehigkeit", "conductivity");
+pub const PLOT_CONDUCTIVITY_AXIS: Localized = Localized::new("κ / mS/cm", "κ / mS/cm");
+pub const PLOT_CORRELATION: Localized =
+    Localized::new("pH und Leitfähigkeit", "pH and conductivity");
+pub const PLOT_CORRELATION_FILE_SUFFIX: Localized =
+    Localized::new("ph-leitfaehigkeit", "ph-conductivity");
+pub const SUMMARY_TITLE: Localized = Localized::new("Ergebnis", "Result");
+pub const SUMMARY_STOICHIOMETRY: Localized =
+    Localized::new("n(Probe) : n(Maßlösung)", "n(analyte) : n(titrant)");
+pub const SUMMARY_STOICHIOMETRY_FILE: Localized = Localized::new("Aus der Datei", "From the file");
+pub const SUMMARY_ANALYTE: Localized = Localized::new("c(Probe)", "c(analyte)");
+pub const INDICATOR_TITLE: Localized = Localized::new("Indikator", "Indicator");
+pub const INDICATOR_NOT_REACHED: Localized = Localized::new(
+    "Der Äquivalenzpunkt wurde nicht erreicht",
+    "The equivalence point was not reached",
+);
+pub const INDICATOR_REASON: Localized = Localized::new(
+    "Der Umschlagsbereich enthält pH",
+    "The transition range contains pH",
+);
+pub const INDICATOR_BEST: Localized = Localized::new("Mitte am nächsten", "middle closest");
+pub const INDICATOR_NONE: Localized = Localized::new(
+    "Kein Umschlagsbereich enthält pH",
+    "No transition range contains pH",
+);
+pub const INDICATOR_NEAREST: Localized = Localized::new("Am nächsten", "Closest");
+pub const INDICATOR_ERROR_TITLE: Localized =
+    Localized::new("Titrationsfehler mit", "Titration error with");
+pub const INDICATOR_ERROR: Localized = Localized::new("Titrationsfehler", "Titration error");
+pub const INDICATOR_ERROR_UNREACHED: Localized = Localized::new(
+    "Die Kurve erreicht den Umschlagspunkt nicht",
+    "The curve does not reach the transition point",
+);
+pub const SUMMARY_NO_EQUIVALENCE: Localized = Localized::new(
+    "Kein Äquivalenzpunkt gefunden",
+    "No equivalence point found",
+);
+pub const FIT_TITLE: Localized = Localized::new(
+    "Angepasstes Modell einer einprotonigen Säure",
+    "Fitted model of a monoprotic acid",
+);
+pub const FIT_RMS: Localized = Localized::new("Residuen (RMS)", "Residuals (RMS)");
+pub const FIT_LARGEST_RESIDUAL: Localized = Localized::new("Größtes Residuum", "Largest residual");
+pub const FIT_ITERATIONS: Localized = Localized::new("Iterationen", "Iterations");
+pub const FIT_NOT_CONVERGED: Localized = Localized::new("nicht konvergiert", "not converged");
+pub const FIT_TOO_FEW_POINTS: Localized = Localized::new(
+    "Zu wenige Messpunkte für die Anpassung",
+    "Too few points to fit the model",
+);
+pub const ACTIVITY_TITLE: Localized = Localized::new(
+    "Aktivitätskorrektur (Debye-Hückel)",
+    "Activity correction (Debye-Hückel)",
+);
+pub const ACTIVITY_IONIC_STRENGTH: Localized =
+    Localized::new("Größte Ionenstärke", "Largest ionic strength");
+pub const ACTIVITY_LARGEST_SHIFT: Localized =
+    Localized::new("Größte Verschiebung", "Largest shift");
+pub const USAGE_EQUIVALENCE: Localized = Localized::new("Äquivalenzpunkt", "equivalence point");
+pub const USAGE_ENDPOINT: Localized = Localized::new("Endpunkt", "endpoint");
+pub const USAGE_BREAKS: Localized = Localized::new("Sprünge bei", "breaks at");
+pub const USAGE_TOTAL: Localized = Localized::new("gesamt", "total");
+pub const USAGE_CONSUMED: Localized = Localized::new("bis zur Äquivalenz", "up to equivalence");
+pub const USAGE_OVERSHOOT: Localized = Localized::new("nach dem Endpunkt", "after the endpoint");
+pub const BUFFERING_TITLE: Localized = Localized::new("Pufferung", "Buffering");
+pub const PLOT_GRAN: Localized = Localized::new("Gran-Auftragung", "Gran plot");
+pub const PLOT_GRAN_FILE_SUFFIX: Localized = Localized::new("gran", "gran");
+pub const PLOT_GRAN_EQUIVALENCE: Localized = Localized::new("V(Äq., Gran)", "V(eq., Gran)");
+pub const PLOT_GRAN_AXIS: Localized = Localized::new("G / G(max)", "G / G(max)");
+pub const PLOT_PRECIPITATION: Localized = Localized::new("Fällungstitration", "Precipitation");
+pub const PLOT_PRECIPITATION_FILE_SUFFIX: Localized = Localized::new("faellung", "precipitation");
+pub const PLOT_PRECIPITATION_ANION: Localized =
+    Localized::new("Anion statt Kation", "Anion instead of cation");
+pub const PROFILE_TITLE: Localized = Localized::new("Profil", "Profile");
+pub const PROFILE_TEACHER: Localized = Localized::new("Lehrkraft", "Teacher");
+pub const PROFILE_STUDENT: Localized = Localized::new("Schüler", "Student");
+pub const PROFILE_QUALITY_CONTROL: Localized =
+    Localized::new("Qualitätskontrolle", "Quality control");
+pub const ROUNDING_TITLE: Localized = Localized::new("Rundung", "Rounding");
+pub const ROUNDING_VOLUME_DECIMALS: Localized =
+    Localized::new("Nachkommastellen (mL)", "Decimals (mL)");
+pub const ROUNDING_SIGNIFICANT_DIGITS: Localized =
+    Localized::new("Signifikante Stellen", "Significant digits");
+pub const NUMBER_FORMAT_TITLE: Localized = Localized::new("Zahlenformat", "Number format");
+pub const NUMBER_FORMAT_AUTO: Localized = Localized::new("Automatisch", "Automatic");
+pub const NUMBER_FORMAT_FIXED: Localized = Localized::new("Nachkommastellen", "Fixed decimals");
+pub const NUMBER_FORMAT_SIGNIFICANT: Localized =
+    Localized::new("Signifikante Stellen", "Significant digits");
+pub const NUMBER_FORMAT_SCIENTIFIC: Localized = Localized::new("Wissenschaftlich", "Scientific");
+pub const NUMBER_FORMAT_DIGITS: Localized = Localized::new("Stellen", "Digits");
+pub const PLOT_COMPLEXOMETRY: Localized = Localized::new("EDTA-Titration", "EDTA titration");
+pub const PLOT_COMPLEXOMETRY_FILE_SUFFIX: Localized = Localized::new("edta", "edta");
+pub const PLOT_COMPLEXOMETRY_PH: Localized = Localized::new("Puffer-pH", "Buffer pH");
+pub const PLOT_TEMPERATURE: Localized = Localized::new("Temperatur", "Temperature");
+pub const PLOT_TEMPERATURE_FILE_SUFFIX: Localized = Localized::new("temperatur", "temperature");
+pub const PLOT_TEMPERATURE_AXIS: Localized = Localized::new("T / °C", "T / °C");
+pub const PLOT_CONDUCTIVITY_ACID: Localized = Localized::new("Säure", "Acid");
+pub const PLOT_CONDUCTIVITY_BASE: Localized = Localized::new("Base", "Base");
+pub const PLOT_BUFFER_CAPACITY: Localized = Localized::new("Pufferkapazität", "Buffer capacity");
+pub const PLOT_BUFFER_CAPACITY_FILE_SUFFIX: Localized =
+    Localized::new("pufferkapazitaet", "buffer-capacity");
+pub const PLOT_BUFFER_CAPACITY_AXIS: Localized = Localized::new("β / mol/L", "β / mol/L");
+pub const PLOT_HAGG: Localized = Localized::new("Hägg-Diagramm", "Hägg diagram");
+pub const PLOT_HAGG_FILE_SUFFIX: Localized = Localized::new("haegg", "hagg");
+pub const PLOT_HAGG_AXIS: Localized = Localized::new("lg c", "lg c");
+pub const PLOT_DERIVATIVE_AXIS: Localized = Localized::new("ΔpH/ΔV", "ΔpH/ΔV");
+pub const PLOT_DEFAULT_FILE_NAME: Localized = Localized::new("diagramm", "diagram");
+
+pub const REGION_INITIAL: Localized = Localized::new("Anfangspunkt", "Initial point");
+pub const REGION_BUFFER: Localized =
+    Localized::new("Vor dem Äquivalenzpunkt", "Before the equivalence point");
+pub const REGION_EQUIVALENCE: Localized = Localized::new("Äquivalenzpunkt", "Equivalence point");
+pub const REGION_EXCESS: Localized =
+    Localized::new("Überschuss der Maßlösung", "Excess of titrant");
+
+pub const TUTORIAL_TITLE: Localized = Localized::new("Einführung", "Tutorial");
+pub const TUTORIAL_SAMPLE_NAME: Localized = Localized::new("Beispiel", "Sample");
+pub const TUTORIAL_SELECT_FILE: Localized = Localized::new(
+    "Gezeigt wird ein Beispieldatensatz. Mit „Datei auswählen“ lädst du eine eigene Tabelle, die bei jeder Änderung neu geladen wird.",
+    "A sample dataset is shown. Use “Select file” to load your own table, which is reloaded whenever it changes.",
+);
+pub const TUTORIAL_TOGGLE_THEME: Localized = Localized::new(
+    "Mit „Dunkel“ wechselst du zwischen hellem und dunklem Design.",
+    "Use “Dark” to switch between the light and dark theme.",
+);
+pub const TUTORIAL_READ_EQUIVALENCE: Localized = Localized::new(
+    "Der markierte Punkt ist der letzte Messpunkt vor dem Äquivalenzpunkt. Dort steigt der pH-Wert sprunghaft an.",
+    "The marked point is the last data point before the equivalence point. There the pH jumps steeply.",
+);
+pub const TUTORIAL_EXPORT: Localized = Localized::new(
+    "Kopiere oder exportiere das Diagramm oder exportiere eine Musterlösung.",
+    "Copy or export the diagram, or export a worked solution.",
+);
+pub const EXAMPLE_LOAD: Localized = Localized::new("Beispiel laden", "Load example");
+pub const EXAMPLE_STRONG_ACID: Localized =
+    Localized::new("Salzsäure mit Natronlauge", "Hydrochloric acid with NaOH");
+pub const EXAMPLE_WEAK_ACID: Localized =
+    Localized::new("Essigsäure mit Natronlauge", "Acetic acid with NaOH");
+pub const EXAMPLE_DIPROTIC: Localized =
+    Localized::new("Maleinsäure mit Natronlauge", "Maleic acid with NaOH");
+pub const EXAMPLE_MIXTURE: Localized = Localized::new(
+    "Salz- und Essigsäure mit Natronlauge",
+    "Hydrochloric and acetic acid with NaOH",
+);
+pub const EXAMPLE_NOISY: Localized =
+    Localized::new("Messreihe mit Messfehlern", "Noisy measurement");
+pub const BUTTON_TUTORIAL: Localized = Localized::new("Einführung starten", "Start tutorial");
+pub const BUTTON_TUTORIAL_NEXT: Localized = Localized::new("Weiter", "Next");
+pub const BUTTON_TUTORIAL_SKIP: Localized = Localized::new("Beenden", "Finish");
+
+pub const REPORT_TITLE: Localized = Localized::new("Prüfbericht", "Test report");
+pub const REPORT_FILE_SUFFIX: Localized = Localized::new("bericht", "report");
+pub const REPORT_HASH: Localized = Localized::new("SHA-256 der Daten", "SHA-256 of the data");
+pub const REPORT_RESULTS: Localized = Localized::new("Ergebnisse", "Results");
+pub const REPORT_PAGE: Localized = Localized::new("Seite", "Page");
+pub const SOLUTION_TITLE: Localized = Localized::new("Musterlösung", "Worked solution");
+pub const HTML_HINT: Localized = Localized::new(
+    "Zeige auf einen Punkt für seine Werte. Mausrad: zoomen, Ziehen: verschieben, Doppelklick: zurücksetzen.",
+    "Point at a data point to see its values. Mouse wheel: zoom, drag: pan, double click: reset.",
+);
+pub const SOLUTION_FILE_SUFFIX: Localized = Localized::new("musterloesung", "solution");
+pub const SOLUTION_GIVEN: Localized = Localized::new("Gegeben", "Given");
+pub const SOLUTION_REGIONS: Localized =
+    Localized::new("Rechenweg nach Bereichen", "Calculation by region");
+pub const SOLUTION_TABLE: Localized = Localized::new("Wertetabelle", "Table of values");
+
+pub const GENERATOR_TITLE: Localized = Localized::new("Aufgabengenerator", "Problem generator");
+pub const GENERATOR_SEED: Localized = Localized::new("Startwert", "Seed");
+pub const GENERATOR_TEST_VOLUME: Localized =
+    Localized::new("V(Probelösung) / L", "V(test solution) / L");
+pub const GENERATOR_TEST_CONCENTRATION: Localized =
+    Localized::new("c(Probelösung) / mol/L", "c(test solution) / mol/L");
+pub const GENERATOR_MEASURING_CONCENTRATION: Localized =
+    Localized::new("c(Maßlösung) / mol/L", "c(titrant) / mol/L");
+pub const GENERATOR_STEP: Localized = Localized::new("Schrittweite / mL", "Step / mL");
+pub const GENERATOR_INVALID: Localized = Localized::new("Ungültige Eingabe", "Invalid input");
+pub const GENERATOR_ANSWER_KEY: Localized = Localized::new("Lösungsschlüssel", "Answer key");
+pub const GENERATOR_ACID: Localized = Localized::new("Säure", "Acid");
+pub const GENERATOR_BASE: Localized = Localized::new("Base", "Base");
+pub const GENERATOR_EQUIVALENCE_VOLUME: Localized =
+    Localized::new("V(Äquivalenzpunkt)", "V(equivalence point)");
+pub const GENERATOR_FILE_PREFIX: Localized = Localized::new("aufgabe", "problem");
+pub const SIMULATION_TITLE: Localized = Localized::new("Simulation", "Simulation");
+pub const SIMULATION_MAX_VOLUME: Localized = Localized::new(
+    "V(Maßlösung) max. / mL (optional)",
+    "V(titrant) max. / mL (optional)",
+);
+pub const BURETTE_TITLE: Localized = Localized::new("Virtuelle Bürette", "Virtual burette");
+pub const BURETTE_READING: Localized = Localized::new("Bürettenstand", "Burette reading");
+pub const BUTTON_BURETTE_UNDO: Localized = Localized::new("Rückgängig", "Undo");
+pub const BUTTON_BURETTE_RESET: Localized = Localized::new("Neu beginnen", "Start over");
+#[cfg(feature = "live")]
+pub const LIVE_TITLE: Localized = Localized::new("pH-Meter", "pH meter");
+#[cfg(feature = "live")]
+pub const LIVE_PORT: Localized = Localized::new("Schnittstelle", "Serial port");
+#[cfg(feature = "live")]
+pub const LIVE_BAUD_RATE: Localized = Localized::new("Baudrate", "Baud rate");
+#[cfg(feature = "live")]
+pub const LIVE_VOLUME: Localized = Localized::new("V(Maßlösung) / mL", "V(titrant) / mL");
+#[cfg(feature = "live")]
+pub const LIVE_POINTS: Localized = Localized::new("Messpunkte", "Data points");
+#[cfg(feature = "live")]
+pub const BUTTON_CONNECT: Localized = Localized::new("Verbinden", "Connect");
+#[cfg(feature = "live")]
+pub const BUTTON_DISCONNECT: Localized = Localized::new("Trennen", "Disconnect");
+#[cfg(feature = "live")]
+pub const BUTTON_LIVE_CLEAR: Localized = Localized::new("Messpunkte löschen", "Clear points");
+#[cfg(feature = "live")]
+pub const CALIBRATION_TITLE: Localized = Localized::new("Kalibrierung", "Calibration");
+#[cfg(feature = "live")]
+pub const CALIBRATION_BUFFER: Localized = Localized::new("Puffer", "Buffer");
+#[cfg(feature = "live")]
+pub const CALIBRATION_SLOPE: Localized = Localized::new("Steilheit", "Slope");
+#[cfg(feature = "live")]
+pub const CALIBRATION_OFFSET: Localized = Localized::new("Nullpunkt", "Offset");
+#[cfg(feature = "live")]
+pub const CALIBRATION_READING: Localized = Localized::new("Messwert", "Reading");
+#[cfg(feature = "live")]
+pub const CALIBRATION_INCOMPLETE: Localized = Localized::new(
+    "Zwei oder drei Puffer mit verschiedenem pH eingeben",
+    "Enter two or three buffers with different pH values",
+);
+#[cfg(feature = "live")]
+pub const BUTTON_TAKE_READING: Localized = Localized::new("Messwert", "Reading");
+#[cfg(feature = "live")]
+pub const BUTTON_APPLY_CALIBRATION: Localized = Localized::new("Übernehmen", "Apply");
+#[cfg(feature = "live")]
+pub const BUTTON_REMOVE_CALIBRATION: Localized =
+    Localized::new("Kalibrierung entfernen", "Remove calibration");
+pub const CALCULATOR_TITLE: Localized = Localized::new("Verdünnungsrechner", "Dilution calculator");
+pub const CALCULATOR_STOCK_CONCENTRATION: Localized =
+    Localized::new("c(Stammlösung) / mol/L", "c(stock solution) / mol/L");
+pub const CALCULATOR_TARGET_CONCENTRATION: Localized =
+    Localized::new("c(Maßlösung) / mol/L", "c(titrant) / mol/L");
+pub const CALCULATOR_TARGET_VOLUME: Localized =
+    Localized::new("V(Maßlösung) / mL", "V(titrant) / mL");
+pub const CALCULATOR_ALIQUOT: Localized = Localized::new("V(Stammlösung)", "V(stock solution)");
+pub const CALCULATOR_WATER: Localized = Localized::new("V(Wasser)", "V(water)");
+pub const CALCULATOR_INVALID: Localized = Localized::new(
+    "Ungültige Eingabe oder Zielkonzentration über der Stammlösung",
+    "Invalid input or target concentration above the stock solution",
+);
+pub const BUTTON_APPLY_CALCULATOR: Localized =
+    Localized::new("In Simulation übernehmen", "Use in simulation");
+pub const OUTLIERS_TITLE: Localized = Localized::new(
+    "Auffällige und ausgeschlossene Messpunkte (Klick ins Diagramm schließt den nächsten aus oder ein)",
+    "Suspicious and excluded points (click the diagram to exclude or include the nearest one)",
+);
+pub const OUTLIERS_NONE: Localized = Localized::new(
+    "Keine auffälligen Messpunkte (Klick ins Diagramm schließt den nächsten aus oder ein)",
+    "No suspicious points (click the diagram to exclude or include the nearest one)",
+);
+pub const BUTTON_EXCLUDE_POINT: Localized = Localized::new("Ausschließen", "Exclude");
+pub const BUTTON_INCLUDE_POINT: Localized = Localized::new("Einbeziehen", "Include");
+pub const DATA_EDITOR_TITLE: Localized = Localized::new("Messwerte", "Data points");
+pub const DATA_EDITOR_VOLUME: Localized = Localized::new("V(Maßlösung) / mL", "V(titrant) / mL");
+pub const DATA_EDITOR_PH: Localized = Localized::new("pH (gemessen)", "pH (measured)");
+pub const DATA_EDITOR_CALCULATED: Localized = Localized::new("berechnet", "calculated");
+pub const DATA_EDITOR_HINT: Localized = Localized::new(
+    "Leere Zeilen werden übersprungen. Änderungen der Datei ersetzen die Bearbeitung.",
+    "Empty rows are skipped. Changes of the file replace the edits.",
+);
+pub const DATA_EDITOR_INVALID: Localized = Localized::new(
+    "Die Volumina müssen aufsteigend und nicht negativ, die pH-Werte Zahlen sein",
+    "The volumes have to be ascending and not negative, the pH values numbers",
+);
+pub const BUTTON_ADD_DATA_POINT: Localized =
+    Localized::new("Messpunkt hinzufügen", "Add data point");
+pub const BUTTON_RESET_DATA: Localized = Localized::new("Bearbeitung verwerfen", "Discard edits");
+pub const ANNOTATION_TITLE: Localized = Localized::new("Anmerkungen", "Annotations");
+pub const ANNOTATION_LABEL: Localized = Localized::new("Beschriftung", "Label");
+pub const ANNOTATION_VOLUME_MARKER: Localized = Localized::new("Volumenmarke", "Volume marker");
+pub const ANNOTATION_PH_LINE: Localized = Localized::new("pH-Linie", "pH line");
+pub const ANNOTATION_VOLUME: Localized = Localized::new("V / mL", "V / mL");
+pub const ANNOTATION_PH: Localized = Localized::new("pH", "pH");
+pub const ANNOTATION_TEXT: Localized = Localized::new("Text", "Text");
+pub const BUTTON_ADD_ANNOTATION: Localized = Localized::new("Hinzufügen", "Add");
+pub const STYLE_TITLE: Localized = Localized::new("Diagrammstil", "Diagram style");
+pub const STYLE_SERIES_COLORS: Localized = Localized::new("Farben der Messreihen", "Series colors");
+pub const STYLE_GRADIENT: Localized = Localized::new(
+    "Farbverlauf bei pH 0, 7 und 14",
+    "Gradient at pH 0, 7 and 14",
+);
+pub const STYLE_GRID: Localized = Localized::new("Gitternetz", "Grid");
+pub const STYLE_PALETTE: Localized = Localized::new("Farbpalette", "Color palette");
+pub const PALETTE_STANDARD: Localized = Localized::new("Standard", "Standard");
+pub const PALETTE_OKABE_ITO: Localized = Localized::new("Okabe-Ito", "Okabe-Ito");
+pub const PALETTE_TOL_BRIGHT: Localized = Localized::new("Tol (hell)", "Tol bright");
+pub const STYLE_HIGH_CONTRAST: Localized = Localized::new("Hoher Kontrast", "High contrast");
+pub const STYLE_POINT_SIZE: Localized = Localized::new("Punktgröße", "Point size");
+pub const STYLE_FONT_SIZE: Localized = Localized::new("Schriftgröße", "Font size");
+pub const BUTTON_RESET_STYLE: Localized = Localized::new("Zurücksetzen", "Reset");
+
+pub const GENERATOR_KEY_FILE_SUFFIX: Localized = Localized::new("loesung", "answers");
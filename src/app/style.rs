@@ -0,0 +1,577 @@
+use std::fmt::{self, Display, Write};
+
+use iced::{
+    widget::{button, checkbox, column, radio, slider, text, text_input, Row},
+    Element, Length,
+};
+
+use super::{diagram::SERIES_STYLES, strings::*, Message};
+
+/// The font size of axis labels the other texts of the diagram are scaled from.
+const DEFAULT_FONT_SIZE: f32 = 10.0;
+/// The factor texts and points are enlarged with while presenting.
+const PRESENTATION_SCALE: f32 = 1.5;
+
+/// A color of the diagram.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rgb(pub [u8; 3]);
+
+impl Rgb {
+    const BLACK: Self = Self([0x00, 0x00, 0x00]);
+    const WHITE: Self = Self([0xff, 0xff, 0xff]);
+
+    /// Parses a hex color like `#1f5fbf` or `1f5fbf`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let hex = text.trim().trim_start_matches('#');
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |index: usize| u8::from_str_radix(&hex[2 * index..2 * index + 2], 16).ok();
+        Some(Self([channel(0)?, channel(1)?, channel(2)?]))
+    }
+}
+
+impl Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [r, g, b] = self.0;
+        write!(f, "#{r:02x}{g:02x}{b:02x}")
+    }
+}
+
+/// The colors of a theme which are not configured by the user.
+struct Palette {
+    foreground: Rgb,
+    background: Rgb,
+    lines: [Rgb; SERIES_STYLES],
+    points: [Rgb; SERIES_STYLES],
+    confidence: Rgb,
+    selection: Rgb,
+}
+
+const LIGHT: Palette = Palette {
+    foreground: Rgb::BLACK,
+    background: Rgb::WHITE,
+    lines: [
+        Rgb([0x00, 0x00, 0xff]),
+        Rgb([0xd0, 0x00, 0x00]),
+        Rgb([0x00, 0x80, 0x00]),
+        Rgb([0xff, 0x8c, 0x00]),
+        Rgb([0x80, 0x00, 0xc0]),
+    ],
+    points: [
+        Rgb([0x00, 0x00, 0xff]),
+        Rgb([0xd0, 0x00, 0x00]),
+        Rgb([0x00, 0x80, 0x00]),
+        Rgb([0xff, 0x8c, 0x00]),
+        Rgb([0x80, 0x00, 0xc0]),
+    ],
+    confidence: Rgb([0x80, 0x80, 0x80]),
+    selection: Rgb([0x1f, 0x5f, 0xbf]),
+};
+
+const DARK: Palette = Palette {
+    foreground: Rgb::WHITE,
+    background: Rgb::BLACK,
+    lines: [
+        Rgb([0x00, 0x00, 0xff]),
+        Rgb([0xff, 0x40, 0x40]),
+        Rgb([0x00, 0xc0, 0x00]),
+        Rgb([0xff, 0xa5, 0x00]),
+        Rgb([0xc0, 0x60, 0xff]),
+    ],
+    points: [
+        Rgb([0x00, 0xff, 0xff]),
+        Rgb([0xff, 0x80, 0x80]),
+        Rgb([0x80, 0xff, 0x80]),
+        Rgb([0xff, 0xd0, 0x80]),
+        Rgb([0xe0, 0xb0, 0xff]),
+    ],
+    confidence: Rgb([0xc0, 0xc0, 0xc0]),
+    selection: Rgb([0x6c, 0xb4, 0xff]),
+};
+
+/// A preset of the series colors and the colored background.
+///
+/// The presets other than the standard colors can be told apart with the common forms of color
+/// blindness.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorPalette {
+    /// The colors of the light or dark theme with a red, yellow and green background.
+    #[default]
+    Standard,
+    /// The palette of Okabe and Ito.
+    OkabeIto,
+    /// The bright palette of Paul Tol.
+    TolBright,
+}
+
+impl ColorPalette {
+    pub const ALL: [Self; 3] = [Self::Standard, Self::OkabeIto, Self::TolBright];
+
+    pub fn label(self) -> Localized {
+        match self {
+            Self::Standard => PALETTE_STANDARD,
+            Self::OkabeIto => PALETTE_OKABE_ITO,
+            Self::TolBright => PALETTE_TOL_BRIGHT,
+        }
+    }
+
+    /// Returns the colors of the lines and points of the series, or `None` for the colors of the
+    /// theme.
+    fn series(self, dark: bool) -> Option<[Rgb; SERIES_STYLES]> {
+        match (self, dark) {
+            (Self::Standard, _) => None,
+            // Black is left out, so the series stay visible on the dark background.
+            (Self::OkabeIto, false) => Some([
+                Rgb([0x00, 0x72, 0xb2]),
+                Rgb([0xd5, 0x5e, 0x00]),
+                Rgb([0x00, 0x9e, 0x73]),
+                Rgb([0xe6, 0x9f, 0x00]),
+                Rgb([0xcc, 0x79, 0xa7]),
+            ]),
+            (Self::OkabeIto, true) => Some([
+                Rgb([0x56, 0xb4, 0xe9]),
+                Rgb([0xd5, 0x5e, 0x00]),
+                Rgb([0x00, 0x9e, 0x73]),
+                Rgb([0xf0, 0xe4, 0x42]),
+                Rgb([0xcc, 0x79, 0xa7]),
+            ]),
+            (Self::TolBright, false) => Some([
+                Rgb([0x44, 0x77, 0xaa]),
+                Rgb([0xee, 0x66, 0x77]),
+                Rgb([0x22, 0x88, 0x33]),
+                Rgb([0xcc, 0xbb, 0x44]),
+                Rgb([0xaa, 0x33, 0x77]),
+            ]),
+            (Self::TolBright, true) => Some([
+                Rgb([0x66, 0xcc, 0xee]),
+                Rgb([0xee, 0x66, 0x77]),
+                Rgb([0x22, 0x88, 0x33]),
+                Rgb([0xcc, 0xbb, 0x44]),
+                Rgb([0xaa, 0x33, 0x77]),
+            ]),
+        }
+    }
+
+    /// Returns the colors of the colored background at pH 0, 7 and 14.
+    pub fn gradient(self) -> [Rgb; 3] {
+        match self {
+            Self::Standard => [
+                Rgb([0xff, 0x00, 0x00]),
+                Rgb([0xff, 0xff, 0x00]),
+                Rgb([0x00, 0x80, 0x00]),
+            ],
+            Self::OkabeIto => [
+                Rgb([0xd5, 0x5e, 0x00]),
+                Rgb([0xf0, 0xe4, 0x42]),
+                Rgb([0x00, 0x72, 0xb2]),
+            ],
+            Self::TolBright => [
+                Rgb([0xee, 0x66, 0x77]),
+                Rgb([0xff, 0xff, 0xff]),
+                Rgb([0x44, 0x77, 0xaa]),
+            ],
+        }
+    }
+}
+
+/// The style of the diagram configured by the user.
+///
+/// Colors which are not configured follow the light or dark theme.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiagramStyle {
+    /// The colors replacing the line and point colors of the theme for each series.
+    pub series_colors: [Option<Rgb>; SERIES_STYLES],
+    /// The radius of data points in px.
+    pub point_size: f32,
+    /// Draws the grid lines behind the graph.
+    pub grid: bool,
+    /// The font size of axis labels in px, which the other texts are scaled with.
+    pub font_size: f32,
+    /// The colors of the colored background at pH 0, 7 and 14.
+    pub gradient: [Rgb; 3],
+    /// The preset of the series colors, which the configured colors replace.
+    pub palette: ColorPalette,
+    /// Draws thicker lines and larger points in the foreground color of the theme where possible.
+    pub high_contrast: bool,
+}
+
+impl Default for DiagramStyle {
+    fn default() -> Self {
+        Self {
+            series_colors: [None; SERIES_STYLES],
+            point_size: 1.0,
+            grid: true,
+            font_size: DEFAULT_FONT_SIZE,
+            gradient: ColorPalette::Standard.gradient(),
+            palette: ColorPalette::Standard,
+            high_contrast: false,
+        }
+    }
+}
+
+impl DiagramStyle {
+    /// Returns the line color of a series in the given theme.
+    pub fn line_color(&self, series: usize, dark: bool) -> Rgb {
+        let palette = if dark { &DARK } else { &LIGHT };
+        let series = series % SERIES_STYLES;
+        let preset = self.palette.series(dark).map(|it| it[series]);
+        self.series_colors[series]
+            .or(preset)
+            .unwrap_or(palette.lines[series])
+    }
+
+    /// Returns the color of the axes and texts in the given theme.
+    pub fn foreground_color(&self, dark: bool) -> Rgb {
+        if dark {
+            DARK.foreground
+        } else {
+            LIGHT.foreground
+        }
+    }
+
+    /// Returns the factor the widths of lines and the size of points are scaled with.
+    ///
+    /// High contrast doubles them.
+    pub fn line_weight(&self) -> f32 {
+        if self.high_contrast {
+            2.0
+        } else {
+            1.0
+        }
+    }
+
+    /// Returns the style of the diagram shown on a projector while presenting.
+    ///
+    /// Texts are enlarged and lines drawn in high contrast, so they can be read from the back of
+    /// a classroom.
+    pub fn presentation(&self) -> Self {
+        Self {
+            font_size: self.font_size * PRESENTATION_SCALE,
+            point_size: self.point_size * PRESENTATION_SCALE,
+            high_contrast: true,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the point color of a series in the given theme.
+    fn point_color(&self, series: usize, dark: bool) -> Rgb {
+        let palette = if dark { &DARK } else { &LIGHT };
+        let series = series % SERIES_STYLES;
+        let preset = self.palette.series(dark).map(|it| it[series]);
+        self.series_colors[series]
+            .or(preset)
+            .unwrap_or(palette.points[series])
+    }
+
+    /// Generates the stylesheet of the diagram.
+    pub fn css(&self, dark: bool) -> String {
+        let palette = if dark { &DARK } else { &LIGHT };
+        let foreground = palette.foreground;
+        let font = |scale: f32| format!("{}px sans-serif", self.font_size * scale);
+        let mut css = String::new();
+        let mut rule = |selector: &str, declarations: &[(&str, String)]| {
+            _ = writeln!(css, "{selector} {{");
+            for (property, value) in declarations {
+                _ = writeln!(css, "    {property}: {value};");
+            }
+            _ = writeln!(css, "}}\n");
+        };
+        let middle = || ("dominant-baseline", "middle".to_string());
+        let weight = self.line_weight();
+        let point_size = weight * self.point_size;
+        if self.grid {
+            let grid_color = if self.high_contrast {
+                foreground.to_string()
+            } else {
+                "#808080".into()
+            };
+            rule(
+                ".grid",
+                &[
+                    ("stroke", grid_color),
+                    ("stroke-width", format!("{}px", 0.5 * weight)),
+                ],
+            );
+        } else {
+            rule(".grid", &[("stroke", "none".into())]);
+        }
+        rule(
+            ".axis",
+            &[
+                ("fill", "none".into()),
+                ("stroke", foreground.to_string()),
+                ("stroke-width", format!("{weight}px")),
+            ],
+        );
+        for selector in [".text", ".axis-number"] {
+            rule(
+                selector,
+                &[
+                    ("fill", foreground.to_string()),
+                    ("font", font(1.0)),
+                    middle(),
+                ],
+            );
+        }
+        rule(
+            ".graph-line",
+            &[
+                ("stroke", self.line_color(0, dark).to_string()),
+                ("stroke-width", format!("{weight}px")),
+                ("fill", "none".into()),
+            ],
+        );
+        rule(
+            ".graph-point",
+            &[
+                ("fill", self.point_color(0, dark).to_string()),
+                ("r", format!("{point_size}px")),
+            ],
+        );
+        for series in 1..SERIES_STYLES {
+            rule(
+                &format!(".graph-line.series-{series}"),
+                &[("stroke", self.line_color(series, dark).to_string())],
+            );
+            rule(
+                &format!(".graph-point.series-{series}"),
+                &[("fill", self.point_color(series, dark).to_string())],
+            );
+        }
+        rule(
+            ".graph-point.excluded",
+            &[
+                ("fill", "none".into()),
+                ("stroke", "#808080".into()),
+                ("stroke-width", "0.5px".into()),
+                ("r", format!("{}px", 1.5 * point_size)),
+            ],
+        );
+        rule(
+            ".outlier-mark",
+            &[
+                ("fill", "none".into()),
+                ("stroke", "red".into()),
+                ("stroke-width", "0.75px".into()),
+                ("r", format!("{}px", 3.0 * point_size)),
+            ],
+        );
+        rule(
+            ".graph-line.secondary",
+            &[("stroke-dasharray", "4 2".into())],
+        );
+        rule(".graph-line.error-bar", &[("stroke-width", "0.5px".into())]);
+        rule(".graph-line.endpoint", &[("stroke-width", "0.5px".into())]);
+        rule(
+            ".graph-line.fit",
+            &[
+                ("stroke-width", "0.5px".into()),
+                ("stroke-dasharray", "1 2".into()),
+            ],
+        );
+        rule(
+            ".graph-line.activity",
+            &[("stroke-dasharray", "6 2".into())],
+        );
+        rule(
+            ".graph-point.highlight",
+            &[
+                ("fill", "red".into()),
+                ("r", format!("{}px", 3.0 * point_size)),
+            ],
+        );
+        rule(
+            ".ladder-rung",
+            &[
+                ("stroke", foreground.to_string()),
+                ("stroke-width", "2px".into()),
+            ],
+        );
+        rule(
+            ".ladder-text",
+            &[("fill", "#808080".into()), ("font", font(0.7)), middle()],
+        );
+        rule(
+            ".ladder-species",
+            &[
+                ("fill", foreground.to_string()),
+                ("font", font(0.9)),
+                middle(),
+            ],
+        );
+        rule(".anchor-end", &[("text-anchor", "end".into())]);
+        rule(".anchor-middle", &[("text-anchor", "middle".into())]);
+        rule(
+            ".overlay-box",
+            &[
+                ("fill", palette.background.to_string()),
+                (
+                    "fill-opacity",
+                    if self.high_contrast { "1" } else { "0.85" }.into(),
+                ),
+                ("stroke", foreground.to_string()),
+                ("stroke-width", "0.5px".into()),
+            ],
+        );
+        rule(
+            ".overlay-title",
+            &[
+                ("fill", foreground.to_string()),
+                ("font", format!("bold {}", font(1.0))),
+                middle(),
+            ],
+        );
+        rule(
+            ".overlay-text",
+            &[
+                ("fill", foreground.to_string()),
+                ("font", font(0.9)),
+                middle(),
+            ],
+        );
+        rule(
+            ".annotation-line",
+            &[
+                ("stroke", foreground.to_string()),
+                ("stroke-width", "0.5px".into()),
+                ("stroke-dasharray", "3 2".into()),
+            ],
+        );
+        rule(
+            ".annotation-text",
+            &[
+                ("fill", foreground.to_string()),
+                ("font", font(0.8)),
+                middle(),
+            ],
+        );
+        rule(
+            ".confidence",
+            &[
+                ("fill", palette.confidence.to_string()),
+                ("fill-opacity", "0.2".into()),
+            ],
+        );
+        rule(
+            ".selection",
+            &[
+                ("fill", palette.selection.to_string()),
+                ("fill-opacity", "0.15".into()),
+            ],
+        );
+        css
+    }
+}
+
+/// A color of the diagram style entered in the style editor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StyleColor {
+    Series(usize),
+    Gradient(usize),
+}
+
+/// The state of the style editor.
+///
+/// Like the fields of a [form](super::form), the colors keep the entered text. Empty series
+/// colors follow the theme.
+pub struct StyleForm {
+    series: [String; SERIES_STYLES],
+    gradient: [String; 3],
+}
+
+impl Default for StyleForm {
+    fn default() -> Self {
+        Self {
+            series: Default::default(),
+            gradient: DiagramStyle::default().gradient.map(|it| it.to_string()),
+        }
+    }
+}
+
+impl StyleForm {
+    /// Selects a palette and replaces the colors of the colored background with its colors.
+    pub fn select_palette(&mut self, palette: ColorPalette, style: &mut DiagramStyle) {
+        style.palette = palette;
+        style.gradient = palette.gradient();
+        self.gradient = style.gradient.map(|it| it.to_string());
+    }
+
+    /// Changes a color and applies it to the style if it is valid.
+    pub fn set(&mut self, color: StyleColor, value: String, style: &mut DiagramStyle) {
+        match color {
+            StyleColor::Series(series) => {
+                if value.trim().is_empty() {
+                    style.series_colors[series] = None;
+                } else if let Some(rgb) = Rgb::parse(&value) {
+                    style.series_colors[series] = Some(rgb);
+                }
+                self.series[series] = value;
+            }
+            StyleColor::Gradient(stop) => {
+                if let Some(rgb) = Rgb::parse(&value) {
+                    style.gradient[stop] = rgb;
+                }
+                self.gradient[stop] = value;
+            }
+        }
+    }
+
+    /// Creates the style editor.
+    ///
+    /// Empty series colors show the color of the theme as their placeholder.
+    pub fn view(&self, style: &DiagramStyle, dark: bool) -> Element<'_, Message> {
+        let color_input = |placeholder: String, value: &str, color| {
+            text_input(&placeholder, value)
+                .on_input(move |it| Message::StyleColorInput(color, it))
+                .width(Length::Fixed(80.0))
+                .into()
+        };
+        let series = (0..SERIES_STYLES).map(|series| {
+            color_input(
+                style.line_color(series, dark).to_string(),
+                &self.series[series],
+                StyleColor::Series(series),
+            )
+        });
+        let gradient = (0..3).map(|stop| {
+            color_input(
+                String::new(),
+                &self.gradient[stop],
+                StyleColor::Gradient(stop),
+            )
+        });
+        let palettes = ColorPalette::ALL.map(|it| {
+            radio(it.label(), it, Some(style.palette), Message::SelectPalette)
+                .size(12)
+                .text_size(12)
+                .into()
+        });
+        column![
+            text(STYLE_TITLE).size(20),
+            text(STYLE_PALETTE).size(12),
+            Row::with_children(palettes.into_iter().collect::<Vec<_>>()).spacing(10),
+            checkbox(
+                STYLE_HIGH_CONTRAST,
+                style.high_contrast,
+                Message::SetHighContrast
+            ),
+            text(STYLE_SERIES_COLORS).size(12),
+            Row::with_children(series.collect::<Vec<_>>()).spacing(5),
+            text(STYLE_GRADIENT).size(12),
+            Row::with_children(gradient.collect::<Vec<_>>()).spacing(5),
+            checkbox(STYLE_GRID, style.grid, Message::SetGrid),
+            text(format!("{STYLE_POINT_SIZE}: {} px", style.point_size)).size(12),
+            slider(0.5..=4.0, style.point_size, Message::SetPointSize)
+                .step(0.5)
+                .width(Length::Fixed(200.0)),
+            text(format!("{STYLE_FONT_SIZE}: {} px", style.font_size)).size(12),
+            slider(6.0..=16.0, style.font_size, Message::SetFontSize)
+                .step(1.0)
+                .width(Length::Fixed(200.0)),
+            button(BUTTON_RESET_STYLE.get()).on_press(Message::ResetStyle),
+        ]
+        .spacing(5)
+        .into()
+    }
+}
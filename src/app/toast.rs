@@ -0,0 +1,124 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use iced::{
+    widget::{button, container, row, text, Column},
+    Alignment, Color, Element, Length, Theme,
+};
+
+use super::{strings::*, Message};
+
+/// The time an info is shown before it disappears on its own.
+///
+/// Errors stay until they are dismissed.
+const INFO_DURATION: Duration = Duration::from_secs(5);
+/// The number of notifications shown at once, the oldest ones are dropped first.
+const MAX_TOASTS: usize = 4;
+
+/// The kind of a notification, which decides its color and how long it is shown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Error,
+}
+
+/// A notification shown above the content.
+#[derive(Debug)]
+struct Toast {
+    id: u64,
+    kind: ToastKind,
+    text: String,
+    shown_at: Instant,
+}
+
+/// The queue of notifications about errors and finished actions.
+///
+/// The notifications are shown above the content, so the diagram stays visible.
+#[derive(Debug, Default)]
+pub struct Toasts {
+    next_id: u64,
+    queue: VecDeque<Toast>,
+}
+
+impl Toasts {
+    pub fn info(&mut self, text: impl Into<String>) {
+        self.push(ToastKind::Info, text.into());
+    }
+
+    pub fn error(&mut self, text: impl Into<String>) {
+        self.push(ToastKind::Error, text.into());
+    }
+
+    /// Shows a notification.
+    ///
+    /// A notification with the same text is replaced, so a file which fails to load again and
+    /// again is only reported once.
+    fn push(&mut self, kind: ToastKind, text: String) {
+        self.queue
+            .retain(|it| !(it.kind == kind && it.text == text));
+        if self.queue.len() == MAX_TOASTS {
+            self.queue.pop_front();
+        }
+        self.queue.push_back(Toast {
+            id: self.next_id,
+            kind,
+            text,
+            shown_at: Instant::now(),
+        });
+        self.next_id += 1;
+    }
+
+    pub fn dismiss(&mut self, id: u64) {
+        self.queue.retain(|it| it.id != id);
+    }
+
+    /// Removes the infos which were shown long enough.
+    pub fn expire(&mut self) {
+        self.queue
+            .retain(|it| it.kind == ToastKind::Error || it.shown_at.elapsed() < INFO_DURATION);
+    }
+
+    /// Checks if an info will disappear on its own, which requires regular ticks.
+    pub fn expiring(&self) -> bool {
+        self.queue.iter().any(|it| it.kind == ToastKind::Info)
+    }
+
+    /// Creates the list of notifications, if there are any.
+    pub fn view(&self) -> Option<Element<'_, Message>> {
+        if self.queue.is_empty() {
+            return None;
+        }
+        let toasts = self.queue.iter().rev().map(|toast| {
+            let (label, color) = match toast.kind {
+                ToastKind::Info => (TOAST_INFO, Color::from_rgb8(0x21, 0x66, 0xac)),
+                ToastKind::Error => (TOAST_ERROR, Color::from_rgb8(0xb2, 0x18, 0x2b)),
+            };
+            let content = row![
+                text(format!("{label}: {}", toast.text))
+                    .size(12)
+                    .width(Length::Fill),
+                button(text("×").size(12))
+                    .on_press(Message::DismissToast(toast.id))
+                    .padding([0, 6]),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center);
+            container(content)
+                .width(Length::Fill)
+                .padding([4, 8])
+                .style(move |_: &Theme| container::Appearance {
+                    text_color: Some(Color::WHITE),
+                    background: Some(color.into()),
+                    ..Default::default()
+                })
+                .into()
+        });
+        Some(
+            Column::with_children(toasts.collect::<Vec<_>>())
+                .spacing(4)
+                .into(),
+        )
+    }
+}
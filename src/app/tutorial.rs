@@ -0,0 +1,109 @@
+use std::{fs, path::PathBuf, sync::Arc};
+
+use tracing::warn;
+
+use crate::{
+    util::config_dir,
+    worker::{
+        analysis::Stoichiometry, model::StrongModel, temperature::STANDARD_TEMPERATURE,
+        uncertainty::Uncertainty, Input, Output,
+    },
+};
+
+use super::{strings::*, Message};
+
+/// The file in the config directory marking the tutorial as done.
+const DONE_FILE_NAME: &str = "tutorial-done";
+
+/// A step of the first-run tutorial.
+///
+/// Every step highlights a part of the UI and is completed by using it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TutorialStep {
+    SelectFile,
+    ToggleTheme,
+    ReadEquivalence,
+    Export,
+}
+
+impl TutorialStep {
+    pub const FIRST: Self = Self::SelectFile;
+
+    pub fn next(self) -> Option<Self> {
+        match self {
+            Self::SelectFile => Some(Self::ToggleTheme),
+            Self::ToggleTheme => Some(Self::ReadEquivalence),
+            Self::ReadEquivalence => Some(Self::Export),
+            Self::Export => None,
+        }
+    }
+
+    /// Returns the instruction shown for the step.
+    pub fn instruction(self) -> Localized {
+        match self {
+            Self::SelectFile => TUTORIAL_SELECT_FILE,
+            Self::ToggleTheme => TUTORIAL_TOGGLE_THEME,
+            Self::ReadEquivalence => TUTORIAL_READ_EQUIVALENCE,
+            Self::Export => TUTORIAL_EXPORT,
+        }
+    }
+
+    /// Checks if the message completes the step.
+    ///
+    /// Steps without an action are completed with the next button.
+    pub fn is_completed_by(self, message: &Message) -> bool {
+        match self {
+            Self::SelectFile => matches!(message, Message::SelectFile),
+            Self::ToggleTheme => matches!(message, Message::SetDark(_)),
+            Self::ReadEquivalence => false,
+            Self::Export => matches!(
+                message,
+                Message::CopyDiagram
+                    | Message::ExportDiagram
+                    | Message::ExportSolution
+                    | Message::ExportHtml
+            ),
+        }
+    }
+}
+
+/// Checks if the tutorial was not completed or skipped yet.
+pub fn is_first_run() -> bool {
+    config_dir().is_some_and(|it| !it.join(DONE_FILE_NAME).exists())
+}
+
+/// Remembers that the tutorial was completed or skipped.
+pub fn mark_done() {
+    let Some(dir) = config_dir() else {
+        return;
+    };
+    if let Err(err) =
+        fs::create_dir_all(&dir).and_then(|()| fs::write(dir.join(DONE_FILE_NAME), ""))
+    {
+        warn!("Could not mark the tutorial as done: {err}");
+    }
+}
+
+/// Returns the sample dataset shown during the tutorial.
+///
+/// It is not backed by a file, so the worker never reloads it.
+pub fn sample() -> (PathBuf, Arc<Output>) {
+    let input = Input {
+        t_v: 0.01,
+        t_c: 0.1,
+        m_c: 0.1,
+        m_v: (0..=40).map(|it| it as f32 * 0.5).collect(),
+        temperature: STANDARD_TEMPERATURE,
+        temperatures: Vec::new(),
+        uncertainty: Uncertainty::default(),
+        stoichiometry: Stoichiometry::default(),
+        second_acid: None,
+        titrant: None,
+        analyte: None,
+        model: &StrongModel,
+    };
+    (
+        PathBuf::from(TUTORIAL_SAMPLE_NAME.get()),
+        Arc::new(input.calculate_output()),
+    )
+}
@@ -0,0 +1,87 @@
+use std::{
+    fmt::{self, Display},
+    path::{Path, PathBuf},
+};
+
+pub const USAGE: &str = "\
+Usage: titration-curve [OPTIONS] [FILE]...
+       titration-curve doctor
+
+Commands:
+  doctor          Check the environment and print a report for bug reports
+
+Arguments:
+  [FILE]...       Load the files, in the running instance if there is one
+
+Options:
+  --safe-mode     Start with default preferences, no session restore and the poll watcher
+  --new-instance  Start another instance instead of using the running one
+  --doctor        Run the doctor command, e.g. if a file is named `doctor`
+  -h, --help      Print this help";
+
+/// The parsed command line arguments.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Args {
+    /// Starts the app in safe mode.
+    pub safe_mode: bool,
+    /// Prints the usage instead of starting the app.
+    pub help: bool,
+    /// Runs the self-test instead of starting the app.
+    pub doctor: bool,
+    /// Starts another instance even if one is running already.
+    pub new_instance: bool,
+    /// The files loaded at the start.
+    pub files: Vec<PathBuf>,
+}
+
+impl Args {
+    /// Parses the arguments, without the name of the executable.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, ArgsError> {
+        let mut parsed = Self::default();
+        for (index, arg) in args.into_iter().enumerate() {
+            match arg.as_str() {
+                "--safe-mode" => parsed.safe_mode = true,
+                "-h" | "--help" => parsed.help = true,
+                "--new-instance" => parsed.new_instance = true,
+                "--doctor" => parsed.doctor = true,
+                // Only the first argument is the command, unless it names a file.
+                "doctor" if index == 0 && !Path::new(&arg).exists() => parsed.doctor = true,
+                _ if arg.starts_with('-') => return Err(ArgsError::UnknownArgument(arg)),
+                _ => parsed.files.push(PathBuf::from(arg)),
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+/// Attaches the output to the console the app was started from.
+///
+/// The app is built for the GUI subsystem on Windows, so it has no console of its own and the
+/// output of the commands would be lost otherwise. Other systems always keep the console.
+pub fn attach_console() {
+    #[cfg(windows)]
+    {
+        const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+        #[link(name = "kernel32")]
+        extern "system" {
+            fn AttachConsole(process_id: u32) -> i32;
+        }
+        // Without a parent console, e.g. when started from the explorer, nothing is shown.
+        unsafe {
+            AttachConsole(ATTACH_PARENT_PROCESS);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ArgsError {
+    UnknownArgument(String),
+}
+
+impl Display for ArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownArgument(arg) => write!(f, "unknown argument `{arg}`"),
+        }
+    }
+}
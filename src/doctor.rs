@@ -0,0 +1,137 @@
+//! The `doctor` command, which checks if the environment supports the app.
+//!
+//! The report is meant to be pasted into bug reports.
+
+use std::{
+    env, fs,
+    path::Path,
+    process,
+    sync::{mpsc::channel, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail, Result};
+use notify::EventKind;
+
+use crate::{
+    app::{
+        diagram::{self, GraphState},
+        options::Options,
+    },
+    util::config_dir,
+    worker::{
+        generator::{Problem, ProblemRanges},
+        watch::{FileWatcher, WatcherBackend},
+    },
+};
+
+/// A check returning details on success.
+type Check = fn() -> Result<String>;
+
+/// The time the watcher gets to report a change.
+const WATCHER_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Runs all checks and prints the report.
+///
+/// Returns whether all checks passed.
+pub fn run() -> bool {
+    println!("titration-curve {}", env!("CARGO_PKG_VERSION"));
+    println!("os: {} ({})", env::consts::OS, env::consts::ARCH);
+    let checks: [(&str, Check); 4] = [
+        ("file dialog", check_file_dialog),
+        ("watcher", check_watcher),
+        ("svg rendering", check_rendering),
+        ("config directory", check_config_dir),
+    ];
+    let mut passed = true;
+    for (name, check) in checks {
+        match check() {
+            Ok(details) => println!("[ok]   {name}: {details}"),
+            Err(err) => {
+                println!("[fail] {name}: {err:#}");
+                passed = false;
+            }
+        }
+    }
+    passed
+}
+
+/// Checks if a file dialog can be shown.
+///
+/// The dialog is not opened, since that would need user interaction. On Linux and BSD it needs a
+/// graphical session.
+fn check_file_dialog() -> Result<String> {
+    if cfg!(any(windows, target_os = "macos")) {
+        return Ok("native dialog".into());
+    }
+    for var in ["WAYLAND_DISPLAY", "DISPLAY"] {
+        if let Some(display) = env::var_os(var) {
+            return Ok(format!("{var}={}", display.to_string_lossy()));
+        }
+    }
+    bail!("neither WAYLAND_DISPLAY nor DISPLAY is set")
+}
+
+/// Checks if the watcher reports a change of a file in a temporary directory.
+fn check_watcher() -> Result<String> {
+    let dir = env::temp_dir().join(format!("titration-curve-doctor-{}", process::id()));
+    fs::create_dir_all(&dir)?;
+    let result = check_watcher_in(&dir);
+    _ = fs::remove_dir_all(&dir);
+    result
+}
+
+fn check_watcher_in(dir: &Path) -> Result<String> {
+    let file = dir.join("table.xlsx");
+    fs::write(&file, "before")?;
+    let (sender, receiver) = channel();
+    let sender = Mutex::new(sender);
+    let mut watcher = FileWatcher::new(
+        WatcherBackend::Native,
+        Arc::new(move |res| _ = sender.lock().unwrap().send(res)),
+    )?;
+    watcher.watch(&file)?;
+    // Let the watcher take its first snapshot, and make sure the modification time changes.
+    thread::sleep(Duration::from_millis(1100));
+    let started = Instant::now();
+    fs::write(&file, "after the change")?;
+    while let Some(timeout) = WATCHER_TIMEOUT.checked_sub(started.elapsed()) {
+        let event = receiver.recv_timeout(timeout)??;
+        if matches!(event.kind, EventKind::Modify(_)) {
+            return Ok(format!(
+                "{:?} watcher reported a change after {:?}",
+                watcher.backend(),
+                started.elapsed()
+            ));
+        }
+    }
+    Err(anyhow!("no change was reported within {WATCHER_TIMEOUT:?}"))
+}
+
+/// Checks if a diagram of a generated problem can be rendered.
+fn check_rendering() -> Result<String> {
+    let problem = Problem::generate(1, &ProblemRanges::default());
+    let output = problem.input.calculate_output();
+    let started = Instant::now();
+    let svg = diagram::render_graph(&Options::default(), &[&output], &[], GraphState::default());
+    let elapsed = started.elapsed();
+    if !svg.starts_with("<svg") || !svg.contains("graph-point") {
+        bail!("the rendered diagram is incomplete");
+    }
+    Ok(format!(
+        "{} points, {} bytes in {elapsed:?}",
+        output.items.len(),
+        svg.len()
+    ))
+}
+
+/// Checks if files can be written to the config directory.
+fn check_config_dir() -> Result<String> {
+    let dir = config_dir().ok_or_else(|| anyhow!("the config directory is unknown"))?;
+    fs::create_dir_all(&dir)?;
+    let file = dir.join(".doctor");
+    fs::write(&file, "")?;
+    fs::remove_file(&file)?;
+    Ok(dir.display().to_string())
+}
@@ -0,0 +1,185 @@
+use std::{
+    collections::hash_map::RandomState,
+    env,
+    fs::{self, OpenOptions},
+    hash::{BuildHasher, Hasher},
+    io::{self, BufRead, BufReader, Write},
+    net::{Ipv4Addr, Shutdown, SocketAddr, TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use iced::futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use tracing::{debug, info, warn};
+
+use crate::util::config_dir;
+
+/// The file in the configuration directory storing the port and the token of the running
+/// instance.
+const PORT_FILE_NAME: &str = "instance-port";
+/// The start of the first line sent by a new instance, which is followed by the token.
+const GREETING: &str = "titration-curve";
+/// The answer of the running instance once it received the paths.
+const ACKNOWLEDGEMENT: &str = "ok";
+/// The time a new instance waits for the running one, which may be busy or gone.
+const TIMEOUT: Duration = Duration::from_millis(500);
+
+/// The outcome of starting the app while another instance may be running.
+pub enum Instance {
+    /// No other instance is running, so this one receives the paths of later launches if the
+    /// listener could be opened.
+    First(Option<InstanceListener>),
+    /// The paths were sent to the running instance, so this one should exit.
+    Forwarded,
+}
+
+/// Sends the paths to the running instance, or makes this the running instance.
+///
+/// The instances talk over a TCP socket on the loopback interface, whose port is stored in the
+/// configuration directory. Any local program can connect to the port, so the running instance
+/// only accepts paths sent with the random token stored next to the port, in a file only the
+/// user can read.
+pub fn acquire(paths: &[PathBuf]) -> Instance {
+    let Some(dir) = config_dir() else {
+        return Instance::First(None);
+    };
+    let port_file = dir.join(PORT_FILE_NAME);
+    let running = fs::read_to_string(&port_file).ok().and_then(|it| {
+        let (port, token) = it.trim().split_once('\n')?;
+        Some((port.parse::<u16>().ok()?, token.to_owned()))
+    });
+    if let Some((port, token)) = running {
+        match forward(port, &token, paths) {
+            Ok(()) => {
+                info!(port, "Opened the files in the running instance");
+                return Instance::Forwarded;
+            }
+            // The port file is left over from an instance which did not exit cleanly.
+            Err(err) => debug!(port, "No running instance: {err}"),
+        }
+    }
+    match InstanceListener::bind(&port_file) {
+        Ok(listener) => Instance::First(Some(listener)),
+        Err(err) => {
+            warn!("Could not listen for other instances: {err}");
+            Instance::First(None)
+        }
+    }
+}
+
+/// Sends the paths to the instance listening on the port and waits until it received them.
+fn forward(port: u16, token: &str, paths: &[PathBuf]) -> io::Result<()> {
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let mut stream = TcpStream::connect_timeout(&address, TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    // The running instance has another working directory.
+    let current_dir = env::current_dir()?;
+    let mut message = format!("{GREETING} {token}\n");
+    for path in paths {
+        message.push_str(&current_dir.join(path).to_string_lossy());
+        message.push('\n');
+    }
+    stream.write_all(message.as_bytes())?;
+    stream.shutdown(Shutdown::Write)?;
+    let mut answer = String::new();
+    BufReader::new(stream).read_line(&mut answer)?;
+    if answer.trim_end() != ACKNOWLEDGEMENT {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "unexpected answer",
+        ));
+    }
+    Ok(())
+}
+
+/// The socket the running instance receives the paths of later launches on.
+#[derive(Debug)]
+pub struct InstanceListener {
+    listener: TcpListener,
+    /// The token other instances have to send along with the paths.
+    token: String,
+}
+
+impl InstanceListener {
+    /// Listens on a free port and stores it with a new token in the port file.
+    fn bind(port_file: &Path) -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+        let port = listener.local_addr()?.port();
+        let token = token();
+        if let Some(dir) = port_file.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // The file is created again, as the permissions only apply to new files.
+        match fs::remove_file(port_file) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        options
+            .open(port_file)?
+            .write_all(format!("{port}\n{token}").as_bytes())?;
+        debug!(port, "Listening for other instances");
+        Ok(Self { listener, token })
+    }
+
+    /// Accepts other instances on its own thread and sends the paths they forward.
+    pub fn spawn(self) -> UnboundedReceiver<Vec<PathBuf>> {
+        let (sender, receiver) = unbounded();
+        thread::Builder::new()
+            .name("instance".into())
+            .spawn(move || self.accept(&sender))
+            .expect("spawn instance thread");
+        receiver
+    }
+
+    /// Accepts other instances until the app exits.
+    fn accept(self, sender: &UnboundedSender<Vec<PathBuf>>) {
+        for stream in self.listener.incoming() {
+            let paths = stream.and_then(|it| receive(it, &self.token));
+            match paths {
+                Ok(Some(paths)) => {
+                    if sender.unbounded_send(paths).is_err() {
+                        return;
+                    }
+                }
+                Ok(None) => debug!("Ignored a connection without the greeting and the token"),
+                Err(err) => warn!("Could not receive the paths of another instance: {err}"),
+            }
+        }
+    }
+}
+
+/// Reads the paths sent by another instance and acknowledges them.
+///
+/// Returns `None` if the connection does not come from another instance of the user, i.e. it
+/// does not send the token.
+fn receive(stream: TcpStream, token: &str) -> io::Result<Option<Vec<PathBuf>>> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    let mut lines = BufReader::new(&stream).lines();
+    if lines.next().transpose()? != Some(format!("{GREETING} {token}")) {
+        return Ok(None);
+    }
+    let paths = lines
+        .filter(|it| it.as_ref().map_or(true, |it| !it.is_empty()))
+        .map(|it| it.map(PathBuf::from))
+        .collect::<io::Result<_>>()?;
+    (&stream).write_all(format!("{ACKNOWLEDGEMENT}\n").as_bytes())?;
+    Ok(Some(paths))
+}
+
+/// Returns a random token of 128 bits as a hex string.
+///
+/// The hashers of the standard library are seeded with random keys from the operating system.
+fn token() -> String {
+    (0..2u64)
+        .map(|index| {
+            let mut hasher = RandomState::new().build_hasher();
+            hasher.write_u64(index);
+            format!("{:016x}", hasher.finish())
+        })
+        .collect()
+}
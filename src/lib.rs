@@ -0,0 +1,7 @@
+//! The calculation of titration curves, without the user interface.
+//!
+//! The app is built on top of this library, which also makes the calculation available to the
+//! benchmarks.
+
+pub mod util;
+pub mod worker;
@@ -0,0 +1,113 @@
+use std::{
+    collections::VecDeque,
+    fmt::{self, Write},
+    sync::Mutex,
+};
+
+use tracing::{
+    field::{Field, Visit},
+    Event, Level, Subscriber,
+};
+use tracing_subscriber::{layer::Context, prelude::*, registry::LookupSpan, EnvFilter, Layer};
+
+/// The environment variable overriding the default log filter.
+pub const FILTER_VARIABLE: &str = "TITRATION_CURVE_LOG";
+/// The filter used if the environment variable is not set.
+const DEFAULT_FILTER: &str = "titration_curve=info";
+/// The maximum number of entries kept for the log panel.
+const MAX_ENTRIES: usize = 500;
+
+/// The recent log entries, shown in the log panel of the app.
+static ENTRIES: Mutex<VecDeque<LogEntry>> = Mutex::new(VecDeque::new());
+
+/// A log event kept for the log panel.
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    pub level: Level,
+    pub target: &'static str,
+    /// The names of the spans the event happened in, separated by colons.
+    pub spans: String,
+    pub message: String,
+}
+
+/// Sets up logging to stderr and to the log panel.
+///
+/// The filter is read from [`FILTER_VARIABLE`] in the `env_logger` syntax, e.g.
+/// `titration_curve::worker=debug`.
+pub fn init() {
+    let filter =
+        EnvFilter::try_from_env(FILTER_VARIABLE).unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    let result = tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+        .with(PanelLayer)
+        .try_init();
+    if let Err(err) = result {
+        eprintln!("Could not set up logging: {err}");
+    }
+}
+
+/// Returns up to `count` of the most recent log entries, oldest first.
+pub fn recent(count: usize) -> Vec<LogEntry> {
+    let entries = ENTRIES.lock().unwrap();
+    let skip = entries.len().saturating_sub(count);
+    entries.iter().skip(skip).cloned().collect()
+}
+
+/// Keeps the recent events for the log panel.
+struct PanelLayer;
+
+impl<S> Layer<S> for PanelLayer
+where
+    S: Subscriber + for<'lookup> LookupSpan<'lookup>,
+{
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut spans = String::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                if !spans.is_empty() {
+                    spans.push(':');
+                }
+                spans.push_str(span.name());
+            }
+        }
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        let entry = LogEntry {
+            level: *metadata.level(),
+            target: metadata.target(),
+            spans,
+            message: visitor.finish(),
+        };
+        let mut entries = ENTRIES.lock().unwrap();
+        if entries.len() == MAX_ENTRIES {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+}
+
+/// Formats the message and the fields of an event on a single line.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn finish(mut self) -> String {
+        self.message.push_str(&self.fields);
+        self.message
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            _ = write!(self.message, "{value:?}");
+        } else {
+            _ = write!(self.fields, " {}={value:?}", field.name());
+        }
+    }
+}
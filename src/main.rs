@@ -1,18 +1,72 @@
 #![windows_subsystem = "windows"]
 
 pub mod app;
-pub mod util;
-pub mod worker;
+pub mod cli;
+pub mod doctor;
+pub mod instance;
+pub mod log;
 
-use app::TitrationCurve;
-use iced::{Application, Settings, Size};
+use std::{env, process::ExitCode, sync::Arc};
 
-fn main() -> iced::Result {
-    TitrationCurve::run(Settings {
+use titration_curve::{util, worker};
+
+use app::{StartupFlags, TitrationCurve};
+use cli::{Args, USAGE};
+use iced::{futures::lock::Mutex, multi_window::Application, Settings, Size};
+use instance::Instance;
+
+fn main() -> ExitCode {
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            cli::attach_console();
+            eprintln!("error: {err}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    if args.help || args.doctor {
+        cli::attach_console();
+    }
+    if args.help {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+    log::init();
+    if args.doctor {
+        return if doctor::run() {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        };
+    }
+    // Safe mode is meant to rule out the configuration, so it never uses the running instance.
+    let listener = if args.new_instance || args.safe_mode {
+        None
+    } else {
+        match instance::acquire(&args.files) {
+            Instance::First(listener) => listener,
+            Instance::Forwarded => return ExitCode::SUCCESS,
+        }
+    };
+    let result = TitrationCurve::run(Settings {
         window: iced::window::Settings {
             min_size: Some(Size::new(880.0, 660.0)),
+            // The app closes the window itself after asking the worker to stop.
+            exit_on_close_request: false,
             ..Default::default()
         },
+        flags: StartupFlags {
+            safe_mode: args.safe_mode,
+            files: args.files,
+            forwarded: listener.map(|it| Arc::new(Mutex::new(it.spawn()))),
+        },
         ..Default::default()
-    })
+    });
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -1,3 +1,5 @@
+use std::{env, ops::RangeInclusive, path::PathBuf};
+
 pub use self::Either::*;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -5,3 +7,54 @@ pub enum Either<L, R> {
     Left(L),
     Right(R),
 }
+
+/// A small seeded random number generator (SplitMix64).
+///
+/// The same seed always produces the same sequence of numbers.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `0.0..1.0`.
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Returns a number in the given range.
+    pub fn range(&mut self, range: RangeInclusive<f32>) -> f32 {
+        range.start() + (range.end() - range.start()) * self.next_f32()
+    }
+
+    /// Returns an index below `len`.
+    pub fn index(&mut self, len: usize) -> usize {
+        (self.next_u64() % len as u64) as usize
+    }
+}
+
+/// Returns the directory the configuration of the app is stored in.
+///
+/// The directory may not exist yet.
+pub fn config_dir() -> Option<PathBuf> {
+    let base = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|it| PathBuf::from(it).join("Library/Application Support"))
+    } else {
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|it| PathBuf::from(it).join(".config")))
+    };
+    base.map(|it| it.join("titration-curve"))
+}
@@ -1,18 +1,83 @@
+pub mod activity;
+pub mod analysis;
+pub mod buffer;
+pub mod calibration;
+pub mod complexometry;
+pub mod conductivity;
+pub mod equilibrium;
+pub mod explain;
+pub mod fit;
+pub mod format;
+pub mod generator;
+pub mod gran;
+pub mod hagg;
+pub mod indicator;
+#[cfg(feature = "live")]
+pub mod live;
+pub mod locale;
+pub mod metadata;
+pub mod mixture;
+pub mod model;
+pub mod parser;
+pub mod precipitation;
+#[cfg(feature = "scripting")]
+pub mod script;
+pub mod simulation;
+pub mod substances;
+pub mod table;
+pub mod tail;
+pub mod temperature;
+pub mod trace;
+pub mod uncertainty;
+pub mod units;
+pub mod validation;
+pub mod watch;
+
 use std::{
+    collections::BTreeMap,
     fmt::{Debug, Display},
-    path::PathBuf,
+    fs, io,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
     sync::{
         mpsc::{channel, Receiver, Sender},
         Arc, Mutex,
     },
-    thread,
-    time::Duration,
+    thread::{self, JoinHandle},
+    time::{Duration, Instant, SystemTime},
 };
 
 use anyhow::Result;
-use calamine::Reader;
-use notify::{Event, EventKind, RecursiveMode, Watcher};
+use iced::futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
 use rfd::FileDialog;
+use rust_xlsxwriter::XlsxError;
+use serde::Serialize;
+use tracing::{debug, debug_span, error, info, info_span, trace, warn};
+
+use self::{
+    analysis::Stoichiometry,
+    equilibrium::{Component, Solution},
+    explain::{number, CalculationStep, Quantity, Recorder},
+    mixture::SecondAcid,
+    model::{StrongModel, TitrationModel},
+    parser::{CellError, Field},
+    substances::Substance,
+    table::TableExport,
+    tail::CsvTail,
+    temperature::STANDARD_TEMPERATURE,
+    trace::{response_summary, signal_summary, Trace, TraceEvent},
+    uncertainty::Uncertainty,
+    watch::{Debounce, EventFilter, FileStamp, FileWatcher, WatchedFolder, WatcherBackend},
+};
+
+/// The maximum time an update is delayed by files which keep changing.
+const MAX_DEBOUNCE_WAIT: Duration = Duration::from_secs(5);
+/// The interval in which a stopping worker is checked for having returned.
+const JOIN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+/// The number of items from which a curve is calculated in parallel.
+///
+/// Smaller curves are calculated faster than the threads are scheduled.
+const PARALLEL_ITEMS: usize = 1024;
 
 /// ## Signals
 ///
@@ -24,6 +89,17 @@ use rfd::FileDialog;
 /// file would be loaded two times in a row.
 ///
 /// If the worker is busy with a new file, update signals will be ignored.
+///
+/// ## Files
+///
+/// Bursts of watcher events are coalesced by waiting until the files stay unchanged for the
+/// debounce window. Files whose modification time and size did not change are not reloaded.
+///
+/// In the tail mode, only the rows appended to CSV files since they were last read are parsed,
+/// which keeps the state of every such file in the worker.
+///
+/// The worker can watch multiple files at once. Every file produces its own output, which is sent
+/// to the app together with the path of the file.
 pub struct Worker {
     /// This flag is true as long as the worker is alive.
     alive: Mutex<bool>,
@@ -32,23 +108,63 @@ pub struct Worker {
     /// The mutex remains locked while sending a signal.
     ///
     /// The value in the mutex represents the lock to be used for skipping signals.
-    signal_lock: Mutex<Option<Signal>>,
+    signal_lock: Mutex<Option<SignalLock>>,
     /// This sender is used to send signals to the worker.
     signal_sender: Sender<Signal>,
     /// This sender is used to send responses to the app.
-    response_sender: Sender<Response>,
+    ///
+    /// The app awaits the responses asynchronously, so it does not have to poll for them.
+    response_sender: UnboundedSender<Response>,
+    /// The filter applied to watcher events before signaling an update.
+    event_filter: Mutex<EventFilter>,
+    /// The backend the watcher is created with, if it is available.
+    preferred_backend: WatcherBackend,
+    /// The backend of the active watcher, once it is created.
+    watcher_backend: Mutex<Option<WatcherBackend>>,
+    /// The window in which watcher events are coalesced.
+    debounce: Mutex<Debounce>,
+    /// The working temperature in °C the loaded files are calculated at.
+    temperature: Mutex<f32>,
+    /// The model the loaded files are calculated with.
+    model: Mutex<&'static dyn TitrationModel>,
+    /// Reads only the rows appended to CSV files instead of parsing them again.
+    tail: Mutex<bool>,
+    /// The time of the last watcher event which passed the filter.
+    last_event: Mutex<Option<Instant>>,
+    /// The trace of signals, responses and watcher events shown in the debug overlay.
+    trace: Trace,
+    /// The thread of the worker, until it is joined.
+    thread: Mutex<Option<JoinHandle<()>>>,
+    /// Whether the file being parsed should be left unloaded.
+    ///
+    /// This is set right when [`Signal::Cancel`] is sent, because the worker only receives the
+    /// signal after the parse.
+    cancelled: Mutex<bool>,
 }
 
 impl Worker {
-    fn new() -> (Self, Receiver<Signal>, Receiver<Response>) {
+    fn new(
+        preferred_backend: WatcherBackend,
+    ) -> (Self, Receiver<Signal>, UnboundedReceiver<Response>) {
         let (signal_sender, signal_receiver) = channel();
-        let (response_sender, response_receiver) = channel();
+        let (response_sender, response_receiver) = unbounded();
         (
             Self {
                 alive: Mutex::new(true),
                 signal_lock: Mutex::default(),
                 signal_sender,
                 response_sender,
+                event_filter: Mutex::default(),
+                preferred_backend,
+                watcher_backend: Mutex::default(),
+                debounce: Mutex::default(),
+                temperature: Mutex::new(STANDARD_TEMPERATURE),
+                model: Mutex::new(&StrongModel),
+                tail: Mutex::default(),
+                last_event: Mutex::default(),
+                trace: Trace::default(),
+                thread: Mutex::default(),
+                cancelled: Mutex::default(),
             },
             signal_receiver,
             response_receiver,
@@ -56,19 +172,49 @@ impl Worker {
     }
 
     /// Spawns a new worker.
-    pub fn spawn() -> (Arc<Self>, Receiver<Response>) {
-        let (worker, signal_receiver, response_receiver) = Self::new();
+    ///
+    /// The preferred watcher backend is used if it is available, otherwise files are polled.
+    pub fn spawn(preferred_backend: WatcherBackend) -> (Arc<Self>, UnboundedReceiver<Response>) {
+        let (worker, signal_receiver, response_receiver) = Self::new(preferred_backend);
         let worker = Arc::new(worker);
-        {
+        let thread = {
             let worker = worker.clone();
             thread::Builder::new()
                 .name("worker".into())
                 .spawn(move || worker_impl(worker, signal_receiver))
-                .expect("spawn worker thread");
-        }
+                .expect("spawn worker thread")
+        };
+        *worker.thread.lock().unwrap() = Some(thread);
         (worker, response_receiver)
     }
 
+    /// Asks the worker to stop without waiting for it.
+    ///
+    /// The signals sent before are handled first, so a pending export is still saved. The
+    /// watchers and the connection to a meter are closed when the worker returns.
+    pub fn stop(&self) {
+        self.send_signal(Signal::Stop);
+    }
+
+    /// Waits until the stopped worker shut down, but at most for the timeout.
+    ///
+    /// A worker which does not return in time, e.g. because a file dialog is still open, is left
+    /// running.
+    pub fn join(&self, timeout: Duration) {
+        let Some(thread) = self.thread.lock().unwrap().take() else {
+            return;
+        };
+        let deadline = Instant::now() + timeout;
+        while !thread.is_finished() && Instant::now() < deadline {
+            thread::sleep(JOIN_POLL_INTERVAL);
+        }
+        if !thread.is_finished() {
+            warn!("The worker did not stop within {timeout:?}");
+        } else if thread.join().is_err() {
+            warn!("The worker thread could not be joined");
+        }
+    }
+
     /// Checks if the worker is alive.
     pub fn is_alive(&self) -> bool {
         *self.alive.lock().unwrap()
@@ -83,8 +229,68 @@ impl Worker {
         *self.alive.lock().unwrap() = alive;
     }
 
+    /// Sets the filter applied to watcher events.
+    pub fn set_event_filter(&self, filter: EventFilter) {
+        *self.event_filter.lock().unwrap() = filter;
+    }
+
+    /// Returns the backend of the active watcher, once it is created.
+    pub fn watcher_backend(&self) -> Option<WatcherBackend> {
+        *self.watcher_backend.lock().unwrap()
+    }
+
+    fn set_watcher_backend(&self, backend: WatcherBackend) {
+        *self.watcher_backend.lock().unwrap() = Some(backend);
+    }
+
+    /// Sets the window in which watcher events are coalesced.
+    pub fn set_debounce(&self, debounce: Debounce) {
+        *self.debounce.lock().unwrap() = debounce;
+    }
+
+    /// Sets the working temperature in °C, which applies from the next time a file is loaded.
+    pub fn set_temperature(&self, temperature: f32) {
+        *self.temperature.lock().unwrap() = temperature;
+    }
+
+    /// Sets the model the curves are calculated with, which applies from the next time a file is
+    /// loaded.
+    pub fn set_model(&self, model: &'static dyn TitrationModel) {
+        *self.model.lock().unwrap() = model;
+    }
+
+    /// Sets whether only the rows appended to CSV files are read on changes.
+    pub fn set_tail(&self, tail: bool) {
+        *self.tail.lock().unwrap() = tail;
+    }
+
+    /// Waits until no watcher event happened for the debounce window.
+    ///
+    /// Files which keep changing delay the update by at most [`MAX_DEBOUNCE_WAIT`].
+    fn wait_for_quiet(&self) {
+        let window = self.debounce.lock().unwrap().duration();
+        let started = Instant::now();
+        while started.elapsed() < MAX_DEBOUNCE_WAIT {
+            let Some(last_event) = *self.last_event.lock().unwrap() else {
+                return;
+            };
+            let quiet = last_event.elapsed();
+            if quiet >= window {
+                return;
+            }
+            thread::sleep(window - quiet);
+        }
+    }
+
+    pub fn trace(&self) -> &Trace {
+        &self.trace
+    }
+
     /// Resets the flag introduced by the given signal.
-    pub fn reset_signal_lock(&self, signal: Signal) {
+    pub fn reset_signal_lock(&self, signal: &Signal) {
+        let Some(signal) = signal.lock() else {
+            return;
+        };
         let mut lock = self.signal_lock.lock().unwrap();
         let Some(lock_signal) = *lock else {
             return;
@@ -105,65 +311,196 @@ impl Worker {
         let mut lock = self.signal_lock.lock().unwrap();
         if let Some(lock) = *lock {
             if signal.should_skip(lock) {
+                self.trace
+                    .record(|| TraceEvent::SkippedSignal(signal_summary(&signal)));
                 return;
             }
         }
+        if let Signal::Cancel = signal {
+            *self.cancelled.lock().unwrap() = true;
+        }
         // This will automatically promote lower locks to higher ones (e.g. `FileDialog` ->
         // `Stop`).
-        if signal.is_lock() {
-            *lock = Some(signal);
+        if let Some(signal_lock) = signal.lock() {
+            *lock = Some(signal_lock);
         }
+        self.trace
+            .record(|| TraceEvent::Signal(signal_summary(&signal)));
         _ = self.signal_sender.send(signal);
         drop(lock);
     }
 
     /// Sends a response to the app.
     pub fn send_response(&self, response: Response) {
-        _ = self.response_sender.send(response);
+        self.trace
+            .record(|| TraceEvent::Response(response_summary(&response)));
+        _ = self.response_sender.unbounded_send(response);
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Signal {
-    /// The worker should update the file.
-    Update = 0,
-    /// The worker should request a file dialog.
-    FileDialog = 1,
+    /// The worker should update all watched files.
+    ///
+    /// While paused, the update is only counted as a pending change.
+    Update,
+    /// The worker should update all loaded files, even while paused.
+    Reload,
+    /// The worker should stop updating the loaded files on changes until it is resumed.
+    Pause,
+    /// The worker should update the loaded files on changes again.
+    ///
+    /// Pending changes are applied right away.
+    Resume,
+    /// The worker should request a file dialog with the given filter name.
+    FileDialog(&'static str),
+    /// The worker should load and watch the given file.
+    LoadPath(PathBuf),
+    /// The worker should request a folder dialog and watch the chosen folder with the given
+    /// pattern.
+    FolderDialog(String),
+    /// The worker should watch a folder and keep its newest matching table loaded.
+    ///
+    /// A previously watched folder is replaced.
+    WatchFolder(WatchedFolder),
+    /// The worker should stop watching the folder, which leaves its loaded table loaded.
+    UnwatchFolder,
+    /// The worker should stop watching the given file.
+    Unload(PathBuf),
+    /// The worker should save a file chosen in a file dialog.
+    Save(SaveRequest),
+    /// The worker should write the calculated values of a titration to a table and save it.
+    ExportTable(TableExport),
+    /// The worker should read pH values from a meter with the given settings.
+    ///
+    /// A previous connection is closed.
+    #[cfg(feature = "live")]
+    Connect(live::LiveConfig),
+    /// The worker should close the connection to the meter.
+    #[cfg(feature = "live")]
+    Disconnect,
+    /// The worker should request a file dialog with the given filter name for a script, which the
+    /// loaded files are then calculated with by the scripted model.
+    #[cfg(feature = "scripting")]
+    ScriptDialog(&'static str),
+    /// The worker should stop parsing the current file.
+    ///
+    /// The file stays watched, so it is parsed again on its next change.
+    Cancel,
     /// The worker should stop itself.
-    Stop = 2,
+    Stop,
 }
 
 impl Signal {
-    /// Checks if the signal activates signal-skipping.
-    pub fn is_lock(self) -> bool {
-        // matches!(self, Self::FileDialog | Self::Update | Self::Stop)
-        true
+    /// Returns the lock introduced by the signal.
+    ///
+    /// Signals without a lock never activate signal-skipping.
+    pub fn lock(&self) -> Option<SignalLock> {
+        match self {
+            Self::Update => Some(SignalLock::Update),
+            Self::FileDialog(_) | Self::FolderDialog(_) => Some(SignalLock::FileDialog),
+            #[cfg(feature = "scripting")]
+            Self::ScriptDialog(_) => Some(SignalLock::FileDialog),
+            Self::Reload
+            | Self::Pause
+            | Self::Resume
+            | Self::LoadPath(_)
+            | Self::WatchFolder(_)
+            | Self::UnwatchFolder
+            | Self::Unload(_)
+            | Self::Save(_)
+            | Self::ExportTable(_)
+            | Self::Cancel => None,
+            #[cfg(feature = "live")]
+            Self::Connect(_) | Self::Disconnect => None,
+            Self::Stop => Some(SignalLock::Stop),
+        }
     }
 
     /// Checks if the signal should be skipped with a given lock.
-    pub fn should_skip(self, lock: Self) -> bool {
+    pub fn should_skip(&self, lock: SignalLock) -> bool {
+        let Some(own_lock) = self.lock() else {
+            // Signals targeting a specific file are only skipped once the worker stops.
+            return lock == SignalLock::Stop;
+        };
         match lock {
-            Self::Update => self <= lock,
-            Self::FileDialog => self <= lock,
-            Self::Stop => true,
+            SignalLock::Update => own_lock <= lock,
+            SignalLock::FileDialog => own_lock <= lock,
+            SignalLock::Stop => true,
         }
     }
+}
+
+/// A file to be saved by the worker.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SaveRequest {
+    /// The name of the filter in the file dialog.
+    pub filter_name: &'static str,
+    /// The extension of the file.
+    pub extension: &'static str,
+    /// The proposed name of the file.
+    pub file_name: String,
+    pub content: Vec<u8>,
+}
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SignalLock {
+    Update = 0,
+    FileDialog = 1,
+    Stop = 2,
+}
+
+impl SignalLock {
     pub fn can_unlock(self, lock: Self) -> bool {
         match lock {
-            Signal::Update => self >= lock,
-            Signal::FileDialog => self >= lock,
-            Signal::Stop => false,
+            SignalLock::Update => self >= lock,
+            SignalLock::FileDialog => self >= lock,
+            SignalLock::Stop => false,
         }
     }
 }
 
 #[derive(Debug)]
 pub enum Response {
-    /// The current file should be unloaded.
-    Unload,
-    Output(Arc<Output>),
-    Error(WorkerError),
+    /// The given file should be unloaded.
+    Unload(PathBuf),
+    /// The given file was loaded at the given time.
+    Output(PathBuf, Arc<Output>, SystemTime),
+    /// The given file could not be loaded.
+    Error(PathBuf, WorkerError),
+    /// The given file was saved.
+    Saved(PathBuf),
+    /// The given file could not be saved.
+    SaveFailed(PathBuf, WorkerError),
+    /// The given number of changes was detected while paused.
+    PendingChanges(u32),
+    /// The given folder is watched, or none anymore.
+    WatchedFolder(Option<PathBuf>),
+    /// The given number of rows of added volumes of a file was read.
+    ///
+    /// This is only sent for large files.
+    Progress {
+        path: PathBuf,
+        rows_done: u32,
+        rows_total: u32,
+    },
+    /// Parsing the given file was cancelled.
+    Cancelled(PathBuf),
+    /// The meter sent a reading, which is the pH or the potential in mV, with the temperature in
+    /// °C if the meter sends one.
+    #[cfg(feature = "live")]
+    Reading(f32, Option<f32>),
+    /// The connection to the meter could not be opened or failed.
+    #[cfg(feature = "live")]
+    LiveFailed(WorkerError),
+    /// The given script was loaded.
+    #[cfg(feature = "scripting")]
+    ScriptLoaded(PathBuf),
+    /// The chosen script could not be loaded.
+    #[cfg(feature = "scripting")]
+    ScriptFailed(WorkerError),
+    /// The worker stopped, either by a signal or because it crashed.
+    Stopped,
 }
 
 #[derive(Debug)]
@@ -171,7 +508,33 @@ pub enum WorkerError {
     FileDoesNotExist,
     TableError(calamine::Error),
     NoTableInWorkbook,
-    TableNotCorrectlyFormatted,
+    InvalidCell(CellError),
+    /// A concentration is given in g/L, but the table does not name a known substance.
+    UnknownMolarMass(Field),
+    /// A value is not labeled in the table, although another one needs it.
+    MissingField(Field),
+    InvalidMapping(PathBuf, toml::de::Error),
+    Io(io::Error),
+    Xlsx(XlsxError),
+    /// An SVG file has no curve data embedded by the export.
+    NoMetadata,
+    InvalidMetadata(serde_json::Error),
+    /// Parsing was stopped by [`Signal::Cancel`].
+    Cancelled,
+    /// A script could not be compiled, with the message of the interpreter.
+    #[cfg(feature = "scripting")]
+    Script(String),
+    /// The weak acid model was chosen for a test solution which is no weak acid.
+    NotAWeakAcid(&'static Substance),
+    /// The file with the measured conductivity of a table has no row with a volume and a
+    /// conductivity.
+    NoConductivity(PathBuf),
+}
+
+impl From<CellError> for WorkerError {
+    fn from(err: CellError) -> Self {
+        Self::InvalidCell(err)
+    }
 }
 
 impl Display for WorkerError {
@@ -181,149 +544,744 @@ impl Display for WorkerError {
 }
 
 fn worker_impl(worker: Arc<Worker>, signal_receiver: Receiver<Signal>) {
-    if let Err(err) = worker_impl_try(worker.clone(), signal_receiver) {
-        eprintln!("[worker] The worker crashed: {err}");
+    // A panic must not skip marking the worker as dead, so the app can restart it.
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        worker_impl_try(worker.clone(), signal_receiver)
+    }));
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err(err)) => error!("The worker crashed: {err}"),
+        Err(_) => error!("The worker panicked"),
     }
     worker.set_alive(false);
-    eprintln!("[worker] The worker shut down");
+    worker.send_response(Response::Stopped);
+    info!("The worker shut down");
 }
 
 fn worker_impl_try(worker: Arc<Worker>, signal_receiver: Receiver<Signal>) -> Result<()> {
-    let mut path: Option<PathBuf> = None;
+    // The loaded files and their stamps at the time they were loaded.
+    let mut paths: BTreeMap<PathBuf, Option<FileStamp>> = BTreeMap::new();
+    // The parse state of the CSV files read in the tail mode.
+    let mut tails: BTreeMap<PathBuf, CsvTail> = BTreeMap::new();
+    // The watched folder and its table which was loaded last.
+    let mut folder: Option<(WatchedFolder, Option<PathBuf>)> = None;
+    // The connection to a meter, which is closed when it is dropped.
+    #[cfg(feature = "live")]
+    let mut live: Option<live::LiveInput> = None;
+    let mut paused = false;
+    // The number of changes while paused.
+    let mut pending = 0;
     let mut watcher = {
+        let preferred_backend = worker.preferred_backend;
         let worker = worker.clone();
-        // INotifyWatcher does not work
-        notify::PollWatcher::new(
-            move |res| {
-                let event: Event = match res {
-                    Ok(event) => event,
-                    Err(err) => {
-                        eprintln!("[watcher] There was an error during the event stream: {err}");
-                        return;
-                    }
-                };
-                if matches!(event.kind, EventKind::Modify(_) | EventKind::Remove(_)) {
-                    worker.send_signal(Signal::Update);
+        let callback = move |res: notify::Result<notify::Event>| {
+            let event = match res {
+                Ok(event) => event,
+                Err(err) => {
+                    warn!("There was an error during the event stream: {err}");
+                    worker
+                        .trace()
+                        .record(|| TraceEvent::Watcher(format!("Error: {err}")));
+                    return;
                 }
-            },
-            notify::Config::default().with_poll_interval(Duration::from_millis(500)),
-        )?
+            };
+            worker
+                .trace()
+                .record(|| TraceEvent::Watcher(format!("{:?} {:?}", event.kind, event.paths)));
+            let filter = *worker.event_filter.lock().unwrap();
+            if filter.matches(&event.kind) {
+                *worker.last_event.lock().unwrap() = Some(Instant::now());
+                worker.send_signal(Signal::Update);
+            }
+        };
+        FileWatcher::new(preferred_backend, Arc::new(callback))?
     };
+    worker.set_watcher_backend(watcher.backend());
     loop {
         let signal = signal_receiver.recv()?;
-        match signal {
-            Signal::FileDialog => 'blk: {
+        match &signal {
+            Signal::FileDialog(filter_name) => 'blk: {
                 let Some(file) = FileDialog::new()
-                    .add_filter(
-                        "Tabelle",
-                        &["xls", "xlsx", "xlsm", "xlsb", "xla", "xlam", "ods"],
-                    )
+                    // Exported diagrams can be loaded like tables.
+                    .add_filter(*filter_name, &[parser::TABLE_EXTENSIONS, &["svg"]].concat())
                     .pick_file()
                 else {
                     break 'blk;
                 };
-                if !file.is_file() {
-                    worker.send_response(Response::Error(WorkerError::FileDoesNotExist));
+                watch_file(&worker, &mut watcher, &mut paths, &mut tails, file)?;
+            }
+            #[cfg(feature = "scripting")]
+            Signal::ScriptDialog(filter_name) => 'blk: {
+                let Some(file) = FileDialog::new()
+                    .add_filter(*filter_name, &["rhai"])
+                    .pick_file()
+                else {
                     break 'blk;
+                };
+                match script::load(&file) {
+                    Ok(()) => {
+                        info!(path = %file.display(), "Loaded the script");
+                        worker.send_response(Response::ScriptLoaded(file));
+                        update_files(&worker, &mut watcher, &mut paths, &mut tails, true);
+                    }
+                    Err(err) => {
+                        warn!("Could not load the script: {err}");
+                        worker.send_response(Response::ScriptFailed(err));
+                    }
                 }
-                watcher.watch(&file, RecursiveMode::NonRecursive)?;
-                load_file(&worker, &file);
-                path = Some(file);
             }
-            Signal::Update => 'blk: {
-                let Some(some_path) = &path else {
+            Signal::LoadPath(file) => {
+                watch_file(&worker, &mut watcher, &mut paths, &mut tails, file.clone())?;
+            }
+            Signal::FolderDialog(pattern) => 'blk: {
+                let Some(path) = FileDialog::new().pick_folder() else {
                     break 'blk;
                 };
-                if !some_path.is_file() {
-                    _ = watcher.unwatch(some_path);
-                    path = None;
-                    worker.send_response(Response::Unload);
-                    break 'blk;
+                let watched = WatchedFolder {
+                    path,
+                    pattern: pattern.clone(),
+                };
+                watch_folder(&worker, &mut watcher, &mut folder, watched)?;
+                update_folder(&worker, &mut watcher, &mut paths, &mut tails, &mut folder)?;
+            }
+            Signal::WatchFolder(watched) => {
+                watch_folder(&worker, &mut watcher, &mut folder, watched.clone())?;
+                update_folder(&worker, &mut watcher, &mut paths, &mut tails, &mut folder)?;
+            }
+            Signal::UnwatchFolder => {
+                if let Some((watched, _)) = folder.take() {
+                    watcher.unwatch(&watched.path);
+                }
+                worker.send_response(Response::WatchedFolder(None));
+            }
+            Signal::Update => {
+                worker.wait_for_quiet();
+                if paused {
+                    pending += 1;
+                    worker.send_response(Response::PendingChanges(pending));
+                } else {
+                    update_folder(&worker, &mut watcher, &mut paths, &mut tails, &mut folder)?;
+                    update_files(&worker, &mut watcher, &mut paths, &mut tails, false);
+                }
+            }
+            Signal::Reload => {
+                update_folder(&worker, &mut watcher, &mut paths, &mut tails, &mut folder)?;
+                update_files(&worker, &mut watcher, &mut paths, &mut tails, true);
+                if pending > 0 {
+                    pending = 0;
+                    worker.send_response(Response::PendingChanges(0));
+                }
+            }
+            Signal::Pause => paused = true,
+            Signal::Resume => {
+                paused = false;
+                if pending > 0 {
+                    update_folder(&worker, &mut watcher, &mut paths, &mut tails, &mut folder)?;
+                    update_files(&worker, &mut watcher, &mut paths, &mut tails, false);
+                    pending = 0;
+                    worker.send_response(Response::PendingChanges(0));
+                }
+            }
+            Signal::Unload(file) => {
+                if paths.remove(file).is_some() {
+                    watcher.unwatch(file);
+                }
+                tails.remove(file);
+                worker.send_response(Response::Unload(file.clone()));
+            }
+            Signal::Save(request) => save_file(&worker, request),
+            Signal::ExportTable(export) => export_table(&worker, export),
+            #[cfg(feature = "live")]
+            Signal::Connect(config) => {
+                // The port may be the same, so the old connection is closed first.
+                live.take();
+                match live::LiveInput::connect(worker.clone(), config) {
+                    Ok(input) => {
+                        live.replace(input);
+                    }
+                    Err(err) => {
+                        warn!("Could not connect to the meter: {err}");
+                        worker.send_response(Response::LiveFailed(WorkerError::Io(err)));
+                    }
                 }
-                load_file(&worker, some_path);
             }
+            #[cfg(feature = "live")]
+            Signal::Disconnect => {
+                live.take();
+            }
+            // The parse was cancelled already, so later files are parsed again.
+            Signal::Cancel => *worker.cancelled.lock().unwrap() = false,
             Signal::Stop => break,
         }
-        worker.reset_signal_lock(signal);
+        // The native watcher may have fallen back to polling.
+        worker.set_watcher_backend(watcher.backend());
+        worker.reset_signal_lock(&signal);
     }
     Ok(())
 }
 
-#[derive(Debug)]
+/// Starts watching a folder instead of the previous one.
+///
+/// The table loaded from the previous folder stays loaded.
+fn watch_folder(
+    worker: &Worker,
+    watcher: &mut FileWatcher,
+    folder: &mut Option<(WatchedFolder, Option<PathBuf>)>,
+    watched: WatchedFolder,
+) -> Result<()> {
+    if !watched.path.is_dir() {
+        worker.send_response(Response::Error(watched.path, WorkerError::FileDoesNotExist));
+        return Ok(());
+    }
+    // The same folder keeps its loaded table when only the pattern changes.
+    let loaded = match folder.take() {
+        Some((previous, loaded)) if previous.path == watched.path => loaded,
+        Some((previous, _)) => {
+            watcher.unwatch(&previous.path);
+            None
+        }
+        None => None,
+    };
+    watcher.watch(&watched.path)?;
+    info!(path = %watched.path.display(), pattern = watched.pattern, "Watching the folder");
+    worker.send_response(Response::WatchedFolder(Some(watched.path.clone())));
+    *folder = Some((watched, loaded));
+    Ok(())
+}
+
+/// Loads the newest matching table of the watched folder in place of the one loaded before.
+fn update_folder(
+    worker: &Worker,
+    watcher: &mut FileWatcher,
+    paths: &mut BTreeMap<PathBuf, Option<FileStamp>>,
+    tails: &mut BTreeMap<PathBuf, CsvTail>,
+    folder: &mut Option<(WatchedFolder, Option<PathBuf>)>,
+) -> Result<()> {
+    let Some((watched, loaded)) = folder else {
+        return Ok(());
+    };
+    let Some(newest) = watched.newest() else {
+        return Ok(());
+    };
+    if loaded.as_ref() == Some(&newest) {
+        return Ok(());
+    }
+    debug!(path = %newest.display(), "Found a newer table in the folder");
+    if let Some(previous) = loaded.replace(newest.clone()) {
+        if paths.remove(&previous).is_some() {
+            watcher.unwatch(&previous);
+            tails.remove(&previous);
+            worker.send_response(Response::Unload(previous));
+        }
+    }
+    watch_file(worker, watcher, paths, tails, newest)
+}
+
+/// Unloads all removed files and reloads the others.
+///
+/// Unless forced, files with an unchanged stamp are skipped.
+fn update_files(
+    worker: &Worker,
+    watcher: &mut FileWatcher,
+    paths: &mut BTreeMap<PathBuf, Option<FileStamp>>,
+    tails: &mut BTreeMap<PathBuf, CsvTail>,
+    force: bool,
+) {
+    let removed: Vec<PathBuf> = paths.keys().filter(|it| !it.is_file()).cloned().collect();
+    for path in removed {
+        watcher.unwatch(&path);
+        paths.remove(&path);
+        tails.remove(&path);
+        worker.send_response(Response::Unload(path));
+    }
+    for (path, stamp) in paths.iter_mut() {
+        let current = FileStamp::read(path);
+        if !force && current.is_some() && current == *stamp {
+            worker
+                .trace()
+                .record(|| TraceEvent::Unchanged(path.display().to_string()));
+            continue;
+        }
+        *stamp = current;
+        if let Err(err) = watcher.refresh(path) {
+            warn!(path = %path.display(), "Could not watch the file again: {err}");
+        }
+        read_file(worker, tails, path, force);
+    }
+}
+
+/// Asks for a path and saves the requested file there.
+fn save_file(worker: &Worker, request: &SaveRequest) {
+    let Some(file) = FileDialog::new()
+        .add_filter(request.filter_name, &[request.extension])
+        .set_file_name(&request.file_name)
+        .save_file()
+    else {
+        return;
+    };
+    match fs::write(&file, &request.content) {
+        Ok(()) => worker.send_response(Response::Saved(file)),
+        Err(err) => worker.send_response(Response::SaveFailed(file, WorkerError::Io(err))),
+    }
+}
+
+/// Writes the table of an export and saves it like any other file.
+fn export_table(worker: &Worker, export: &TableExport) {
+    let content = match export.write() {
+        Ok(content) => content,
+        Err(err) => {
+            let file = PathBuf::from(&export.file_name);
+            worker.send_response(Response::SaveFailed(file, WorkerError::Xlsx(err)));
+            return;
+        }
+    };
+    save_file(
+        worker,
+        &SaveRequest {
+            filter_name: export.filter_name,
+            extension: export.format.extension(),
+            file_name: export.file_name.clone(),
+            content,
+        },
+    );
+}
+
+/// Starts watching the given file and loads it.
+///
+/// Files that are already watched are only reloaded.
+fn watch_file(
+    worker: &Worker,
+    watcher: &mut FileWatcher,
+    paths: &mut BTreeMap<PathBuf, Option<FileStamp>>,
+    tails: &mut BTreeMap<PathBuf, CsvTail>,
+    file: PathBuf,
+) -> Result<()> {
+    if !file.is_file() {
+        worker.send_response(Response::Error(file, WorkerError::FileDoesNotExist));
+        return Ok(());
+    }
+    if !paths.contains_key(&file) {
+        watcher.watch(&file)?;
+    }
+    let stamp = FileStamp::read(&file);
+    read_file(worker, tails, &file, false);
+    paths.insert(file, stamp);
+    Ok(())
+}
+
+#[derive(Clone, Debug)]
 pub struct Input {
     pub t_v: f32,
     pub t_c: f32,
     pub m_c: f32,
     pub m_v: Vec<f32>,
-    pub acid: f32,
-    pub base: f32,
+    /// The working temperature in °C, which sets the ionic product of water.
+    pub temperature: f32,
+    /// The temperature in °C at every added volume, read from a temperature column.
+    ///
+    /// Without a column it is empty, and volumes without a temperature are calculated at the
+    /// working temperature.
+    pub temperatures: Vec<f32>,
+    pub uncertainty: Uncertainty,
+    /// The ratio the acid and the base react in.
+    pub stoichiometry: Stoichiometry,
+    /// A weak acid titrated together with the acid of the test solution.
+    pub second_acid: Option<SecondAcid>,
+    /// The substance of the measuring solution, if the table names a known one.
+    pub titrant: Option<&'static Substance>,
+    /// The substance of the test solution, if the table names a known one.
+    pub analyte: Option<&'static Substance>,
+    /// The model the curve is calculated with.
+    pub model: &'static dyn TitrationModel,
 }
 
 impl Input {
+    /// Returns the amount of acid in the test solution in mol.
+    ///
+    /// The protons of a second acid are counted as the amount of the first acid releasing them.
+    pub fn acid_amount(&self) -> f32 {
+        let second = self.second_acid.as_ref().map_or(0.0, |it| {
+            it.amount(self.t_v) * it.protons() as f32 / self.stoichiometry.titrant as f32
+        });
+        self.t_c * self.t_v + second
+    }
+
+    /// Returns the temperature in °C at the added volume with the given index.
+    pub fn temperature_at(&self, index: usize) -> f32 {
+        self.temperatures
+            .get(index)
+            .copied()
+            .filter(|it| it.is_finite())
+            .unwrap_or(self.temperature)
+    }
+
+    /// Returns the volume of measuring solution at the (last) equivalence point in mL.
+    pub fn equivalence_volume(&self) -> f32 {
+        self.acid_amount() * 1000.0 / self.m_c / self.stoichiometry.factor()
+    }
+
+    /// Returns the volumes of measuring solution at every equivalence point in mL.
+    ///
+    /// The first acid is neutralized first, then the protons of a second acid one after another.
+    pub fn equivalence_volumes(&self) -> Vec<f32> {
+        let mut volume = self.t_c * self.t_v * 1000.0 / self.m_c / self.stoichiometry.factor();
+        let mut volumes = vec![volume];
+        if let Some(second) = &self.second_acid {
+            let step =
+                second.amount(self.t_v) * 1000.0 / self.m_c / self.stoichiometry.analyte as f32;
+            for _ in 0..second.protons() {
+                volume += step;
+                volumes.push(volume);
+            }
+        }
+        volumes
+    }
+
+    /// Calculates the output with the model of the input.
     pub fn calculate_output(&self) -> Output {
-        let mut items = Vec::new();
-        let mut last: Option<OutputItem> = None;
-        let mut current = OutputItem::default();
-        let mut second_half = false;
-        let mut last_m_v = 0.0;
-        for &m_v in &self.m_v {
-            current.m_v = m_v;
-            current.total_v = m_v + self.t_v * 1000.0;
-            if !second_half {
-                current.n2 = if let Some(OutputItem { m_v: l_m_v, .. }) = &last {
-                    let diff = m_v - l_m_v;
-                    self.m_c / 1000.0 * diff
-                } else {
-                    m_v / 1000.0 * self.m_c
-                };
-                current.n1 = if let Some(OutputItem { n1, .. }) = &last {
-                    n1 - current.n2
-                } else {
-                    0.001 // todo - remove magic numbers
-                };
-                current.c1 = current.n1 / (current.total_v / 1000.0);
-                current.c2 = f32::NAN;
-                current.ph = -current.c1.log10();
-                current.poh = 14.0 - current.ph;
-                if !current.ph.is_finite() || current.ph > 14.0 {
-                    println!("first: {current:#?}");
-                    last = None;
-                    second_half = true;
-                    last_m_v = m_v;
-                    continue;
-                } // Wrong - v = 10.0 is errornous and just gets skipped
+        self.model.calculate(self)
+    }
+
+    /// Explains the calculation of the output item at the given index step by step.
+    pub fn explain(&self, index: usize) -> Vec<CalculationStep> {
+        self.model.explain(self, index)
+    }
+
+    /// Calculates every item from the amounts added up to its volume.
+    ///
+    /// The pH is not taken from a formula per region of the curve, but solved from the charge
+    /// balance of the whole solution, which also holds at the equivalence point.
+    fn calculate(&self, recorder: &mut impl Recorder) -> Output {
+        let mut items = Vec::with_capacity(self.m_v.len());
+        for (index, &m_v) in self.m_v.iter().enumerate() {
+            items.push(self.item(m_v, self.temperature_at(index), recorder));
+            recorder.finish(items.len() - 1);
+        }
+        self.output(items)
+    }
+
+    /// Calculates the item after adding a volume of measuring solution in mL at a temperature in
+    /// °C.
+    fn item(&self, m_v: f32, temperature: f32, recorder: &mut impl Recorder) -> OutputItem {
+        let pkw = temperature::pkw(temperature);
+        // The amount of the acid in the test solution.
+        let n0 = self.acid_amount();
+        let mut current = OutputItem {
+            m_v,
+            temperature,
+            ..Default::default()
+        };
+        current.total_v = m_v + self.t_v * 1000.0;
+        recorder.step(
+            Quantity::TotalVolume,
+            || {
+                format!(
+                    "V = V(m) + V(t) · 1000 = {} mL + {} L · 1000 = {} mL",
+                    number(m_v),
+                    number(self.t_v),
+                    number(current.total_v)
+                )
+            },
+            current.total_v,
+        );
+        current.n2 = m_v / 1000.0 * self.m_c;
+        recorder.step(
+            Quantity::AddedAmount,
+            || {
+                format!(
+                    "n₂ = V(m) / 1000 · c(m) = {} mL / 1000 · {} mol/L = {} mol",
+                    number(m_v),
+                    number(self.m_c),
+                    number(current.n2)
+                )
+            },
+            current.n2,
+        );
+        let Stoichiometry { analyte, titrant } = self.stoichiometry;
+        let factor = self.stoichiometry.factor();
+        // The ratio is only written if the acid and the base do not react 1 : 1.
+        let ratio = |a: u8, b: u8| {
+            if a == b {
+                String::new()
             } else {
-                current.n1 = f32::NAN;
-                current.n2 = if let Some(OutputItem {
-                    m_v: l_m_v,
-                    n2: l_n2,
-                    ..
-                }) = &last
-                {
-                    let diff = m_v - l_m_v;
-                    l_n2 + m_v / 1000.0 * diff * self.m_c
+                format!(" · {a} / {b}")
+            }
+        };
+        // The protons of a second acid are written as part of the amount of the first acid.
+        let initial = |symbols: bool| match &self.second_acid {
+            None if symbols => "c(t) · V(t)".to_owned(),
+            None => format!("{} mol/L · {} L", number(self.t_c), number(self.t_v)),
+            Some(second) => {
+                let protons = second.protons() as f32 / titrant as f32;
+                let protons = if protons == 1.0 {
+                    String::new()
                 } else {
-                    let diff = m_v - last_m_v;
-                    m_v / 1000.0 * diff * self.m_c
+                    format!("{} · ", number(protons))
                 };
-                current.c1 = f32::NAN;
-                current.c2 = current.n2 / (current.total_v / 1000.0);
-                current.poh = -current.c2.log10();
-                current.ph = 14.0 - current.poh;
+                if symbols {
+                    format!("(c(t) + {protons}c(t₂)) · V(t)")
+                } else {
+                    format!(
+                        "({} mol/L + {protons}{} mol/L) · {} L",
+                        number(self.t_c),
+                        number(second.concentration),
+                        number(self.t_v)
+                    )
+                }
             }
-            items.push(current.clone());
-            last = Some(current.clone());
+        };
+        let remaining = n0 - current.n2 * factor;
+        let liters = current.total_v / 1000.0;
+        if remaining > 0.0 {
+            current.n1 = remaining;
+            recorder.step(
+                Quantity::RemainingAmount,
+                || {
+                    format!(
+                        "n₁ = {} − n₂{ratio} = {} − {} mol{ratio} = {} mol",
+                        initial(true),
+                        initial(false),
+                        number(current.n2),
+                        number(remaining),
+                        ratio = ratio(analyte, titrant),
+                    )
+                },
+                remaining,
+            );
+            current.c1 = remaining / liters;
+            recorder.step(
+                Quantity::TestConcentration,
+                || {
+                    format!(
+                        "c₁ = n₁ / (V / 1000) = {} mol / ({} mL / 1000) = {} mol/L",
+                        number(remaining),
+                        number(current.total_v),
+                        number(current.c1)
+                    )
+                },
+                current.c1,
+            );
+            current.c2 = f32::NAN;
+        } else {
+            current.n1 = f32::NAN;
+            current.c1 = f32::NAN;
+            current.c2 = (current.n2 - n0 / factor) / liters;
+            recorder.step(
+                Quantity::ExcessConcentration,
+                || {
+                    format!(
+                        "c₂ = (n₂ − {}{ratio}) / (V / 1000) = ({} mol − {}{ratio}) / ({} mL / 1000) = {} mol/L",
+                        initial(true),
+                        number(current.n2),
+                        initial(false),
+                        number(current.total_v),
+                        number(current.c2),
+                        ratio = ratio(titrant, analyte),
+                    )
+                },
+                current.c2,
+            );
+        }
+        let equilibrium = self.solution_at(m_v, temperature).equilibrium();
+        trace!(
+            m_v,
+            iterations = equilibrium.iterations,
+            "Solved the charge balance"
+        );
+        current.ph = equilibrium.ph as f32;
+        recorder.step(
+            Quantity::Ph,
+            || {
+                // The anions of a second acid depend on the pH, so their charge is given at the
+                // solved pH.
+                let second = self.second_acid.as_ref().map_or(String::new(), |it| {
+                    let charge = it
+                        .component(self.t_v as f64 / liters as f64)
+                        .released_protons(equilibrium.ph);
+                    format!(" + {} mol/L", number(charge as f32))
+                });
+                format!(
+                    "c(H₃O⁺) + {} mol/L = c(OH⁻) + {} mol/L{second} ⇒ pH = {}",
+                    number(current.n2 * analyte as f32 / liters),
+                    number(self.t_c * self.t_v * titrant as f32 / liters),
+                    number(current.ph)
+                )
+            },
+            current.ph,
+        );
+        current.poh = pkw - current.ph;
+        recorder.step(
+            Quantity::Poh,
+            || {
+                format!(
+                    "pOH = pKw − pH = {} − {} = {}",
+                    number(pkw),
+                    number(current.ph),
+                    number(current.poh)
+                )
+            },
+            current.poh,
+        );
+        current
+    }
+
+    /// Creates the output of the calculated items.
+    fn output(&self, items: Vec<OutputItem>) -> Output {
+        Output {
+            input: self.clone(),
+            items,
+            excluded: Vec::new(),
+            pairs: self.model.acid_base_pairs(self),
+            sheet: None,
+            measured_conductivity: None,
+        }
+    }
+
+    /// Returns the solution after adding a volume of measuring solution in mL.
+    ///
+    /// The strong acid and base protolyse completely, so only their counter ions remain. Their
+    /// charges balance the reaction, e.g. SO₄²⁻ for H₂SO₄ + 2 NaOH. A second acid is diluted like
+    /// the test solution.
+    pub fn solution(&self, m_v: f32) -> Solution {
+        self.solution_at(m_v, self.temperature)
+    }
+
+    /// Returns the solution after adding a volume of measuring solution in mL at a temperature in
+    /// °C.
+    fn solution_at(&self, m_v: f32, temperature: f32) -> Solution {
+        // The amounts are calculated with doubles, as near the equivalence point their difference
+        // is below the rounding of single precision floats.
+        let (t_v, t_c, m_c, m_v) = (
+            self.t_v as f64,
+            self.t_c as f64,
+            self.m_c as f64,
+            m_v as f64,
+        );
+        let liters = m_v / 1000.0 + t_v;
+        let kw = 10f64.powf(-temperature::pkw(temperature) as f64);
+        let solution = Solution::new(kw)
+            .with(Component::ion(
+                t_c * t_v / liters,
+                -i32::from(self.stoichiometry.titrant),
+            ))
+            .with(Component::ion(
+                m_c * m_v / 1000.0 / liters,
+                i32::from(self.stoichiometry.analyte),
+            ));
+        match &self.second_acid {
+            Some(second) => solution.with(second.component(t_v / liters)),
+            None => solution,
+        }
+    }
+
+    /// Returns the acid-base pairs present during the titration, sorted by their pKa.
+    ///
+    /// Strong acids and bases protolyse completely, so only the pairs of water and of a second
+    /// acid remain.
+    pub fn acid_base_pairs(&self) -> Vec<AcidBasePair> {
+        let mut pairs = vec![
+            AcidBasePair::new("H₃O⁺", "H₂O", 0.0),
+            AcidBasePair::new("H₂O", "OH⁻", temperature::pkw(self.temperature)),
+        ];
+        if let Some(second) = &self.second_acid {
+            pairs.extend(second.acid_base_pairs());
+            pairs.sort_by(|a, b| a.pka.total_cmp(&b.pka));
         }
-        Output { items }
+        pairs
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Output {
+    /// The input the output was calculated from.
+    pub input: Input,
     pub items: Vec<OutputItem>,
+    /// The items excluded from the curve by the user, with their index among all items.
+    ///
+    /// They are still drawn, but left out of every analysis.
+    pub excluded: Vec<(usize, OutputItem)>,
+    /// The acid-base pairs of the titrated system, sorted by their pKa.
+    pub pairs: Vec<AcidBasePair>,
+    /// The name of the worksheet the input was read from, if it was read from a workbook.
+    pub sheet: Option<String>,
+    /// The conductivity measured in the same run, with the added volume in mL and the
+    /// conductivity in mS/cm, if the table has a file of it.
+    ///
+    /// See [`conductivity::read_measured`].
+    pub measured_conductivity: Option<Vec<(f32, f32)>>,
 }
 
 impl Output {
+    /// Returns the output with the items at the given volumes excluded from the curve.
+    ///
+    /// Previously excluded items at other volumes are included again at their previous position.
+    /// The items keep the values calculated with all volumes, so excluding one does not change
+    /// its neighbours.
+    pub fn excluding(&self, volumes: &[f32]) -> Output {
+        self.with_all_items(self.all_items(), volumes)
+    }
+
+    /// Returns the output with its items sorted by volume and the items at the same volume merged.
+    ///
+    /// Merged items take the average of their values, so repeated readings at one volume count as
+    /// a single point. The volumes of the input follow the items, so the explanation of an item
+    /// describes it, and the sorted volumes are no longer reported as decreasing or repeated.
+    pub fn monotonic(&self) -> Output {
+        let mut all = self.all_items();
+        all.sort_by(|a, b| a.m_v.total_cmp(&b.m_v));
+        let all = all
+            .chunk_by(|a, b| a.m_v == b.m_v)
+            .map(OutputItem::average)
+            .collect();
+        let excluded: Vec<f32> = self.excluded.iter().map(|(_, it)| it.m_v).collect();
+        self.with_all_items(all, &excluded)
+    }
+
+    /// Returns the included and the excluded items in the order of the curve.
+    fn all_items(&self) -> Vec<OutputItem> {
+        let mut all = self.items.clone();
+        for (index, item) in &self.excluded {
+            all.insert((*index).min(all.len()), item.clone());
+        }
+        all
+    }
+
+    /// Returns the output with the given items, of which the ones at the given volumes are
+    /// excluded.
+    ///
+    /// The volumes and temperatures of the input are taken from the included items, so an index
+    /// of an item is also an index of the input.
+    fn with_all_items(&self, all: Vec<OutputItem>, volumes: &[f32]) -> Output {
+        let mut items = Vec::with_capacity(all.len());
+        let mut excluded = Vec::new();
+        for (index, item) in all.into_iter().enumerate() {
+            if volumes.contains(&item.m_v) {
+                excluded.push((index, item));
+            } else {
+                items.push(item);
+            }
+        }
+        // The temperatures of the table follow their items.
+        let temperatures = if self.input.temperatures.is_empty() {
+            Vec::new()
+        } else {
+            items.iter().map(|it| it.temperature).collect()
+        };
+        let input = Input {
+            m_v: items.iter().map(|it| it.m_v).collect(),
+            temperatures,
+            ..self.input.clone()
+        };
+        Output {
+            input,
+            items,
+            excluded,
+            pairs: self.pairs.clone(),
+            sheet: self.sheet.clone(),
+            measured_conductivity: self.measured_conductivity.clone(),
+        }
+    }
+
     pub fn max_m_v(&self) -> f32 {
         self.items
             .iter()
@@ -331,9 +1289,71 @@ impl Output {
             .reduce(f32::max)
             .unwrap_or(0.0)
     }
+
+    /// Returns the index of the first item after the equivalence point.
+    ///
+    /// If the equivalence point was not reached, the number of items is returned.
+    pub fn equivalence_index(&self) -> usize {
+        self.items
+            .iter()
+            .position(|it| it.c1.is_nan())
+            .unwrap_or(self.items.len())
+    }
+
+    /// Returns the region of the curve the item at the given index belongs to.
+    ///
+    /// The last item before and the first item after the equivalence point form the equivalence
+    /// region.
+    pub fn region(&self, index: usize) -> Region {
+        let split = self.equivalence_index();
+        if index == 0 {
+            Region::Initial
+        } else if index + 1 < split {
+            Region::Buffer
+        } else if index <= split {
+            Region::Equivalence
+        } else {
+            Region::Excess
+        }
+    }
+}
+
+/// A region of the titration curve.
+///
+/// The regions are ordered as they are passed during the titration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Region {
+    /// The pure test solution.
+    Initial,
+    /// The test solution is partially neutralized.
+    Buffer,
+    /// The steep jump around the equivalence point.
+    Equivalence,
+    /// The measuring solution is in excess.
+    Excess,
+}
+
+impl Region {
+    pub fn next(self) -> Option<Self> {
+        match self {
+            Self::Initial => Some(Self::Buffer),
+            Self::Buffer => Some(Self::Equivalence),
+            Self::Equivalence => Some(Self::Excess),
+            Self::Excess => None,
+        }
+    }
+
+    pub fn previous(self) -> Option<Self> {
+        match self {
+            Self::Initial => None,
+            Self::Buffer => Some(Self::Initial),
+            Self::Equivalence => Some(Self::Buffer),
+            Self::Excess => Some(Self::Equivalence),
+        }
+    }
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize)]
 pub struct OutputItem {
     pub m_v: f32,
     pub ph: f32,
@@ -343,91 +1363,160 @@ pub struct OutputItem {
     pub c1: f32,
     pub c2: f32,
     pub poh: f32,
+    /// The standard uncertainty of the pH propagated from the input.
+    pub ph_uncertainty: f32,
+    /// The temperature of the solution in °C.
+    pub temperature: f32,
 }
 
-/// Loads a file from the given path.
-///
-/// The table format is the following:
-///
-/// ```text
-/// t: test solution
-/// m: measuring solution
-/// V: volume
-/// c: concentration
-/// acid: acid used for titration
-/// base: base used for titration
+impl OutputItem {
+    /// Returns the item with the average values of items at the same volume.
+    fn average(items: &[OutputItem]) -> OutputItem {
+        let mean = |value: fn(&OutputItem) -> f32| {
+            items.iter().map(value).sum::<f32>() / items.len() as f32
+        };
+        OutputItem {
+            m_v: items[0].m_v,
+            ph: mean(|it| it.ph),
+            total_v: mean(|it| it.total_v),
+            n1: mean(|it| it.n1),
+            n2: mean(|it| it.n2),
+            c1: mean(|it| it.c1),
+            c2: mean(|it| it.c2),
+            poh: mean(|it| it.poh),
+            ph_uncertainty: mean(|it| it.ph_uncertainty),
+            temperature: mean(|it| it.temperature),
+        }
+    }
+}
+
+/// A conjugate acid-base pair.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AcidBasePair {
+    pub acid: String,
+    pub base: String,
+    pub pka: f32,
+}
+
+impl AcidBasePair {
+    pub fn new(acid: impl Into<String>, base: impl Into<String>, pka: f32) -> Self {
+        Self {
+            acid: acid.into(),
+            base: base.into(),
+            pka,
+        }
+    }
+}
+
+/// Reads a file in the tail mode if it is a CSV file, or loads it completely otherwise.
+fn read_file(worker: &Worker, tails: &mut BTreeMap<PathBuf, CsvTail>, path: &Path, force: bool) {
+    if *worker.tail.lock().unwrap() && parser::is_csv(path) {
+        tail_file(worker, tails, path, force);
+    } else {
+        tails.remove(path);
+        load_file(worker, path);
+    }
+}
+
+/// Reads the rows appended to a CSV file since it was last read and sends the extended output to
+/// the app.
 ///
-/// +--------+----+-------+----+----+------+
-/// |        |    | V (t) |    |    | acid |
-/// +--------+----+-------+----+----+------+
-/// |        |    | c (t) |    |    |      |
-/// +--------+----+-------+----+----+------+
-/// |        |    | c (m) |    |    | base |
-/// +--------+----+-------+----+----+------+
-/// |        |    |       |    |    |      |
-/// +--------+----+-------+----+----+------+
-/// |        |    |       |    |    |      |
-/// +--------+----+-------+----+----+------+
-/// | V0 (m) |    |       |    |    |      |
-/// +--------+----+-------+----+----+------+
-/// | V1 (m) |    |       |    |    |      |
-/// +--------+----+-------+----+----+------+
-/// | ...    |    |       |    |    |      |
-/// +--------+----+-------+----+----+------+
-/// ```
-fn load_file(worker: &Worker, path: &PathBuf) {
-    let mut workbook = match calamine::open_workbook_auto(path) {
-        Ok(workbook) => workbook,
+/// The file is parsed completely the first time, when forced, or if the appended rows cannot be
+/// read.
+fn tail_file(worker: &Worker, tails: &mut BTreeMap<PathBuf, CsvTail>, path: &Path, force: bool) {
+    let _span = info_span!("tail", path = %path.display()).entered();
+    if let Some(tail) = tails.get_mut(path).filter(|_| !force) {
+        match tail.append(path) {
+            Some(0) => {
+                debug!("No rows were appended");
+                return;
+            }
+            Some(added) => {
+                info!(added, "Read the appended rows");
+                send_output(worker, path, tail.output().clone());
+                return;
+            }
+            None => debug!("Parsing the file again"),
+        }
+    }
+    let temperature = *worker.temperature.lock().unwrap();
+    match CsvTail::load(path, temperature, *worker.model.lock().unwrap()) {
+        Ok(tail) => {
+            info!(points = tail.output().items.len(), "Loaded the file");
+            send_output(worker, path, tail.output().clone());
+            tails.insert(path.to_path_buf(), tail);
+        }
         Err(err) => {
-            worker.send_response(Response::Error(WorkerError::TableError(err)));
-            return;
+            warn!("Could not load the file: {err}");
+            tails.remove(path);
+            worker.send_response(Response::Error(path.to_path_buf(), err));
         }
+    }
+}
+
+/// Loads a file from the given path and sends the result to the app.
+fn load_file(worker: &Worker, path: &Path) {
+    let _span = info_span!("load", path = %path.display()).entered();
+    let mut progress = |rows_done, rows_total| {
+        worker.send_response(Response::Progress {
+            path: path.to_path_buf(),
+            rows_done,
+            rows_total,
+        });
+        !*worker.cancelled.lock().unwrap()
     };
-    let Some(worksheet) = workbook.worksheet_range_at(0) else {
-        worker.send_response(Response::Error(WorkerError::NoTableInWorkbook));
-        return;
-    };
-    let worksheet = match worksheet {
-        Ok(worksheet) => worksheet,
+    let parsed = debug_span!("parse")
+        .in_scope(|| parser::parse_file(path, &mut progress))
+        .and_then(|(input, sheet)| {
+            debug!(volumes = input.m_v.len(), "Parsed the file");
+            let input = Input {
+                temperature: *worker.temperature.lock().unwrap(),
+                model: *worker.model.lock().unwrap(),
+                ..input
+            };
+            input.model.check(&input)?;
+            Ok((input, sheet))
+        });
+    match parsed {
+        Ok((input, sheet)) => {
+            let output = Output {
+                sheet,
+                ..debug_span!("calculate").in_scope(|| input.calculate_output())
+            };
+            info!(points = output.items.len(), "Loaded the file");
+            send_output(worker, path, output);
+        }
+        Err(WorkerError::Cancelled) => {
+            info!("Cancelled loading the file");
+            worker.send_response(Response::Cancelled(path.to_path_buf()));
+        }
         Err(err) => {
-            worker.send_response(Response::Error(WorkerError::TableError(err)));
-            return;
+            warn!("Could not load the file: {err}");
+            worker.send_response(Response::Error(path.to_path_buf(), err));
         }
-    };
-    let (h, w) = worksheet.get_size();
-    if h < 6 || w < 6 {
-        worker.send_response(Response::Error(WorkerError::TableNotCorrectlyFormatted));
-        return;
     }
-    let (Some(t_v), Some(t_c), Some(m_c)) = (
-        worksheet[(0, 2)].as_f64(),
-        worksheet[(1, 2)].as_f64(),
-        worksheet[(2, 2)].as_f64(),
-    ) else {
-        worker.send_response(Response::Error(WorkerError::TableNotCorrectlyFormatted));
-        return;
-    };
-    let mut m_v = Vec::new();
-    for row in worksheet.rows().skip(5) {
-        if row.is_empty() {
-            worker.send_response(Response::Error(WorkerError::TableNotCorrectlyFormatted));
-            return;
+}
+
+/// Sends the output of a file to the app, together with the conductivity measured in the same
+/// run.
+///
+/// An invalid file of the conductivity fails the whole file, so it is not silently left out.
+fn send_output(worker: &Worker, path: &Path, output: Output) {
+    match conductivity::read_measured(path) {
+        Ok(measured_conductivity) => {
+            let output = Output {
+                measured_conductivity,
+                ..output
+            };
+            worker.send_response(Response::Output(
+                path.to_path_buf(),
+                Arc::new(output),
+                SystemTime::now(),
+            ));
         }
-        let Some(cell) = row[0].as_f64() else {
-            worker.send_response(Response::Error(WorkerError::TableNotCorrectlyFormatted));
-            return;
-        };
-        m_v.push(cell as f32);
-    }
-    // todo - acid and base support
-    let input = Input {
-        t_v: t_v as f32,
-        t_c: t_c as f32,
-        m_c: m_c as f32,
-        m_v,
-        acid: 0.0,
-        base: 0.0,
-    };
-    let output = input.calculate_output();
-    worker.send_response(Response::Output(Arc::new(output)));
+        Err(err) => {
+            warn!("Could not load the measured conductivity: {err}");
+            worker.send_response(Response::Error(path.to_path_buf(), err));
+        }
+    }
 }
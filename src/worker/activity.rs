@@ -0,0 +1,72 @@
+use super::{Input, Output, OutputItem};
+
+/// The constant A of the Debye–Hückel equation for water at 25 °C in (L/mol)^½.
+const DEBYE_HUCKEL_A: f32 = 0.509;
+/// The constant B of the Debye–Hückel equation for water at 25 °C in (L/mol)^½ per Å.
+const DEBYE_HUCKEL_B: f32 = 0.328;
+/// The effective diameter of the hydrated H₃O⁺ ion in Å.
+const HYDRONIUM_SIZE: f32 = 9.0;
+/// The effective diameter of the hydrated OH⁻ ion in Å.
+const HYDROXIDE_SIZE: f32 = 3.5;
+
+/// Calculates the ionic strength I = ½ Σ c · z² at an item in mol/L.
+///
+/// All ions of the solution of the model are summed with their charges at the pH of the item,
+/// e.g. SO₄²⁻ of sulfuric acid, Ba²⁺ of baryta water and the anions of a second acid. Returns
+/// `None` if the model does not describe the ions of the solution.
+pub fn ionic_strength(input: &Input, item: &OutputItem) -> Option<f32> {
+    let solution = input.model.solution(input, item.m_v, item.temperature)?;
+    Some(solution.ionic_strength(item.ph as f64) as f32)
+}
+
+/// Returns the relative permittivity of water at a temperature in °C.
+///
+/// The fit of Malmberg and Maryott holds from 0 °C to 100 °C.
+fn permittivity(temperature: f32) -> f32 {
+    let t = temperature;
+    87.740 - 0.40008 * t + 9.398e-4 * t * t - 1.410e-6 * t * t * t
+}
+
+/// Returns the constants A and B of the Debye–Hückel equation at a temperature in °C.
+///
+/// A is proportional to (ε · T)^−3/2 and B to (ε · T)^−1/2, so both are scaled from their values
+/// at 25 °C. The density of water is taken as constant.
+fn debye_huckel_constants(temperature: f32) -> (f32, f32) {
+    let reference = permittivity(25.0) * 298.15;
+    let ratio = reference / (permittivity(temperature) * (temperature + 273.15));
+    (
+        DEBYE_HUCKEL_A * ratio.powf(1.5),
+        DEBYE_HUCKEL_B * ratio.sqrt(),
+    )
+}
+
+/// Calculates the activity coefficient of a singly charged ion of the given size in Å at a
+/// temperature in °C with the extended Debye–Hückel equation lg γ = −A · √I / (1 + B · a · √I).
+pub fn activity_coefficient(ionic_strength: f32, size: f32, temperature: f32) -> f32 {
+    let (a, b) = debye_huckel_constants(temperature);
+    let root = ionic_strength.max(0.0).sqrt();
+    10f32.powf(-a * root / (1.0 + b * size * root))
+}
+
+/// Calculates the pH with activities instead of concentrations at every item.
+///
+/// Before the equivalence point pH = −lg(γ · c(H₃O⁺)), afterwards pOH = −lg(γ · c(OH⁻)). The
+/// activity coefficients are below one, so the curve is raised before and lowered after the
+/// equivalence point. Without the ions of the solution there is no corrected curve.
+pub fn corrected_curve(output: &Output) -> Vec<(f32, f32)> {
+    output
+        .items
+        .iter()
+        .map_while(|item| {
+            let ionic_strength = ionic_strength(&output.input, item)?;
+            let ph = if item.c1.is_finite() {
+                item.ph
+                    - activity_coefficient(ionic_strength, HYDRONIUM_SIZE, item.temperature).log10()
+            } else {
+                item.ph
+                    + activity_coefficient(ionic_strength, HYDROXIDE_SIZE, item.temperature).log10()
+            };
+            Some((item.m_v, ph))
+        })
+        .collect()
+}
@@ -0,0 +1,299 @@
+use serde::{Deserialize, Serialize};
+
+use super::{substances::Substance, uncertainty, Output};
+
+/// Calculates the first derivative of the titration curve.
+///
+/// Every point is placed between two neighbouring items and holds the change of the pH per added
+/// volume in 1/mL. Neighbours with the same volume are skipped.
+pub fn derivative(output: &Output) -> Vec<(f32, f32)> {
+    output
+        .items
+        .windows(2)
+        .filter(|items| items[1].m_v > items[0].m_v)
+        .map(|items| {
+            let d_v = items[1].m_v - items[0].m_v;
+            (
+                (items[0].m_v + items[1].m_v) / 2.0,
+                (items[1].ph - items[0].ph) / d_v,
+            )
+        })
+        .collect()
+}
+
+/// Calculates the slope of the titration curve at every item in 1/mL.
+///
+/// Unlike the derivative, the slope is placed on the items themselves, using the neighbours on
+/// both sides. The first and last items only use the one neighbour they have.
+pub fn slopes(output: &Output) -> Vec<f32> {
+    let items = &output.items;
+    (0..items.len())
+        .map(|index| {
+            let before = &items[index.saturating_sub(1)];
+            let after = &items[(index + 1).min(items.len() - 1)];
+            let d_v = after.m_v - before.m_v;
+            if d_v > 0.0 {
+                (after.ph - before.ph) / d_v
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+/// The smallest deviation of a suspicious pH from its neighbours.
+///
+/// Small deviations in flat parts of the curve are noise rather than misread values.
+const OUTLIER_MIN_DEVIATION: f32 = 0.5;
+/// The number of scaled median absolute deviations a suspicious pH is away from its neighbours.
+const OUTLIER_THRESHOLD: f32 = 3.0;
+/// Scales the median absolute deviation to the standard deviation of normally distributed values.
+const MAD_SCALE: f32 = 1.4826;
+
+/// Finds the items whose pH stands out from their neighbours, e.g. because it was misread.
+///
+/// Every item is compared with the median of a window reaching up to two items to each side, like
+/// a Hampel filter. The curve changes monotonically, so the median of a window is its middle item,
+/// even at the steep equivalence point. Only items breaking the order stand out. The first and
+/// last items only have neighbours on one side and are never flagged.
+pub fn outliers(output: &Output) -> Vec<usize> {
+    let items = &output.items;
+    (1..items.len().saturating_sub(1))
+        .filter(|&index| {
+            let radius = index.min(items.len() - 1 - index).min(2);
+            let mut window: Vec<f32> = items[index - radius..=index + radius]
+                .iter()
+                .map(|it| it.ph)
+                .collect();
+            let median = middle(&mut window);
+            let mut deviations: Vec<f32> = window.iter().map(|it| (it - median).abs()).collect();
+            let spread = MAD_SCALE * middle(&mut deviations);
+            let deviation = (items[index].ph - median).abs();
+            deviation > OUTLIER_MIN_DEVIATION && deviation > OUTLIER_THRESHOLD * spread
+        })
+        .collect()
+}
+
+/// Returns the median of an odd number of values.
+fn middle(values: &mut [f32]) -> f32 {
+    values.sort_by(f32::total_cmp);
+    values[values.len() / 2]
+}
+
+/// Interpolates the pH at an added volume in mL.
+///
+/// Volumes outside of the measured range have no pH.
+pub fn ph_at(output: &Output, m_v: f32) -> Option<f32> {
+    output
+        .items
+        .windows(2)
+        .find(|items| items[0].m_v <= m_v && m_v <= items[1].m_v)
+        .map(|items| {
+            let d_v = items[1].m_v - items[0].m_v;
+            if d_v > 0.0 {
+                items[0].ph + (items[1].ph - items[0].ph) * (m_v - items[0].m_v) / d_v
+            } else {
+                items[0].ph
+            }
+        })
+}
+
+/// Finds the first volume in mL at which the curve reaches a pH, which inverts [`ph_at`].
+///
+/// The root of the interpolated pH minus the given pH is searched between neighbouring items, so
+/// measured curves which are not strictly monotonic yield their first crossing. A pH the curve
+/// never reaches has no volume.
+pub fn volume_at(output: &Output, ph: f32) -> Option<f32> {
+    output
+        .items
+        .windows(2)
+        .find(|items| (items[0].ph - ph) * (items[1].ph - ph) <= 0.0)
+        .map(|items| {
+            let d_ph = items[1].ph - items[0].ph;
+            if d_ph != 0.0 {
+                items[0].m_v + (items[1].m_v - items[0].m_v) * (ph - items[0].ph) / d_ph
+            } else {
+                items[0].m_v
+            }
+        })
+}
+
+/// Integrates the buffer capacity β = dn/dpH of the titration between two pH values.
+///
+/// The result is the amount of added measuring solution in mol which changes the pH from one
+/// value to the other. Between two items, β is assumed to be constant, so parts of the pH range
+/// covered by an item pair add the matching part of the added amount.
+pub fn integrate_buffer_capacity(output: &Output, from_ph: f32, to_ph: f32) -> f32 {
+    let (low, high) = (from_ph.min(to_ph), from_ph.max(to_ph));
+    output
+        .items
+        .windows(2)
+        .map(|items| {
+            let (ph_0, ph_1) = (items[0].ph, items[1].ph);
+            let d_ph = (ph_1 - ph_0).abs();
+            if d_ph == 0.0 || !d_ph.is_finite() {
+                return 0.0;
+            }
+            let overlap = high.min(ph_0.max(ph_1)) - low.max(ph_0.min(ph_1));
+            let d_n = output.input.m_c * (items[1].m_v - items[0].m_v) / 1000.0;
+            d_n * (overlap / d_ph).max(0.0)
+        })
+        .sum()
+}
+
+/// How much measuring solution a titration used compared to what was needed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TitrantUsage {
+    /// The volume needed to reach the equivalence point in mL, calculated from the input.
+    pub equivalence_volume: f32,
+    /// The standard uncertainty of the equivalence volume in mL.
+    pub equivalence_uncertainty: f32,
+    /// The volume at the steepest part of the measured curve in mL.
+    pub endpoint_volume: Option<f32>,
+    /// The total added volume in mL.
+    pub total_volume: f32,
+}
+
+impl TitrantUsage {
+    /// Returns the fraction of the added volume which was needed to reach the equivalence point.
+    pub fn consumed_fraction(&self) -> f32 {
+        if self.total_volume > 0.0 {
+            (self.equivalence_volume / self.total_volume).min(1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the volume added after the detected endpoint in mL.
+    pub fn overshoot(&self) -> Option<f32> {
+        self.endpoint_volume
+            .map(|it| (self.total_volume - it).max(0.0))
+    }
+}
+
+/// Compares the added measuring solution with the amount needed for the equivalence point.
+///
+/// The endpoint is detected where the pH changes the most per added volume.
+pub fn titrant_usage(output: &Output) -> TitrantUsage {
+    let input = &output.input;
+    let endpoint_volume = derivative(output)
+        .into_iter()
+        .filter(|it| it.1.is_finite())
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|it| it.0);
+    TitrantUsage {
+        equivalence_volume: input.equivalence_volume(),
+        equivalence_uncertainty: uncertainty::equivalence_uncertainty(input),
+        endpoint_volume,
+        total_volume: output.max_m_v(),
+    }
+}
+
+/// The ratio of the amounts of analyte and measuring solution reacting with each other.
+///
+/// The calculation balances the reaction with the ratio, so the acid releases `titrant` protons
+/// and the base takes up `analyte` protons, e.g. 1 : 2 for H₂SO₄ + 2 NaOH.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stoichiometry {
+    pub analyte: u8,
+    pub titrant: u8,
+}
+
+impl Default for Stoichiometry {
+    fn default() -> Self {
+        Self {
+            analyte: 1,
+            titrant: 1,
+        }
+    }
+}
+
+impl Stoichiometry {
+    /// The ratios of common titrations, e.g. 1 : 2 for sulfuric acid with sodium hydroxide.
+    pub const COMMON: [Self; 4] = [
+        Self {
+            analyte: 1,
+            titrant: 1,
+        },
+        Self {
+            analyte: 1,
+            titrant: 2,
+        },
+        Self {
+            analyte: 2,
+            titrant: 1,
+        },
+        Self {
+            analyte: 1,
+            titrant: 3,
+        },
+    ];
+
+    /// Returns the ratio of an acid titrated with a base.
+    ///
+    /// The ratio is not reduced, so it keeps the protons of both, e.g. 2 : 2 for H₂SO₄ and
+    /// Ba(OH)₂.
+    pub fn of(acid: &Substance, base: &Substance) -> Self {
+        Self {
+            analyte: base.protons,
+            titrant: acid.protons,
+        }
+    }
+
+    /// Parses a ratio like `1:2` or `1 : 2`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (analyte, titrant) = text.split_once(':')?;
+        let stoichiometry = Self {
+            analyte: analyte.trim().parse().ok()?,
+            titrant: titrant.trim().parse().ok()?,
+        };
+        (stoichiometry.analyte > 0 && stoichiometry.titrant > 0).then_some(stoichiometry)
+    }
+
+    /// Returns the stoichiometric factor n(analyte) / n(measuring solution).
+    pub fn factor(self) -> f32 {
+        self.analyte as f32 / self.titrant as f32
+    }
+}
+
+/// The concentration of the test solution determined from the measured curve.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AnalyteResult {
+    /// The volume at the detected equivalence point in mL.
+    pub equivalence_volume: f32,
+    /// The determined concentration in mol/L.
+    pub concentration: f32,
+    /// The standard uncertainty of the concentration in mol/L.
+    pub uncertainty: f32,
+}
+
+/// Determines the concentration of the test solution from the detected equivalence point.
+///
+/// The amount of measuring solution added up to the equivalence point reacted with the analyte,
+/// so c(t) = f · c(m) · V(eq) / V(t). The uncertainties of the used values add in quadrature.
+pub fn analyte_concentration(
+    output: &Output,
+    stoichiometry: Stoichiometry,
+) -> Option<AnalyteResult> {
+    let input = &output.input;
+    let equivalence_volume = titrant_usage(output).endpoint_volume?;
+    if input.t_v <= 0.0 || equivalence_volume <= 0.0 {
+        return None;
+    }
+    let concentration =
+        stoichiometry.factor() * input.m_c * equivalence_volume / 1000.0 / input.t_v;
+    let relative = [
+        (input.uncertainty.m_c, input.m_c),
+        (input.uncertainty.t_v, input.t_v),
+        (input.uncertainty.m_v, equivalence_volume),
+    ]
+    .into_iter()
+    .map(|(uncertainty, value)| (uncertainty / value).powi(2))
+    .sum::<f32>()
+    .sqrt();
+    Some(AnalyteResult {
+        equivalence_volume,
+        concentration,
+        uncertainty: concentration.abs() * relative,
+    })
+}
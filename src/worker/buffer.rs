@@ -0,0 +1,43 @@
+use super::Output;
+
+/// The distance of two points of the buffer capacity curve on the pH axis.
+const PH_STEP: f32 = 0.1;
+/// The number of bisection steps used to find the volume at which a pH is reached.
+const VOLUME_BISECTION_STEPS: usize = 40;
+
+/// Calculates the buffer capacity β = dC(base)/dpH over the pH in mol/L.
+///
+/// The points are evenly spaced on the pH axis between the first and last measured pH, so the
+/// minimum at the equivalence point is resolved although only few volumes are measured there.
+/// The volume reaching every pH is found by bisection, since the pH rises with the volume.
+///
+/// The solutions are the ones of the model, so a model without them, like a script, has no
+/// buffer capacity curve.
+pub fn buffer_capacity(output: &Output) -> Vec<(f32, f32)> {
+    let input = &output.input;
+    let max_m_v = output.max_m_v();
+    let (Some(first), Some(last)) = (output.items.first(), output.items.last()) else {
+        return Vec::new();
+    };
+    if !(first.ph.is_finite() && last.ph.is_finite()) || last.ph <= first.ph {
+        return Vec::new();
+    }
+    let count = ((last.ph - first.ph) / PH_STEP).floor() as usize;
+    let solution = |m_v| input.model.solution(input, m_v, input.temperature);
+    (0..=count)
+        .map_while(|step| {
+            let ph = first.ph + step as f32 * PH_STEP;
+            let (mut low, mut high) = (0.0, max_m_v);
+            for _ in 0..VOLUME_BISECTION_STEPS {
+                let middle = (low + high) / 2.0;
+                if solution(middle)?.ph() < ph as f64 {
+                    low = middle;
+                } else {
+                    high = middle;
+                }
+            }
+            let solution = solution((low + high) / 2.0)?;
+            Some((ph, solution.buffer_capacity(ph as f64) as f32))
+        })
+        .collect()
+}
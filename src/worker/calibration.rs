@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+use super::temperature::{STANDARD_TEMPERATURE, ZERO_CELSIUS};
+
+/// The Faraday constant in C/mol.
+const FARADAY: f32 = 96_485.33;
+/// The gas constant in J/(mol·K).
+const GAS_CONSTANT: f32 = 8.314_463;
+/// The pH at which an ideal glass electrode shows no potential.
+pub const ZERO_POINT_PH: f32 = 7.0;
+/// The largest number of buffers of a calibration.
+pub const MAX_BUFFERS: usize = 3;
+
+/// A buffer solution measured with the electrode.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationPoint {
+    /// The pH of the buffer at the temperature of the measurement.
+    pub ph: f32,
+    /// The potential read in the buffer in mV.
+    pub mv: f32,
+}
+
+/// A 2- or 3-point calibration of a pH electrode, which converts its potential into a pH.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Calibration {
+    pub points: Vec<CalibrationPoint>,
+    /// The temperature of the buffers in °C.
+    pub temperature: f32,
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Self {
+            points: Vec::new(),
+            temperature: STANDARD_TEMPERATURE,
+        }
+    }
+}
+
+impl Calibration {
+    /// Fits the calibration line through the buffers by least squares.
+    ///
+    /// Two buffers define the line exactly, a third one averages out errors of the readings.
+    /// Returns `None` unless there are two or three buffers with different pH values.
+    pub fn line(&self) -> Option<CalibrationLine> {
+        if !(2..=MAX_BUFFERS).contains(&self.points.len()) {
+            return None;
+        }
+        let count = self.points.len() as f32;
+        let mean_ph = self.points.iter().map(|it| it.ph).sum::<f32>() / count;
+        let mean_mv = self.points.iter().map(|it| it.mv).sum::<f32>() / count;
+        let (covariance, variance) = self.points.iter().fold((0.0, 0.0), |(cov, var), it| {
+            let dph = it.ph - mean_ph;
+            (cov + dph * (it.mv - mean_mv), var + dph * dph)
+        });
+        let slope = covariance / variance;
+        if !slope.is_finite() || slope == 0.0 {
+            return None;
+        }
+        Some(CalibrationLine {
+            slope,
+            offset: mean_mv + slope * (ZERO_POINT_PH - mean_ph),
+            temperature: self.temperature,
+        })
+    }
+}
+
+/// The potential of an electrode as a linear function of the pH.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CalibrationLine {
+    /// The change of the potential per pH unit in mV, which is negative for a glass electrode.
+    pub slope: f32,
+    /// The potential at [`ZERO_POINT_PH`] in mV, also called the asymmetry potential.
+    pub offset: f32,
+    /// The temperature in °C the slope was measured at.
+    pub temperature: f32,
+}
+
+impl CalibrationLine {
+    /// Converts a potential in mV measured at a temperature in °C into the pH.
+    ///
+    /// The slope grows with the absolute temperature by the Nernst equation, so readings at
+    /// another temperature than the calibration are compensated.
+    pub fn ph(&self, mv: f32, temperature: f32) -> f32 {
+        let slope = self.slope * (temperature + ZERO_CELSIUS) / (self.temperature + ZERO_CELSIUS);
+        ZERO_POINT_PH + (mv - self.offset) / slope
+    }
+
+    /// Returns the slope relative to the theoretical slope at the temperature of the calibration.
+    ///
+    /// Electrodes in good condition reach about 95 % to 102 %.
+    pub fn efficiency(&self) -> f32 {
+        self.slope / nernst_slope(self.temperature)
+    }
+}
+
+/// Returns the theoretical slope of a glass electrode at a temperature in °C in mV per pH.
+///
+/// By the Nernst equation, the slope is −ln(10)·R·T/F, which is about −59.16 mV at 25 °C.
+pub fn nernst_slope(temperature: f32) -> f32 {
+    -std::f32::consts::LN_10 * GAS_CONSTANT * (temperature + ZERO_CELSIUS) / FARADAY * 1000.0
+}
@@ -0,0 +1,61 @@
+use super::{
+    substances::{MetalIon, EDTA_PKAS},
+    Input,
+};
+
+/// The pH the solution is buffered at during a complexometric titration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BufferPh(pub f32);
+
+/// Titrations of calcium and magnesium are usually buffered with ammonia.
+impl Default for BufferPh {
+    fn default() -> Self {
+        Self(10.0)
+    }
+}
+
+/// Returns the fraction of EDTA which is fully deprotonated (Y⁴⁻) at a pH.
+pub fn alpha_y4(ph: f32) -> f64 {
+    let h = 10f64.powf(-f64::from(ph));
+    // The terms of the denominator, from h⁶ down to K₁ · … · K₆.
+    let mut term = h.powi(EDTA_PKAS.len() as i32);
+    let mut sum = term;
+    for pka in EDTA_PKAS {
+        term *= 10f64.powf(-f64::from(pka)) / h;
+        sum += term;
+    }
+    term / sum
+}
+
+/// Returns the conditional formation constant of the metal complex at a pH.
+pub fn conditional_constant(metal: &MetalIon, ph: f32) -> f64 {
+    10f64.powf(f64::from(metal.log_kf)) * alpha_y4(ph)
+}
+
+/// Calculates pM for every added volume of EDTA.
+///
+/// The test solution holds the metal ion and the measuring solution EDTA, both with the
+/// concentrations of the input. Every point holds the added volume in mL and pM.
+pub fn complexometry(input: &Input, metal: &MetalIon, ph: BufferPh) -> Vec<(f32, f32)> {
+    let k = conditional_constant(metal, ph.0);
+    let n_metal = f64::from(input.t_c * input.t_v);
+    input
+        .m_v
+        .iter()
+        .map(|&m_v| {
+            let n_edta = f64::from(input.m_c * m_v / 1000.0);
+            let volume = f64::from(input.t_v + m_v / 1000.0);
+            let (c_metal, c_edta) = (n_metal / volume, n_edta / volume);
+            // K' · [M]² + b · [M] − c(M) = 0 with b = K' · (c(Y) − c(M)) + 1
+            let b = k * (c_edta - c_metal) + 1.0;
+            let root = (b * b + 4.0 * k * c_metal).sqrt();
+            // Avoid subtracting nearly equal numbers after the equivalence point.
+            let metal = if b > 0.0 {
+                2.0 * c_metal / (b + root)
+            } else {
+                (root - b) / (2.0 * k)
+            };
+            (m_v, -metal.log10() as f32)
+        })
+        .collect()
+}
@@ -0,0 +1,226 @@
+use std::{
+    array, fs, io,
+    path::{Path, PathBuf},
+};
+
+use super::{
+    parser::{cell_number, csv_text, csv_worksheet},
+    substances::{Substance, WEAK_ACIDS},
+    Input, WorkerError,
+};
+
+/// The end of the name of a file with the measured conductivity of a table.
+const MEASURED_SUFFIX: &str = ".conductivity.csv";
+
+/// An ion with its molar conductivity at infinite dilution and 25 °C.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ion {
+    pub formula: &'static str,
+    /// The molar conductivity in S·cm²/mol.
+    pub conductivity: f32,
+}
+
+pub const OXONIUM: Ion = Ion {
+    formula: "H₃O⁺",
+    conductivity: 349.6,
+};
+
+pub const HYDROXIDE: Ion = Ion {
+    formula: "OH⁻",
+    conductivity: 199.1,
+};
+
+/// The counter ions of the acids and bases in the substance database.
+pub const COUNTER_IONS: &[Ion] = &[
+    Ion {
+        formula: "Na⁺",
+        conductivity: 50.1,
+    },
+    Ion {
+        formula: "K⁺",
+        conductivity: 73.5,
+    },
+    Ion {
+        formula: "Cl⁻",
+        conductivity: 76.3,
+    },
+    Ion {
+        formula: "Br⁻",
+        conductivity: 78.1,
+    },
+    Ion {
+        formula: "NO₃⁻",
+        conductivity: 71.4,
+    },
+    Ion {
+        formula: "ClO₄⁻",
+        conductivity: 67.3,
+    },
+    Ion {
+        formula: "SO₄²⁻",
+        conductivity: 160.0,
+    },
+    Ion {
+        formula: "Ba²⁺",
+        conductivity: 127.2,
+    },
+    Ion {
+        formula: "CH₃COO⁻",
+        conductivity: 40.9,
+    },
+];
+
+/// Returns the counter ion of an acid or base.
+pub fn counter_ion(substance: &Substance) -> Option<&'static Ion> {
+    COUNTER_IONS
+        .iter()
+        .find(|it| it.formula == substance.counter_ion)
+}
+
+/// Calculates the conductivity of the solution for every added volume.
+///
+/// Every point holds the added volume in mL and the conductivity in mS/cm. The ions are assumed
+/// to be ideally diluted, so their contributions simply add up. The ions are taken from the
+/// solution of the titration model, so a weak acid only conducts with its protolysed part. Its
+/// anion replaces the one of the chosen strong acid. A second acid only adds its oxonium ions, as
+/// the table has no conductivities of its anions.
+///
+/// Models without a solution, like a script, have no conductivity.
+pub fn conductivity(input: &Input, acid: &Substance, base: &Substance) -> Vec<(f32, f32)> {
+    let acid = input
+        .analyte
+        .filter(|it| WEAK_ACIDS.contains(it))
+        .unwrap_or(acid);
+    let anion = counter_ion(acid).map_or(0.0, |it| it.conductivity);
+    let cation = counter_ion(base).map_or(0.0, |it| it.conductivity);
+    input
+        .m_v
+        .iter()
+        .enumerate()
+        .map_while(|(index, &m_v)| {
+            let solution = input
+                .model
+                .solution(input, m_v, input.temperature_at(index))?;
+            let ph = solution.ph();
+            // The acid and the base are the first two components. A strong acid is an anion
+            // already, while a weak one forms an anion for every released proton.
+            let [acid, base] = [&solution.components[0], &solution.components[1]];
+            let anions = if acid.pkas.is_empty() {
+                acid.concentration
+            } else {
+                acid.released_protons(ph)
+            };
+            let oxonium = 10f64.powf(-ph);
+            let hydroxide = solution.kw / oxonium;
+            // Concentrations in mol/L times S·cm²/mol give mS/cm.
+            let kappa = anions * f64::from(anion)
+                + base.concentration * f64::from(cation)
+                + oxonium * f64::from(OXONIUM.conductivity)
+                + hydroxide * f64::from(HYDROXIDE.conductivity);
+            Some((m_v, kappa as f32))
+        })
+        .collect()
+}
+
+/// Returns the path of the measured conductivity of a table, `<table>.conductivity.csv`.
+pub fn measured_path(table: &Path) -> PathBuf {
+    let mut path = table.as_os_str().to_owned();
+    path.push(MEASURED_SUFFIX);
+    path.into()
+}
+
+/// Returns whether the file at the path is the measured conductivity of a table.
+pub fn is_measured(path: &Path) -> bool {
+    path.file_name().is_some_and(|it| {
+        it.to_string_lossy()
+            .to_lowercase()
+            .ends_with(MEASURED_SUFFIX)
+    })
+}
+
+/// Reads the measured conductivity of a table, if there is one.
+///
+/// The file is a CSV file next to the table, see [`measured_path`], like a conductometer exports
+/// it for the same run. Its first two columns hold the added volume in mL and the conductivity in
+/// mS/cm. Rows without two numbers, like a header, are skipped. The points are sorted by volume.
+pub fn read_measured(table: &Path) -> Result<Option<Vec<(f32, f32)>>, WorkerError> {
+    let path = measured_path(table);
+    let bytes = match fs::read(&path) {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(WorkerError::Io(err)),
+    };
+    let worksheet = csv_worksheet(&csv_text(&bytes));
+    let mut points: Vec<(f32, f32)> = worksheet
+        .rows()
+        .filter_map(|row| {
+            let volume = cell_number(row.first()?)?;
+            let conductivity = cell_number(row.get(1)?)?;
+            Some((volume as f32, conductivity as f32))
+        })
+        .collect();
+    if points.is_empty() {
+        return Err(WorkerError::NoConductivity(path));
+    }
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    Ok(Some(points))
+}
+
+/// Pairs measured points with the given volumes, e.g. the ones of the pH curve.
+///
+/// The conductivity at every volume is interpolated linearly between the neighbouring
+/// measurements, so both quantities are known at the same volumes. Volumes outside of the
+/// measured range are left out, as the conductivity is unknown there.
+pub fn paired(measured: &[(f32, f32)], volumes: &[f32]) -> Vec<(f32, f32)> {
+    volumes
+        .iter()
+        .filter_map(|&volume| {
+            let after = measured.partition_point(|it| it.0 < volume);
+            let &(v2, k2) = measured.get(after)?;
+            if v2 == volume {
+                return Some((volume, k2));
+            }
+            let &(v1, k1) = measured.get(after.checked_sub(1)?)?;
+            Some((volume, k1 + (k2 - k1) * (volume - v1) / (v2 - v1)))
+        })
+        .collect()
+}
+
+/// Returns the endpoint of a conductometric titration, where two straight lines fitted to the
+/// points before and after it intersect.
+///
+/// The conductivity changes linearly with the added volume on both sides of the endpoint, but
+/// with another slope after it, once it is corrected for the dilution by the volume of the test
+/// solution in mL. The points are split where both lines fit best, and the intersection is kept
+/// between the points next to the split.
+pub fn endpoint(points: &[(f32, f32)], test_volume: f32) -> Option<f32> {
+    // The sums of x, y, x², xy and y² of the points before every index, so every line is fitted
+    // in constant time.
+    let mut sums = vec![[0.0; 5]];
+    for &(x, y) in points {
+        let dilution = (test_volume + x) / test_volume;
+        let (x, y) = (f64::from(x), f64::from(y * dilution));
+        let [sx, sy, sxx, sxy, syy] = sums[sums.len() - 1];
+        sums.push([sx + x, sy + y, sxx + x * x, sxy + x * y, syy + y * y]);
+    }
+    // Fits a line to the points in the range, returning its slope, intercept and squared error.
+    let line = |from: usize, to: usize| {
+        let count = (to - from) as f64;
+        let [sx, sy, sxx, sxy, syy]: [f64; 5] = array::from_fn(|it| sums[to][it] - sums[from][it]);
+        let variance = sxx - sx * sx / count;
+        let covariance = sxy - sx * sy / count;
+        let slope = covariance / variance;
+        let error = (syy - sy * sy / count - covariance * slope).max(0.0);
+        (variance > 0.0).then_some((slope, (sy - slope * sx) / count, error))
+    };
+    let (split, before, after) = (2..=points.len().saturating_sub(2))
+        .filter_map(|split| Some((split, line(0, split)?, line(split, points.len())?)))
+        .min_by(|a, b| (a.1 .2 + a.2 .2).total_cmp(&(b.1 .2 + b.2 .2)))?;
+    let crossing = ((after.1 - before.1) / (before.0 - after.0)) as f32;
+    let (first, last) = (points[split - 1].0, points[split].0);
+    Some(if crossing.is_finite() {
+        crossing.clamp(first.min(last), first.max(last))
+    } else {
+        last
+    })
+}
@@ -0,0 +1,207 @@
+use std::f64::consts::LN_10;
+
+/// The range of pH values the charge balance is solved in.
+const PH_RANGE: (f64, f64) = (-2.0, 16.0);
+/// The maximum number of Newton steps, which is only reached if most steps fall back to bisection.
+const MAX_ITERATIONS: usize = 100;
+/// The change of the pH below which the solution is converged.
+const CONVERGENCE: f64 = 1e-12;
+
+/// A dissolved acid, base or spectator ion.
+///
+/// The component exists in one protonation state more than it has acid constants. Its most
+/// protonated form has the given charge, and every deprotonation lowers the charge by one, so
+/// e.g. acetic acid has one pKa and charge 0, while the ammonium ion has one pKa and charge +1.
+/// Ions of strong acids and bases, like Cl⁻ or Na⁺, have no acid constants at all.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Component {
+    /// The total concentration of all protonation states in mol/L.
+    pub concentration: f64,
+    /// The pKa values from the most protonated form on, sorted ascending.
+    pub pkas: Vec<f64>,
+    /// The charge of the most protonated form.
+    pub charge: i32,
+}
+
+impl Component {
+    /// Creates an ion which does not take part in any acid-base reaction.
+    pub fn ion(concentration: f64, charge: i32) -> Self {
+        Self {
+            concentration,
+            pkas: Vec::new(),
+            charge,
+        }
+    }
+
+    /// Creates an acid with the charge of its most protonated form.
+    pub fn acid(concentration: f64, pkas: &[f64], charge: i32) -> Self {
+        Self {
+            concentration,
+            pkas: pkas.to_vec(),
+            charge,
+        }
+    }
+
+    /// Returns the fractions of the protonation states at a pH, from the most protonated form on.
+    ///
+    /// The fraction of the form which released j protons is proportional to
+    /// Ka₁ · … · Kaⱼ / [H₃O⁺]ʲ, which is summed in logarithms so strong acids do not overflow.
+    fn fractions(&self, ph: f64) -> Vec<f64> {
+        let mut logs = Vec::with_capacity(self.pkas.len() + 1);
+        logs.push(0.0);
+        for pka in &self.pkas {
+            logs.push(logs[logs.len() - 1] + (ph - pka) * LN_10);
+        }
+        let max = logs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let weights: Vec<f64> = logs.iter().map(|it| (it - max).exp()).collect();
+        let total: f64 = weights.iter().sum();
+        weights.into_iter().map(|it| it / total).collect()
+    }
+
+    /// Returns the concentrations of the protonation states at a pH in mol/L, from the most
+    /// protonated form on.
+    pub fn species(&self, ph: f64) -> Vec<f64> {
+        self.fractions(ph)
+            .into_iter()
+            .map(|it| it * self.concentration)
+            .collect()
+    }
+
+    /// Returns the concentration of the protons released at a pH in mol/L.
+    ///
+    /// Every released proton lowers the charge by one, so this is also the negative charge the
+    /// component gained.
+    pub fn released_protons(&self, ph: f64) -> f64 {
+        self.concentration * self.deprotonation(ph).0
+    }
+
+    /// Returns the mean and the variance of the number of released protons at a pH.
+    fn deprotonation(&self, ph: f64) -> (f64, f64) {
+        let fractions = self.fractions(ph);
+        let mean = fractions
+            .iter()
+            .enumerate()
+            .map(|(j, fraction)| j as f64 * fraction)
+            .sum::<f64>();
+        let variance = fractions
+            .iter()
+            .enumerate()
+            .map(|(j, fraction)| (j as f64 - mean).powi(2) * fraction)
+            .sum::<f64>();
+        (mean, variance)
+    }
+}
+
+/// An aqueous solution of any mixture of acids, bases and spectator ions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Solution {
+    pub components: Vec<Component>,
+    /// The ionic product of water.
+    pub kw: f64,
+}
+
+/// The pH of a solution with the number of steps needed to solve the charge balance.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Equilibrium {
+    pub ph: f64,
+    pub iterations: usize,
+}
+
+impl Solution {
+    pub fn new(kw: f64) -> Self {
+        Self {
+            components: Vec::new(),
+            kw,
+        }
+    }
+
+    /// Returns the solution with another component.
+    pub fn with(mut self, component: Component) -> Self {
+        self.components.push(component);
+        self
+    }
+
+    /// Returns the excess of positive charges at a pH in mol/L and its derivative by the pH.
+    ///
+    /// The excess is [H₃O⁺] − [OH⁻] + Σ c · (z − n̄), where n̄ is the mean number of released
+    /// protons of a component. Its derivative is −ln 10 · ([H₃O⁺] + [OH⁻] + Σ c · Var(n)), which
+    /// is negative everywhere, so the charge balance has exactly one root.
+    fn charge_excess(&self, ph: f64) -> (f64, f64) {
+        let hydronium = 10f64.powf(-ph);
+        let hydroxide = self.kw / hydronium;
+        let mut excess = hydronium - hydroxide;
+        let mut slope = hydronium + hydroxide;
+        for component in &self.components {
+            let (mean, variance) = component.deprotonation(ph);
+            excess += component.concentration * (component.charge as f64 - mean);
+            slope += component.concentration * variance;
+        }
+        (excess, -LN_10 * slope)
+    }
+
+    /// Solves the charge balance for the pH.
+    ///
+    /// Newton steps are taken on the pH, which falls back to bisection whenever a step leaves the
+    /// range known to contain the root.
+    pub fn equilibrium(&self) -> Equilibrium {
+        let (mut low, mut high) = PH_RANGE;
+        let mut ph = 7.0;
+        for iterations in 1..=MAX_ITERATIONS {
+            let (excess, slope) = self.charge_excess(ph);
+            let newton = ph - excess / slope;
+            if (newton - ph).abs() < CONVERGENCE {
+                return Equilibrium {
+                    ph: newton,
+                    iterations,
+                };
+            }
+            if excess > 0.0 {
+                low = ph;
+            } else {
+                high = ph;
+            }
+            ph = if newton.is_finite() && low < newton && newton < high {
+                newton
+            } else {
+                (low + high) / 2.0
+            };
+        }
+        Equilibrium {
+            ph,
+            iterations: MAX_ITERATIONS,
+        }
+    }
+
+    pub fn ph(&self) -> f64 {
+        self.equilibrium().ph
+    }
+
+    /// Returns the ionic strength I = ½ · Σ c · z² at a pH in mol/L.
+    ///
+    /// Every protonation state of a component counts with its own charge, and the oxonium and
+    /// hydroxide ions are included.
+    pub fn ionic_strength(&self, ph: f64) -> f64 {
+        let hydronium = 10f64.powf(-ph);
+        let hydroxide = self.kw / hydronium;
+        let ions: f64 =
+            self.components
+                .iter()
+                .flat_map(|component| {
+                    component.species(ph).into_iter().enumerate().map(
+                        |(released, concentration)| {
+                            concentration * f64::from(component.charge - released as i32).powi(2)
+                        },
+                    )
+                })
+                .sum();
+        (hydronium + hydroxide + ions) / 2.0
+    }
+
+    /// Returns the buffer capacity β = dC(base)/dpH at a pH in mol/L.
+    ///
+    /// Adding a strong base raises the excess of positive charges by its concentration, so the
+    /// base needed to raise the pH is the negative derivative of the excess.
+    pub fn buffer_capacity(&self, ph: f64) -> f64 {
+        -self.charge_excess(ph).1
+    }
+}
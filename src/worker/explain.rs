@@ -0,0 +1,161 @@
+use std::mem;
+
+use super::{format::NumberFormat, locale::localized};
+
+/// A quantity calculated for every output item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Quantity {
+    /// The total volume of the solution.
+    TotalVolume,
+    /// The amount of substance added with the measuring solution.
+    AddedAmount,
+    /// The amount of substance of the test solution which has not been neutralized yet.
+    RemainingAmount,
+    /// The concentration of the test solution.
+    TestConcentration,
+    /// The concentration of the excess measuring solution.
+    ExcessConcentration,
+    Ph,
+    Poh,
+}
+
+/// A single step of the calculation of an output item.
+#[derive(Clone, Debug)]
+pub struct CalculationStep {
+    pub quantity: Quantity,
+    /// The formula with the actual values substituted.
+    pub formula: String,
+    pub result: f32,
+}
+
+/// Records the steps of a calculation.
+///
+/// The calculation reports every step to the recorder, so explanations always match the
+/// calculated values.
+pub trait Recorder {
+    /// Records a step of the current item.
+    ///
+    /// The formula is only formatted if the recorder needs it.
+    fn step(&mut self, quantity: Quantity, formula: impl FnOnce() -> String, result: f32);
+
+    /// Finishes the current item, which was pushed at the given index.
+    fn finish(&mut self, index: usize);
+}
+
+/// The unit recorder ignores all steps.
+impl Recorder for () {
+    fn step(&mut self, _quantity: Quantity, _formula: impl FnOnce() -> String, _result: f32) {}
+
+    fn finish(&mut self, _index: usize) {}
+}
+
+/// Records the steps of the output item at a single index.
+pub struct Explainer {
+    index: usize,
+    current: Vec<CalculationStep>,
+    steps: Option<Vec<CalculationStep>>,
+}
+
+impl Explainer {
+    pub fn new(index: usize) -> Self {
+        Self {
+            index,
+            current: Vec::new(),
+            steps: None,
+        }
+    }
+
+    /// Returns the recorded steps.
+    ///
+    /// If there is no item at the index, no steps are returned.
+    pub fn into_steps(self) -> Vec<CalculationStep> {
+        self.steps.unwrap_or_default()
+    }
+}
+
+impl Recorder for Explainer {
+    fn step(&mut self, quantity: Quantity, formula: impl FnOnce() -> String, result: f32) {
+        if self.steps.is_some() {
+            return;
+        }
+        self.current.push(CalculationStep {
+            quantity,
+            formula: formula(),
+            result,
+        });
+    }
+
+    fn finish(&mut self, index: usize) {
+        if index == self.index && self.steps.is_none() {
+            self.steps = Some(mem::take(&mut self.current));
+        } else {
+            self.current.clear();
+        }
+    }
+}
+
+/// Formats a number for a formula.
+///
+/// Small numbers are written in scientific notation.
+pub fn number(value: f32) -> String {
+    localized(if value != 0.0 && value.abs() < 0.01 {
+        format!("{value:.3e}")
+    } else {
+        format!("{value:.3}")
+    })
+}
+
+/// The rounding rules of reported results.
+///
+/// Only the reported values are rounded, all calculations keep their full precision.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rounding {
+    /// The number of decimals of volumes in mL, for example 2 to report to 0.01 mL.
+    pub volume_decimals: Option<u8>,
+    /// The number of significant digits of concentrations and other derived results.
+    pub significant_digits: Option<u8>,
+}
+
+impl Rounding {
+    /// Formats a volume in mL.
+    pub fn volume(&self, value: f32) -> String {
+        match self.volume_decimals {
+            Some(decimals) => localized(format!("{value:.*}", decimals as usize)),
+            None => number(value),
+        }
+    }
+
+    /// Formats a concentration or another derived result.
+    pub fn value(&self, value: f32) -> String {
+        match self.significant_digits {
+            Some(digits) => NumberFormat::significant(digits).format(value),
+            None => number(value),
+        }
+    }
+
+    /// Rounds a volume in mL to the decimals, for values which are written as numbers.
+    pub fn round_volume(&self, value: f32) -> f32 {
+        match self.volume_decimals {
+            Some(decimals) => round(value, i32::from(decimals)),
+            None => value,
+        }
+    }
+
+    /// Rounds a concentration or another derived result to the significant digits, for values
+    /// which are written as numbers.
+    pub fn round_value(&self, value: f32) -> f32 {
+        match self.significant_digits {
+            Some(digits) if value != 0.0 && value.is_finite() => {
+                let magnitude = f64::from(value).abs().log10().floor() as i32;
+                round(value, i32::from(digits.max(1)) - 1 - magnitude)
+            }
+            _ => value,
+        }
+    }
+}
+
+/// Rounds a value to a number of decimals, which are negative to round to tens and above.
+fn round(value: f32, decimals: i32) -> f32 {
+    let scale = 10f64.powi(decimals);
+    ((f64::from(value) * scale).round() / scale) as f32
+}
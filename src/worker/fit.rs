@@ -0,0 +1,242 @@
+use super::{
+    analysis,
+    equilibrium::{Component, Solution},
+    temperature, Output,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The range of fitted pKa values, from acids which protolyse completely to water itself.
+const PKA_RANGE: (f64, f64) = (-2.0, 14.0);
+/// The maximum number of Levenberg–Marquardt iterations.
+const MAX_ITERATIONS: usize = 100;
+/// The step of the parameters used to approximate the derivatives of the model.
+const DERIVATIVE_STEP: f64 = 1e-6;
+/// The change of the parameters below which the fit is converged.
+const CONVERGENCE: f64 = 1e-9;
+/// The number of points of a fitted curve drawn over the volume axis.
+pub const CURVE_POINTS: usize = 200;
+
+/// A monoprotic acid titrated with a strong base.
+///
+/// The pH follows from the charge balance [H₃O⁺] + [Na⁺] = [OH⁻] + [A⁻], which is solved like
+/// the calculation of the output.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AcidModel {
+    /// The volume of the test solution in mL.
+    pub test_volume: f32,
+    /// The concentration of the analyte in mol/L.
+    pub test_concentration: f32,
+    pub pka: f32,
+    /// The concentration of the measuring solution in mol/L.
+    pub measuring_concentration: f32,
+    /// The pKw of water at the working temperature.
+    pub pkw: f32,
+}
+
+impl AcidModel {
+    /// Calculates the pH after adding a volume of measuring solution in mL.
+    pub fn ph(&self, m_v: f32) -> f32 {
+        ph(
+            self.test_volume as f64,
+            self.test_concentration as f64,
+            self.pka as f64,
+            self.measuring_concentration as f64,
+            10f64.powf(-self.pkw as f64),
+            m_v as f64,
+        ) as f32
+    }
+}
+
+/// A model fitted to the measured pH values of a titration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CurveFit {
+    pub model: AcidModel,
+    /// The measured minus the fitted pH at every measured volume in mL.
+    pub residuals: Vec<(f32, f32)>,
+    /// The root mean square of the residuals.
+    pub rms: f32,
+    pub iterations: usize,
+    /// Whether the parameters settled before the maximum number of iterations, without the fit
+    /// failing.
+    pub converged: bool,
+}
+
+impl CurveFit {
+    /// Returns the residual with the largest magnitude and its volume.
+    pub fn largest_residual(&self) -> Option<(f32, f32)> {
+        self.residuals
+            .iter()
+            .copied()
+            .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+    }
+
+    /// Samples the fitted curve between no added volume and the given volume in mL.
+    pub fn curve(&self, max_m_v: f32) -> Vec<(f32, f32)> {
+        (0..=CURVE_POINTS)
+            .map(|it| {
+                let m_v = max_m_v * it as f32 / CURVE_POINTS as f32;
+                (m_v, self.model.ph(m_v))
+            })
+            .collect()
+    }
+}
+
+/// Fits the model of a monoprotic acid to the measured pH values with the Levenberg–Marquardt
+/// method.
+///
+/// The fitted parameters are the analyte concentration, as its logarithm so it stays positive,
+/// and the pKa. The volume of the test solution, the concentration of the measuring solution and
+/// the working temperature are taken from the input. The concentration starts at the value of the
+/// steepest part of the curve and the pKa at the pH halfway to it. At least three measured points
+/// are needed.
+///
+/// A fit whose normal equations become singular, or which finds no step lowering the residuals,
+/// stops early and is not converged, so its parameters are no valid result.
+pub fn fit(output: &Output) -> Option<CurveFit> {
+    let points: Vec<(f64, f64)> = output
+        .items
+        .iter()
+        .filter(|it| it.ph.is_finite())
+        .map(|it| (it.m_v as f64, it.ph as f64))
+        .collect();
+    let input = &output.input;
+    let test_volume = input.t_v as f64 * 1000.0;
+    let measuring_concentration = input.m_c as f64;
+    let pkw = temperature::pkw(input.temperature);
+    let kw = 10f64.powf(-pkw as f64);
+    if points.len() < 3 || test_volume <= 0.0 || measuring_concentration <= 0.0 {
+        return None;
+    }
+    let endpoint = analysis::titrant_usage(output).endpoint_volume;
+    let concentration = match endpoint {
+        Some(endpoint) if endpoint > 0.0 => measuring_concentration * endpoint as f64 / test_volume,
+        _ => input.t_c as f64,
+    };
+    let pka = endpoint
+        .and_then(|it| analysis::ph_at(output, it / 2.0))
+        .map_or(7.0, |it| it as f64);
+    let model = |params: [f64; 2], m_v: f64| {
+        ph(
+            test_volume,
+            10f64.powf(params[0]),
+            params[1],
+            measuring_concentration,
+            kw,
+            m_v,
+        )
+    };
+    let residuals = |params: [f64; 2]| -> Vec<f64> {
+        points
+            .iter()
+            .map(|&(m_v, ph)| ph - model(params, m_v))
+            .collect()
+    };
+    let cost = |residuals: &[f64]| residuals.iter().map(|it| it * it).sum::<f64>();
+    let mut params = [
+        concentration.max(f64::MIN_POSITIVE).log10(),
+        pka.clamp(PKA_RANGE.0, PKA_RANGE.1),
+    ];
+    let mut current = residuals(params);
+    let mut damping = 1e-3;
+    let mut iterations = 0;
+    let mut converged = false;
+    // Whether no step could be taken, as the normal equations are singular or every step raised
+    // the residuals.
+    let mut failed = false;
+    while iterations < MAX_ITERATIONS && !converged && !failed {
+        iterations += 1;
+        // The Jacobian of the residuals is the negative Jacobian of the model.
+        let jacobian: Vec<[f64; 2]> = points
+            .iter()
+            .map(|&(m_v, _)| {
+                let at = model(params, m_v);
+                [0, 1].map(|param| {
+                    let mut moved = params;
+                    moved[param] += DERIVATIVE_STEP;
+                    (model(moved, m_v) - at) / DERIVATIVE_STEP
+                })
+            })
+            .collect();
+        let mut normal = [[0.0; 2]; 2];
+        let mut gradient = [0.0; 2];
+        for (row, residual) in jacobian.iter().zip(&current) {
+            for i in 0..2 {
+                gradient[i] += row[i] * residual;
+                for j in 0..2 {
+                    normal[i][j] += row[i] * row[j];
+                }
+            }
+        }
+        loop {
+            let a = normal[0][0] * (1.0 + damping);
+            let d = normal[1][1] * (1.0 + damping);
+            let (b, c) = (normal[0][1], normal[1][0]);
+            let determinant = a * d - b * c;
+            if determinant == 0.0 || !determinant.is_finite() || damping > 1e12 {
+                failed = true;
+                break;
+            }
+            let step = [
+                (d * gradient[0] - b * gradient[1]) / determinant,
+                (a * gradient[1] - c * gradient[0]) / determinant,
+            ];
+            let candidate = [
+                params[0] + step[0],
+                (params[1] + step[1]).clamp(PKA_RANGE.0, PKA_RANGE.1),
+            ];
+            let next = residuals(candidate);
+            if cost(&next) <= cost(&current) {
+                converged = (candidate[0] - params[0]).abs() < CONVERGENCE
+                    && (candidate[1] - params[1]).abs() < CONVERGENCE;
+                params = candidate;
+                current = next;
+                damping /= 10.0;
+                break;
+            }
+            damping *= 10.0;
+        }
+    }
+    let rms = (cost(&current) / current.len() as f64).sqrt() as f32;
+    Some(CurveFit {
+        model: AcidModel {
+            test_volume: test_volume as f32,
+            test_concentration: 10f64.powf(params[0]) as f32,
+            pka: params[1] as f32,
+            measuring_concentration: measuring_concentration as f32,
+            pkw,
+        },
+        residuals: points
+            .iter()
+            .zip(&current)
+            .map(|(&(m_v, _), &residual)| (m_v as f32, residual as f32))
+            .collect(),
+        rms,
+        iterations,
+        converged,
+    })
+}
+
+/// Solves the charge balance of the model for the pH.
+fn ph(
+    test_volume: f64,
+    test_concentration: f64,
+    pka: f64,
+    measuring_concentration: f64,
+    kw: f64,
+    m_v: f64,
+) -> f64 {
+    let total_volume = test_volume + m_v;
+    Solution::new(kw)
+        .with(Component::acid(
+            test_concentration * test_volume / total_volume,
+            &[pka],
+            0,
+        ))
+        .with(Component::ion(
+            measuring_concentration * m_v / total_volume,
+            1,
+        ))
+        .ph()
+}
@@ -0,0 +1,63 @@
+//! Fitting the model of a monoprotic acid to a curve.
+
+use super::{fit, AcidModel};
+use crate::worker::{
+    analysis::Stoichiometry, model::StrongModel, temperature, uncertainty::Uncertainty, Input,
+};
+
+/// Creates the output of a titration whose pH follows the model at every added volume in mL.
+fn curve(model: AcidModel, m_v: Vec<f32>) -> crate::worker::Output {
+    let input = Input {
+        t_v: model.test_volume / 1000.0,
+        t_c: model.test_concentration,
+        m_c: model.measuring_concentration,
+        m_v,
+        temperature: 25.0,
+        temperatures: Vec::new(),
+        uncertainty: Uncertainty::default(),
+        stoichiometry: Stoichiometry::default(),
+        second_acid: None,
+        titrant: None,
+        analyte: None,
+        model: &StrongModel,
+    };
+    let mut output = input.calculate_output();
+    for item in &mut output.items {
+        item.ph = model.ph(item.m_v);
+    }
+    output
+}
+
+#[test]
+fn recovers_the_concentration() {
+    let model = AcidModel {
+        test_volume: 20.0,
+        test_concentration: 0.08,
+        pka: 4.2,
+        measuring_concentration: 0.1,
+        pkw: temperature::pkw(25.0),
+    };
+    let output = curve(model, (0..=60).map(|it| it as f32 * 0.5).collect());
+    let fit = fit(&output).unwrap();
+    assert!(fit.converged);
+    let error = (fit.model.test_concentration - model.test_concentration).abs();
+    assert!(error < 1e-4, "c(t) = {}", fit.model.test_concentration);
+    assert!(
+        (fit.model.pka - model.pka).abs() < 1e-2,
+        "pKa = {}",
+        fit.model.pka
+    );
+    assert!(fit.rms < 1e-3);
+}
+
+#[test]
+fn needs_three_points() {
+    let model = AcidModel {
+        test_volume: 20.0,
+        test_concentration: 0.1,
+        pka: 4.8,
+        measuring_concentration: 0.1,
+        pkw: temperature::pkw(25.0),
+    };
+    assert_eq!(fit(&curve(model, vec![0.0, 10.0])), None);
+}
@@ -0,0 +1,93 @@
+use super::{explain::number, locale::localized};
+
+/// The notation numbers are written in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Notation {
+    /// Three decimals, or scientific notation for numbers too small for them.
+    #[default]
+    Auto,
+    /// A fixed number of decimals, like `0.125`.
+    Fixed,
+    /// A number of significant digits, like `0.0125`, and scientific notation for very small
+    /// numbers.
+    Significant,
+    /// Scientific notation with a number of decimals of the mantissa, like `1.25e-2`.
+    Scientific,
+}
+
+impl Notation {
+    pub const ALL: [Self; 4] = [Self::Auto, Self::Fixed, Self::Significant, Self::Scientific];
+}
+
+/// The way values are written on the axes, in readouts and in exported tables.
+///
+/// The decimal separator follows the number locale.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NumberFormat {
+    pub notation: Notation,
+    /// The number of decimals or significant digits, depending on the notation.
+    pub digits: u8,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            notation: Notation::Auto,
+            digits: 3,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Returns the format with the given number of significant digits.
+    pub fn significant(digits: u8) -> Self {
+        Self {
+            notation: Notation::Significant,
+            digits,
+        }
+    }
+
+    /// Formats a number.
+    pub fn format(&self, value: f32) -> String {
+        self.format_or(value, number)
+    }
+
+    /// Formats a number, or with the default of the place it is shown at for [`Notation::Auto`].
+    pub fn format_or(&self, value: f32, auto: impl FnOnce(f32) -> String) -> String {
+        let digits = self.digits as usize;
+        match self.notation {
+            Notation::Auto => auto(value),
+            Notation::Fixed => localized(format!("{value:.digits$}")),
+            Notation::Significant => {
+                let digits = self.digits.max(1) as i32;
+                if value == 0.0 || !value.is_finite() {
+                    return localized(format!("{value:.*}", (digits - 1) as usize));
+                }
+                let magnitude = value.abs().log10().floor() as i32;
+                localized(if magnitude < -2 {
+                    format!("{value:.*e}", (digits - 1) as usize)
+                } else {
+                    format!("{value:.*}", (digits - 1 - magnitude).max(0) as usize)
+                })
+            }
+            Notation::Scientific => localized(format!("{value:.digits$e}")),
+        }
+    }
+
+    /// Returns the number format of a spreadsheet cell showing values in this format.
+    ///
+    /// Spreadsheets have no notation for significant digits, so they show those values like
+    /// [`Notation::Auto`] as well.
+    pub fn spreadsheet_format(&self) -> String {
+        let decimals = if self.digits == 0 {
+            String::new()
+        } else {
+            format!(".{}", "0".repeat(self.digits as usize))
+        };
+        match self.notation {
+            Notation::Auto | Notation::Significant => "General".into(),
+            Notation::Fixed => format!("0{decimals}"),
+            Notation::Scientific => format!("0{decimals}E+00"),
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use std::ops::RangeInclusive;
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+use crate::util::Rng;
+
+use super::{
+    analysis::Stoichiometry,
+    model::StrongModel,
+    substances::{Substance, STRONG_ACIDS, STRONG_BASES},
+    temperature::STANDARD_TEMPERATURE,
+    uncertainty::Uncertainty,
+    Input,
+};
+
+/// The maximum number of volumes in a generated problem.
+const MAX_VOLUMES: usize = 200;
+
+/// The ranges the parameters of generated problems are chosen from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProblemRanges {
+    /// The volume of the test solution in L.
+    pub test_volume: RangeInclusive<f32>,
+    /// The concentration of the test solution in mol/L.
+    pub test_concentration: RangeInclusive<f32>,
+    /// The concentration of the measuring solution in mol/L.
+    pub measuring_concentration: RangeInclusive<f32>,
+    /// The volume added between two measurements in mL.
+    pub step: f32,
+}
+
+impl Default for ProblemRanges {
+    fn default() -> Self {
+        Self {
+            test_volume: 0.01..=0.05,
+            test_concentration: 0.05..=0.2,
+            measuring_concentration: 0.05..=0.2,
+            step: 1.0,
+        }
+    }
+}
+
+/// A randomized titration problem of a strong acid with a strong base.
+#[derive(Clone, Debug)]
+pub struct Problem {
+    /// The seed the problem was generated from.
+    pub seed: u64,
+    pub acid: Substance,
+    pub base: Substance,
+    pub input: Input,
+}
+
+impl Problem {
+    /// Generates a problem.
+    ///
+    /// The same seed and ranges always generate the same problem.
+    pub fn generate(seed: u64, ranges: &ProblemRanges) -> Self {
+        let mut rng = Rng::new(seed);
+        let acid = STRONG_ACIDS[rng.index(STRONG_ACIDS.len())];
+        let base = STRONG_BASES[rng.index(STRONG_BASES.len())];
+        // Round to values which can be prepared in a lab.
+        let t_v = round_to(rng.range(ranges.test_volume.clone()), 0.001);
+        let t_c = round_to(rng.range(ranges.test_concentration.clone()), 0.01);
+        let m_c = round_to(rng.range(ranges.measuring_concentration.clone()), 0.01);
+        let mut problem = Self {
+            seed,
+            acid,
+            base,
+            input: Input {
+                t_v,
+                t_c,
+                m_c,
+                m_v: Vec::new(),
+                temperature: STANDARD_TEMPERATURE,
+                temperatures: Vec::new(),
+                uncertainty: Uncertainty::default(),
+                stoichiometry: Stoichiometry::of(&acid, &base),
+                second_acid: None,
+                titrant: None,
+                analyte: None,
+                model: &StrongModel,
+            },
+        };
+        // Measure up to twice the equivalence volume.
+        let step = ranges.step.max(0.01);
+        let count = ((2.0 * problem.equivalence_volume() / step).ceil() as usize).min(MAX_VOLUMES);
+        problem.input.m_v = (0..=count).map(|it| it as f32 * step).collect();
+        problem
+    }
+
+    /// Returns the volume of measuring solution at the equivalence point in mL.
+    pub fn equivalence_volume(&self) -> f32 {
+        self.input.equivalence_volume()
+    }
+
+    /// Writes the spreadsheet handed out to students.
+    ///
+    /// The concentration of the test solution is left blank, since it is to be determined.
+    pub fn write_worksheet(&self) -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        worksheet.write_string(0, 1, "V (Probelösung) / L")?;
+        worksheet.write_number(0, 2, self.input.t_v)?;
+        worksheet.write_string(1, 1, "c (Probelösung) / mol/L")?;
+        worksheet.write_string(2, 1, "c (Maßlösung) / mol/L")?;
+        worksheet.write_number(2, 2, self.input.m_c)?;
+        worksheet.write_string(0, 5, self.acid.formula)?;
+        worksheet.write_string(2, 5, self.base.formula)?;
+        worksheet.write_string(4, 0, "V (Maßlösung) / mL")?;
+        for (row, &m_v) in (5..).zip(&self.input.m_v) {
+            worksheet.write_number(row, 0, m_v)?;
+        }
+        workbook.save_to_buffer()
+    }
+}
+
+fn round_to(value: f32, step: f32) -> f32 {
+    (value / step).round().max(1.0) * step
+}
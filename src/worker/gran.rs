@@ -0,0 +1,134 @@
+use super::{analysis, temperature, Output};
+
+/// The part of a Gran function's range used for the regression, relative to its largest value.
+///
+/// Points close to the equivalence point are left out, since the measured pH bends away from
+/// the linear model there.
+const FIT_RANGE: (f64, f64) = (0.1, 0.9);
+
+/// A straight line fitted to a Gran function.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GranLine {
+    pub slope: f32,
+    pub intercept: f32,
+}
+
+impl GranLine {
+    /// Returns the volume where the line reaches zero in mL.
+    pub fn root(&self) -> Option<f32> {
+        (self.slope != 0.0).then(|| -self.intercept / self.slope)
+    }
+
+    pub fn at(&self, m_v: f32) -> f32 {
+        self.slope * m_v + self.intercept
+    }
+}
+
+/// The Gran functions of a titration of an acid with a base and their regression lines.
+///
+/// Before the equivalence point, G = V · 10^(−pH) falls to zero at the equivalence volume,
+/// afterwards G = V · 10^(pH − 14) rises from zero there. Both are scaled to their largest value,
+/// which does not move the point where they reach zero.
+#[derive(Clone, Debug, Default)]
+pub struct GranPlot {
+    pub before: Vec<(f32, f32)>,
+    pub after: Vec<(f32, f32)>,
+    pub before_line: Option<GranLine>,
+    pub after_line: Option<GranLine>,
+}
+
+impl GranPlot {
+    /// Returns the equivalence volume extrapolated from the regression lines in mL.
+    ///
+    /// The line before the equivalence point is preferred, since the excess of a weak titrant
+    /// bends the function afterwards.
+    pub fn equivalence_volume(&self) -> Option<f32> {
+        [self.before_line, self.after_line]
+            .into_iter()
+            .flatten()
+            .filter_map(|it| it.root())
+            .find(|it| it.is_finite())
+    }
+}
+
+/// Calculates the Gran plot of a titration.
+///
+/// The measured points are split at the steepest part of the curve, so the equivalence volume
+/// calculated from the input does not influence the result.
+pub fn gran(output: &Output) -> GranPlot {
+    let Some(endpoint) = analysis::titrant_usage(output).endpoint_volume else {
+        return GranPlot::default();
+    };
+    let pkw = temperature::pkw(output.input.temperature) as f64;
+    // The functions are calculated in f64, since 10^pH exceeds the range of f32 values.
+    let function = |before: bool| -> Vec<(f32, f64)> {
+        output
+            .items
+            .iter()
+            .filter(|it| (it.m_v < endpoint) == before && it.ph.is_finite())
+            .map(|it| {
+                let exponent = if before {
+                    -it.ph as f64
+                } else {
+                    it.ph as f64 - pkw
+                };
+                (it.m_v, it.total_v as f64 * 10f64.powf(exponent))
+            })
+            .collect()
+    };
+    let (before, before_line) = scale_and_fit(function(true));
+    let (after, after_line) = scale_and_fit(function(false));
+    GranPlot {
+        before,
+        after,
+        before_line,
+        after_line,
+    }
+}
+
+/// Scales the values of a Gran function to its largest value and fits a line to it.
+///
+/// If too few points are in the fit range, all points are used.
+fn scale_and_fit(points: Vec<(f32, f64)>) -> (Vec<(f32, f32)>, Option<GranLine>) {
+    let max = points.iter().map(|it| it.1).fold(0.0, f64::max);
+    if max <= 0.0 {
+        return (Vec::new(), None);
+    }
+    let scaled: Vec<(f32, f64)> = points.iter().map(|&(x, y)| (x, y / max)).collect();
+    let in_range: Vec<(f32, f64)> = scaled
+        .iter()
+        .copied()
+        .filter(|it| (FIT_RANGE.0..=FIT_RANGE.1).contains(&it.1))
+        .collect();
+    let line = if in_range.len() >= 2 {
+        fit(&in_range)
+    } else {
+        fit(&scaled)
+    };
+    let scaled = scaled.into_iter().map(|(x, y)| (x, y as f32)).collect();
+    (scaled, line)
+}
+
+/// Fits a line to the points with the method of least squares.
+fn fit(points: &[(f32, f64)]) -> Option<GranLine> {
+    let count = points.len() as f64;
+    if count < 2.0 {
+        return None;
+    }
+    let mean_x = points.iter().map(|it| it.0 as f64).sum::<f64>() / count;
+    let mean_y = points.iter().map(|it| it.1).sum::<f64>() / count;
+    let (mut covariance, mut variance) = (0.0, 0.0);
+    for &(x, y) in points {
+        let d_x = x as f64 - mean_x;
+        covariance += d_x * (y - mean_y);
+        variance += d_x * d_x;
+    }
+    if variance == 0.0 {
+        return None;
+    }
+    let slope = covariance / variance;
+    Some(GranLine {
+        slope: slope as f32,
+        intercept: (mean_y - slope * mean_x) as f32,
+    })
+}
@@ -0,0 +1,87 @@
+use super::{equilibrium::Component, temperature, Input};
+
+/// The distance of two points of a line on the pH axis.
+const PH_STEP: f32 = 0.1;
+/// The highest pH of the diagram.
+const MAX_PH: f32 = 14.0;
+
+/// A line of the logarithmic concentration diagram.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HaggLine {
+    /// The formula of the species, e.g. `H₃O⁺`.
+    pub species: String,
+    /// The pairs of a pH and the decadic logarithm of the concentration in mol/L.
+    pub points: Vec<(f32, f32)>,
+}
+
+/// Calculates the logarithmic concentration diagram (Hägg diagram) of the test solution.
+///
+/// Every protonation state of every component of the model's solution before the titration gets
+/// a line of lg c over the pH, next to the lines of H₃O⁺ and OH⁻. The pH of the solution is where
+/// the charge balance holds, e.g. where the lines of H₃O⁺ and A⁻ cross for a strong acid.
+pub fn hagg(input: &Input) -> Vec<HaggLine> {
+    let pkw = temperature::pkw(input.temperature);
+    let steps = (MAX_PH / PH_STEP).round() as usize;
+    let phs: Vec<f32> = (0..=steps).map(|it| it as f32 * PH_STEP).collect();
+    let line = |species: String, lg: &dyn Fn(f32) -> f32| HaggLine {
+        species,
+        points: phs.iter().map(|&ph| (ph, lg(ph))).collect(),
+    };
+    let mut lines = vec![
+        line("H₃O⁺".into(), &|ph| -ph),
+        line("OH⁻".into(), &|ph| ph - pkw),
+    ];
+    // A model without a solution, like a script, only has the lines of water.
+    let Some(solution) = input.model.solution(input, 0.0, input.temperature) else {
+        return lines;
+    };
+    for component in solution
+        .components
+        .iter()
+        .filter(|it| it.concentration > 0.0)
+    {
+        for state in 0..=component.pkas.len() {
+            let lg = |ph: f32| component.species(ph as f64)[state].log10() as f32;
+            lines.push(line(species_formula(component, state), &lg));
+        }
+    }
+    lines
+}
+
+/// Returns the generic formula of a protonation state of a component, e.g. `HA` or `A²⁻`.
+///
+/// Acids and anions are written as A, and cations of strong bases as M.
+fn species_formula(component: &Component, state: usize) -> String {
+    let protons = component.pkas.len() - state;
+    let charge = component.charge - state as i32;
+    let mut formula = match protons {
+        0 => String::new(),
+        1 => "H".into(),
+        protons => format!("H{}", script(protons as u32, "₀₁₂₃₄₅₆₇₈₉")),
+    };
+    formula.push(if component.pkas.is_empty() && charge > 0 {
+        'M'
+    } else {
+        'A'
+    });
+    if charge.unsigned_abs() > 1 {
+        formula.push_str(&script(charge.unsigned_abs(), "⁰¹²³⁴⁵⁶⁷⁸⁹"));
+    }
+    match charge {
+        0 => {}
+        charge if charge > 0 => formula.push('⁺'),
+        _ => formula.push('⁻'),
+    }
+    formula
+}
+
+/// Writes a number with the given sub- or superscript digits.
+fn script(number: u32, digits: &str) -> String {
+    let digits: Vec<char> = digits.chars().collect();
+    number
+        .to_string()
+        .chars()
+        .filter_map(|it| it.to_digit(10))
+        .map(|it| digits[it as usize])
+        .collect()
+}
@@ -0,0 +1,87 @@
+use super::{
+    analysis,
+    substances::{Indicator, INDICATORS},
+    Output,
+};
+
+/// The indicators suitable for a titration, judged by the pH at its equivalence point.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recommendation {
+    /// The pH of the curve at the equivalence volume.
+    pub equivalence_ph: f32,
+    /// The indicators whose transition range contains the equivalence pH, from the one whose
+    /// middle is closest to it on.
+    pub suitable: Vec<&'static Indicator>,
+    /// The indicator whose transition range is closest to the equivalence pH, if none contains it.
+    pub nearest: Option<&'static Indicator>,
+}
+
+/// Returns the pH of the curve at the (last) equivalence volume calculated from the input.
+///
+/// The pH is interpolated between the items, so it follows measured values as well. Curves not
+/// reaching the equivalence volume have no equivalence pH.
+pub fn equivalence_ph(output: &Output) -> Option<f32> {
+    analysis::ph_at(output, output.input.equivalence_volume())
+}
+
+/// Recommends the indicators changing their color at the equivalence point of a titration.
+///
+/// An indicator is suitable if its transition range brackets the equivalence pH, since the color
+/// then changes within the steep jump of the curve.
+pub fn recommend(output: &Output) -> Option<Recommendation> {
+    let ph = equivalence_ph(output)?;
+    let distance = |indicator: &Indicator| (indicator.middle() - ph).abs();
+    let mut suitable: Vec<&Indicator> = INDICATORS.iter().filter(|it| it.brackets(ph)).collect();
+    suitable.sort_by(|a, b| distance(a).total_cmp(&distance(b)));
+    let nearest = suitable.is_empty().then(|| {
+        let gap =
+            |indicator: &Indicator| (indicator.transition.0 - ph).max(ph - indicator.transition.1);
+        INDICATORS.iter().min_by(|a, b| gap(a).total_cmp(&gap(b)))
+    });
+    Some(Recommendation {
+        equivalence_ph: ph,
+        suitable,
+        nearest: nearest.flatten(),
+    })
+}
+
+/// The systematic error of a titration whose endpoint is shown by an indicator.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TitrationError {
+    /// The pH the indicator changes its color at, in the middle of its transition range.
+    pub endpoint_ph: f32,
+    /// The volume at which the curve reaches the endpoint pH in mL.
+    pub endpoint_volume: f32,
+    /// The volume at the equivalence point calculated from the input in mL.
+    pub equivalence_volume: f32,
+}
+
+impl TitrationError {
+    /// Returns the volume added too much (or too little, if negative) in mL.
+    pub fn volume(&self) -> f32 {
+        self.endpoint_volume - self.equivalence_volume
+    }
+
+    /// Returns the error relative to the equivalence volume.
+    pub fn relative(&self) -> f32 {
+        self.volume() / self.equivalence_volume
+    }
+}
+
+/// Estimates the titration error of an indicator from the curve.
+///
+/// The titration stops where the curve reaches the pH the indicator changes its color at, which
+/// is found by inverting the curve. Its difference to the equivalence volume is the error, so
+/// indicators changing before the equivalence pH give a negative error.
+pub fn titration_error(output: &Output, indicator: &Indicator) -> Option<TitrationError> {
+    let equivalence_volume = output.input.equivalence_volume();
+    if !equivalence_volume.is_finite() || equivalence_volume <= 0.0 {
+        return None;
+    }
+    let endpoint_ph = indicator.middle();
+    Some(TitrationError {
+        endpoint_ph,
+        endpoint_volume: analysis::volume_at(output, endpoint_ph)?,
+        equivalence_volume,
+    })
+}
@@ -0,0 +1,175 @@
+use std::{
+    io,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use serial2::SerialPort;
+use tracing::{debug, info, warn};
+
+use super::{
+    analysis::Stoichiometry, model::StrongModel, temperature, uncertainty::Uncertainty, Input,
+    Output, OutputItem, Response, Worker, WorkerError,
+};
+
+/// The time a read waits for data from the meter, which bounds the delay of a disconnect.
+const READ_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// The settings of the serial port a pH meter is connected to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LiveConfig {
+    /// The name of the port, e.g. `/dev/ttyUSB0` or `COM3`.
+    pub port: String,
+    pub baud_rate: u32,
+}
+
+/// A connection to a pH meter, which sends every reading to the app.
+///
+/// The port is read on its own thread, which stops when the connection is dropped.
+pub struct LiveInput {
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl LiveInput {
+    /// Opens the serial port and starts reading from it.
+    pub fn connect(worker: Arc<Worker>, config: &LiveConfig) -> io::Result<Self> {
+        let mut port = SerialPort::open(&config.port, config.baud_rate)?;
+        port.set_read_timeout(READ_TIMEOUT)?;
+        info!(
+            port = config.port,
+            baud_rate = config.baud_rate,
+            "Connected to the meter"
+        );
+        let running = Arc::new(AtomicBool::new(true));
+        let thread = {
+            let running = running.clone();
+            thread::spawn(move || read_port(&worker, &port, &running))
+        };
+        Ok(Self {
+            running,
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for LiveInput {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        info!("Disconnected from the meter");
+    }
+}
+
+/// Reads lines from the port until the connection is dropped or the port fails.
+fn read_port(worker: &Worker, port: &SerialPort, running: &AtomicBool) {
+    let mut buffer = [0; 256];
+    let mut line = Vec::new();
+    while running.load(Ordering::Relaxed) {
+        let read = match port.read(&mut buffer) {
+            Ok(read) => read,
+            Err(err) if err.kind() == io::ErrorKind::TimedOut => continue,
+            Err(err) => {
+                warn!("Could not read from the meter: {err}");
+                worker.send_response(Response::LiveFailed(WorkerError::Io(err)));
+                return;
+            }
+        };
+        for &byte in &buffer[..read] {
+            if byte != b'\n' {
+                line.push(byte);
+                continue;
+            }
+            let text = String::from_utf8_lossy(&line);
+            match parse_reading(&text) {
+                Some(value) => {
+                    worker.send_response(Response::Reading(value, parse_temperature(&text)));
+                }
+                None => debug!(line = %text.trim(), "Ignored a line without a reading"),
+            }
+            line.clear();
+        }
+    }
+}
+
+/// Parses the reading in a line sent by the meter.
+///
+/// The meter sends one reading per line, and the first number in the line is taken as the pH,
+/// so lines like `7.01`, `pH 7.01` or `pH=7.01 25.0°C` are accepted. Meters sending the raw
+/// potential of the electrode, like `-35.2 mV`, are read the same way and calibrated in the app.
+pub fn parse_reading(line: &str) -> Option<f32> {
+    line.split(|it: char| !(it.is_ascii_digit() || matches!(it, '.' | '-' | '+')))
+        .find_map(|it| it.parse().ok())
+        .filter(|it: &f32| it.is_finite())
+}
+
+/// Parses the temperature in °C in a line sent by the meter, which is the number before `°C`.
+pub fn parse_temperature(line: &str) -> Option<f32> {
+    let end = line.find("°C")?;
+    let start = line[..end]
+        .trim_end()
+        .rfind(|it: char| !(it.is_ascii_digit() || matches!(it, '.' | '-' | '+')))
+        .map_or(0, |it| it + 1);
+    line[start..end]
+        .trim()
+        .parse()
+        .ok()
+        .filter(|it: &f32| it.is_finite())
+}
+
+/// Creates the output of pH readings at volumes of added measuring solution in mL.
+///
+/// The points are the volume, the pH and the temperature in °C, which is NaN if the meter sends
+/// none, so the point is taken at the working temperature. Only the pH is measured, so the
+/// amounts and concentrations are unknown.
+pub fn output(points: &[(f32, f32, f32)], temperature: f32) -> Output {
+    let temperatures = if points.iter().any(|it| it.2.is_finite()) {
+        points.iter().map(|it| it.2).collect()
+    } else {
+        Vec::new()
+    };
+    let input = Input {
+        t_v: 0.0,
+        t_c: 0.0,
+        m_c: 0.0,
+        m_v: points.iter().map(|(m_v, _, _)| *m_v).collect(),
+        temperature,
+        temperatures,
+        uncertainty: Uncertainty::default(),
+        stoichiometry: Stoichiometry::default(),
+        second_acid: None,
+        titrant: None,
+        analyte: None,
+        model: &StrongModel,
+    };
+    let items = points
+        .iter()
+        .enumerate()
+        .map(|(index, &(m_v, ph, _))| OutputItem {
+            m_v,
+            ph,
+            temperature: input.temperature_at(index),
+            total_v: f32::NAN,
+            n1: f32::NAN,
+            n2: f32::NAN,
+            c1: f32::NAN,
+            c2: f32::NAN,
+            poh: temperature::pkw(input.temperature_at(index)) - ph,
+            ph_uncertainty: 0.0,
+        })
+        .collect();
+    Output {
+        pairs: input.acid_base_pairs(),
+        input,
+        items,
+        excluded: Vec::new(),
+        sheet: None,
+        measured_conductivity: None,
+    }
+}
@@ -0,0 +1,115 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[cfg(test)]
+mod tests;
+
+static NUMBER_LOCALE: AtomicU8 = AtomicU8::new(NumberLocale::Point as u8);
+
+/// The way the decimals and the thousands of a number are separated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NumberLocale {
+    /// Numbers like `1,234.5`.
+    #[default]
+    Point = 0,
+    /// Numbers like `1.234,5`, as written in German.
+    Comma = 1,
+}
+
+/// Returns the locale numbers are parsed and displayed in.
+pub fn number_locale() -> NumberLocale {
+    match NUMBER_LOCALE.load(Ordering::Relaxed) {
+        1 => NumberLocale::Comma,
+        _ => NumberLocale::Point,
+    }
+}
+
+/// Sets the locale numbers are parsed and displayed in.
+pub fn set_number_locale(locale: NumberLocale) {
+    NUMBER_LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+impl NumberLocale {
+    pub fn decimal_separator(self) -> char {
+        match self {
+            Self::Point => '.',
+            Self::Comma => ',',
+        }
+    }
+
+    pub fn thousands_separator(self) -> char {
+        match self {
+            Self::Point => ',',
+            Self::Comma => '.',
+        }
+    }
+
+    /// Parses a number written as text.
+    ///
+    /// Both `3.5` and `3,5` are read as a decimal number. A separator is only taken as a
+    /// thousands separator if it can not be the decimal separator: one of two different
+    /// separators before the other, or one occurring several times. Spaces between the digits are
+    /// ignored as well.
+    ///
+    /// A single separator followed by exactly three digits, like `1,125`, is only read as the
+    /// decimal separator of the locale. Written with the thousands separator of the locale, it
+    /// could be either, so it is not read as a number and reported instead of guessed.
+    ///
+    /// Only finite numbers are read, so text like `inf` or `NaN` is not a number.
+    pub fn parse(self, text: &str) -> Option<f64> {
+        let mut text: String = text
+            .trim()
+            .chars()
+            .filter(|it| !matches!(it, ' ' | '\u{a0}' | '\u{202f}' | '\''))
+            .collect();
+        if !text
+            .chars()
+            .all(|it| it.is_ascii_digit() || matches!(it, '+' | '-' | '.' | ',' | 'e' | 'E'))
+        {
+            return None;
+        }
+        let last_point = text.rfind('.');
+        let last_comma = text.rfind(',');
+        let thousands = match (last_point, last_comma) {
+            (Some(point), Some(comma)) => Some(if point < comma { '.' } else { ',' }),
+            (Some(_), None) | (None, Some(_)) => {
+                let separator = if last_point.is_some() { '.' } else { ',' };
+                let mut parts = text.split(separator);
+                let integer = parts.next().unwrap_or_default();
+                let fraction = parts.next().unwrap_or_default();
+                let repeated = parts.next().is_some();
+                let integer = integer.trim_start_matches(['-', '+']);
+                let ambiguous = separator == self.thousands_separator()
+                    && (1..=3).contains(&integer.len())
+                    && !integer.starts_with('0')
+                    && fraction.len() == 3
+                    && fraction.chars().all(|it| it.is_ascii_digit());
+                if ambiguous && !repeated {
+                    return None;
+                }
+                repeated.then_some(separator)
+            }
+            (None, None) => None,
+        };
+        if let Some(thousands) = thousands {
+            text.retain(|it| it != thousands);
+        }
+        text.replace(',', ".")
+            .parse()
+            .ok()
+            .filter(|it: &f64| it.is_finite())
+    }
+
+    /// Writes a number formatted with a decimal point with the decimal separator of the locale.
+    pub fn localize(self, formatted: String) -> String {
+        match self {
+            Self::Point => formatted,
+            Self::Comma => formatted.replace('.', ","),
+        }
+    }
+}
+
+/// Writes a number formatted with a decimal point with the decimal separator of the current
+/// locale.
+pub fn localized(formatted: String) -> String {
+    number_locale().localize(formatted)
+}
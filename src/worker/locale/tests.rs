@@ -0,0 +1,58 @@
+//! Numbers written in tables and typed into fields, in both locales.
+
+use super::NumberLocale::{self, Comma, Point};
+
+fn parse(locale: NumberLocale, text: &str) -> Option<f64> {
+    locale.parse(text)
+}
+
+#[test]
+fn decimal_separators() {
+    for locale in [Point, Comma] {
+        assert_eq!(parse(locale, "3.5"), Some(3.5));
+        assert_eq!(parse(locale, "3,5"), Some(3.5));
+        assert_eq!(parse(locale, " -0,25 "), Some(-0.25));
+        assert_eq!(parse(locale, "1e-3"), Some(0.001));
+    }
+}
+
+#[test]
+fn thousands_separators() {
+    assert_eq!(parse(Point, "1,234.5"), Some(1234.5));
+    assert_eq!(parse(Comma, "1.234,5"), Some(1234.5));
+    assert_eq!(parse(Point, "1,234,567"), Some(1234567.0));
+    assert_eq!(parse(Comma, "1.234.567"), Some(1234567.0));
+    assert_eq!(parse(Point, "1 234 567"), Some(1234567.0));
+}
+
+/// A single separator before three digits is only read as the decimal separator of the locale.
+#[test]
+fn ambiguous_groups() {
+    assert_eq!(parse(Point, "1.125"), Some(1.125));
+    assert_eq!(parse(Comma, "1,125"), Some(1.125));
+    assert_eq!(parse(Point, "1,125"), None);
+    assert_eq!(parse(Comma, "1.125"), None);
+    assert_eq!(parse(Comma, "-12.500"), None);
+    // A leading zero or a longer integer part is never a group of thousands.
+    assert_eq!(parse(Point, "0,125"), Some(0.125));
+    assert_eq!(parse(Comma, "1234.125"), Some(1234.125));
+    assert_eq!(parse(Point, "1,12"), Some(1.12));
+}
+
+/// Text which Rust reads as a float but which is no measured value.
+#[test]
+fn not_finite() {
+    for text in [
+        "inf", "-inf", "infinity", "Infinity", "NaN", "nan", "1e999", "-1e999",
+    ] {
+        assert_eq!(parse(Point, text), None, "{text}");
+        assert_eq!(parse(Comma, text), None, "{text}");
+    }
+}
+
+#[test]
+fn not_numbers() {
+    for text in ["", "-", "pH", "0x10", "3.5 mL", "1_000"] {
+        assert_eq!(parse(Point, text), None, "{text}");
+    }
+}
@@ -0,0 +1,165 @@
+use serde::{de::Error, Deserialize, Serialize};
+
+use super::{
+    analysis::Stoichiometry,
+    mixture::SecondAcid,
+    model::{find_model, StrongModel},
+    substances::find_substance,
+    uncertainty::Uncertainty,
+    Input, Output, OutputItem, WorkerError,
+};
+
+/// The id of the metadata element of exported diagrams.
+pub const METADATA_ID: &str = "titration-curve";
+/// The version of the metadata format, which is raised on incompatible changes.
+const VERSION: u32 = 1;
+const CDATA_START: &str = "<![CDATA[";
+const CDATA_END: &str = "]]>";
+/// The end marker of a CDATA section split into two sections, which is how JSON containing it is
+/// written.
+const CDATA_END_SPLIT: &str = "]]]]><![CDATA[>";
+
+/// The curves of an exported diagram.
+#[derive(Serialize, Deserialize)]
+struct Metadata {
+    version: u32,
+    curves: Vec<Curve>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Curve {
+    /// The name of the worksheet the input was read from.
+    sheet: Option<String>,
+    input: InputData,
+    /// The calculated table, which is only written for other programs, since the app calculates
+    /// it again from the input.
+    #[serde(skip_deserializing)]
+    items: Vec<OutputItem>,
+}
+
+/// The input of a curve, with the substances and the model referred to by name.
+#[derive(Serialize, Deserialize)]
+struct InputData {
+    t_v: f32,
+    t_c: f32,
+    m_c: f32,
+    m_v: Vec<f32>,
+    temperature: f32,
+    /// The temperatures of a temperature column, where missing cells are `null`.
+    #[serde(default)]
+    temperatures: Vec<Option<f32>>,
+    #[serde(default)]
+    uncertainty: Uncertainty,
+    #[serde(default)]
+    stoichiometry: Stoichiometry,
+    #[serde(default)]
+    second_acid: Option<SecondAcid>,
+    /// The formula of the measuring solution.
+    #[serde(default)]
+    titrant: Option<String>,
+    /// The formula of the test solution.
+    #[serde(default)]
+    analyte: Option<String>,
+    /// The identifier of the model.
+    model: String,
+}
+
+impl From<&Input> for InputData {
+    fn from(input: &Input) -> Self {
+        Self {
+            t_v: input.t_v,
+            t_c: input.t_c,
+            m_c: input.m_c,
+            m_v: input.m_v.clone(),
+            temperature: input.temperature,
+            temperatures: input
+                .temperatures
+                .iter()
+                .map(|it| Some(*it).filter(|it| it.is_finite()))
+                .collect(),
+            uncertainty: input.uncertainty,
+            stoichiometry: input.stoichiometry,
+            second_acid: input.second_acid.clone(),
+            titrant: input.titrant.map(|it| it.formula.to_owned()),
+            analyte: input.analyte.map(|it| it.formula.to_owned()),
+            model: input.model.id().to_owned(),
+        }
+    }
+}
+
+impl From<InputData> for Input {
+    /// Creates the input, where a model unknown to this build is replaced by the strong model.
+    fn from(data: InputData) -> Self {
+        Self {
+            t_v: data.t_v,
+            t_c: data.t_c,
+            m_c: data.m_c,
+            m_v: data.m_v,
+            temperature: data.temperature,
+            temperatures: data
+                .temperatures
+                .into_iter()
+                .map(|it| it.unwrap_or(f32::NAN))
+                .collect(),
+            uncertainty: data.uncertainty,
+            stoichiometry: data.stoichiometry,
+            second_acid: data.second_acid,
+            titrant: data.titrant.as_deref().and_then(find_substance),
+            analyte: data.analyte.as_deref().and_then(find_substance),
+            model: find_model(&data.model).unwrap_or(&StrongModel),
+        }
+    }
+}
+
+/// Returns the content of the metadata element of an exported diagram, which describes the
+/// inputs and the calculated tables of the drawn curves as JSON.
+///
+/// The JSON is wrapped in a CDATA section, so it needs no escaping.
+pub fn svg_metadata(outputs: &[&Output]) -> String {
+    let metadata = Metadata {
+        version: VERSION,
+        curves: outputs
+            .iter()
+            .map(|it| Curve {
+                sheet: it.sheet.clone(),
+                input: InputData::from(&it.input),
+                items: it.items.clone(),
+            })
+            .collect(),
+    };
+    let json = serde_json::to_string(&metadata).expect("serialize the metadata");
+    format!(
+        "{CDATA_START}{}{CDATA_END}",
+        json.replace(CDATA_END, CDATA_END_SPLIT)
+    )
+}
+
+/// Reads the input of the first curve and the name of its worksheet from the metadata of an
+/// exported diagram.
+pub fn read_svg(text: &str) -> Result<(Input, Option<String>), WorkerError> {
+    let start = text
+        .find(&format!("id=\"{METADATA_ID}\""))
+        .and_then(|index| text[index..].find('>').map(|it| index + it + 1))
+        .ok_or(WorkerError::NoMetadata)?;
+    let end = text[start..]
+        .find("</metadata>")
+        .ok_or(WorkerError::NoMetadata)?;
+    let content = text[start..start + end].trim();
+    let json = content
+        .strip_prefix(CDATA_START)
+        .and_then(|it| it.strip_suffix(CDATA_END))
+        .unwrap_or(content)
+        .replace(CDATA_END_SPLIT, CDATA_END);
+    let metadata: Metadata = serde_json::from_str(&json).map_err(WorkerError::InvalidMetadata)?;
+    if metadata.version > VERSION {
+        return Err(WorkerError::InvalidMetadata(serde_json::Error::custom(
+            format!("unsupported version {}", metadata.version),
+        )));
+    }
+    let curve = metadata
+        .curves
+        .into_iter()
+        .next()
+        .ok_or(WorkerError::NoMetadata)?;
+    Ok((curve.input.into(), curve.sheet))
+}
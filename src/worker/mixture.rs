@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+use super::{equilibrium::Component, AcidBasePair};
+
+/// The digits written below the hydrogen of a generic acid, e.g. H₂A.
+const SUBSCRIPTS: [&str; 10] = ["", "", "₂", "₃", "₄", "₅", "₆", "₇", "₈", "₉"];
+/// The charges written above a generic acid, e.g. HA⁻ or A²⁻.
+const CHARGES: [&str; 10] = ["", "⁻", "²⁻", "³⁻", "⁴⁻", "⁵⁻", "⁶⁻", "⁷⁻", "⁸⁻", "⁹⁻"];
+
+/// A weak acid in the test solution besides the strong acid, e.g. acetic acid next to
+/// hydrochloric acid.
+///
+/// The measuring solution neutralizes the strong acid first and then every proton of the weak
+/// acid by its pKa, so the curve breaks once more for every proton which is sufficiently weaker
+/// than the one before.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SecondAcid {
+    /// The concentration in the test solution in mol/L.
+    pub concentration: f32,
+    /// The pKa values from the most protonated form on, sorted ascending.
+    pub pkas: Vec<f32>,
+}
+
+impl SecondAcid {
+    /// Returns the number of protons the acid releases in the titration.
+    pub fn protons(&self) -> usize {
+        self.pkas.len()
+    }
+
+    /// Returns the amount of the acid in the given volume of test solution in L.
+    pub fn amount(&self, t_v: f32) -> f32 {
+        self.concentration * t_v
+    }
+
+    /// Returns the acid diluted to the given fraction of its concentration in the test solution.
+    pub fn component(&self, dilution: f64) -> Component {
+        let pkas: Vec<f64> = self.pkas.iter().map(|it| *it as f64).collect();
+        Component::acid(self.concentration as f64 * dilution, &pkas, 0)
+    }
+
+    /// Returns the acid-base pairs of the acid with generic names, e.g. H₂A/HA⁻ and HA⁻/A²⁻.
+    pub fn acid_base_pairs(&self) -> Vec<AcidBasePair> {
+        let protons = self.protons().min(SUBSCRIPTS.len() - 1);
+        let species = |released: usize| {
+            let hydrogens = protons - released;
+            let hydrogen = if hydrogens == 0 {
+                String::new()
+            } else {
+                format!("H{}", SUBSCRIPTS[hydrogens])
+            };
+            format!("{hydrogen}A{}", CHARGES[released])
+        };
+        self.pkas
+            .iter()
+            .take(protons)
+            .enumerate()
+            .map(|(released, pka)| {
+                AcidBasePair::new(species(released), species(released + 1), *pka)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,233 @@
+use std::fmt::Debug;
+
+use rayon::prelude::*;
+
+#[cfg(feature = "scripting")]
+use super::script::ScriptModel;
+use super::{
+    equilibrium::{Component, Solution},
+    explain::{number, CalculationStep, Explainer, Quantity},
+    substances::{Substance, WEAK_ACIDS},
+    temperature, uncertainty, AcidBasePair, Input, Output, OutputItem, WorkerError, PARALLEL_ITEMS,
+};
+
+/// The models the curve can be calculated with, in the order they are offered.
+///
+/// The first model is the default.
+pub const MODELS: &[&dyn TitrationModel] = &[
+    &StrongModel,
+    &WeakAcidModel,
+    #[cfg(feature = "scripting")]
+    &ScriptModel,
+];
+
+/// The chemistry a curve is calculated with.
+///
+/// A model turns an input into the points of the curve and the acid-base pairs of the titrated
+/// system. The uncertainties of the input are propagated for every model alike.
+pub trait TitrationModel: Debug + Sync {
+    /// Returns the identifier of the model, which does not change between versions.
+    fn id(&self) -> &'static str;
+
+    /// Calculates the item after adding a volume of measuring solution in mL at a temperature in
+    /// °C.
+    fn item(&self, input: &Input, m_v: f32, temperature: f32) -> OutputItem;
+
+    /// Returns the acid-base pairs present during the titration, sorted by their pKa.
+    fn acid_base_pairs(&self, input: &Input) -> Vec<AcidBasePair>;
+
+    /// Returns the solution after adding a volume of measuring solution in mL at a temperature in
+    /// °C, with all dissolved acids, bases and spectator ions.
+    ///
+    /// Models without a solution of their own dissolve the acid and the base as strong ones.
+    /// Models which only calculate the pH, like a script, return `None`, so no species are
+    /// derived from a solution they do not describe.
+    fn solution(&self, input: &Input, m_v: f32, temperature: f32) -> Option<Solution> {
+        Some(input.solution_at(m_v, temperature))
+    }
+
+    /// Checks if the model can calculate the titration of the input.
+    ///
+    /// Models which apply to every titration accept all inputs.
+    fn check(&self, _input: &Input) -> Result<(), WorkerError> {
+        Ok(())
+    }
+
+    /// Explains the calculation of the output item at the given index step by step.
+    ///
+    /// Models without an explanation return no steps.
+    fn explain(&self, _input: &Input, _index: usize) -> Vec<CalculationStep> {
+        Vec::new()
+    }
+
+    /// Calculates the output with the uncertainties of the input propagated to the pH.
+    fn calculate(&self, input: &Input) -> Output {
+        // Every item only depends on its own volume, so large curves are calculated in parallel.
+        let item = |(index, &m_v)| self.item(input, m_v, input.temperature_at(index));
+        let items = if input.m_v.len() < PARALLEL_ITEMS {
+            input.m_v.iter().enumerate().map(item).collect()
+        } else {
+            input.m_v.par_iter().enumerate().map(item).collect()
+        };
+        let mut output = input.output(items);
+        uncertainty::propagate(input, &mut output);
+        output
+    }
+}
+
+/// Finds a model by its identifier.
+pub fn find_model(id: &str) -> Option<&'static dyn TitrationModel> {
+    MODELS.iter().copied().find(|it| it.id() == id)
+}
+
+/// A strong acid titrated with a strong base, optionally next to a weak second acid.
+#[derive(Debug)]
+pub struct StrongModel;
+
+impl TitrationModel for StrongModel {
+    fn id(&self) -> &'static str {
+        "strong"
+    }
+
+    fn item(&self, input: &Input, m_v: f32, temperature: f32) -> OutputItem {
+        input.item(m_v, temperature, &mut ())
+    }
+
+    fn acid_base_pairs(&self, input: &Input) -> Vec<AcidBasePair> {
+        input.acid_base_pairs()
+    }
+
+    fn explain(&self, input: &Input, index: usize) -> Vec<CalculationStep> {
+        let mut explainer = Explainer::new(index);
+        input.calculate(&mut explainer);
+        explainer.into_steps()
+    }
+}
+
+/// A monoprotic weak acid titrated with a strong base.
+///
+/// The acid is the one named in the table, or acetic acid if the table names none. Other test
+/// solutions are rejected by [`TitrationModel::check`]. The pKa of the acid follows the
+/// temperature of every point. Only monoprotic acids are supported, since every proton of a
+/// polyprotic acid has a pKa of its own.
+#[derive(Debug)]
+pub struct WeakAcidModel;
+
+impl WeakAcidModel {
+    /// Returns the titrated acid.
+    fn acid(input: &Input) -> &'static Substance {
+        input.analyte.unwrap_or(&WEAK_ACIDS[0])
+    }
+
+    /// Returns the solution after adding a volume of measuring solution in mL, with the
+    /// concentration of the acid and the charge of the cations of the base in mol/L.
+    fn weak_solution(input: &Input, m_v: f32, temperature: f32) -> (Solution, f64, f64) {
+        let (t_v, t_c, m_c, m_v) = (
+            input.t_v as f64,
+            input.t_c as f64,
+            input.m_c as f64,
+            m_v as f64,
+        );
+        let liters = m_v / 1000.0 + t_v;
+        let acid = t_c * t_v * f64::from(input.stoichiometry.titrant) / liters;
+        let base = m_c * m_v / 1000.0 / liters;
+        let charge = i32::from(input.stoichiometry.analyte);
+        let pka = Self::acid(input).pka_at(temperature) as f64;
+        let kw = 10f64.powf(-temperature::pkw(temperature) as f64);
+        let solution = Solution::new(kw)
+            .with(Component::acid(acid, &[pka], 0))
+            .with(Component::ion(base, charge));
+        let solution = match &input.second_acid {
+            Some(second) => solution.with(second.component(t_v / liters)),
+            None => solution,
+        };
+        (solution, acid, base * f64::from(charge))
+    }
+}
+
+impl TitrationModel for WeakAcidModel {
+    fn id(&self) -> &'static str {
+        "weak-acid"
+    }
+
+    fn item(&self, input: &Input, m_v: f32, temperature: f32) -> OutputItem {
+        // The amounts do not depend on the strength of the acid, only the pH does.
+        let mut item = input.item(m_v, temperature, &mut ());
+        item.ph = Self::weak_solution(input, m_v, temperature).0.ph() as f32;
+        item.poh = temperature::pkw(temperature) - item.ph;
+        item
+    }
+
+    fn acid_base_pairs(&self, input: &Input) -> Vec<AcidBasePair> {
+        let acid = Self::acid(input);
+        let mut pairs = input.acid_base_pairs();
+        pairs.push(AcidBasePair::new(
+            acid.formula,
+            acid.counter_ion,
+            acid.pka_at(input.temperature),
+        ));
+        pairs.sort_by(|a, b| a.pka.total_cmp(&b.pka));
+        pairs
+    }
+
+    fn solution(&self, input: &Input, m_v: f32, temperature: f32) -> Option<Solution> {
+        Some(Self::weak_solution(input, m_v, temperature).0)
+    }
+
+    /// Accepts weak acids and tables without a test solution, but no strong acids or bases.
+    fn check(&self, input: &Input) -> Result<(), WorkerError> {
+        match input.analyte {
+            Some(analyte) if !WEAK_ACIDS.contains(analyte) => {
+                Err(WorkerError::NotAWeakAcid(analyte))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Explains the amounts like the strong model and the pH with the protolysis of the acid.
+    fn explain(&self, input: &Input, index: usize) -> Vec<CalculationStep> {
+        let Some(&m_v) = input.m_v.get(index) else {
+            return Vec::new();
+        };
+        let mut steps = StrongModel.explain(input, index);
+        steps.retain(|it| !matches!(it.quantity, Quantity::Ph | Quantity::Poh));
+        let temperature = input.temperature_at(index);
+        let item = self.item(input, m_v, temperature);
+        let acid = Self::acid(input);
+        let (solution, concentration, cations) = Self::weak_solution(input, m_v, temperature);
+        let ph = solution.ph();
+        let base = Component::acid(concentration, &[acid.pka_at(temperature) as f64], 0);
+        let second = input.second_acid.as_ref().map_or(String::new(), |it| {
+            let liters = m_v as f64 / 1000.0 + input.t_v as f64;
+            let charge = it.component(input.t_v as f64 / liters).released_protons(ph);
+            format!(" + {} mol/L", number(charge as f32))
+        });
+        steps.push(CalculationStep {
+            quantity: Quantity::Ph,
+            formula: format!(
+                "c({ion}) = c(HA) · Kₐ / (Kₐ + c(H₃O⁺)) = {} mol/L · 10^−{} / (10^−{} + c(H₃O⁺)); \
+                 c(H₃O⁺) + {} mol/L = c(OH⁻) + c({ion}){second} ⇒ c({ion}) = {} mol/L, pH = {}",
+                number(concentration as f32),
+                number(acid.pka_at(temperature)),
+                number(acid.pka_at(temperature)),
+                number(cations as f32),
+                number(base.released_protons(ph) as f32),
+                number(item.ph),
+                ion = acid.counter_ion,
+            ),
+            result: item.ph,
+        });
+        let pkw = temperature::pkw(temperature);
+        steps.push(CalculationStep {
+            quantity: Quantity::Poh,
+            formula: format!(
+                "pOH = pKw − pH = {} − {} = {}",
+                number(pkw),
+                number(item.ph),
+                number(item.poh)
+            ),
+            result: item.poh,
+        });
+        steps
+    }
+}
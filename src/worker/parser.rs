@@ -0,0 +1,840 @@
+use std::{
+    borrow::Cow,
+    fs, io,
+    path::{Path, PathBuf},
+    str,
+};
+
+use calamine::{DataType, Range, Reader};
+use encoding_rs::WINDOWS_1252;
+use serde::Deserialize;
+
+use super::{
+    analysis::Stoichiometry,
+    conductivity,
+    locale::number_locale,
+    metadata,
+    mixture::SecondAcid,
+    model::StrongModel,
+    substances::{find_substance, Substance},
+    temperature::STANDARD_TEMPERATURE,
+    uncertainty::Uncertainty,
+    units::{unit_text, ConcentrationUnit, VolumeUnit},
+    Input, WorkerError,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Words marking a label as referring to the test solution.
+const TEST_QUALIFIERS: &[&str] = &["t", "probe", "probelösung", "probelsg", "test", "analyt"];
+/// Words marking a label as the uncertainty of a value, e.g. `u(c, Probelösung)`.
+const UNCERTAINTY_QUANTITIES: &[&str] = &["u", "unsicherheit", "uncertainty"];
+/// Words marking a label as referring to the measuring solution.
+const MEASURING_QUALIFIERS: &[&str] = &[
+    "m",
+    "maßlösung",
+    "masslösung",
+    "massloesung",
+    "maßlsg",
+    "titrant",
+    "titer",
+];
+/// The number of rows of added volumes read between two reports of the progress.
+pub const PROGRESS_ROWS: u32 = 500;
+/// The extensions of the tables which can be loaded.
+pub const TABLE_EXTENSIONS: &[&str] = &["xls", "xlsx", "xlsm", "xlsb", "xla", "xlam", "ods", "csv"];
+
+/// A value read from a single cell of the table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Field {
+    /// The volume of the test solution.
+    TestVolume,
+    /// The concentration of the test solution.
+    TestConcentration,
+    /// The concentration of the measuring solution.
+    MeasuringConcentration,
+    /// A volume of added measuring solution.
+    MeasuringVolume,
+    /// The concentration of a second acid in the test solution.
+    SecondConcentration,
+    /// The pKa values of a second acid in the test solution.
+    SecondPka,
+    /// The temperature of the solution in °C at an added volume.
+    Temperature,
+}
+
+/// The absolute positions of the values in the table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Layout {
+    pub test_volume: (u32, u32),
+    pub test_concentration: (u32, u32),
+    pub measuring_concentration: (u32, u32),
+    /// The first cell of the column containing the added volumes.
+    pub first_volume: (u32, u32),
+    /// The name or formula of the titrated acid.
+    pub acid: (u32, u32),
+    /// The name or formula of the added base.
+    pub base: (u32, u32),
+    /// The ratio the acid and the base react in, e.g. `1:2`.
+    pub stoichiometry: (u32, u32),
+    /// The concentration of a second acid, which is only read if it is labeled.
+    pub second_concentration: Option<(u32, u32)>,
+    /// The first of the pKa values of a second acid, which are read to the right.
+    pub second_pkas: Option<(u32, u32)>,
+    /// The first cell of the column containing the temperatures at the added volumes, which is
+    /// only read if it is labeled.
+    pub first_temperature: Option<(u32, u32)>,
+    /// The cells of the uncertainties, which are only read if they are labeled.
+    pub uncertainty: UncertaintyCells,
+}
+
+/// The positions of the uncertainties of the values in the table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UncertaintyCells {
+    pub test_volume: Option<(u32, u32)>,
+    pub test_concentration: Option<(u32, u32)>,
+    pub measuring_concentration: Option<(u32, u32)>,
+    /// The uncertainty of every reading of the added volume.
+    pub measuring_volume: Option<(u32, u32)>,
+}
+
+/// The fixed layout documented in [`parse_file`].
+impl Default for Layout {
+    fn default() -> Self {
+        Self {
+            test_volume: (0, 2),
+            test_concentration: (1, 2),
+            measuring_concentration: (2, 2),
+            first_volume: (5, 0),
+            acid: (0, 5),
+            base: (2, 5),
+            stoichiometry: (1, 5),
+            second_concentration: None,
+            second_pkas: None,
+            first_temperature: None,
+            uncertainty: UncertaintyCells::default(),
+        }
+    }
+}
+
+impl Layout {
+    /// Overrides the positions given in a mapping.
+    pub fn apply(&mut self, mapping: &LayoutMapping) {
+        let overrides = [
+            (&mut self.test_volume, mapping.test_volume),
+            (&mut self.test_concentration, mapping.test_concentration),
+            (
+                &mut self.measuring_concentration,
+                mapping.measuring_concentration,
+            ),
+            (&mut self.first_volume, mapping.first_volume),
+            (&mut self.acid, mapping.acid),
+            (&mut self.base, mapping.base),
+            (&mut self.stoichiometry, mapping.stoichiometry),
+        ];
+        for (position, mapped) in overrides {
+            if let Some(CellPosition(row, column)) = mapped {
+                *position = (row, column);
+            }
+        }
+        let optional = [
+            (&mut self.second_concentration, mapping.second_concentration),
+            (&mut self.second_pkas, mapping.second_pkas),
+            (&mut self.first_temperature, mapping.first_temperature),
+        ];
+        for (position, mapped) in optional {
+            if let Some(CellPosition(row, column)) = mapped {
+                *position = Some((row, column));
+            }
+        }
+    }
+
+    /// Detects the layout of a worksheet by searching for labels.
+    ///
+    /// A label for a single value (e.g. `c (Probelösung)`) refers to the number to its right, or
+    /// below it if there is none. A label for the added volumes (e.g. `V (Maßlösung)`) is the
+    /// header of their column. Values without a label keep their position in the fixed layout.
+    pub fn detect(worksheet: &Range<DataType>) -> Self {
+        let mut layout = Self::default();
+        let (start_row, start_column) = worksheet.start().unwrap_or_default();
+        for (row, column, cell) in worksheet.used_cells() {
+            let position = (start_row + row as u32, start_column + column as u32);
+            if let Some(field) = cell.get_string().and_then(parse_uncertainty_label) {
+                let value = value_next_to(worksheet, position);
+                let uncertainty = &mut layout.uncertainty;
+                match field {
+                    Field::TestVolume => uncertainty.test_volume = value,
+                    Field::TestConcentration => uncertainty.test_concentration = value,
+                    Field::MeasuringConcentration => uncertainty.measuring_concentration = value,
+                    Field::MeasuringVolume => uncertainty.measuring_volume = value,
+                    // The second acid and the temperatures are only used for the curve, not for
+                    // the uncertainty.
+                    Field::SecondConcentration | Field::SecondPka | Field::Temperature => {}
+                }
+                continue;
+            }
+            let Some(field) = cell.get_string().and_then(parse_label) else {
+                continue;
+            };
+            // A label of a single value without a number next to it keeps its fixed position.
+            let value = value_next_to(worksheet, position);
+            let below = (position.0 + 1, position.1);
+            match field {
+                Field::TestVolume => layout.test_volume = value.unwrap_or(layout.test_volume),
+                Field::TestConcentration => {
+                    layout.test_concentration = value.unwrap_or(layout.test_concentration);
+                }
+                Field::MeasuringConcentration => {
+                    layout.measuring_concentration =
+                        value.unwrap_or(layout.measuring_concentration);
+                }
+                Field::MeasuringVolume => layout.first_volume = below,
+                Field::Temperature => layout.first_temperature = Some(below),
+                // A labeled value of the second acid must be given, so a missing one is reported
+                // as the empty cell to the right of its label.
+                Field::SecondConcentration => {
+                    layout.second_concentration =
+                        Some(value.unwrap_or((position.0, position.1 + 1)));
+                }
+                Field::SecondPka => {
+                    layout.second_pkas = Some(value.unwrap_or((position.0, position.1 + 1)));
+                }
+            }
+        }
+        layout
+    }
+}
+
+/// A layout read from a mapping file next to the table.
+///
+/// The positions are given as cell names, e.g.:
+///
+/// ```toml
+/// test_volume = "C1"
+/// test_concentration = "C2"
+/// measuring_concentration = "C3"
+/// first_volume = "A6"
+/// acid = "F1"
+/// base = "F3"
+/// stoichiometry = "F2"
+/// second_concentration = "C4"
+/// second_pkas = "C5"
+/// first_temperature = "B6"
+/// ```
+///
+/// Positions which are not given are detected as usual.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LayoutMapping {
+    pub test_volume: Option<CellPosition>,
+    pub test_concentration: Option<CellPosition>,
+    pub measuring_concentration: Option<CellPosition>,
+    /// The first cell of the column containing the added volumes.
+    pub first_volume: Option<CellPosition>,
+    pub acid: Option<CellPosition>,
+    pub base: Option<CellPosition>,
+    pub stoichiometry: Option<CellPosition>,
+    pub second_concentration: Option<CellPosition>,
+    /// The first of the pKa values of a second acid, which are read to the right.
+    pub second_pkas: Option<CellPosition>,
+    /// The first cell of the column containing the temperatures at the added volumes.
+    pub first_temperature: Option<CellPosition>,
+}
+
+impl LayoutMapping {
+    /// The name of a mapping file applying to all tables in its directory.
+    pub const DIRECTORY_FILE_NAME: &'static str = "layout.toml";
+
+    /// Returns the possible mapping files for a table, in the order of their priority.
+    ///
+    /// These are `<table>.layout.toml` and `layout.toml` in the directory of the table.
+    pub fn paths(table: &Path) -> [PathBuf; 2] {
+        let mut own = table.as_os_str().to_owned();
+        own.push(".layout.toml");
+        [own.into(), table.with_file_name(Self::DIRECTORY_FILE_NAME)]
+    }
+
+    /// Reads the mapping file of a table, if there is one.
+    pub fn find(table: &Path) -> Result<Option<Self>, WorkerError> {
+        for path in Self::paths(table) {
+            let content = match fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(WorkerError::Io(err)),
+            };
+            return toml::from_str(&content)
+                .map(Some)
+                .map_err(|err| WorkerError::InvalidMapping(path, err));
+        }
+        Ok(None)
+    }
+}
+
+/// The absolute position of a cell, deserialized from its name (e.g. `C2`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct CellPosition(pub u32, pub u32);
+
+impl TryFrom<String> for CellPosition {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        parse_cell_name(&name)
+            .map(|(row, column)| Self(row, column))
+            .ok_or_else(|| format!("invalid cell name `{name}`"))
+    }
+}
+
+/// Parses the name of a cell as shown in spreadsheet applications (e.g. `C2`).
+pub fn parse_cell_name(name: &str) -> Option<(u32, u32)> {
+    let name = name.trim();
+    let split = name.find(|it: char| !it.is_ascii_alphabetic())?;
+    let (letters, digits) = name.split_at(split);
+    if letters.is_empty() {
+        return None;
+    }
+    let mut column = 0u32;
+    for letter in letters.bytes() {
+        column = column
+            .checked_mul(26)?
+            .checked_add((letter.to_ascii_uppercase() - b'A') as u32 + 1)?;
+    }
+    let row: u32 = digits.parse().ok()?;
+    Some((row.checked_sub(1)?, column - 1))
+}
+
+/// Parses the label of a field from the content of a cell.
+///
+/// Labels consist of a quantity (`V`, `c`, `Volumen`, ...) followed by a qualifier naming the
+/// solution (`t`, `Probelösung`, `m`, `Maßlösung`, ...). Units and punctuation are ignored.
+///
+/// The concentration of a second acid is qualified with a `2`, e.g. `c (Probelösung 2)` or
+/// `c (t2)`. The first acid is strong, so a pKa label (`pKa`, `pKs`) always refers to the second
+/// acid. A temperature label (`T / °C`, `ϑ`, `Temperatur`) is the header of the column of
+/// temperatures.
+fn parse_label(content: &str) -> Option<Field> {
+    let content = content.to_lowercase();
+    let mut words = content
+        .split(|it: char| !it.is_alphanumeric())
+        .filter(|it| !it.is_empty());
+    let quantity = words.next()?;
+    let words: Vec<&str> = words.collect();
+    let test = words.iter().any(|it| TEST_QUALIFIERS.contains(it));
+    let measuring = words.iter().any(|it| MEASURING_QUALIFIERS.contains(it));
+    let second = words.iter().any(|it| matches!(*it, "2" | "t2"));
+    let field = match quantity {
+        "pka" | "pks" => Field::SecondPka,
+        "t" | "ϑ" | "θ" | "temperatur" | "temperature" if !test && !measuring => {
+            Field::Temperature
+        }
+        "c" | "konzentration" | "concentration" if second => Field::SecondConcentration,
+        "v" | "volumen" | "volume" if test => Field::TestVolume,
+        "v" | "volumen" | "volume" if measuring => Field::MeasuringVolume,
+        // A plain volume column is the column of added volumes.
+        "volumen" | "volume" => Field::MeasuringVolume,
+        "c" | "konzentration" | "concentration" if test => Field::TestConcentration,
+        "c" | "konzentration" | "concentration" if measuring => Field::MeasuringConcentration,
+        _ => return None,
+    };
+    Some(field)
+}
+
+/// Parses the label of the uncertainty of a field, e.g. `u(V, Maßlösung)`.
+///
+/// The uncertainty of the added volume applies to every reading, so it is a single value.
+fn parse_uncertainty_label(content: &str) -> Option<Field> {
+    let lower = content.to_lowercase();
+    let mut words = lower
+        .split(|it: char| !it.is_alphanumeric())
+        .filter(|it| !it.is_empty());
+    if !UNCERTAINTY_QUANTITIES.contains(&words.next()?) {
+        return None;
+    }
+    parse_label(&words.collect::<Vec<_>>().join(" "))
+}
+
+/// Returns the position of the number to the right of or below the given position.
+fn value_next_to(worksheet: &Range<DataType>, (row, column): (u32, u32)) -> Option<(u32, u32)> {
+    [(row, column + 1), (row + 1, column)]
+        .into_iter()
+        .find(|&position| {
+            worksheet
+                .get_value(position)
+                .and_then(cell_number)
+                .is_some()
+        })
+}
+
+/// A cell which does not contain the expected number.
+#[derive(Clone, Debug)]
+pub struct CellError {
+    /// The zero-based row of the cell.
+    pub row: u32,
+    /// The zero-based column of the cell.
+    pub column: u32,
+    /// The value the cell should contain.
+    pub field: Field,
+    /// The content of the cell, if it is not empty.
+    pub found: Option<String>,
+}
+
+impl CellError {
+    /// Returns the name of the cell as shown in spreadsheet applications (e.g. `C2`).
+    pub fn cell_name(&self) -> String {
+        cell_name(self.row, self.column)
+    }
+}
+
+/// Returns the name of a cell as shown in spreadsheet applications (e.g. `C2`).
+pub fn cell_name(row: u32, column: u32) -> String {
+    let mut letters = String::new();
+    let mut column = column + 1;
+    while column > 0 {
+        column -= 1;
+        letters.insert(0, char::from(b'A' + (column % 26) as u8));
+        column /= 26;
+    }
+    format!("{letters}{}", row + 1)
+}
+
+/// Parses the input from the first worksheet of the workbook at the given path.
+///
+/// Without labels, the table format is the following:
+///
+/// ```text
+/// t: test solution
+/// m: measuring solution
+/// V: volume
+/// c: concentration
+/// acid: acid used for titration
+/// base: base used for titration
+/// ratio: ratio the acid and the base react in
+///
+/// +--------+----+-------+----+----+-------+
+/// |        |    | V (t) |    |    | acid  |
+/// +--------+----+-------+----+----+-------+
+/// |        |    | c (t) |    |    | ratio |
+/// +--------+----+-------+----+----+-------+
+/// |        |    | c (m) |    |    | base  |
+/// +--------+----+-------+----+----+-------+
+/// |        |    |       |    |    |       |
+/// +--------+----+-------+----+----+-------+
+/// |        |    |       |    |    |       |
+/// +--------+----+-------+----+----+-------+
+/// | V0 (m) |    |       |    |    |       |
+/// +--------+----+-------+----+----+-------+
+/// | V1 (m) |    |       |    |    |       |
+/// +--------+----+-------+----+----+-------+
+/// | ...    |    |       |    |    |       |
+/// +--------+----+-------+----+----+-------+
+/// ```
+///
+/// A mapping file next to the table may override the layout (see [`LayoutMapping`]).
+///
+/// The cell to the right of a single value may give its unit, e.g. `mL` for the volume of the
+/// test solution or `mmol/L` and `g/L` for a concentration. Mass concentrations are converted with
+/// the molar mass of the acid or base named in the table. The unit of the added volumes may
+/// follow the header of their column, e.g. `V (Maßlösung) / L`. Without units, the volume of the
+/// test solution is in L, the added volumes are in mL and concentrations are in mol/L.
+///
+/// The ratio the acid and the base react in is written like `1:2` for H₂SO₄ with NaOH. Without
+/// it, the ratio follows from the acid and base named in the table, or is 1 : 1.
+///
+/// A weak acid titrated together with the acid, e.g. acetic acid next to hydrochloric acid, is
+/// given by its concentration next to a label like `c (Probelösung 2)` and its pKa values in the
+/// cells to the right of a label like `pKs`. Both labels are needed for a second acid.
+///
+/// Standard uncertainties may be given next to labels like `u(V, Probelösung)` or `u(V, m)`, in
+/// the units of their values. Values without an uncertainty are taken as exact.
+///
+/// A column with a label like `T / °C` gives the temperature of the solution at the added volume
+/// in the same row. Rows without a temperature are calculated at the working temperature.
+///
+/// CSV files are read like a worksheet with the same layout, in UTF-8 or Windows-1252, see
+/// [`csv_text`]. SVG diagrams exported by the app are read from the input embedded in their
+/// metadata.
+///
+/// Returns the input with the name of the worksheet it was read from, which CSV files do not have.
+/// The progress is reported like in [`parse_worksheet_with_progress`].
+pub fn parse_file(
+    path: &Path,
+    progress: &mut dyn FnMut(u32, u32) -> bool,
+) -> Result<(Input, Option<String>), WorkerError> {
+    if is_svg(path) {
+        let text = fs::read_to_string(path).map_err(WorkerError::Io)?;
+        return metadata::read_svg(&text);
+    }
+    let mapping = LayoutMapping::find(path)?;
+    if is_csv(path) {
+        let bytes = fs::read(path).map_err(WorkerError::Io)?;
+        let worksheet = csv_worksheet(&csv_text(&bytes));
+        let input = parse_worksheet_with_progress(&worksheet, mapping.as_ref(), progress)?;
+        return Ok((input, None));
+    }
+    let mut workbook = calamine::open_workbook_auto(path).map_err(WorkerError::TableError)?;
+    let sheet = workbook.sheet_names().first().cloned();
+    let worksheet = workbook
+        .worksheet_range_at(0)
+        .ok_or(WorkerError::NoTableInWorkbook)?
+        .map_err(WorkerError::TableError)?;
+    let input = parse_worksheet_with_progress(&worksheet, mapping.as_ref(), progress)?;
+    Ok((input, sheet))
+}
+
+/// Parses the input from a worksheet.
+///
+/// The layout is detected from labels in the worksheet (see [`Layout::detect`]) and then
+/// overridden by the mapping. All positions are absolute, so empty leading rows or columns do not
+/// shift the layout.
+///
+/// The added volumes are read downwards until the first empty cell.
+pub fn parse_worksheet(
+    worksheet: &Range<DataType>,
+    mapping: Option<&LayoutMapping>,
+) -> Result<Input, WorkerError> {
+    parse_worksheet_with_progress(worksheet, mapping, &mut |_, _| true)
+}
+
+/// Parses the input from a worksheet like [`parse_worksheet`] and reports the read rows.
+///
+/// Every [`PROGRESS_ROWS`] rows of added volumes, the progress is called with the number of read
+/// rows and the number of rows up to the end of the worksheet. Once it returns `false`, parsing
+/// stops with [`WorkerError::Cancelled`].
+pub fn parse_worksheet_with_progress(
+    worksheet: &Range<DataType>,
+    mapping: Option<&LayoutMapping>,
+    progress: &mut dyn FnMut(u32, u32) -> bool,
+) -> Result<Input, WorkerError> {
+    let mut layout = Layout::detect(worksheet);
+    if let Some(mapping) = mapping {
+        layout.apply(mapping);
+    }
+    let t_v = read_number(worksheet, layout.test_volume, Field::TestVolume)?;
+    let t_c = read_number(
+        worksheet,
+        layout.test_concentration,
+        Field::TestConcentration,
+    )?;
+    let m_c = read_number(
+        worksheet,
+        layout.measuring_concentration,
+        Field::MeasuringConcentration,
+    )?;
+    let (first_row, column) = layout.first_volume;
+    let last_row = worksheet.end().map_or(0, |(row, _)| row);
+    let rows_total = last_row.max(first_row) - first_row + 1;
+    let mut m_v = Vec::new();
+    let mut temperatures = Vec::new();
+    for row in first_row..=last_row.max(first_row) {
+        let rows_done = row - first_row;
+        if rows_done > 0 && rows_done % PROGRESS_ROWS == 0 && !progress(rows_done, rows_total) {
+            return Err(WorkerError::Cancelled);
+        }
+        let is_empty = worksheet
+            .get_value((row, column))
+            .is_none_or(|it| it.is_empty());
+        // At least one volume is required.
+        if is_empty && !m_v.is_empty() {
+            break;
+        }
+        m_v.push(read_number(
+            worksheet,
+            (row, column),
+            Field::MeasuringVolume,
+        )?);
+        if let Some((first_temperature, temperature_column)) = layout.first_temperature {
+            let position = (first_temperature + rows_done, temperature_column);
+            temperatures.push(read_temperature(worksheet, position)?);
+        }
+    }
+    let read_uncertainty = |position: Option<(u32, u32)>, field| {
+        position.map_or(Ok(0.0), |it| read_number(worksheet, it, field))
+    };
+    let cells = layout.uncertainty;
+    let uncertainty = Uncertainty {
+        t_v: read_uncertainty(cells.test_volume, Field::TestVolume)?,
+        t_c: read_uncertainty(cells.test_concentration, Field::TestConcentration)?,
+        m_c: read_uncertainty(cells.measuring_concentration, Field::MeasuringConcentration)?,
+        m_v: read_uncertainty(cells.measuring_volume, Field::MeasuringVolume)?,
+    };
+    // Convert everything to L, mL and mol/L before the calculation.
+    let t_v_unit =
+        unit_next_to(worksheet, layout.test_volume, VolumeUnit::parse).unwrap_or(VolumeUnit::Liter);
+    let m_v_unit = volume_unit(worksheet, &layout);
+    let acid = string_at(worksheet, layout.acid).and_then(find_substance);
+    let base = string_at(worksheet, layout.base).and_then(find_substance);
+    let concentration = |position, substance: Option<&'static Substance>, field| {
+        let unit = unit_next_to(worksheet, position, ConcentrationUnit::parse)
+            .unwrap_or(ConcentrationUnit::MolPerLiter);
+        move |value| {
+            unit.to_mol_per_liter(value, substance)
+                .ok_or(WorkerError::UnknownMolarMass(field))
+        }
+    };
+    let t_c_unit = concentration(layout.test_concentration, acid, Field::TestConcentration);
+    let m_c_unit = concentration(
+        layout.measuring_concentration,
+        base,
+        Field::MeasuringConcentration,
+    );
+    let uncertainty = Uncertainty {
+        t_v: t_v_unit.to_liters(uncertainty.t_v),
+        t_c: t_c_unit(uncertainty.t_c)?,
+        m_c: m_c_unit(uncertainty.m_c)?,
+        m_v: m_v_unit.to_milliliters(uncertainty.m_v),
+    };
+    // Without a ratio in the table, it follows from the protons of the named acid and base.
+    let stoichiometry = string_at(worksheet, layout.stoichiometry)
+        .and_then(Stoichiometry::parse)
+        .unwrap_or_else(|| Stoichiometry {
+            analyte: base.map_or(1, |it| it.protons),
+            titrant: acid.map_or(1, |it| it.protons),
+        });
+    let second_acid = read_second_acid(worksheet, &layout)?;
+    Ok(Input {
+        t_v: t_v_unit.to_liters(t_v),
+        t_c: t_c_unit(t_c)?,
+        m_c: m_c_unit(m_c)?,
+        m_v: m_v
+            .into_iter()
+            .map(|it| m_v_unit.to_milliliters(it))
+            .collect(),
+        temperature: STANDARD_TEMPERATURE,
+        temperatures,
+        uncertainty,
+        stoichiometry,
+        second_acid,
+        titrant: base,
+        analyte: acid,
+        model: &StrongModel,
+    })
+}
+
+/// Reads the concentration and the pKa values of a second acid, if both are labeled.
+///
+/// The pKa values are read to the right until the first cell without a number and are sorted, so
+/// their order in the table does not matter.
+fn read_second_acid(
+    worksheet: &Range<DataType>,
+    layout: &Layout,
+) -> Result<Option<SecondAcid>, WorkerError> {
+    let (concentration, first_pka) = match (layout.second_concentration, layout.second_pkas) {
+        (None, None) => return Ok(None),
+        (Some(_), None) => return Err(WorkerError::MissingField(Field::SecondPka)),
+        (None, Some(_)) => return Err(WorkerError::MissingField(Field::SecondConcentration)),
+        (Some(concentration), Some(first_pka)) => (concentration, first_pka),
+    };
+    let unit = unit_next_to(worksheet, concentration, ConcentrationUnit::parse)
+        .unwrap_or(ConcentrationUnit::MolPerLiter);
+    let value = read_number(worksheet, concentration, Field::SecondConcentration)?;
+    let concentration = unit
+        .to_mol_per_liter(value, None)
+        .ok_or(WorkerError::UnknownMolarMass(Field::SecondConcentration))?;
+    let mut pkas = vec![read_number(worksheet, first_pka, Field::SecondPka)?];
+    let (row, column) = first_pka;
+    pkas.extend(
+        (column + 1..)
+            .map_while(|column| worksheet.get_value((row, column)).and_then(cell_number))
+            .map(|it| it as f32),
+    );
+    pkas.sort_by(f32::total_cmp);
+    Ok(Some(SecondAcid {
+        concentration,
+        pkas,
+    }))
+}
+
+/// Reads the temperature in °C at an added volume, which is NaN if the cell is empty.
+pub fn read_temperature(
+    worksheet: &Range<DataType>,
+    position: (u32, u32),
+) -> Result<f32, CellError> {
+    if worksheet.get_value(position).is_none_or(|it| it.is_empty()) {
+        return Ok(f32::NAN);
+    }
+    read_number(worksheet, position, Field::Temperature)
+}
+
+/// Returns the unit of the added volumes given in the header of their column.
+///
+/// Without a unit, the volumes are in mL.
+pub fn volume_unit(worksheet: &Range<DataType>, layout: &Layout) -> VolumeUnit {
+    let (first_row, column) = layout.first_volume;
+    first_row
+        .checked_sub(1)
+        .and_then(|row| string_at(worksheet, (row, column)))
+        .and_then(|it| VolumeUnit::parse(unit_text(it)))
+        .unwrap_or(VolumeUnit::Milliliter)
+}
+
+/// Returns whether the file at the path is a CSV file, judged by its extension.
+pub fn is_csv(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|it| it.eq_ignore_ascii_case("csv"))
+}
+
+/// Returns whether the file at the path can be loaded, judged by its extension.
+///
+/// The measured conductivity of a table is loaded with the table, so it is no table of its own.
+pub fn is_table(path: &Path) -> bool {
+    let extension = path.extension().is_some_and(|extension| {
+        TABLE_EXTENSIONS
+            .iter()
+            .any(|it| extension.eq_ignore_ascii_case(it))
+    });
+    extension && !conductivity::is_measured(path)
+}
+
+/// Returns whether the file at the path is an SVG file, judged by its extension.
+pub fn is_svg(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|it| it.eq_ignore_ascii_case("svg"))
+}
+
+/// Returns the separator of the fields in a line of a CSV file.
+///
+/// Spreadsheet applications with a German locale separate the fields with semicolons, others
+/// with commas. Tabs are accepted as well. Separators inside quoted fields are not counted.
+pub fn csv_delimiter(line: &str) -> char {
+    let mut quoted = false;
+    let unquoted: String = line
+        .chars()
+        .filter(|&it| {
+            if it == '"' {
+                quoted = !quoted;
+            }
+            !quoted
+        })
+        .collect();
+    [';', '\t']
+        .into_iter()
+        .find(|it| unquoted.contains(*it))
+        .unwrap_or(',')
+}
+
+/// Splits a line of a CSV file into the text of its fields.
+///
+/// A field in quotes may contain the delimiter and quotes written twice. Whitespace around a
+/// field is removed.
+fn csv_fields(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.trim_end_matches('\r').chars().peekable();
+    while let Some(char) = chars.next() {
+        match char {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            _ if char == delimiter && !quoted => {
+                fields.push(field.trim().to_owned());
+                field.clear();
+            }
+            _ => field.push(char),
+        }
+    }
+    fields.push(field.trim().to_owned());
+    fields
+}
+
+/// Splits a line of a CSV file into cells.
+///
+/// Numbers become numeric cells, parsed with the current number locale. Quotes around text are
+/// removed.
+pub fn csv_cells(line: &str, delimiter: char) -> Vec<DataType> {
+    csv_fields(line, delimiter)
+        .into_iter()
+        .map(|field| {
+            if field.is_empty() {
+                DataType::Empty
+            } else if let Some(value) = number_locale().parse(&field) {
+                DataType::Float(value)
+            } else {
+                DataType::String(field)
+            }
+        })
+        .collect()
+}
+
+/// Decodes the bytes of a CSV file.
+///
+/// Excel writes UTF-8 with a byte order mark, which is removed so it does not end up in the first
+/// cell. Its default CSV export is in the ANSI code page instead, which for German systems is
+/// Windows-1252, so text which is no valid UTF-8 is read in that encoding.
+pub fn csv_text(bytes: &[u8]) -> Cow<'_, str> {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    match str::from_utf8(bytes) {
+        Ok(text) => Cow::Borrowed(text),
+        Err(_) => WINDOWS_1252.decode_without_bom_handling(bytes).0,
+    }
+}
+
+/// Reads the text of a CSV file as a worksheet starting at the cell `A1`.
+///
+/// The separator is detected from the first line.
+pub fn csv_worksheet(text: &str) -> Range<DataType> {
+    let delimiter = csv_delimiter(text.lines().next().unwrap_or_default());
+    let rows: Vec<Vec<DataType>> = text
+        .lines()
+        .map(|line| csv_cells(line, delimiter))
+        .collect();
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0).max(1);
+    let mut worksheet = Range::new((0, 0), (rows.len().max(1) as u32 - 1, width as u32 - 1));
+    for (row, cells) in rows.into_iter().enumerate() {
+        for (column, cell) in cells.into_iter().enumerate() {
+            worksheet.set_value((row as u32, column as u32), cell);
+        }
+    }
+    worksheet
+}
+
+/// Returns the text of the cell at the given absolute position, if it contains text.
+fn string_at(worksheet: &Range<DataType>, position: (u32, u32)) -> Option<&str> {
+    worksheet.get_value(position).and_then(|it| it.get_string())
+}
+
+/// Parses the unit in the cell to the right of a value.
+///
+/// Other text, like a comment, is ignored.
+fn unit_next_to<T>(
+    worksheet: &Range<DataType>,
+    (row, column): (u32, u32),
+    parse: fn(&str) -> Option<T>,
+) -> Option<T> {
+    let text = string_at(worksheet, (row, column + 1))?;
+    parse(text).or_else(|| parse(unit_text(text)))
+}
+
+/// Returns the number in a cell.
+///
+/// Numbers stored as text, like `3,5` in tables written with a German locale, are parsed with the
+/// current number locale.
+pub fn cell_number(cell: &DataType) -> Option<f64> {
+    cell.as_f64()
+        .or_else(|| cell.get_string().and_then(|it| number_locale().parse(it)))
+}
+
+/// Reads a number from the cell at the given absolute position.
+fn read_number(
+    worksheet: &Range<DataType>,
+    (row, column): (u32, u32),
+    field: Field,
+) -> Result<f32, CellError> {
+    let cell = worksheet.get_value((row, column));
+    if let Some(value) = cell.and_then(cell_number) {
+        return Ok(value as f32);
+    }
+    Err(CellError {
+        row,
+        column,
+        field,
+        found: cell.map(|it| it.to_string()).filter(|it| !it.is_empty()),
+    })
+}
@@ -0,0 +1,72 @@
+//! Lines of CSV files as written by spreadsheet applications.
+
+use calamine::DataType;
+
+use super::{csv_cells, csv_delimiter, csv_text};
+
+fn text(text: &str) -> DataType {
+    DataType::String(text.to_owned())
+}
+
+#[test]
+fn plain_fields() {
+    assert_eq!(
+        csv_cells("V / mL; pH ;;7\r", ';'),
+        [
+            text("V / mL"),
+            text("pH"),
+            DataType::Empty,
+            DataType::Float(7.0)
+        ]
+    );
+}
+
+/// A German export writes decimal commas in quotes when the fields are separated by commas.
+#[test]
+fn quoted_delimiter() {
+    assert_eq!(
+        csv_cells(r#""3,5","12,25",1"#, ','),
+        [
+            DataType::Float(3.5),
+            DataType::Float(12.25),
+            DataType::Float(1.0)
+        ]
+    );
+    assert_eq!(
+        csv_cells(r#"Probe,"NaOH, 0.1 mol/L""#, ','),
+        [text("Probe"), text("NaOH, 0.1 mol/L")]
+    );
+}
+
+#[test]
+fn doubled_quotes() {
+    assert_eq!(
+        csv_cells(r#""Probe ""A""";"""";"#, ';'),
+        [text(r#"Probe "A""#), text(r#"""#), DataType::Empty]
+    );
+}
+
+#[test]
+fn delimiter_outside_quotes() {
+    assert_eq!(csv_delimiter(r#""a;b",1"#), ',');
+    assert_eq!(csv_delimiter(r#""a,b";1"#), ';');
+    assert_eq!(csv_delimiter("a\tb"), '\t');
+}
+
+/// Excel writes a byte order mark before UTF-8 text.
+#[test]
+fn byte_order_mark() {
+    assert_eq!(
+        csv_text("\u{FEFF}V (Maßlösung) / mL;pH".as_bytes()),
+        "V (Maßlösung) / mL;pH"
+    );
+}
+
+/// The default CSV export of a German Excel is in Windows-1252.
+#[test]
+fn windows_1252() {
+    assert_eq!(
+        csv_text(b"V (Ma\xdfl\xf6sung) / \xb5L;\x80"),
+        "V (Maßlösung) / µL;€"
+    );
+}
@@ -0,0 +1,47 @@
+use super::{substances::Salt, Input};
+
+/// A point of a precipitation titration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PrecipitationPoint {
+    /// The added volume of measuring solution in mL.
+    pub m_v: f32,
+    /// The negative decadic logarithm of the concentration of the cation, e.g. pAg.
+    pub p_cation: f32,
+    /// The negative decadic logarithm of the concentration of the anion, e.g. pCl.
+    pub p_anion: f32,
+}
+
+/// Calculates a precipitation titration, e.g. of chloride with silver nitrate.
+///
+/// The test solution holds the anion and the measuring solution the cation of the salt, both with
+/// the concentrations of the input. Both ions are assumed to stay in equilibrium with the
+/// precipitate, so their concentrations follow from the solubility product and the excess of one
+/// of them.
+pub fn precipitation(input: &Input, salt: &Salt) -> Vec<PrecipitationPoint> {
+    let n_anion = input.t_c * input.t_v;
+    let pksp = -salt.ksp.log10();
+    input
+        .m_v
+        .iter()
+        .map(|&m_v| {
+            let n_cation = input.m_c * m_v / 1000.0;
+            let volume = input.t_v + m_v / 1000.0;
+            // c(cation) − c(anion) = excess and c(cation) · c(anion) = Ksp
+            let excess = f64::from((n_cation - n_anion) / volume);
+            let ksp = f64::from(salt.ksp);
+            let root = (excess * excess + 4.0 * ksp).sqrt();
+            // Avoid subtracting nearly equal numbers while the anion is in excess.
+            let cation = if excess >= 0.0 {
+                (excess + root) / 2.0
+            } else {
+                2.0 * ksp / (root - excess)
+            };
+            let p_cation = -cation.log10() as f32;
+            PrecipitationPoint {
+                m_v,
+                p_cation,
+                p_anion: pksp - p_cation,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,168 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+
+use rhai::{Dynamic, Engine, Map, Scope, AST};
+use tracing::debug;
+
+use super::{
+    equilibrium::Solution, model::TitrationModel, temperature, AcidBasePair, Input, OutputItem,
+    WorkerError,
+};
+
+/// The function a script defines to calculate the pH.
+const PH_FUNCTION: &str = "ph";
+/// The number of operations a script may take for one point.
+///
+/// A pH formula takes far fewer, so only a script which does not end reaches it, e.g. with
+/// `loop {}`.
+const MAX_OPERATIONS: u64 = 1_000_000;
+/// The depth of nested function calls a script may reach, which limits recursion.
+const MAX_CALL_LEVELS: usize = 32;
+/// The depth of nested expressions in a script and in its functions.
+const MAX_EXPR_DEPTHS: (usize, usize) = (64, 32);
+
+/// The script the scripted model is calculated with, once one is loaded.
+static SCRIPT: RwLock<Option<Script>> = RwLock::new(None);
+
+/// A compiled Rhai script calculating the pH after adding a volume of measuring solution.
+struct Script {
+    path: PathBuf,
+    engine: Engine,
+    ast: AST,
+}
+
+impl Script {
+    /// Calls the pH function of the script, or returns `None` if it fails or returns no number.
+    ///
+    /// A script exceeding the limits of the engine, e.g. with an endless loop, is an error.
+    fn ph(&self, input: &Input, m_v: f32, temperature: f32) -> Result<Option<f32>, WorkerError> {
+        let mut parameters = Map::new();
+        for (name, value) in [
+            ("t_v", input.t_v),
+            ("t_c", input.t_c),
+            ("m_c", input.m_c),
+            ("temperature", temperature),
+            ("pkw", temperature::pkw(temperature)),
+        ] {
+            parameters.insert(name.into(), Dynamic::from_float(value as f64));
+        }
+        let result = self.engine.call_fn::<Dynamic>(
+            &mut Scope::new(),
+            &self.ast,
+            PH_FUNCTION,
+            (m_v as f64, parameters),
+        );
+        match result {
+            // Scripts may return whole numbers, e.g. `7` at the equivalence point.
+            Ok(ph) => Ok(ph
+                .as_float()
+                .or_else(|_| ph.as_int().map(|it| it as f64))
+                .ok()
+                .map(|it| it as f32)),
+            Err(err) if err.unwrap_inner().is_system_exception() => {
+                Err(WorkerError::Script(err.to_string()))
+            }
+            Err(err) => {
+                debug!(m_v, "The script failed: {err}");
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Loads and compiles the script at the given path, which the scripted model is calculated with
+/// from then on.
+///
+/// The script has to define the function `ph(v, p)`, otherwise the previous script is kept.
+pub fn load(path: &Path) -> Result<(), WorkerError> {
+    let source = fs::read_to_string(path).map_err(WorkerError::Io)?;
+    let mut engine = Engine::new();
+    // The script runs for every point, so it must not be able to stall the worker.
+    engine
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_call_levels(MAX_CALL_LEVELS)
+        .set_max_expr_depths(MAX_EXPR_DEPTHS.0, MAX_EXPR_DEPTHS.1);
+    let ast = engine
+        .compile(source)
+        .map_err(|err| WorkerError::Script(err.to_string()))?;
+    if !ast
+        .iter_functions()
+        .any(|it| it.name == PH_FUNCTION && it.params.len() == 2)
+    {
+        return Err(WorkerError::Script(format!(
+            "Function not found: {PH_FUNCTION}(v, p)"
+        )));
+    }
+    *SCRIPT.write().unwrap() = Some(Script {
+        path: path.to_path_buf(),
+        engine,
+        ast,
+    });
+    Ok(())
+}
+
+/// Returns the path of the loaded script.
+pub fn path() -> Option<PathBuf> {
+    SCRIPT.read().unwrap().as_ref().map(|it| it.path.clone())
+}
+
+/// A model calculating the pH with a loaded script, for systems without a built-in model.
+///
+/// The script defines `ph(v, p)`, which is called for every point with the added volume `v` in
+/// mL and a map `p` of the volume of the test solution `t_v` in L, the concentrations `t_c` and
+/// `m_c` in mol/L, the `temperature` in °C and `pkw`:
+///
+/// ```rhai
+/// fn ph(v, p) {
+///     let excess = (p.m_c * v / 1000.0 - p.t_c * p.t_v) / (p.t_v + v / 1000.0);
+///     if excess < 0.0 { -(-excess).log() } else { p.pkw + excess.log() }
+/// }
+/// ```
+///
+/// The amounts are calculated as for a strong acid. Without a script, or where it fails, the
+/// points have no pH. A script taking too many operations or calls for a point, e.g. with an
+/// endless loop or recursion, fails to load the file.
+#[derive(Debug)]
+pub struct ScriptModel;
+
+impl TitrationModel for ScriptModel {
+    fn id(&self) -> &'static str {
+        "script"
+    }
+
+    fn item(&self, input: &Input, m_v: f32, temperature: f32) -> OutputItem {
+        let mut item = input.item(m_v, temperature, &mut ());
+        item.ph = SCRIPT
+            .read()
+            .unwrap()
+            .as_ref()
+            .and_then(|it| it.ph(input, m_v, temperature).ok().flatten())
+            .unwrap_or(f32::NAN);
+        item.poh = temperature::pkw(temperature) - item.ph;
+        item
+    }
+
+    fn acid_base_pairs(&self, input: &Input) -> Vec<AcidBasePair> {
+        input.acid_base_pairs()
+    }
+
+    /// Runs the script for every point once, so a script exceeding the limits of the engine is
+    /// reported instead of leaving the points without a pH.
+    fn check(&self, input: &Input) -> Result<(), WorkerError> {
+        let Some(script) = &*SCRIPT.read().unwrap() else {
+            return Ok(());
+        };
+        for (index, &m_v) in input.m_v.iter().enumerate() {
+            script.ph(input, m_v, input.temperature_at(index))?;
+        }
+        Ok(())
+    }
+
+    /// The script only calculates the pH, so the species of the solution are unknown.
+    fn solution(&self, _input: &Input, _m_v: f32, _temperature: f32) -> Option<Solution> {
+        None
+    }
+}
@@ -0,0 +1,98 @@
+use super::{
+    analysis::Stoichiometry, model::StrongModel, temperature::STANDARD_TEMPERATURE,
+    uncertainty::Uncertainty, Input, Output,
+};
+
+/// The maximum number of volumes in a simulated titration.
+const MAX_VOLUMES: usize = 1000;
+/// The largest change of the pH between two neighbouring points of a refined curve.
+const MAX_PH_STEP: f32 = 0.2;
+/// The smallest distance between two volumes inserted by the refinement in mL.
+const MIN_VOLUME_STEP: f32 = 0.001;
+/// The maximum number of refinement passes.
+const MAX_REFINEMENTS: usize = 12;
+
+/// A titration simulated from its parameters instead of measured volumes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Simulation {
+    /// The volume of the test solution in L.
+    pub test_volume: f32,
+    /// The concentration of the test solution in mol/L.
+    pub test_concentration: f32,
+    /// The concentration of the measuring solution in mol/L.
+    pub measuring_concentration: f32,
+    /// The largest volume of added measuring solution in mL.
+    ///
+    /// If not given, up to twice the equivalence volume is added.
+    pub max_volume: Option<f32>,
+    /// The volume added between two measurements away from the equivalence point in mL.
+    pub step: f32,
+}
+
+impl Simulation {
+    /// Returns the volume of measuring solution at the equivalence point in mL.
+    pub fn equivalence_volume(&self) -> f32 {
+        self.test_concentration * self.test_volume * 1000.0 / self.measuring_concentration
+    }
+
+    /// Generates evenly spaced volumes of added measuring solution.
+    pub fn volumes(&self) -> Vec<f32> {
+        let max = self.max_volume.unwrap_or(2.0 * self.equivalence_volume());
+        let step = self.step.max(max / MAX_VOLUMES as f32).max(MIN_VOLUME_STEP);
+        let count = (max / step).floor() as usize;
+        (0..=count).map(|it| it as f32 * step).collect()
+    }
+
+    /// Returns the input of the simulated titration with evenly spaced volumes.
+    pub fn input(&self) -> Input {
+        Input {
+            t_v: self.test_volume,
+            t_c: self.test_concentration,
+            m_c: self.measuring_concentration,
+            m_v: self.volumes(),
+            temperature: STANDARD_TEMPERATURE,
+            temperatures: Vec::new(),
+            uncertainty: Uncertainty::default(),
+            stoichiometry: Stoichiometry::default(),
+            second_acid: None,
+            titrant: None,
+            analyte: None,
+            model: &StrongModel,
+        }
+    }
+
+    /// Calculates the simulated curve, adding points where it is steep.
+    ///
+    /// Between two points whose pH differs by more than [`MAX_PH_STEP`], the volume in the
+    /// middle is added and the curve is calculated again, so the jump at the equivalence point is
+    /// drawn smoothly.
+    pub fn output(&self) -> Output {
+        let mut input = self.input();
+        let mut output = input.calculate_output();
+        for _ in 0..MAX_REFINEMENTS {
+            let mut added = Vec::new();
+            for items in output.items.windows(2) {
+                let (from, to) = (items[0].m_v, items[1].m_v);
+                if (items[1].ph - items[0].ph).abs() <= MAX_PH_STEP {
+                    continue;
+                }
+                // The calculation skips some volumes, so the gaps around them are split instead.
+                let mut previous = from;
+                for &volume in input.m_v.iter().filter(|it| **it > from && **it <= to) {
+                    if volume - previous > 2.0 * MIN_VOLUME_STEP {
+                        added.push((previous + volume) / 2.0);
+                    }
+                    previous = volume;
+                }
+            }
+            added.truncate(MAX_VOLUMES.saturating_sub(input.m_v.len()));
+            if added.is_empty() {
+                break;
+            }
+            input.m_v.extend(added);
+            input.m_v.sort_by(f32::total_cmp);
+            output = input.calculate_output();
+        }
+        output
+    }
+}
@@ -0,0 +1,286 @@
+use super::temperature::{PkTable, ACETIC_ACID_PKA, AMMONIUM_PKA};
+
+/// An acid or base of the substance database.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Substance {
+    pub name: &'static str,
+    pub formula: &'static str,
+    /// The pKa of the acid, or of the conjugate acid of the base.
+    pub pka: f32,
+    /// The molar mass in g/mol.
+    pub molar_mass: f32,
+    /// The ion remaining in the solution besides oxonium or hydroxide ions.
+    pub counter_ion: &'static str,
+    /// The number of protons the acid releases, or the base takes up, in the titration.
+    pub protons: u8,
+    /// The pKa at other temperatures, where measurements are available.
+    pub pka_table: Option<PkTable>,
+}
+
+impl Substance {
+    /// Returns the pKa at a temperature in °C.
+    ///
+    /// Without measurements at other temperatures, the pKa at 25 °C is used.
+    pub fn pka_at(&self, temperature: f32) -> f32 {
+        self.pka_table.map_or(self.pka, |it| it.at(temperature))
+    }
+}
+
+/// Strong acids, which protolyse completely in water.
+pub const STRONG_ACIDS: &[Substance] = &[
+    Substance {
+        name: "Salzsäure",
+        formula: "HCl",
+        pka: -6.0,
+        molar_mass: 36.46,
+        counter_ion: "Cl⁻",
+        protons: 1,
+        pka_table: None,
+    },
+    Substance {
+        name: "Salpetersäure",
+        formula: "HNO₃",
+        pka: -1.32,
+        molar_mass: 63.01,
+        counter_ion: "NO₃⁻",
+        protons: 1,
+        pka_table: None,
+    },
+    Substance {
+        name: "Bromwasserstoffsäure",
+        formula: "HBr",
+        pka: -9.0,
+        molar_mass: 80.91,
+        counter_ion: "Br⁻",
+        protons: 1,
+        pka_table: None,
+    },
+    Substance {
+        name: "Perchlorsäure",
+        formula: "HClO₄",
+        pka: -10.0,
+        molar_mass: 100.46,
+        counter_ion: "ClO₄⁻",
+        protons: 1,
+        pka_table: None,
+    },
+    // The second proton is not released completely (pKa 1.99), but still reacts with a strong
+    // base before the equivalence point.
+    Substance {
+        name: "Schwefelsäure",
+        formula: "H₂SO₄",
+        pka: -3.0,
+        molar_mass: 98.08,
+        counter_ion: "SO₄²⁻",
+        protons: 2,
+        pka_table: None,
+    },
+];
+
+/// Strong bases, which protolyse completely in water.
+pub const STRONG_BASES: &[Substance] = &[
+    Substance {
+        name: "Natronlauge",
+        formula: "NaOH",
+        pka: 14.8,
+        molar_mass: 40.0,
+        counter_ion: "Na⁺",
+        protons: 1,
+        pka_table: None,
+    },
+    Substance {
+        name: "Kalilauge",
+        formula: "KOH",
+        pka: 14.7,
+        molar_mass: 56.11,
+        counter_ion: "K⁺",
+        protons: 1,
+        pka_table: None,
+    },
+    Substance {
+        name: "Barytlauge",
+        formula: "Ba(OH)₂",
+        pka: 13.36,
+        molar_mass: 171.34,
+        counter_ion: "Ba²⁺",
+        protons: 2,
+        pka_table: None,
+    },
+];
+
+/// Weak acids, which protolyse partially in water.
+pub const WEAK_ACIDS: &[Substance] = &[Substance {
+    name: "Essigsäure",
+    formula: "CH₃COOH",
+    pka: 4.756,
+    molar_mass: 60.05,
+    counter_ion: "CH₃COO⁻",
+    protons: 1,
+    pka_table: Some(ACETIC_ACID_PKA),
+}];
+
+/// Weak bases, which protolyse partially in water.
+pub const WEAK_BASES: &[Substance] = &[Substance {
+    name: "Ammoniak",
+    formula: "NH₃",
+    pka: 9.245,
+    molar_mass: 17.03,
+    counter_ion: "NH₄⁺",
+    protons: 1,
+    pka_table: Some(AMMONIUM_PKA),
+}];
+
+/// Finds an acid or base by its name or formula.
+///
+/// The case is ignored and digits may be written without subscripts, e.g. `HNO3`.
+pub fn find_substance(text: &str) -> Option<&'static Substance> {
+    let normalize = |text: &str| -> String {
+        text.trim()
+            .chars()
+            .map(|it| match it {
+                '₀'..='₉' => char::from(b'0' + (it as u32 - '₀' as u32) as u8),
+                it => it,
+            })
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+    let text = normalize(text);
+    STRONG_ACIDS
+        .iter()
+        .chain(STRONG_BASES)
+        .chain(WEAK_ACIDS)
+        .chain(WEAK_BASES)
+        .find(|it| normalize(it.name) == text || normalize(it.formula) == text)
+}
+
+/// A sparingly soluble salt precipitating during a titration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Salt {
+    pub name: &'static str,
+    pub formula: &'static str,
+    /// The ion added with the measuring solution.
+    pub cation: &'static str,
+    /// The ion of the test solution.
+    pub anion: &'static str,
+    /// The solubility product at 25 °C in mol²/L².
+    pub ksp: f32,
+}
+
+/// Silver salts used in argentometric titrations.
+pub const SILVER_SALTS: &[Salt] = &[
+    Salt {
+        name: "Silberchlorid",
+        formula: "AgCl",
+        cation: "Ag⁺",
+        anion: "Cl⁻",
+        ksp: 1.8e-10,
+    },
+    Salt {
+        name: "Silberbromid",
+        formula: "AgBr",
+        cation: "Ag⁺",
+        anion: "Br⁻",
+        ksp: 5.0e-13,
+    },
+    Salt {
+        name: "Silberiodid",
+        formula: "AgI",
+        cation: "Ag⁺",
+        anion: "I⁻",
+        ksp: 8.3e-17,
+    },
+    Salt {
+        name: "Silberthiocyanat",
+        formula: "AgSCN",
+        cation: "Ag⁺",
+        anion: "SCN⁻",
+        ksp: 1.0e-12,
+    },
+];
+
+/// A metal ion titrated with EDTA.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MetalIon {
+    pub name: &'static str,
+    pub formula: &'static str,
+    /// The decadic logarithm of the formation constant of the EDTA complex at 25 °C.
+    pub log_kf: f32,
+}
+
+/// Metal ions which are commonly determined by complexometric titration.
+pub const EDTA_METALS: &[MetalIon] = &[
+    MetalIon {
+        name: "Calcium",
+        formula: "Ca²⁺",
+        log_kf: 10.65,
+    },
+    MetalIon {
+        name: "Magnesium",
+        formula: "Mg²⁺",
+        log_kf: 8.79,
+    },
+    MetalIon {
+        name: "Zink",
+        formula: "Zn²⁺",
+        log_kf: 16.5,
+    },
+];
+
+/// The pKa values of EDTA from H₆Y²⁺ to HY³⁻.
+pub const EDTA_PKAS: [f32; 6] = [0.0, 1.5, 2.0, 2.66, 6.16, 10.24];
+
+/// An acid-base indicator, which changes its color within a pH range.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Indicator {
+    pub name: &'static str,
+    /// The pH range in which the color changes, from the acidic to the basic end.
+    pub transition: (f32, f32),
+}
+
+impl Indicator {
+    /// Returns the pH in the middle of the transition range.
+    pub fn middle(&self) -> f32 {
+        (self.transition.0 + self.transition.1) / 2.0
+    }
+
+    /// Checks if the color changes at a pH.
+    pub fn brackets(&self, ph: f32) -> bool {
+        self.transition.0 <= ph && ph <= self.transition.1
+    }
+}
+
+/// Indicators of acid-base titrations, sorted by their transition range.
+pub const INDICATORS: &[Indicator] = &[
+    Indicator {
+        name: "Methylorange",
+        transition: (3.1, 4.4),
+    },
+    Indicator {
+        name: "Bromkresolgrün",
+        transition: (3.8, 5.4),
+    },
+    Indicator {
+        name: "Methylrot",
+        transition: (4.4, 6.2),
+    },
+    Indicator {
+        name: "Bromthymolblau",
+        transition: (6.0, 7.6),
+    },
+    Indicator {
+        name: "Phenolrot",
+        transition: (6.8, 8.4),
+    },
+    Indicator {
+        name: "Thymolblau",
+        transition: (8.0, 9.6),
+    },
+    Indicator {
+        name: "Phenolphthalein",
+        transition: (8.2, 10.0),
+    },
+    Indicator {
+        name: "Thymolphthalein",
+        transition: (9.3, 10.5),
+    },
+];
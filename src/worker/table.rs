@@ -0,0 +1,160 @@
+use std::{fmt::Write, sync::Arc};
+
+use rust_xlsxwriter::{Format, Workbook, XlsxError};
+
+use super::{
+    analysis,
+    explain::Rounding,
+    format::{Notation, NumberFormat},
+    locale::number_locale,
+    Output,
+};
+
+/// The headers of the exported columns.
+const HEADERS: [&str; 9] = [
+    "V(m) / mL",
+    "V / mL",
+    "n₁ / mol",
+    "n₂ / mol",
+    "c₁ / mol/L",
+    "c₂ / mol/L",
+    "pH",
+    "pOH",
+    "ΔpH/ΔV / 1/mL",
+];
+
+/// The file format of an exported table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableFormat {
+    Csv,
+    Xlsx,
+}
+
+impl TableFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Xlsx => "xlsx",
+        }
+    }
+}
+
+/// The calculated values of a titration to be written to a table by the worker.
+#[derive(Clone, Debug)]
+pub struct TableExport {
+    pub output: Arc<Output>,
+    pub format: TableFormat,
+    /// The name of the filter in the file dialog.
+    pub filter_name: &'static str,
+    /// The proposed name of the file.
+    pub file_name: String,
+    /// The format of the values, which CSV files are written in and workbooks show.
+    pub number_format: NumberFormat,
+    /// The rounding of the reported results, which the values are rounded to.
+    pub rounding: Rounding,
+}
+
+// Outputs are never changed after they are calculated, so exports are compared by identity.
+impl PartialEq for TableExport {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.output, &other.output)
+            && self.format == other.format
+            && self.filter_name == other.filter_name
+            && self.file_name == other.file_name
+            && self.number_format == other.number_format
+            && self.rounding == other.rounding
+    }
+}
+
+impl Eq for TableExport {}
+
+impl TableExport {
+    /// Writes the table in its format.
+    pub fn write(&self) -> Result<Vec<u8>, XlsxError> {
+        let rows = rows(&self.output, self.rounding);
+        match self.format {
+            TableFormat::Csv => Ok(write_csv(&rows, self.number_format).into_bytes()),
+            TableFormat::Xlsx => write_xlsx(&rows, self.number_format),
+        }
+    }
+}
+
+/// Returns the values of every item in the order of the headers, rounded like the reported
+/// results.
+///
+/// The volumes are rounded to their decimals and the amounts, concentrations and slopes to the
+/// significant digits. The pH and pOH are not rounded, as the rules only cover volumes and
+/// derived results. Values which are not defined for an item, like c₂ before the equivalence
+/// point, are `NaN`.
+fn rows(output: &Output, rounding: Rounding) -> Vec<[f32; 9]> {
+    output
+        .items
+        .iter()
+        .zip(analysis::slopes(output))
+        .map(|(item, slope)| {
+            [
+                rounding.round_volume(item.m_v),
+                rounding.round_volume(item.total_v),
+                rounding.round_value(item.n1),
+                rounding.round_value(item.n2),
+                rounding.round_value(item.c1),
+                rounding.round_value(item.c2),
+                item.ph,
+                item.poh,
+                rounding.round_value(slope),
+            ]
+        })
+        .collect()
+}
+
+/// Writes the rows as comma separated values, leaving undefined values empty.
+///
+/// Values are written with the precision of their rounding, unless a notation is chosen. Values written with a
+/// decimal comma are separated by semicolons instead.
+fn write_csv(rows: &[[f32; 9]], number_format: NumberFormat) -> String {
+    let formatted = number_format.notation != Notation::Auto;
+    let delimiter = if formatted && number_locale().decimal_separator() == ',' {
+        ';'
+    } else {
+        ','
+    };
+    let mut csv = HEADERS.join(&delimiter.to_string());
+    csv.push('\n');
+    for row in rows {
+        for (index, value) in row.iter().enumerate() {
+            if index > 0 {
+                csv.push(delimiter);
+            }
+            if !value.is_finite() {
+                continue;
+            }
+            if formatted {
+                csv.push_str(&number_format.format(*value));
+            } else {
+                _ = write!(csv, "{value}");
+            }
+        }
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Writes the rows into a workbook, leaving undefined values empty.
+///
+/// The cells keep the rounded values and only show them in the number format.
+fn write_xlsx(rows: &[[f32; 9]], number_format: NumberFormat) -> Result<Vec<u8>, XlsxError> {
+    let cell_format = Format::new().set_num_format(number_format.spreadsheet_format());
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    for (column, header) in (0..).zip(HEADERS) {
+        worksheet.write_string(0, column, header)?;
+    }
+    for (row, values) in (1..).zip(rows) {
+        for (column, &value) in (0..).zip(values) {
+            if value.is_finite() {
+                worksheet.write_number_with_format(row, column, value, &cell_format)?;
+            }
+        }
+    }
+    workbook.save_to_buffer()
+}
@@ -0,0 +1,150 @@
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use calamine::DataType;
+use tracing::debug;
+
+use super::{
+    model::TitrationModel,
+    parser::{
+        cell_number, csv_cells, csv_delimiter, csv_text, csv_worksheet, parse_worksheet,
+        volume_unit, Layout, LayoutMapping,
+    },
+    units::VolumeUnit,
+    Input, Output, WorkerError,
+};
+
+/// The parse state of a CSV file a data logger appends rows to.
+///
+/// Only complete lines are read, so a row the logger is still writing is read with the next
+/// change. The rows read before are assumed to stay unchanged, so edits of them are only noticed
+/// if the file gets shorter.
+#[derive(Debug)]
+pub struct CsvTail {
+    /// The number of bytes read up to the end of the last complete line.
+    offset: u64,
+    delimiter: char,
+    /// The zero-based column of the added volumes.
+    column: u32,
+    /// The zero-based column of the temperatures, if the file has one.
+    temperature_column: Option<u32>,
+    unit: VolumeUnit,
+    output: Output,
+}
+
+impl CsvTail {
+    /// Parses the complete lines of a CSV file with the working temperature in °C and the model
+    /// the curve is calculated with.
+    pub fn load(
+        path: &Path,
+        temperature: f32,
+        model: &'static dyn TitrationModel,
+    ) -> Result<Self, WorkerError> {
+        let mut bytes = Vec::new();
+        File::open(path)
+            .and_then(|mut it| it.read_to_end(&mut bytes))
+            .map_err(WorkerError::Io)?;
+        let complete = complete_lines(&bytes);
+        let text = csv_text(&bytes[..complete]);
+        let worksheet = csv_worksheet(&text);
+        let mapping = LayoutMapping::find(path)?;
+        let mut layout = Layout::detect(&worksheet);
+        if let Some(mapping) = &mapping {
+            layout.apply(mapping);
+        }
+        let input = Input {
+            temperature,
+            model,
+            ..parse_worksheet(&worksheet, mapping.as_ref())?
+        };
+        model.check(&input)?;
+        Ok(Self {
+            offset: complete as u64,
+            delimiter: csv_delimiter(text.lines().next().unwrap_or_default()),
+            column: layout.first_volume.1,
+            temperature_column: layout.first_temperature.map(|it| it.1),
+            unit: volume_unit(&worksheet, &layout),
+            output: input.calculate_output(),
+        })
+    }
+
+    pub fn output(&self) -> &Output {
+        &self.output
+    }
+
+    /// Reads the rows appended since the last read and adds their points to the output.
+    ///
+    /// Returns the number of added points, or `None` if the file has to be parsed again because
+    /// it got shorter or a new row has no valid volume.
+    pub fn append(&mut self, path: &Path) -> Option<usize> {
+        let mut file = File::open(path).ok()?;
+        if file.metadata().ok()?.len() < self.offset {
+            debug!("The file got shorter");
+            return None;
+        }
+        file.seek(SeekFrom::Start(self.offset)).ok()?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes).ok()?;
+        let complete = complete_lines(&bytes);
+        let mut volumes = Vec::new();
+        let mut temperatures = Vec::new();
+        for line in csv_text(&bytes[..complete]).lines() {
+            let cells = csv_cells(line, self.delimiter);
+            match cells.get(self.column as usize).and_then(cell_number) {
+                Some(value) => {
+                    volumes.push(self.unit.to_milliliters(value as f32));
+                    if let Some(column) = self.temperature_column {
+                        let temperature = cells.get(column as usize).and_then(cell_number);
+                        temperatures.push(temperature.map_or(f32::NAN, |it| it as f32));
+                    }
+                }
+                // Empty lines at the end of the file are ignored.
+                None if cells.iter().all(DataType::is_empty) => {}
+                None => return None,
+            }
+        }
+        self.offset += complete as u64;
+        let added = volumes.len();
+        if added > 0 {
+            self.extend(volumes, temperatures);
+        }
+        Some(added)
+    }
+
+    /// Adds the points at the given volumes in mL to the output, with their temperatures in °C if
+    /// the file has a temperature column.
+    fn extend(&mut self, volumes: Vec<f32>, temperatures: Vec<f32>) {
+        let input = &mut self.output.input;
+        if input.uncertainty.is_zero() {
+            // Every point only depends on its own volume, so only the new ones are calculated.
+            let added = Input {
+                m_v: volumes.clone(),
+                temperatures: temperatures.clone(),
+                ..input.clone()
+            }
+            .calculate_output();
+            input.m_v.extend(volumes);
+            input.temperatures.extend(temperatures);
+            self.output.items.extend(added.items);
+        } else {
+            // The propagated uncertainties depend on the slope between neighbouring points.
+            input.m_v.extend(volumes);
+            input.temperatures.extend(temperatures);
+            self.output = input.calculate_output();
+        }
+    }
+}
+
+/// Returns the length of the complete lines at the start of the bytes of a file.
+///
+/// The offset is counted in bytes, as the characters of a file in Windows-1252 differ in length
+/// from their UTF-8 text.
+fn complete_lines(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .rposition(|&it| it == b'\n')
+        .map_or(0, |it| it + 1)
+}
@@ -0,0 +1,82 @@
+/// The temperature at which the constants of the substance database are given, in °C.
+pub const STANDARD_TEMPERATURE: f32 = 25.0;
+/// The offset of the Celsius scale from the absolute temperature in K.
+pub const ZERO_CELSIUS: f32 = 273.15;
+
+/// Measured pK values of an equilibrium at several temperatures.
+///
+/// The points are pairs of the temperature in °C and the pK value, sorted by temperature.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PkTable(pub &'static [(f32, f32)]);
+
+impl PkTable {
+    /// Interpolates the pK value at a temperature in °C.
+    ///
+    /// By the van 't Hoff equation, pK is linear in 1/T as long as the reaction enthalpy is
+    /// constant, so the value is interpolated over the reciprocal absolute temperature between
+    /// the neighbouring points. Outside of the table the value at the nearest end is returned,
+    /// since the enthalpy is unknown there.
+    pub fn at(&self, temperature: f32) -> f32 {
+        let reciprocal = |it: f32| 1.0 / (it + ZERO_CELSIUS);
+        let points = self.0;
+        let index = points.partition_point(|(it, _)| *it < temperature);
+        match (index.checked_sub(1).map(|it| points[it]), points.get(index)) {
+            (Some((t1, pk1)), Some(&(t2, pk2))) => {
+                let fraction =
+                    (reciprocal(temperature) - reciprocal(t1)) / (reciprocal(t2) - reciprocal(t1));
+                pk1 + fraction * (pk2 - pk1)
+            }
+            (Some((_, pk)), None) | (None, Some(&(_, pk))) => pk,
+            (None, None) => f32::NAN,
+        }
+    }
+
+    /// Returns the range of temperatures covered by measurements in °C.
+    pub fn range(&self) -> Option<(f32, f32)> {
+        Some((self.0.first()?.0, self.0.last()?.0))
+    }
+}
+
+/// The ionic product of water from 0 °C to 100 °C, after Bandura and Lvov (2006).
+pub const WATER_PKW: PkTable = PkTable(&[
+    (0.0, 14.946),
+    (10.0, 14.535),
+    (20.0, 14.167),
+    (25.0, 13.995),
+    (30.0, 13.836),
+    (40.0, 13.535),
+    (50.0, 13.275),
+    (60.0, 13.018),
+    (70.0, 12.804),
+    (80.0, 12.613),
+    (90.0, 12.438),
+    (100.0, 12.265),
+]);
+
+/// The pKa of acetic acid from 0 °C to 60 °C, after Harned and Ehlers (1933).
+pub const ACETIC_ACID_PKA: PkTable = PkTable(&[
+    (0.0, 4.780),
+    (10.0, 4.763),
+    (20.0, 4.757),
+    (25.0, 4.756),
+    (30.0, 4.757),
+    (40.0, 4.769),
+    (50.0, 4.787),
+    (60.0, 4.812),
+]);
+
+/// The pKa of the ammonium ion from 0 °C to 50 °C, after Bates and Pinching (1949).
+pub const AMMONIUM_PKA: PkTable = PkTable(&[
+    (0.0, 10.081),
+    (10.0, 9.731),
+    (20.0, 9.400),
+    (25.0, 9.245),
+    (30.0, 9.093),
+    (40.0, 8.805),
+    (50.0, 8.539),
+]);
+
+/// Returns the pKw of water at a temperature in °C.
+pub fn pkw(temperature: f32) -> f32 {
+    WATER_PKW.at(temperature)
+}
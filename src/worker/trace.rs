@@ -0,0 +1,153 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use super::{Response, Signal};
+
+/// The maximum number of entries kept in a trace.
+const TRACE_CAPACITY: usize = 200;
+
+/// An event recorded for debugging.
+#[derive(Clone, Debug)]
+pub enum TraceEvent {
+    /// A signal was sent to the worker.
+    Signal(String),
+    /// A signal was skipped because of the signal lock.
+    SkippedSignal(String),
+    /// A response was sent to the app.
+    Response(String),
+    /// The watcher reported an event or error.
+    Watcher(String),
+    /// A file was not reloaded, because it did not change.
+    Unchanged(String),
+    /// The diagram was rendered in the given time.
+    Render(Duration),
+}
+
+#[derive(Clone, Debug)]
+pub struct TraceEntry {
+    /// The time since the trace was created.
+    pub elapsed: Duration,
+    pub event: TraceEvent,
+}
+
+/// Records the traffic between the app and the worker.
+///
+/// Nothing is recorded until the trace is enabled, so it costs nothing in normal use.
+pub struct Trace {
+    enabled: AtomicBool,
+    started: Instant,
+    entries: Mutex<VecDeque<TraceEntry>>,
+}
+
+impl Default for Trace {
+    fn default() -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            started: Instant::now(),
+            entries: Mutex::default(),
+        }
+    }
+}
+
+impl Trace {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Records an event, if the trace is enabled.
+    ///
+    /// The event is only created if it is recorded. The oldest entries are dropped once the
+    /// capacity is reached.
+    pub fn record(&self, event: impl FnOnce() -> TraceEvent) {
+        if !self.is_enabled() {
+            return;
+        }
+        let entry = TraceEntry {
+            elapsed: self.started.elapsed(),
+            event: event(),
+        };
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() == TRACE_CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// Returns the most recent entries, newest first.
+    pub fn recent(&self, count: usize) -> Vec<TraceEntry> {
+        let entries = self.entries.lock().unwrap();
+        entries.iter().rev().take(count).cloned().collect()
+    }
+}
+
+/// Returns a short description of a signal.
+///
+/// Unlike the debug representation, it leaves out the content of saved files.
+pub fn signal_summary(signal: &Signal) -> String {
+    match signal {
+        Signal::Update => "Update".into(),
+        Signal::Reload => "Reload".into(),
+        Signal::Pause => "Pause".into(),
+        Signal::Resume => "Resume".into(),
+        Signal::FileDialog(_) => "FileDialog".into(),
+        #[cfg(feature = "scripting")]
+        Signal::ScriptDialog(_) => "ScriptDialog".into(),
+        Signal::LoadPath(path) => format!("LoadPath({})", path.display()),
+        Signal::FolderDialog(pattern) => format!("FolderDialog({pattern})"),
+        Signal::WatchFolder(folder) => {
+            format!("WatchFolder({}, {})", folder.path.display(), folder.pattern)
+        }
+        Signal::UnwatchFolder => "UnwatchFolder".into(),
+        Signal::Unload(path) => format!("Unload({})", path.display()),
+        Signal::Save(request) => format!("Save({})", request.file_name),
+        Signal::ExportTable(export) => format!("ExportTable({})", export.file_name),
+        #[cfg(feature = "live")]
+        Signal::Connect(config) => format!("Connect({})", config.port),
+        #[cfg(feature = "live")]
+        Signal::Disconnect => "Disconnect".into(),
+        Signal::Cancel => "Cancel".into(),
+        Signal::Stop => "Stop".into(),
+    }
+}
+
+/// Returns a short description of a response.
+///
+/// Unlike the debug representation, it leaves out the calculated output.
+pub fn response_summary(response: &Response) -> String {
+    match response {
+        Response::Unload(path) => format!("Unload({})", path.display()),
+        Response::Output(path, output, _) => {
+            format!("Output({}, {} items)", path.display(), output.items.len())
+        }
+        Response::Error(path, err) => format!("Error({}, {err})", path.display()),
+        Response::Saved(path) => format!("Saved({})", path.display()),
+        Response::SaveFailed(path, err) => format!("SaveFailed({}, {err})", path.display()),
+        Response::PendingChanges(count) => format!("PendingChanges({count})"),
+        Response::WatchedFolder(path) => format!("WatchedFolder({:?})", path),
+        Response::Progress {
+            path,
+            rows_done,
+            rows_total,
+        } => format!("Progress({}, {rows_done}/{rows_total})", path.display()),
+        Response::Cancelled(path) => format!("Cancelled({})", path.display()),
+        #[cfg(feature = "live")]
+        Response::Reading(value, temperature) => format!("Reading({value}, {temperature:?})"),
+        #[cfg(feature = "live")]
+        Response::LiveFailed(err) => format!("LiveFailed({err})"),
+        #[cfg(feature = "scripting")]
+        Response::ScriptLoaded(path) => format!("ScriptLoaded({})", path.display()),
+        #[cfg(feature = "scripting")]
+        Response::ScriptFailed(err) => format!("ScriptFailed({err})"),
+        Response::Stopped => "Stopped".into(),
+    }
+}
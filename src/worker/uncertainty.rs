@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+use super::{analysis, Input, Output};
+
+/// The coverage factor of the reported confidence intervals, which covers about 95 %.
+pub const COVERAGE_FACTOR: f32 = 2.0;
+
+/// The standard uncertainties of the measured values of an input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Uncertainty {
+    /// The uncertainty of the volume of the test solution in L.
+    pub t_v: f32,
+    /// The uncertainty of the concentration of the test solution in mol/L.
+    pub t_c: f32,
+    /// The uncertainty of the concentration of the measuring solution in mol/L.
+    pub m_c: f32,
+    /// The uncertainty of every reading of the added volume in mL.
+    pub m_v: f32,
+}
+
+impl Uncertainty {
+    pub fn is_zero(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Propagates the uncertainties of the input to the pH of every item.
+///
+/// The sensitivity to each value is found by calculating the output again with that value
+/// increased by its uncertainty. The sensitivity to the added volume is taken from the slope of
+/// the curve instead, since shifted volumes do not match the items anymore. The contributions are
+/// independent and added in quadrature.
+pub fn propagate(input: &Input, output: &mut Output) {
+    let uncertainty = input.uncertainty;
+    if uncertainty.is_zero() {
+        return;
+    }
+    let shifted = [
+        Input {
+            t_v: input.t_v + uncertainty.t_v,
+            uncertainty: Uncertainty::default(),
+            ..input.clone()
+        },
+        Input {
+            t_c: input.t_c + uncertainty.t_c,
+            uncertainty: Uncertainty::default(),
+            ..input.clone()
+        },
+        Input {
+            m_c: input.m_c + uncertainty.m_c,
+            uncertainty: Uncertainty::default(),
+            ..input.clone()
+        },
+    ]
+    .map(|it| it.calculate_output());
+    let slopes = analysis::slopes(output);
+    for (index, (item, slope)) in output.items.iter_mut().zip(slopes).enumerate() {
+        let mut variance = (slope * uncertainty.m_v).powi(2);
+        for shifted in &shifted {
+            // The shifted outputs have the same volumes, so their items are matched by index.
+            if let Some(other) = shifted.items.get(index).filter(|it| it.m_v == item.m_v) {
+                let difference = other.ph - item.ph;
+                if difference.is_finite() {
+                    variance += difference.powi(2);
+                }
+            }
+        }
+        item.ph_uncertainty = variance.sqrt();
+    }
+}
+
+/// Returns the standard uncertainty of the equivalence volume in mL.
+///
+/// The volume is a product of quotients, so the relative uncertainties add in quadrature.
+pub fn equivalence_uncertainty(input: &Input) -> f32 {
+    let uncertainty = input.uncertainty;
+    let volume = input.equivalence_volume();
+    let relative = [
+        (uncertainty.t_v, input.t_v),
+        (uncertainty.t_c, input.t_c),
+        (uncertainty.m_c, input.m_c),
+    ]
+    .into_iter()
+    .filter(|(_, value)| *value != 0.0)
+    .map(|(uncertainty, value)| (uncertainty / value).powi(2))
+    .sum::<f32>()
+    .sqrt();
+    volume.abs() * relative
+}
@@ -0,0 +1,119 @@
+use super::{substances::Substance, Input};
+
+/// The unit of a volume in the table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VolumeUnit {
+    Milliliter,
+    Liter,
+}
+
+impl VolumeUnit {
+    /// Parses a unit like `mL` or `L`.
+    pub fn parse(text: &str) -> Option<Self> {
+        match normalize(text).as_str() {
+            "ml" => Some(Self::Milliliter),
+            "l" => Some(Self::Liter),
+            _ => None,
+        }
+    }
+
+    pub fn to_liters(self, value: f32) -> f32 {
+        match self {
+            Self::Milliliter => value / 1000.0,
+            Self::Liter => value,
+        }
+    }
+
+    pub fn to_milliliters(self, value: f32) -> f32 {
+        match self {
+            Self::Milliliter => value,
+            Self::Liter => value * 1000.0,
+        }
+    }
+}
+
+/// The quantity the progress of a titration is plotted as.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum XQuantity {
+    /// The added volume of measuring solution in mL.
+    #[default]
+    Volume,
+    /// The titration degree τ, the added amount of measuring solution per amount of analyte,
+    /// scaled by the stoichiometry so the equivalence point is at 1.
+    Degree,
+    /// The added amount of measuring solution in mmol.
+    Amount,
+}
+
+impl XQuantity {
+    pub const ALL: [Self; 3] = [Self::Volume, Self::Degree, Self::Amount];
+
+    /// Returns the value of the quantity per mL of added measuring solution.
+    ///
+    /// All quantities grow linearly with the added volume. Returns `None` if the concentrations
+    /// needed for the conversion are unknown, like for readings of a pH meter.
+    pub fn per_milliliter(self, input: &Input) -> Option<f32> {
+        let factor = match self {
+            Self::Volume => return Some(1.0),
+            // mol/L · mL = mmol
+            Self::Degree => 1.0 / input.equivalence_volume(),
+            Self::Amount => input.m_c,
+        };
+        (factor.is_finite() && factor > 0.0).then_some(factor)
+    }
+}
+
+/// The unit of a concentration in the table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConcentrationUnit {
+    MolPerLiter,
+    MillimolPerLiter,
+    /// A mass concentration, which needs the molar mass of the dissolved substance.
+    GramPerLiter,
+}
+
+impl ConcentrationUnit {
+    /// Parses a unit like `mol/L`, `mmol/L`, `M` or `g/L`.
+    pub fn parse(text: &str) -> Option<Self> {
+        match normalize(text).as_str() {
+            "mol/l" | "m" => Some(Self::MolPerLiter),
+            "mmol/l" | "mm" => Some(Self::MillimolPerLiter),
+            "g/l" => Some(Self::GramPerLiter),
+            _ => None,
+        }
+    }
+
+    /// Converts a concentration to mol/L.
+    ///
+    /// Mass concentrations can only be converted if the substance is known.
+    pub fn to_mol_per_liter(self, value: f32, substance: Option<&Substance>) -> Option<f32> {
+        match self {
+            Self::MolPerLiter => Some(value),
+            Self::MillimolPerLiter => Some(value / 1000.0),
+            Self::GramPerLiter => substance.map(|it| value / it.molar_mass),
+        }
+    }
+}
+
+/// Finds the unit in the text of a cell.
+///
+/// The unit may be the whole text, follow the quantity after ` / ` (e.g. `V (Maßlösung) / mL`)
+/// or be written in brackets (e.g. `V (Maßlösung) [mL]`).
+pub fn unit_text(text: &str) -> &str {
+    if let Some((_, unit)) = text.split_once(" / ") {
+        return unit.trim();
+    }
+    if let (Some(start), Some(end)) = (text.rfind('['), text.rfind(']')) {
+        if start < end {
+            return text[start + 1..end].trim();
+        }
+    }
+    text.trim()
+}
+
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|it| !it.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
@@ -0,0 +1,61 @@
+use std::collections::HashSet;
+
+use super::{parser::Field, Input};
+
+/// The highest plausible concentration of a solution in mol/L.
+///
+/// Even concentrated hydrochloric acid only has about 12 mol/L.
+pub const MAX_CONCENTRATION: f32 = 20.0;
+
+/// A chemically implausible value of an input, which is calculated anyway.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Warning {
+    /// A volume or concentration is negative.
+    Negative(Field, f32),
+    /// The volume of the test solution or a concentration is zero.
+    Zero(Field),
+    /// A concentration is above [`MAX_CONCENTRATION`].
+    HighConcentration(Field, f32),
+    /// The added volume at the given index is smaller than the one before.
+    Decreasing { index: usize, volume: f32 },
+    /// The added volume at the given index was already added at a previous index.
+    Duplicate { index: usize, volume: f32 },
+}
+
+/// Checks the input for chemically implausible values.
+///
+/// The warnings do not stop the calculation, so the curve is shown as far as it can be calculated.
+pub fn validate(input: &Input) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+    let values = [
+        (Field::TestVolume, input.t_v),
+        (Field::TestConcentration, input.t_c),
+        (Field::MeasuringConcentration, input.m_c),
+    ];
+    let second = input
+        .second_acid
+        .as_ref()
+        .map(|it| (Field::SecondConcentration, it.concentration));
+    for (field, value) in values.into_iter().chain(second) {
+        if value < 0.0 {
+            warnings.push(Warning::Negative(field, value));
+        } else if value == 0.0 {
+            warnings.push(Warning::Zero(field));
+        } else if field != Field::TestVolume && value > MAX_CONCENTRATION {
+            warnings.push(Warning::HighConcentration(field, value));
+        }
+    }
+    let mut seen = HashSet::new();
+    for (index, &volume) in input.m_v.iter().enumerate() {
+        if volume < 0.0 {
+            warnings.push(Warning::Negative(Field::MeasuringVolume, volume));
+        }
+        if index > 0 && volume < input.m_v[index - 1] {
+            warnings.push(Warning::Decreasing { index, volume });
+        }
+        if !seen.insert(volume.to_bits()) {
+            warnings.push(Warning::Duplicate { index, volume });
+        }
+    }
+    warnings
+}
@@ -0,0 +1,284 @@
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use notify::{
+    event::{MetadataKind, ModifyKind},
+    Event, EventKind, PollWatcher, RecommendedWatcher, RecursiveMode, Watcher,
+};
+use tracing::warn;
+
+use super::parser::is_table;
+
+/// Decides which watcher events cause the watched files to be reloaded.
+///
+/// Removals are always passed through, so deleted files are unloaded, and so are creations, so
+/// new files in a watched folder are found.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EventFilter {
+    /// Every modification.
+    AnyModify,
+    /// Every modification except attribute changes, e.g. permissions or access times.
+    #[default]
+    IgnoreAttributes,
+    /// Only changes of the file content.
+    ///
+    /// Polling reports content changes as changed write times, so these are passed as well.
+    DataOnly,
+}
+
+impl EventFilter {
+    /// Checks if an event of the given kind should trigger an update.
+    pub fn matches(self, kind: &EventKind) -> bool {
+        let EventKind::Modify(modify) = kind else {
+            return matches!(kind, EventKind::Create(_) | EventKind::Remove(_));
+        };
+        match self {
+            Self::AnyModify => true,
+            Self::IgnoreAttributes => !matches!(
+                modify,
+                ModifyKind::Metadata(
+                    MetadataKind::AccessTime
+                        | MetadataKind::Permissions
+                        | MetadataKind::Ownership
+                        | MetadataKind::Extended
+                )
+            ),
+            Self::DataOnly => matches!(
+                modify,
+                ModifyKind::Data(_) | ModifyKind::Metadata(MetadataKind::WriteTime)
+            ),
+        }
+    }
+}
+
+/// The time in milliseconds the watched files have to stay unchanged before they are reloaded.
+///
+/// Bursts of events, e.g. from a spreadsheet application saving a file in several steps, are
+/// coalesced into a single reload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Debounce(pub u32);
+
+impl Default for Debounce {
+    fn default() -> Self {
+        Self(300)
+    }
+}
+
+impl Debounce {
+    pub fn duration(self) -> Duration {
+        Duration::from_millis(self.0.into())
+    }
+}
+
+/// The modification time and size of a file.
+///
+/// Files with an unchanged stamp are not reloaded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FileStamp {
+    modified: SystemTime,
+    len: u64,
+}
+
+impl FileStamp {
+    /// Reads the stamp of a file, if its metadata is available.
+    pub fn read(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        Some(Self {
+            modified: metadata.modified().ok()?,
+            len: metadata.len(),
+        })
+    }
+}
+
+/// A folder whose most recently modified table is loaded, e.g. where a measuring instrument
+/// exports every run to a new file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WatchedFolder {
+    pub path: PathBuf,
+    /// The pattern the file names have to match, with `*` for any text and `?` for any character.
+    ///
+    /// An empty pattern matches every table.
+    pub pattern: String,
+}
+
+impl WatchedFolder {
+    /// Returns the most recently modified table in the folder whose name matches the pattern.
+    pub fn newest(&self) -> Option<PathBuf> {
+        let pattern = match self.pattern.trim() {
+            "" => "*",
+            pattern => pattern,
+        };
+        fs::read_dir(&self.path)
+            .ok()?
+            .filter_map(Result::ok)
+            .filter(|it| {
+                let name = it.file_name();
+                let name = name.to_string_lossy();
+                is_table(&it.path()) && glob_matches(pattern, &name)
+            })
+            .filter_map(|it| {
+                let metadata = it.metadata().ok().filter(fs::Metadata::is_file)?;
+                Some((metadata.modified().ok()?, it.path()))
+            })
+            .max()
+            .map(|(_, path)| path)
+    }
+}
+
+/// Checks if a file name matches a pattern with `*` for any text and `?` for any character.
+///
+/// The case is ignored, as file systems of instruments often do not keep it.
+pub fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().flat_map(char::to_lowercase).collect();
+    let name: Vec<char> = name.chars().flat_map(char::to_lowercase).collect();
+    let (mut p, mut n) = (0, 0);
+    // The position after the last star and the position in the name it was matched up to.
+    let mut star = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p + 1, n));
+                p += 1;
+            }
+            Some(&it) if it == '?' || it == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            // The last star takes one more character.
+            _ => match star {
+                Some((after, matched)) => {
+                    p = after;
+                    n = matched + 1;
+                    star = Some((after, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|it| *it == '*')
+}
+
+/// The interval in which the poll watcher checks the watched files.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The way changes of the watched files are detected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WatcherBackend {
+    /// The notification mechanism of the operating system.
+    Native,
+    /// Periodically comparing the modification times.
+    Poll,
+}
+
+/// The handler all watcher events are passed to.
+pub type EventCallback = Arc<dyn Fn(notify::Result<Event>) + Send + Sync>;
+
+enum Inner {
+    Native(RecommendedWatcher),
+    Poll(PollWatcher),
+}
+
+/// Watches files with the native watcher, falling back to polling if it fails.
+///
+/// Native watchers lose track of files which are replaced on save, which many spreadsheet
+/// applications do. Therefore files are watched again after every reload.
+pub struct FileWatcher {
+    inner: Inner,
+    callback: EventCallback,
+    watched: BTreeSet<PathBuf>,
+}
+
+impl FileWatcher {
+    /// Creates a watcher with the preferred backend.
+    pub fn new(preferred: WatcherBackend, callback: EventCallback) -> notify::Result<Self> {
+        let inner = match preferred {
+            WatcherBackend::Native => match native_watcher(&callback) {
+                Ok(watcher) => Inner::Native(watcher),
+                Err(err) => {
+                    warn!("The native watcher is unavailable: {err}");
+                    Inner::Poll(poll_watcher(&callback)?)
+                }
+            },
+            WatcherBackend::Poll => Inner::Poll(poll_watcher(&callback)?),
+        };
+        Ok(Self {
+            inner,
+            callback,
+            watched: BTreeSet::new(),
+        })
+    }
+
+    pub fn backend(&self) -> WatcherBackend {
+        match self.inner {
+            Inner::Native(_) => WatcherBackend::Native,
+            Inner::Poll(_) => WatcherBackend::Poll,
+        }
+    }
+
+    /// Starts watching a file.
+    ///
+    /// If the native watcher cannot watch the file, all files are moved to a poll watcher.
+    pub fn watch(&mut self, path: &Path) -> notify::Result<()> {
+        if let Inner::Native(watcher) = &mut self.inner {
+            match watcher.watch(path, RecursiveMode::NonRecursive) {
+                Ok(()) => {
+                    self.watched.insert(path.to_path_buf());
+                    return Ok(());
+                }
+                Err(err) => {
+                    warn!("Falling back to polling: {err}");
+                    let mut watcher = poll_watcher(&self.callback)?;
+                    for path in &self.watched {
+                        watcher.watch(path, RecursiveMode::NonRecursive)?;
+                    }
+                    self.inner = Inner::Poll(watcher);
+                }
+            }
+        }
+        let Inner::Poll(watcher) = &mut self.inner else {
+            unreachable!("the native watcher was replaced");
+        };
+        watcher.watch(path, RecursiveMode::NonRecursive)?;
+        self.watched.insert(path.to_path_buf());
+        Ok(())
+    }
+
+    pub fn unwatch(&mut self, path: &Path) {
+        if !self.watched.remove(path) {
+            return;
+        }
+        _ = match &mut self.inner {
+            Inner::Native(watcher) => watcher.unwatch(path),
+            Inner::Poll(watcher) => watcher.unwatch(path),
+        };
+    }
+
+    /// Watches a file again after it was reloaded.
+    ///
+    /// This is only needed for the native watcher, which loses replaced files.
+    pub fn refresh(&mut self, path: &Path) -> notify::Result<()> {
+        if !matches!(self.inner, Inner::Native(_)) || !self.watched.contains(path) {
+            return Ok(());
+        }
+        self.unwatch(path);
+        self.watch(path)
+    }
+}
+
+fn native_watcher(callback: &EventCallback) -> notify::Result<RecommendedWatcher> {
+    let callback = callback.clone();
+    RecommendedWatcher::new(move |res| callback(res), notify::Config::default())
+}
+
+fn poll_watcher(callback: &EventCallback) -> notify::Result<PollWatcher> {
+    let callback = callback.clone();
+    PollWatcher::new(
+        move |res| callback(res),
+        notify::Config::default().with_poll_interval(POLL_INTERVAL),
+    )
+}
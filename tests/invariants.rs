@@ -0,0 +1,286 @@
+//! Chemical invariants of the calculated curves, checked for random titrations.
+//!
+//! The titrations are of a strong acid with a strong base, for which the pH is known in closed
+//! form up to the dissociation of water.
+
+use proptest::prelude::*;
+use titration_curve::worker::{
+    analysis::Stoichiometry,
+    conductivity,
+    explain::Quantity,
+    hagg,
+    model::{StrongModel, WeakAcidModel},
+    substances::{STRONG_ACIDS, STRONG_BASES},
+    temperature,
+    uncertainty::Uncertainty,
+    Input,
+};
+
+/// The deviation allowed from an exact invariant, which covers the rounding of `f32`.
+const TOLERANCE: f32 = 1e-3;
+
+/// Creates a titration of an acid with the given concentrations, volume of the test solution in
+/// L and added volumes in mL.
+fn input(t_c: f32, m_c: f32, t_v: f32, m_v: Vec<f32>, temperature: f32) -> Input {
+    Input {
+        t_v,
+        t_c,
+        m_c,
+        m_v,
+        temperature,
+        temperatures: Vec::new(),
+        uncertainty: Uncertainty::default(),
+        stoichiometry: Stoichiometry::default(),
+        second_acid: None,
+        titrant: None,
+        analyte: None,
+        model: &StrongModel,
+    }
+}
+
+/// Returns the equivalence volume in mL.
+fn equivalence_volume(input: &Input) -> f32 {
+    input.t_c * input.t_v * 1000.0 / input.m_c
+}
+
+/// Generates a titration at 25 °C whose added volumes reach twice the equivalence volume.
+fn titration() -> impl Strategy<Value = Input> {
+    (0.001f32..1.0, 0.001f32..1.0, 0.005f32..0.1).prop_flat_map(|(t_c, m_c, t_v)| {
+        let end = 2.0 * t_c * t_v * 1000.0 / m_c;
+        prop::collection::vec(0.0..end, 1..50).prop_map(move |mut m_v| {
+            m_v.sort_by(f32::total_cmp);
+            input(t_c, m_c, t_v, m_v, 25.0)
+        })
+    })
+}
+
+proptest! {
+    #[test]
+    fn ph_rises_with_added_base(input in titration()) {
+        let output = input.calculate_output();
+        for pair in output.items.windows(2) {
+            prop_assert!(
+                pair[1].ph >= pair[0].ph - TOLERANCE,
+                "pH falls from {} at {} mL to {} at {} mL",
+                pair[0].ph,
+                pair[0].m_v,
+                pair[1].ph,
+                pair[1].m_v,
+            );
+        }
+    }
+
+    #[test]
+    fn ph_and_poh_add_up_to_pkw(input in titration(), temperature in 0.0f32..50.0) {
+        let input = Input { temperature, ..input };
+        let pkw = temperature::pkw(temperature);
+        for item in input.calculate_output().items {
+            prop_assert!((item.ph + item.poh - pkw).abs() < TOLERANCE);
+        }
+    }
+
+    /// A temperature column holding the same temperature in every row gives the curve at that
+    /// working temperature.
+    #[test]
+    fn temperature_column_matches_working_temperature(
+        input in titration(),
+        temperature in 0.0f32..50.0,
+    ) {
+        let column = Input {
+            temperatures: vec![temperature; input.m_v.len()],
+            ..input.clone()
+        };
+        let working = Input { temperature, ..input };
+        let items = working.calculate_output().items;
+        for (item, expected) in column.calculate_output().items.iter().zip(&items) {
+            prop_assert_eq!(item.ph, expected.ph);
+            prop_assert_eq!(item.temperature, temperature);
+        }
+    }
+
+    /// A weak acid releases at most as many protons as a strong one of the same concentration.
+    #[test]
+    fn weak_acid_is_not_more_acidic_than_strong_acid(input in titration()) {
+        let strong = input.calculate_output().items;
+        let weak = Input { model: &WeakAcidModel, ..input }.calculate_output().items;
+        for (item, strong) in weak.iter().zip(&strong) {
+            prop_assert!(item.ph >= strong.ph - TOLERANCE, "pH {} at {} mL", item.ph, item.m_v);
+        }
+    }
+
+    /// The Hägg diagram shows the species of the model, so the lines of a weak acid and its
+    /// anion cross at its pKa.
+    #[test]
+    fn weak_acid_species_cross_at_the_pka(input in titration()) {
+        let lines = hagg::hagg(&Input { model: &WeakAcidModel, ..input });
+        let lg = |species: &str| {
+            let line = lines.iter().find(|it| it.species == species).expect(species);
+            let (_, lg) = line.points.iter().min_by(|a, b| (a.0 - 4.756).abs().total_cmp(&(b.0 - 4.756).abs())).unwrap();
+            *lg
+        };
+        prop_assert!((lg("HA") - lg("A⁻")).abs() < 0.1);
+    }
+
+    /// A weak acid only conducts with its protolysed part, so it never conducts more than a
+    /// strong acid of the same concentration.
+    #[test]
+    fn weak_acid_conducts_less_than_strong_acid(input in titration()) {
+        let (acid, base) = (&STRONG_ACIDS[0], &STRONG_BASES[0]);
+        let strong = conductivity::conductivity(&input, acid, base);
+        let weak = conductivity::conductivity(&Input { model: &WeakAcidModel, ..input }, acid, base);
+        prop_assert_eq!(weak.len(), strong.len());
+        for (weak, strong) in weak.iter().zip(&strong) {
+            prop_assert!(weak.1 <= strong.1 * (1.0 + TOLERANCE), "κ {} at {} mL", weak.1, weak.0);
+        }
+    }
+
+    /// The conductivity of a strong acid falls until the equivalence point and rises after it, so
+    /// the endpoint is found next to the equivalence volume.
+    ///
+    /// The measuring solution is more concentrated than the test solution, as usual for
+    /// conductometry, so the dilution does not bend the curve more than the equivalence point.
+    #[test]
+    fn conductivity_endpoint_is_the_equivalence_volume(
+        t_c in 0.001f32..1.0,
+        ratio in 1.0f32..10.0,
+        t_v in 0.005f32..0.1,
+    ) {
+        let m_c = ratio * t_c;
+        let equivalence = t_c * t_v * 1000.0 / m_c;
+        let step = equivalence / 20.0;
+        let m_v = (0..=40).map(|it| it as f32 * step).collect();
+        let input = input(t_c, m_c, t_v, m_v, 25.0);
+        let points = conductivity::conductivity(&input, &STRONG_ACIDS[0], &STRONG_BASES[0]);
+        let endpoint = conductivity::endpoint(&points, t_v * 1000.0).unwrap();
+        prop_assert!((endpoint - equivalence).abs() <= step * (1.0 + TOLERANCE), "{endpoint} mL");
+    }
+
+    /// A measured conductivity paired with its own volumes is unchanged, and between two
+    /// measurements it lies between their values.
+    #[test]
+    fn measured_conductivity_is_paired_by_volume(
+        measured in prop::collection::vec((0.0f32..50.0, 0.0f32..20.0), 2..30),
+    ) {
+        let mut measured = measured;
+        measured.sort_by(|a, b| a.0.total_cmp(&b.0));
+        measured.dedup_by(|a, b| a.0 == b.0);
+        let volumes: Vec<f32> = measured.iter().map(|it| it.0).collect();
+        prop_assert_eq!(&conductivity::paired(&measured, &volumes), &measured);
+        let middles: Vec<f32> = volumes.windows(2).map(|it| (it[0] + it[1]) / 2.0).collect();
+        let paired = conductivity::paired(&measured, &middles);
+        prop_assert_eq!(paired.len(), middles.len());
+        for (pair, point) in measured.windows(2).zip(&paired) {
+            let (low, high) = (pair[0].1.min(pair[1].1), pair[0].1.max(pair[1].1));
+            prop_assert!((low - TOLERANCE..=high + TOLERANCE).contains(&point.1));
+        }
+        let outside = [volumes[0] - 1.0, volumes[volumes.len() - 1] + 1.0];
+        prop_assert!(conductivity::paired(&measured, &outside).is_empty());
+    }
+
+    #[test]
+    fn ph_stays_within_0_and_14(input in titration()) {
+        for item in input.calculate_output().items {
+            prop_assert!((0.0..=14.0).contains(&item.ph), "pH {} at {} mL", item.ph, item.m_v);
+        }
+    }
+
+    /// The added volumes cannot hit the equivalence volume exactly, so the neutral point has to
+    /// lie between the volumes just before and after it.
+    #[test]
+    fn ph_is_neutral_at_the_equivalence_point(input in titration(), temperature in 0.0f32..50.0) {
+        let equivalence = equivalence_volume(&input);
+        let input = Input {
+            m_v: vec![equivalence * (1.0 - 1e-6), equivalence * (1.0 + 1e-6)],
+            temperature,
+            ..input
+        };
+        let output = input.calculate_output();
+        let (before, after) = (output.items[0].ph, output.items[1].ph);
+        let neutral = temperature::pkw(temperature) / 2.0;
+        prop_assert!(
+            before <= neutral + TOLERANCE && neutral - TOLERANCE <= after,
+            "pH {before} and {after} around the neutral point {neutral}",
+        );
+    }
+
+    /// With equal concentrations, the equivalence volume is the volume of the test solution. Both
+    /// are exact floats in steps of 1/1024 L, so the volume added hits the equivalence point.
+    #[test]
+    fn ph_is_neutral_after_adding_the_same_volume(
+        concentration in 0.001f32..1.0,
+        steps in 5u16..100,
+    ) {
+        let t_v = f32::from(steps) / 1024.0;
+        let input = input(concentration, concentration, t_v, vec![t_v * 1000.0], 25.0);
+        let ph = input.calculate_output().items[0].ph;
+        prop_assert!((ph - temperature::pkw(25.0) / 2.0).abs() < 0.01, "pH {ph}");
+    }
+
+    /// Sorting shuffled volumes with repeated readings gives the curve of the distinct volumes in
+    /// order.
+    #[test]
+    fn monotonic_curve_follows_the_sorted_volumes(
+        input in titration(),
+        repeats in prop::collection::vec(0usize..50, 0..10),
+    ) {
+        let sorted = input.calculate_output();
+        let mut m_v = input.m_v.clone();
+        m_v.extend(repeats.iter().map(|it| input.m_v[it % input.m_v.len()]));
+        m_v.reverse();
+        let output = Input { m_v, ..input }.calculate_output().monotonic();
+        let mut distinct = sorted.items.clone();
+        distinct.dedup_by(|a, b| a.m_v == b.m_v);
+        prop_assert_eq!(output.items.len(), distinct.len());
+        for (index, (item, expected)) in output.items.iter().zip(&distinct).enumerate() {
+            prop_assert_eq!(item.m_v, expected.m_v);
+            prop_assert!((item.ph - expected.ph).abs() < TOLERANCE, "pH {} at {} mL", item.ph, item.m_v);
+            // The explanation of an item describes the item, not the volume read at its index.
+            prop_assert_eq!(output.input.m_v[index], item.m_v);
+            let ph = output.input.explain(index).iter().find(|it| it.quantity == Quantity::Ph).map_or(f32::NAN, |it| it.result);
+            prop_assert!((ph - item.ph).abs() < TOLERANCE, "explained pH {} at {} mL", ph, item.m_v);
+        }
+    }
+
+    /// Excluding points and including them again keeps the order of the volumes as read.
+    #[test]
+    fn excluding_keeps_the_order(
+        input in titration(),
+        excluded in prop::collection::vec(0usize..50, 0..5),
+    ) {
+        let mut m_v = input.m_v.clone();
+        m_v.reverse();
+        let output = Input { m_v: m_v.clone(), ..input }.calculate_output();
+        let volumes: Vec<f32> = excluded.iter().map(|it| m_v[it % m_v.len()]).collect();
+        let excluding = output.excluding(&volumes);
+        let kept: Vec<f32> = m_v.iter().copied().filter(|it| !volumes.contains(it)).collect();
+        prop_assert_eq!(&excluding.input.m_v, &kept);
+        let included: Vec<f32> = excluding.excluding(&[]).items.iter().map(|it| it.m_v).collect();
+        prop_assert_eq!(included, m_v);
+    }
+
+    /// The same excess of acid before and of base after the equivalence point gives pH values
+    /// mirrored around the neutral point, apart from the dilution by the added volume.
+    #[test]
+    fn curve_is_symmetric_around_the_equivalence_point(
+        input in titration(),
+        share in 0.05f32..0.95,
+    ) {
+        let equivalence = equivalence_volume(&input);
+        let distance = share * equivalence;
+        let input = Input {
+            m_v: vec![equivalence - distance, equivalence + distance],
+            ..input
+        };
+        let output = input.calculate_output();
+        let (before, after) = (&output.items[0], &output.items[1]);
+        let total_v = |m_v: f32| m_v + input.t_v * 1000.0;
+        let dilution = (total_v(after.m_v) / total_v(before.m_v)).log10();
+        let deviation = before.ph + after.ph - temperature::pkw(input.temperature);
+        prop_assert!(
+            deviation.abs() <= dilution + 0.01,
+            "pH {} and {} deviate by {deviation}",
+            before.ph,
+            after.ph,
+        );
+    }
+}